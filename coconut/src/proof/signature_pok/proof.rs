@@ -26,6 +26,12 @@ pub struct SignaturePoK<E: Pairing> {
 }
 
 impl<E: Pairing> SignaturePoK<E> {
+    /// Returns the randomized signature. This is randomized with fresh randomness for every proof, so proofs
+    /// for the same signature can't be linked by it.
+    pub fn randomized_sig(&self) -> &RandomizedSignature<E> {
+        &self.randomized_sig
+    }
+
     /// Verifies underlying proof of knowledge using supplied arguments.
     /// `indexed_revealed_messages_sorted_by_index` must produce items sorted by unique indices, otherwise,
     /// an error will be returned.
@@ -25,6 +25,7 @@ type Result<T, E = PSError> = core::result::Result<T, E>;
 )]
 #[serde(bound = "")]
 pub struct RandomizedSignature<E: Pairing>(Signature<E>);
+utils::impl_deref! { RandomizedSignature<E: Pairing>(Signature<E>) }
 
 impl<E: Pairing> RandomizedSignature<E> {
     /// Randomizes provided signature using supplied scalars.
@@ -1,6 +1,6 @@
 use alloc::vec::Vec;
 
-use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
 use ark_ff::PrimeField;
 
 use utils::serde_utils::ArkObjectBytes;
@@ -48,6 +48,49 @@ impl<E: Pairing> PublicKey<E> {
         }
     }
 
+    /// Aggregates the public keys of the signers of a multi-signature, see
+    /// `AggregatedSignature::new_multi_signature`. Returns `None` if no keys are given or they don't support
+    /// the same number of messages.
+    ///
+    /// As aggregating keys is vulnerable to rogue key attacks, the keys must be trusted, like by requiring
+    /// each signer to prove knowledge of its secret key when registering its public key.
+    pub fn aggregate<'a, I>(public_keys: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = &'a Self>,
+    {
+        let mut public_keys = public_keys.into_iter();
+        let first = public_keys.next()?;
+        let mut alpha_tilde = first.alpha_tilde.into_group();
+        let mut beta = first
+            .beta
+            .iter()
+            .map(|b| b.into_group())
+            .collect::<Vec<_>>();
+        let mut beta_tilde = first
+            .beta_tilde
+            .iter()
+            .map(|b| b.into_group())
+            .collect::<Vec<_>>();
+        for pk in public_keys {
+            if pk.beta.len() != beta.len() || pk.beta_tilde.len() != beta_tilde.len() {
+                return None;
+            }
+            alpha_tilde += pk.alpha_tilde;
+            for (b, pk_b) in beta.iter_mut().zip(pk.beta.iter()) {
+                *b += pk_b;
+            }
+            for (b, pk_b) in beta_tilde.iter_mut().zip(pk.beta_tilde.iter()) {
+                *b += pk_b;
+            }
+        }
+
+        Some(Self {
+            alpha_tilde: alpha_tilde.into_affine(),
+            beta: E::G1::normalize_batch(&beta),
+            beta_tilde: E::G2::normalize_batch(&beta_tilde),
+        })
+    }
+
     /// Returns max amount of messages supported by this public key.
     pub fn supported_message_count(&self) -> usize {
         self.beta.len()
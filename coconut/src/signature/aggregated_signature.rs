@@ -4,7 +4,7 @@ use itertools::{process_results, Itertools};
 use secret_sharing_and_dkg::common::ParticipantId;
 use serde::{Deserialize, Serialize};
 
-use ark_ec::pairing::Pairing;
+use ark_ec::{pairing::Pairing, AffineRepr};
 
 use ark_serialize::*;
 use utils::iter::validate;
@@ -57,6 +57,27 @@ impl<E: Pairing> AggregatedSignature<E> {
 
         Ok(Self(Signature::combine(h, s_mul_l)))
     }
+
+    /// Creates new `AggregatedSignature` from signatures over the same messages by different signers, i.e.
+    /// a multi-signature. All signatures must have the same `sigma_1` which is the case for signatures created
+    /// with `Signature::new_deterministic` over the same messages. This signature can be verified using the
+    /// public key given by `PublicKey::aggregate` over the signers' public keys.
+    pub fn new_multi_signature<'a, SI>(signatures: SI) -> Result<Self>
+    where
+        SI: IntoIterator<Item = &'a Signature<E>>,
+    {
+        let mut signatures = signatures.into_iter();
+        let first = signatures.next().ok_or(AggregatedPSError::NoSignatures)?;
+        let mut sigma_2 = first.sigma_2.into_group();
+        for (idx, sig) in signatures.enumerate() {
+            if sig.sigma_1 != first.sigma_1 {
+                Err(AggregatedPSError::DifferentSigma1At(idx + 1))?
+            }
+            sigma_2 += sig.sigma_2;
+        }
+
+        Ok(Self(Signature::combine(first.sigma_1, sigma_2)))
+    }
 }
 
 #[cfg(test)]
@@ -78,7 +99,7 @@ mod aggregated_signature_tests {
 
     use crate::{
         helpers::n_rand,
-        setup::test_setup,
+        setup::{test_setup, PublicKey, SecretKey},
         signature::{aggregated_signature::AggregatedSignature, error::AggregatedPSError},
         BlindSignature, CommitmentOrMessage, MessageCommitment, Signature,
     };
@@ -161,6 +182,31 @@ mod aggregated_signature_tests {
         )
     }
 
+    #[test]
+    fn multi_signature() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (sk_1, pk_1, params, msgs) = test_setup::<Bls12_381, Blake2b512, _>(&mut rng, 5);
+        let sk_2 = SecretKey::rand(&mut rng, 5);
+        let pk_2 = PublicKey::new(&sk_2, &params);
+
+        let sig_1 = Signature::<Bls12_381>::new_deterministic::<Blake2b512>(&msgs, &sk_1).unwrap();
+        let sig_2 = Signature::<Bls12_381>::new_deterministic::<Blake2b512>(&msgs, &sk_2).unwrap();
+        let aggregated = AggregatedSignature::new_multi_signature([&sig_1, &sig_2]).unwrap();
+        let aggregated_pk = PublicKey::aggregate([&pk_1, &pk_2]).unwrap();
+        aggregated.verify(&msgs, &aggregated_pk, &params).unwrap();
+        assert!(aggregated.verify(&msgs, &pk_1, &params).is_err());
+
+        let sig_3 = Signature::<Bls12_381>::new(&mut rng, &msgs, &sk_2, &params).unwrap();
+        assert_eq!(
+            AggregatedSignature::new_multi_signature([&sig_1, &sig_3]),
+            Err(AggregatedPSError::DifferentSigma1At(1))
+        );
+        assert_eq!(
+            AggregatedSignature::<Bls12_381>::new_multi_signature([]),
+            Err(AggregatedPSError::NoSignatures)
+        );
+    }
+
     #[test]
     fn empty_signature() {
         let mut rng = StdRng::seed_from_u64(0u64);
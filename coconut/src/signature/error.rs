@@ -57,6 +57,8 @@ pub enum AggregatedPSError {
     InvalidSigma1For(ParticipantId),
     ParticipantIdsMustBeUniqueAndSorted(InvalidPair<ParticipantId>),
     PSError(PSError),
+    /// Index of the signature whose `sigma_1` differs from the first signature's in a multi-signature
+    DifferentSigma1At(usize),
 }
 
 impl From<InvalidPair<ParticipantId>> for AggregatedPSError {
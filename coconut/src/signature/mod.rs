@@ -8,6 +8,6 @@ mod ps_signature;
 
 pub use aggregated_signature::AggregatedSignature;
 pub use blind_signature::*;
-pub use error::{BlindPSError, PSError};
+pub use error::{AggregatedPSError, BlindPSError, PSError};
 pub use message_commitment::MessageCommitment;
 pub use ps_signature::Signature;
@@ -112,6 +112,20 @@ impl<E: Pairing> Signature<E> {
         self.verify_pairing(beta_tilde_mul_m + alpha_tilde, g_tilde)
     }
 
+    /// Rerandomizes the signature by multiplying both of its elements with the same random non-zero scalar.
+    /// The result is a valid signature on the same messages under the same public key which can't be linked
+    /// to the original signature without knowing the messages. Rerandomizing is only needed when the signature
+    /// itself is shown or stored, as `SignaturePoKGenerator::init` randomizes the signature with fresh
+    /// randomness for every proof.
+    pub fn rerandomize<R: RngCore>(&self, rng: &mut R) -> Self {
+        let mut t = E::ScalarField::rand(rng);
+        while t.is_zero() {
+            t = E::ScalarField::rand(rng);
+        }
+        let t = t.into_bigint();
+        Self::combine(self.sigma_1.mul_bigint(t), self.sigma_2.mul_bigint(t))
+    }
+
     /// Checks if a signature has zero elements. A valid signature should not have zero elements.
     pub fn is_zero(&self) -> bool {
         self.sigma_1.is_zero() || self.sigma_2.is_zero()
@@ -207,6 +221,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn rerandomized_signature() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (sk, pk, params, msgs) = test_setup::<Bls12_381, Blake2b512, _>(&mut rng, 5);
+
+        let sig = Signature::new(&mut rng, &msgs, &sk, &params).unwrap();
+        let sig_1 = sig.rerandomize(&mut rng);
+        let sig_2 = sig.rerandomize(&mut rng);
+        assert_ne!(sig_1, sig);
+        assert_ne!(sig_2, sig);
+        assert_ne!(sig_1, sig_2);
+        sig_1.verify(&msgs, &pk, &params).unwrap();
+        sig_2.verify(&msgs, &pk, &params).unwrap();
+    }
+
     #[test]
     fn valid_signature_other_pubkey() {
         let mut rng = StdRng::seed_from_u64(0u64);
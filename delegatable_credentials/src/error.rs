@@ -30,6 +30,22 @@ pub enum DelegationError {
     IncompatiblePublicKey,
     UnequalSizeOfSequence(usize, usize),
     InvalidOneOfNProof,
+    /// The given window table wasn't created for the expected group element
+    IncompatibleWindowTable,
+    /// The pairing check for the `element_index`-th element of the `group_index`-th member of the anonymity set
+    /// failed
+    OneOfNProofFailedAt {
+        group_index: usize,
+        element_index: usize,
+    },
+    /// Number of elements in the instance and the number expected by the verifier
+    UnexpectedArity(usize, usize),
+    /// Element of the instance at this index is the identity
+    IdentityElementInInstance(usize),
+    /// Anonymity set size and the minimum size allowed
+    AnonymitySetTooSmall(usize, usize),
+    /// Anonymity set size and the maximum size allowed
+    AnonymitySetTooLarge(usize, usize),
     TooManyAttributes(usize),
     TooManyCommitments(usize),
     TooManyWitnesses(usize),
@@ -1,40 +1,100 @@
 use crate::error::DelegationError;
-use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, Group};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, Group, VariableBaseMSM};
 use ark_ff::{PrimeField, Zero};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{
     cfg_iter,
     collections::BTreeMap,
     ops::{Add, Neg, Sub},
-    rand::RngCore,
+    rand::{Rng, RngCore},
     vec,
     vec::Vec,
     UniformRand,
 };
-use dock_crypto_utils::msm::WindowTable;
+use dock_crypto_utils::{msm::WindowTable, serde_utils::ArkObjectBytes};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
 /// SRS used for the 1-of-N proof
-#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
-pub struct OneOfNSrs<E: Pairing>(E::G1Affine);
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+pub struct OneOfNSrs<E: Pairing>(#[serde_as(as = "ArkObjectBytes")] E::G1Affine);
+
+/// Trapdoor of the SRS. Anyone knowing it can create proofs without knowing the witness so it must be destroyed
+/// after the setup. It's zeroized when dropped.
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct Trapdoor<E: Pairing>(E::ScalarField);
 
 /// Proof that 1 out of `N` public vectors of group elements when scaled (multiplied) by a scalar result in
 /// a specific public group element. Based on NIZK argument in Section 7.2, Fig 6 of the
 /// paper [Improved Constructions of Anonymous Credentials From SPS-EQ](https://eprint.iacr.org/2021/1680)
-#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
 pub struct OneOfNProof<E: Pairing> {
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
     pub z: Vec<E::G1Affine>,
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
     pub d: Vec<E::G1Affine>,
+    #[serde_as(as = "Vec<Vec<ArkObjectBytes>>")]
     pub a: Vec<Vec<E::G2Affine>>,
 }
 
+/// The actual vector hidden among the decoys, its instance and the witness, for creating a proof with
+/// `OneOfNProof::new_batch`
+pub type OneOfNWitness<'a, E> = (
+    &'a [<E as Pairing>::G2Affine],
+    &'a [<E as Pairing>::G2Affine],
+    <E as Pairing>::ScalarField,
+);
+
+/// A proof along with the permuted anonymity set it was created over, as returned by
+/// `OneOfNProof::new_with_permutation`
+pub type OneOfNProofWithOrdering<'a, E> = (OneOfNProof<E>, Vec<&'a [<E as Pairing>::G2Affine]>);
+
+/// Terms of the randomized multi-pairing check of one or more proofs
+struct PairingTerms<E: Pairing> {
+    g1: Vec<E::G1>,
+    g2: Vec<E::G2Prepared>,
+    /// `a_i_j` of all equations along with their random weights in `r`, all of these are paired with `P1`
+    a: Vec<E::G2Affine>,
+    r: Vec<E::ScalarField>,
+}
+
 impl<E: Pairing> OneOfNSrs<E> {
     /// Returns the SRS and trapdoor
-    pub fn new<R: RngCore>(rng: &mut R, P1: &E::G1Affine) -> (Self, E::ScalarField) {
-        let z = E::ScalarField::rand(rng);
-        (Self(P1.mul_bigint(z.into_bigint()).into_affine()), z)
+    pub fn new<R: RngCore>(rng: &mut R, P1: &E::G1Affine) -> (Self, Trapdoor<E>) {
+        let z = Trapdoor(E::ScalarField::rand(rng));
+        (Self::from_trapdoor(P1, z.reveal()), z)
+    }
+
+    /// Create the SRS from a trapdoor `z` generated elsewhere, like in a multi-party setup
+    pub fn from_trapdoor(P1: &E::G1Affine, z: &E::ScalarField) -> Self {
+        Self(P1.mul_bigint(z.into_bigint()).into_affine())
+    }
+
+    /// Combine SRS created by several parties using the same `P1`. The combination is additive in the
+    /// trapdoor, i.e. the SRS points are added so the trapdoor of the result is the sum of the trapdoors
+    /// of `parts` and is unknown to any party unless all collude.
+    pub fn combine(parts: &[Self]) -> Result<Self, DelegationError> {
+        if parts.is_empty() {
+            return Err(DelegationError::NeedNonZeroSize);
+        }
+        Ok(Self(parts.iter().map(|p| p.0).sum::<E::G1>().into_affine()))
+    }
+}
+
+impl<E: Pairing> Trapdoor<E> {
+    /// The trapdoor, needed only for combining with the trapdoors of other parties in a multi-party setup
+    pub fn reveal(&self) -> &E::ScalarField {
+        &self.0
     }
 }
 
@@ -52,31 +112,160 @@ impl<E: Pairing> OneOfNProof<E> {
         srs: &OneOfNSrs<E>,
         P1: &E::G1Affine,
     ) -> Result<Self, DelegationError> {
-        if actual.len() != instance.len() {
-            return Err(DelegationError::UnequalSizeOfSequence(
-                actual.len(),
-                instance.len(),
-            ));
+        let sorted_decoys = Self::sort_decoys(decoys);
+        let P1_table = WindowTable::new(4, P1.into_group());
+        Self::new_with_sorted_decoys(
+            rng,
+            actual,
+            &sorted_decoys,
+            instance,
+            witness,
+            srs,
+            &P1_table,
+        )
+    }
+
+    /// Same as `Self::new` but takes the window table for `P1`, created as `WindowTable::new(n, P1.into_group())`,
+    /// so that it can be computed once, serialized and reused by other provers. Since the table might come from
+    /// elsewhere, it's checked against `P1` by comparing a multiplication of a random scalar using the table with
+    /// the one using `P1`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_table<R: RngCore>(
+        rng: &mut R,
+        actual: &[E::G2Affine],
+        decoys: Vec<&[E::G2Affine]>,
+        instance: &[E::G2Affine],
+        witness: &E::ScalarField,
+        srs: &OneOfNSrs<E>,
+        P1: &E::G1Affine,
+        P1_table: &WindowTable<E::G1>,
+    ) -> Result<Self, DelegationError> {
+        let r = E::ScalarField::rand(rng);
+        if P1_table.multiply(&r) != *P1 * r {
+            return Err(DelegationError::IncompatibleWindowTable);
         }
+        let sorted_decoys = Self::sort_decoys(decoys);
+        Self::new_with_sorted_decoys(
+            rng,
+            actual,
+            &sorted_decoys,
+            instance,
+            witness,
+            srs,
+            P1_table,
+        )
+    }
 
-        let m = actual.len();
-        let n = decoys.len() + 1;
-        let mut z = Vec::with_capacity(n);
-        let mut a = Vec::with_capacity(n);
-        let mut d = Vec::with_capacity(n);
+    /// Create a proof for each of the given `(actual, instance, witness)` tuples where each `actual` is hidden
+    /// among the same `decoys`. Same as calling `Self::new` for each tuple but the window table for `P1`
+    /// and the ordering of `decoys` are computed only once.
+    pub fn new_batch<R: RngCore>(
+        rng: &mut R,
+        witnesses: &[OneOfNWitness<E>],
+        decoys: Vec<&[E::G2Affine]>,
+        srs: &OneOfNSrs<E>,
+        P1: &E::G1Affine,
+    ) -> Result<Vec<Self>, DelegationError> {
+        let sorted_decoys = Self::sort_decoys(decoys);
+        let P1_table = WindowTable::new(4, P1.into_group());
+        let mut proofs = Vec::with_capacity(witnesses.len());
+        for (actual, instance, witness) in witnesses {
+            proofs.push(Self::new_with_sorted_decoys(
+                rng,
+                actual,
+                &sorted_decoys,
+                instance,
+                witness,
+                srs,
+                &P1_table,
+            )?);
+        }
+        Ok(proofs)
+    }
 
+    /// Expects `sorted_decoys` to be sorted by the key created by `Self::map_key`
+    fn new_with_sorted_decoys<R: RngCore>(
+        rng: &mut R,
+        actual: &[E::G2Affine],
+        sorted_decoys: &[(Vec<u8>, &[E::G2Affine])],
+        instance: &[E::G2Affine],
+        witness: &E::ScalarField,
+        srs: &OneOfNSrs<E>,
+        P1_table: &WindowTable<E::G1>,
+    ) -> Result<Self, DelegationError> {
         // The proof contains vectors `d`, `a` and `z` and each of these contain 1 item per `decoy` and
         // `actual`. To hide which item corresponds to the `actual`, the members of these 3 vectors need
         // to be sorted in certain order. Using a BtreeMap to order the members
 
-        // Use BtreeMap to order the group of decoys + actual
-        let mut all = BTreeMap::new();
-        all.insert(Self::map_key(actual), (0, actual));
-        for (i, pk) in decoys.into_iter().enumerate() {
-            all.insert(Self::map_key(pk), (i + 1, pk));
+        // Place `actual` among the already sorted decoys
+        let n = sorted_decoys.len() + 1;
+        let actual_key = Self::map_key(actual);
+        let pos = match sorted_decoys.binary_search_by(|(k, _)| k.cmp(&actual_key)) {
+            Ok(p) => p,
+            Err(p) => p,
+        };
+        let mut all = Vec::with_capacity(n);
+        for (i, (_, pk)) in sorted_decoys.iter().enumerate() {
+            if i == pos {
+                all.push((0, actual));
+            }
+            all.push((i + 1, *pk));
+        }
+        if pos == sorted_decoys.len() {
+            all.push((0, actual));
         }
+        Self::new_with_ordering(rng, all, instance, witness, srs, P1_table)
+    }
 
+    /// Same as `Self::new` but rather than ordering `actual` and `decoys` by their serialization, they are
+    /// ordered by a random permutation sampled from `rng`. Since the sorted order depends only on the
+    /// serialized group elements, someone who can choose the decoys might be able to influence where `actual`
+    /// lands; with a random permutation, its position is uniformly random regardless of the decoys.
+    ///
+    /// The tradeoff is that the verifier can't reconstruct the order on its own, so the returned ordering of
+    /// `actual` and `decoys` must be sent along with the proof and passed to `Self::verify_with_given_order`.
+    /// The ordering doesn't reveal `actual` but the verifier must check that it is a permutation of the
+    /// anonymity set it expects.
+    pub fn new_with_permutation<'a, R: RngCore>(
+        rng: &mut R,
+        actual: &'a [E::G2Affine],
+        decoys: Vec<&'a [E::G2Affine]>,
+        instance: &[E::G2Affine],
+        witness: &E::ScalarField,
+        srs: &OneOfNSrs<E>,
+        P1: &E::G1Affine,
+    ) -> Result<OneOfNProofWithOrdering<'a, E>, DelegationError> {
+        let mut all = Vec::with_capacity(decoys.len() + 1);
+        all.push((0, actual));
+        for (i, pk) in decoys.into_iter().enumerate() {
+            all.push((i + 1, pk));
+        }
+        // Fisher-Yates shuffle
+        for i in (1..all.len()).rev() {
+            let j = rng.gen_range(0..=i);
+            all.swap(i, j);
+        }
+        let ordering = all.iter().map(|(_, pk)| *pk).collect();
         let P1_table = WindowTable::new(4, P1.into_group());
+        let proof = Self::new_with_ordering(rng, all, instance, witness, srs, &P1_table)?;
+        Ok((proof, ordering))
+    }
+
+    /// Expects `all` to contain `actual` and decoys in the order they should appear in the proof where the
+    /// first item of each tuple is 0 for `actual` and the (1-based) index of the decoy otherwise
+    fn new_with_ordering<R: RngCore>(
+        rng: &mut R,
+        all: Vec<(usize, &[E::G2Affine])>,
+        instance: &[E::G2Affine],
+        witness: &E::ScalarField,
+        srs: &OneOfNSrs<E>,
+        P1_table: &WindowTable<E::G1>,
+    ) -> Result<Self, DelegationError> {
+        let m = instance.len();
+        let n = all.len();
+        let mut z = Vec::with_capacity(n);
+        let mut a = Vec::with_capacity(n);
+        let mut d = Vec::with_capacity(n);
 
         let s = E::ScalarField::rand(rng);
         let s_repr = s.into_bigint();
@@ -87,7 +276,10 @@ impl<E: Pairing> OneOfNProof<E> {
             .collect::<Vec<_>>();
         let mut actual_at = 0;
 
-        for (_, (i, pk)) in all.into_iter() {
+        for (i, pk) in all.into_iter() {
+            if pk.len() != m {
+                return Err(DelegationError::UnequalSizeOfSequence(pk.len(), m));
+            }
             if i == 0 {
                 // For `actual`
                 actual_at = a.len();
@@ -103,9 +295,6 @@ impl<E: Pairing> OneOfNProof<E> {
                 z.push(E::G1::zero());
             } else {
                 // For `decoys`
-                if pk.len() != m {
-                    return Err(DelegationError::UnequalSizeOfSequence(pk.len(), m));
-                }
                 let d_i = E::ScalarField::rand(rng);
                 let d_i_repr = d_i.into_bigint();
                 let z_i = random_challenges[i - 1].into_bigint();
@@ -137,49 +326,190 @@ impl<E: Pairing> OneOfNProof<E> {
         })
     }
 
-    pub fn verify(
+    /// Verify the proof. All the `n * m` pairing equations are checked together using a random linear
+    /// combination, the randomness for which is sampled from `rng`.
+    pub fn verify<R: RngCore>(
         &self,
+        rng: &mut R,
         possible: Vec<&[E::G2Affine]>,
         instance: &[E::G2Affine],
         srs: &OneOfNSrs<E>,
         P1: &E::G1Affine,
     ) -> Result<(), DelegationError> {
-        let n = possible.len();
-        let m = instance.len();
-        if self.a.len() != n {
-            return Err(DelegationError::UnequalSizeOfSequence(self.a.len(), n));
+        self.validate_shape(possible.len(), instance.len())?;
+        let ordered = Self::order_possible(possible);
+        self.verify_ordered(rng, ordered, instance, srs, P1)
+    }
+
+    /// Same as `Self::verify` but `ordered_possible` is expected to be in the order used when creating the
+    /// proof, like the one returned by `Self::new_with_permutation`. The caller should check that
+    /// `ordered_possible` is a permutation of the expected anonymity set.
+    pub fn verify_with_given_order<R: RngCore>(
+        &self,
+        rng: &mut R,
+        ordered_possible: Vec<&[E::G2Affine]>,
+        instance: &[E::G2Affine],
+        srs: &OneOfNSrs<E>,
+        P1: &E::G1Affine,
+    ) -> Result<(), DelegationError> {
+        self.validate_shape(ordered_possible.len(), instance.len())?;
+        self.verify_ordered(rng, ordered_possible, instance, srs, P1)
+    }
+
+    /// Verify several proofs, each with its own anonymity set, instance and SRS, with a single multi-pairing. The
+    /// pairing equations of all proofs are combined using a random linear combination, the randomness for which
+    /// is sampled from `rng`, and since `P1` is common to all, its terms across all proofs need only 1 pairing.
+    /// The check that the `z` of each proof sum to the one in its SRS is still done per proof. A failure doesn't
+    /// tell which proof is invalid.
+    #[allow(clippy::type_complexity)]
+    pub fn verify_many<R: RngCore>(
+        rng: &mut R,
+        proofs: Vec<(&Self, Vec<&[E::G2Affine]>, &[E::G2Affine], &OneOfNSrs<E>)>,
+        P1: &E::G1Affine,
+    ) -> Result<(), DelegationError> {
+        if proofs.is_empty() {
+            return Err(DelegationError::NeedNonZeroSize);
         }
-        if self.d.len() != n {
-            return Err(DelegationError::UnequalSizeOfSequence(self.d.len(), n));
+        let mut terms = PairingTerms::default();
+        for (proof, possible, instance, srs) in proofs {
+            proof.validate_shape(possible.len(), instance.len())?;
+            proof.check_z(srs)?;
+            let ordered = Self::order_possible(possible);
+            proof.add_pairing_terms(rng, ordered, instance, &mut terms)?;
         }
-        if self.z.len() != n {
-            return Err(DelegationError::UnequalSizeOfSequence(self.z.len(), n));
+        terms.check(P1)
+    }
+
+    /// Expects the shape of the proof to be validated already
+    fn verify_ordered<R: RngCore>(
+        &self,
+        rng: &mut R,
+        all: Vec<&[E::G2Affine]>,
+        instance: &[E::G2Affine],
+        srs: &OneOfNSrs<E>,
+        P1: &E::G1Affine,
+    ) -> Result<(), DelegationError> {
+        self.check_z(srs)?;
+        let mut terms = PairingTerms::default();
+        self.add_pairing_terms(rng, all, instance, &mut terms)?;
+        terms.check(P1)
+    }
+
+    /// Rather than checking each of the `n * m` equations `e(-d_i, pk_i_j) * e(z_i, instance_j) * e(P1, a_i_j) == 1`
+    /// separately, a random linear combination of them is checked with a single multi-pairing. The terms with
+    /// `instance_j` are combined across equations and the ones with `P1` are combined in `PairingTerms::check`, so
+    /// that the terms of several proofs can be combined as well.
+    fn add_pairing_terms<R: RngCore>(
+        &self,
+        rng: &mut R,
+        all: Vec<&[E::G2Affine]>,
+        instance: &[E::G2Affine],
+        terms: &mut PairingTerms<E>,
+    ) -> Result<(), DelegationError> {
+        let m = instance.len();
+        let mut z_combined = vec![E::G1::zero(); m];
+        for (i, pk) in all.into_iter().enumerate() {
+            if pk.len() != m {
+                return Err(DelegationError::UnequalSizeOfSequence(pk.len(), m));
+            }
+            for j in 0..m {
+                let r_i_j = E::ScalarField::rand(rng);
+                terms
+                    .g1
+                    .push(self.d[i].mul_bigint(r_i_j.into_bigint()).neg());
+                terms.g2.push(E::G2Prepared::from(pk[j]));
+                z_combined[j] += self.z[i].mul_bigint(r_i_j.into_bigint());
+                terms.a.push(self.a[i][j]);
+                terms.r.push(r_i_j);
+            }
         }
+        terms.g1.extend(z_combined);
+        // Prepared instance points are shared by all equations
+        terms
+            .g2
+            .extend(instance.iter().map(|i| E::G2Prepared::from(*i)));
+        Ok(())
+    }
 
-        // The sum of all `z` should match the one in SRS
-        if self.z.iter().sum::<E::G1>().into_affine() != srs.0 {
-            return Err(DelegationError::InvalidOneOfNProof);
+    /// Same as `Self::verify` but additionally checks that the anonymity set, i.e. `possible`, has at least
+    /// `min_set_size` and at most `max_set_size` members. The size check happens before any pairing is computed
+    /// so an oversized set is rejected cheaply.
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_with_set_bounds<R: RngCore>(
+        &self,
+        rng: &mut R,
+        possible: Vec<&[E::G2Affine]>,
+        instance: &[E::G2Affine],
+        srs: &OneOfNSrs<E>,
+        P1: &E::G1Affine,
+        min_set_size: usize,
+        max_set_size: usize,
+    ) -> Result<(), DelegationError> {
+        let n = possible.len();
+        if n < min_set_size {
+            return Err(DelegationError::AnonymitySetTooSmall(n, min_set_size));
+        }
+        if n > max_set_size {
+            return Err(DelegationError::AnonymitySetTooLarge(n, max_set_size));
         }
+        self.verify(rng, possible, instance, srs, P1)
+    }
 
-        // Use BtreeMap to order given inputs, similar to proof
-        let mut all = BTreeMap::new();
-        for pk in possible.into_iter() {
-            all.insert(Self::map_key(pk), pk);
+    /// Same as `Self::verify` but additionally checks that the instance, and thus each member of the anonymity set,
+    /// has exactly `expected_arity` elements and that none of the instance elements is the identity. Since the
+    /// pairing equations hold trivially at the positions where both `actual` and instance are the identity, a prover
+    /// could otherwise pad a shorter vector with identity elements to pass for a longer one. These checks happen
+    /// before any pairing is computed.
+    pub fn verify_with_arity<R: RngCore>(
+        &self,
+        rng: &mut R,
+        possible: Vec<&[E::G2Affine]>,
+        instance: &[E::G2Affine],
+        srs: &OneOfNSrs<E>,
+        P1: &E::G1Affine,
+        expected_arity: usize,
+    ) -> Result<(), DelegationError> {
+        if instance.len() != expected_arity {
+            return Err(DelegationError::UnexpectedArity(
+                instance.len(),
+                expected_arity,
+            ));
         }
+        if let Some(j) = instance.iter().position(|i| i.is_zero()) {
+            return Err(DelegationError::IdentityElementInInstance(j));
+        }
+        self.verify(rng, possible, instance, srs, P1)
+    }
+
+    /// Same as `Self::verify` but checks each of the `n * m` pairing equations individually and on failure,
+    /// returns the position of the first failing equation as `DelegationError::OneOfNProofFailedAt`. This is
+    /// much slower than `Self::verify` and meant for debugging malformed proofs or anonymity sets. The reported
+    /// indices are as per the order in which the proof arranges `possible`, i.e. sorted by the serialized keys.
+    pub fn verify_verbose(
+        &self,
+        possible: Vec<&[E::G2Affine]>,
+        instance: &[E::G2Affine],
+        srs: &OneOfNSrs<E>,
+        P1: &E::G1Affine,
+    ) -> Result<(), DelegationError> {
+        let m = instance.len();
+        self.validate_shape(possible.len(), m)?;
+        self.check_z(srs)?;
+        let all = Self::order_possible(possible);
 
         let prepared_instance = instance
             .iter()
             .map(|i| E::G2Prepared::from(*i))
             .collect::<Vec<_>>();
 
-        // TODO: Optimize using randomized pairing check
-        for (i, pk) in all.values().enumerate() {
+        for (i, pk) in all.into_iter().enumerate() {
             if pk.len() != m {
                 return Err(DelegationError::UnequalSizeOfSequence(pk.len(), m));
             }
-            for j in 0..pk.len() {
+            let minus_d_i = self.d[i].into_group().neg().into_affine();
+            for j in 0..m {
                 if !E::multi_pairing(
-                    [self.d[i].into_group().neg().into_affine(), self.z[i], *P1],
+                    [minus_d_i, self.z[i], *P1],
                     [
                         E::G2Prepared::from(pk[j]),
                         prepared_instance[j].clone(),
@@ -188,7 +518,10 @@ impl<E: Pairing> OneOfNProof<E> {
                 )
                 .is_zero()
                 {
-                    return Err(DelegationError::InvalidOneOfNProof);
+                    return Err(DelegationError::OneOfNProofFailedAt {
+                        group_index: i,
+                        element_index: j,
+                    });
                 }
             }
         }
@@ -196,6 +529,65 @@ impl<E: Pairing> OneOfNProof<E> {
         Ok(())
     }
 
+    /// Check that the proof is for an anonymity set of size `n` where each member has `m` elements. This
+    /// is cheap and lets a malformed proof be rejected before any pairing is computed.
+    pub fn validate_shape(&self, n: usize, m: usize) -> Result<(), DelegationError> {
+        if self.a.len() != n {
+            return Err(DelegationError::UnequalSizeOfSequence(self.a.len(), n));
+        }
+        if self.d.len() != n {
+            return Err(DelegationError::UnequalSizeOfSequence(self.d.len(), n));
+        }
+        if self.z.len() != n {
+            return Err(DelegationError::UnequalSizeOfSequence(self.z.len(), n));
+        }
+        for a_i in &self.a {
+            if a_i.len() != m {
+                return Err(DelegationError::UnequalSizeOfSequence(a_i.len(), m));
+            }
+        }
+        Ok(())
+    }
+
+    /// Number of pairing equations, `n * m`, checked when verifying this proof where `n` is the size of the anonymity
+    /// set and `m` the number of elements in each of its members. This is the number of multi-pairings computed by
+    /// `Self::verify_verbose` while `Self::verify` combines them into one multi-pairing of `n * m + m + 1` pairings.
+    /// Returns an error if the proof is malformed, so this can be used to check the proof's shape before verifying.
+    pub fn verification_pairing_count(&self) -> Result<usize, DelegationError> {
+        let n = self.a.len();
+        let m = self.a.first().map_or(0, |a| a.len());
+        self.validate_shape(n, m)?;
+        Ok(n * m)
+    }
+
+    /// Check that the `z` sum to the one in SRS
+    fn check_z(&self, srs: &OneOfNSrs<E>) -> Result<(), DelegationError> {
+        if self.z.iter().sum::<E::G1>().into_affine() != srs.0 {
+            return Err(DelegationError::InvalidOneOfNProof);
+        }
+        Ok(())
+    }
+
+    /// Order `possible` the same way as when creating the proof with `Self::new`
+    fn order_possible(possible: Vec<&[E::G2Affine]>) -> Vec<&[E::G2Affine]> {
+        // Use BtreeMap to order given inputs, similar to proof
+        let mut all = BTreeMap::new();
+        for pk in possible.into_iter() {
+            all.insert(Self::map_key(pk), pk);
+        }
+        all.into_values().collect()
+    }
+
+    /// Order decoys by their key, same as the order used in the proof
+    fn sort_decoys(decoys: Vec<&[E::G2Affine]>) -> Vec<(Vec<u8>, &[E::G2Affine])> {
+        // Use BtreeMap to order the decoys
+        let mut sorted_decoys = BTreeMap::new();
+        for pk in decoys.into_iter() {
+            sorted_decoys.insert(Self::map_key(pk), pk);
+        }
+        sorted_decoys.into_iter().collect()
+    }
+
     /// Create key for the BtreeMap
     fn map_key(pk: &[E::G2Affine]) -> Vec<u8> {
         let mut key = vec![];
@@ -204,12 +596,36 @@ impl<E: Pairing> OneOfNProof<E> {
     }
 }
 
+impl<E: Pairing> Default for PairingTerms<E> {
+    fn default() -> Self {
+        Self {
+            g1: vec![],
+            g2: vec![],
+            a: vec![],
+            r: vec![],
+        }
+    }
+}
+
+impl<E: Pairing> PairingTerms<E> {
+    /// Add the combined term with `P1` and check that the multi-pairing is the identity
+    fn check(mut self, P1: &E::G1Affine) -> Result<(), DelegationError> {
+        self.g1.push(P1.into_group());
+        self.g2
+            .push(E::G2Prepared::from(E::G2::msm_unchecked(&self.a, &self.r)));
+        if !E::multi_pairing(E::G1::normalize_batch(&self.g1), self.g2).is_zero() {
+            return Err(DelegationError::InvalidOneOfNProof);
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use ark_bls12_381::Bls12_381;
     use ark_std::rand::{rngs::StdRng, SeedableRng};
-    use std::{ops::Mul, time::Instant};
+    use std::{mem::ManuallyDrop, ops::Mul, time::Instant};
 
     type Fr = <Bls12_381 as Pairing>::ScalarField;
     type G2Affine = <Bls12_381 as Pairing>::G2Affine;
@@ -249,7 +665,7 @@ mod tests {
             for i in 0..count_decoys {
                 let mut temp_d = d.clone();
                 temp_d.insert(i, &actual);
-                proof.verify(temp_d, &instance, srs, P1).unwrap();
+                proof.verify(rng, temp_d, &instance, srs, P1).unwrap();
             }
             let verifying_time = start.elapsed();
 
@@ -260,4 +676,792 @@ mod tests {
             check(&mut rng, 5, i, &P1, &srs);
         }
     }
+
+    #[test]
+    fn one_of_n_proof_with_corrupted_a() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let P1 = <Bls12_381 as Pairing>::G1Affine::rand(&mut rng);
+        let (srs, _) = OneOfNSrs::<Bls12_381>::new(&mut rng, &P1);
+
+        let size = 3;
+        let count_decoys = 5;
+        let actual = (0..size)
+            .map(|_| G2Affine::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let decoys = (0..count_decoys)
+            .map(|_| {
+                (0..size)
+                    .map(|_| G2Affine::rand(&mut rng))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let witness = Fr::rand(&mut rng);
+        let instance = actual
+            .iter()
+            .map(|b| b.mul(witness).into_affine())
+            .collect::<Vec<_>>();
+
+        let d = decoys.iter().map(|d| d.as_slice()).collect::<Vec<_>>();
+        let proof =
+            OneOfNProof::new(&mut rng, &actual, d.clone(), &instance, &witness, &srs, &P1).unwrap();
+
+        let mut possible = d.clone();
+        possible.insert(2, &actual);
+
+        let start = Instant::now();
+        proof
+            .verify(&mut rng, possible.clone(), &instance, &srs, &P1)
+            .unwrap();
+        let randomized_time = start.elapsed();
+
+        // Check each pairing equation individually to compare time
+        let start = Instant::now();
+        let mut all = BTreeMap::new();
+        for pk in possible.iter() {
+            all.insert(OneOfNProof::<Bls12_381>::map_key(pk), *pk);
+        }
+        let prepared_instance = instance
+            .iter()
+            .map(|i| <Bls12_381 as Pairing>::G2Prepared::from(*i))
+            .collect::<Vec<_>>();
+        for (i, pk) in all.values().enumerate() {
+            for j in 0..pk.len() {
+                assert!(Bls12_381::multi_pairing(
+                    [proof.d[i].into_group().neg().into_affine(), proof.z[i], P1],
+                    [
+                        <Bls12_381 as Pairing>::G2Prepared::from(pk[j]),
+                        prepared_instance[j].clone(),
+                        <Bls12_381 as Pairing>::G2Prepared::from(proof.a[i][j]),
+                    ],
+                )
+                .is_zero());
+            }
+        }
+        let individual_time = start.elapsed();
+
+        println!(
+            "For {} decoys of size {} each, verifying with randomized pairing check takes {:?} and with individual checks takes {:?}",
+            count_decoys, size, randomized_time, individual_time
+        );
+
+        // Corrupting any single `a[i][j]` makes verification fail
+        for i in 0..proof.a.len() {
+            for j in 0..proof.a[i].len() {
+                let mut bad_proof = proof.clone();
+                bad_proof.a[i][j] = G2Affine::rand(&mut rng);
+                assert!(matches!(
+                    bad_proof.verify(&mut rng, possible.clone(), &instance, &srs, &P1),
+                    Err(DelegationError::InvalidOneOfNProof)
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn one_of_n_proof_verbose_failure() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let P1 = <Bls12_381 as Pairing>::G1Affine::rand(&mut rng);
+        let (srs, _) = OneOfNSrs::<Bls12_381>::new(&mut rng, &P1);
+
+        let size = 3;
+        let count_decoys = 4;
+        let actual = (0..size)
+            .map(|_| G2Affine::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let decoys = (0..count_decoys)
+            .map(|_| {
+                (0..size)
+                    .map(|_| G2Affine::rand(&mut rng))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let witness = Fr::rand(&mut rng);
+        let instance = actual
+            .iter()
+            .map(|b| b.mul(witness).into_affine())
+            .collect::<Vec<_>>();
+
+        let d = decoys.iter().map(|d| d.as_slice()).collect::<Vec<_>>();
+        let proof =
+            OneOfNProof::new(&mut rng, &actual, d.clone(), &instance, &witness, &srs, &P1).unwrap();
+
+        let mut possible = d.clone();
+        possible.insert(1, &actual);
+
+        proof
+            .verify_verbose(possible.clone(), &instance, &srs, &P1)
+            .unwrap();
+
+        let mut bad_proof = proof.clone();
+        bad_proof.a[2][1] = G2Affine::rand(&mut rng);
+        assert!(bad_proof
+            .verify(&mut rng, possible.clone(), &instance, &srs, &P1)
+            .is_err());
+        assert!(matches!(
+            bad_proof.verify_verbose(possible, &instance, &srs, &P1),
+            Err(DelegationError::OneOfNProofFailedAt {
+                group_index: 2,
+                element_index: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn one_of_n_proof_batch() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let P1 = <Bls12_381 as Pairing>::G1Affine::rand(&mut rng);
+        let (srs, _) = OneOfNSrs::<Bls12_381>::new(&mut rng, &P1);
+
+        let size = 3;
+        let count_decoys = 6;
+        let count_proofs = 5;
+        let decoys = (0..count_decoys)
+            .map(|_| {
+                (0..size)
+                    .map(|_| G2Affine::rand(&mut rng))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let actuals = (0..count_proofs)
+            .map(|_| {
+                (0..size)
+                    .map(|_| G2Affine::rand(&mut rng))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let witnesses = (0..count_proofs)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let instances = actuals
+            .iter()
+            .zip(witnesses.iter())
+            .map(|(a, w)| a.iter().map(|b| b.mul(w).into_affine()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let d = decoys.iter().map(|d| d.as_slice()).collect::<Vec<_>>();
+        let inputs = (0..count_proofs)
+            .map(|i| (actuals[i].as_slice(), instances[i].as_slice(), witnesses[i]))
+            .collect::<Vec<_>>();
+
+        let start = Instant::now();
+        let proofs = OneOfNProof::new_batch(&mut rng, &inputs, d.clone(), &srs, &P1).unwrap();
+        let batch_time = start.elapsed();
+        assert_eq!(proofs.len(), count_proofs);
+
+        let start = Instant::now();
+        for i in 0..count_proofs {
+            OneOfNProof::new(
+                &mut rng,
+                &actuals[i],
+                d.clone(),
+                &instances[i],
+                &witnesses[i],
+                &srs,
+                &P1,
+            )
+            .unwrap();
+        }
+        let individual_time = start.elapsed();
+        println!(
+            "Creating {} proofs with {} decoys of size {} each takes {:?} in batch and {:?} individually",
+            count_proofs, count_decoys, size, batch_time, individual_time
+        );
+
+        for (i, proof) in proofs.iter().enumerate() {
+            let mut possible = d.clone();
+            possible.insert(i % count_decoys, &actuals[i]);
+            proof
+                .verify(&mut rng, possible.clone(), &instances[i], &srs, &P1)
+                .unwrap();
+            proof
+                .verify_verbose(possible, &instances[i], &srs, &P1)
+                .unwrap();
+
+            // Doesn't verify for another instance
+            let mut possible = d.clone();
+            possible.push(&actuals[(i + 1) % count_proofs]);
+            assert!(proof
+                .verify(
+                    &mut rng,
+                    possible,
+                    &instances[(i + 1) % count_proofs],
+                    &srs,
+                    &P1
+                )
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn combining_srs() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let P1 = <Bls12_381 as Pairing>::G1Affine::rand(&mut rng);
+        let (srs1, z1) = OneOfNSrs::<Bls12_381>::new(&mut rng, &P1);
+        let (srs2, z2) = OneOfNSrs::<Bls12_381>::new(&mut rng, &P1);
+        assert_eq!(
+            OneOfNSrs::<Bls12_381>::from_trapdoor(&P1, z1.reveal()).0,
+            srs1.0
+        );
+
+        assert!(matches!(
+            OneOfNSrs::<Bls12_381>::combine(&[]),
+            Err(DelegationError::NeedNonZeroSize)
+        ));
+        let srs = OneOfNSrs::combine(&[srs1, srs2]).unwrap();
+        assert_eq!(
+            OneOfNSrs::<Bls12_381>::from_trapdoor(&P1, &(*z1.reveal() + z2.reveal())).0,
+            srs.0
+        );
+
+        let size = 3;
+        let actual = (0..size)
+            .map(|_| G2Affine::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let decoys = (0..4)
+            .map(|_| {
+                (0..size)
+                    .map(|_| G2Affine::rand(&mut rng))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let witness = Fr::rand(&mut rng);
+        let instance = actual
+            .iter()
+            .map(|b| b.mul(witness).into_affine())
+            .collect::<Vec<_>>();
+
+        let d = decoys.iter().map(|d| d.as_slice()).collect::<Vec<_>>();
+        let proof =
+            OneOfNProof::new(&mut rng, &actual, d.clone(), &instance, &witness, &srs, &P1).unwrap();
+        let mut possible = d.clone();
+        possible.insert(2, &actual);
+        proof
+            .verify(&mut rng, possible, &instance, &srs, &P1)
+            .unwrap();
+    }
+
+    #[test]
+    fn serde_roundtrip() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let P1 = <Bls12_381 as Pairing>::G1Affine::rand(&mut rng);
+        let (srs, _) = OneOfNSrs::<Bls12_381>::new(&mut rng, &P1);
+
+        let size = 2;
+        let actual = (0..size)
+            .map(|_| G2Affine::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let decoys = (0..2)
+            .map(|_| {
+                (0..size)
+                    .map(|_| G2Affine::rand(&mut rng))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let witness = Fr::rand(&mut rng);
+        let instance = actual
+            .iter()
+            .map(|b| b.mul(witness).into_affine())
+            .collect::<Vec<_>>();
+
+        let d = decoys.iter().map(|d| d.as_slice()).collect::<Vec<_>>();
+        let proof =
+            OneOfNProof::new(&mut rng, &actual, d.clone(), &instance, &witness, &srs, &P1).unwrap();
+
+        let ser = serde_json::to_string(&srs).unwrap();
+        let deser_srs = serde_json::from_str::<OneOfNSrs<Bls12_381>>(&ser).unwrap();
+        assert_eq!(deser_srs, srs);
+
+        let ser = serde_json::to_string(&proof).unwrap();
+        let deser_proof = serde_json::from_str::<OneOfNProof<Bls12_381>>(&ser).unwrap();
+        assert_eq!(deser_proof, proof);
+
+        let mut possible = d.clone();
+        possible.insert(1, &actual);
+        deser_proof
+            .verify(&mut rng, possible, &instance, &deser_srs, &P1)
+            .unwrap();
+    }
+
+    #[test]
+    fn one_of_n_proof_with_ragged_a() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let P1 = <Bls12_381 as Pairing>::G1Affine::rand(&mut rng);
+        let (srs, _) = OneOfNSrs::<Bls12_381>::new(&mut rng, &P1);
+
+        let size = 3;
+        let actual = (0..size)
+            .map(|_| G2Affine::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let decoys = (0..3)
+            .map(|_| {
+                (0..size)
+                    .map(|_| G2Affine::rand(&mut rng))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let witness = Fr::rand(&mut rng);
+        let instance = actual
+            .iter()
+            .map(|b| b.mul(witness).into_affine())
+            .collect::<Vec<_>>();
+
+        let d = decoys.iter().map(|d| d.as_slice()).collect::<Vec<_>>();
+        let proof =
+            OneOfNProof::new(&mut rng, &actual, d.clone(), &instance, &witness, &srs, &P1).unwrap();
+        let mut possible = d.clone();
+        possible.insert(0, &actual);
+        proof.validate_shape(possible.len(), size).unwrap();
+
+        // Wrong anonymity set size
+        assert!(matches!(
+            proof.validate_shape(possible.len() + 1, size),
+            Err(DelegationError::UnequalSizeOfSequence(4, 5))
+        ));
+
+        // Remove an element from one of the `a[i]`
+        let mut bad_proof = proof.clone();
+        bad_proof.a[1].pop();
+        assert!(matches!(
+            bad_proof.validate_shape(possible.len(), size),
+            Err(DelegationError::UnequalSizeOfSequence(2, 3))
+        ));
+        assert!(matches!(
+            bad_proof.verify(&mut rng, possible.clone(), &instance, &srs, &P1),
+            Err(DelegationError::UnequalSizeOfSequence(2, 3))
+        ));
+
+        // Add an extra element to one of the `a[i]`
+        let mut bad_proof = proof.clone();
+        bad_proof.a[3].push(G2Affine::rand(&mut rng));
+        assert!(matches!(
+            bad_proof.verify_verbose(possible, &instance, &srs, &P1),
+            Err(DelegationError::UnequalSizeOfSequence(4, 3))
+        ));
+    }
+
+    #[test]
+    fn one_of_n_proof_with_permutation() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let P1 = <Bls12_381 as Pairing>::G1Affine::rand(&mut rng);
+        let (srs, _) = OneOfNSrs::<Bls12_381>::new(&mut rng, &P1);
+
+        let size = 3;
+        let count_decoys = 5;
+        let actual = (0..size)
+            .map(|_| G2Affine::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let decoys = (0..count_decoys)
+            .map(|_| {
+                (0..size)
+                    .map(|_| G2Affine::rand(&mut rng))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let witness = Fr::rand(&mut rng);
+        let instance = actual
+            .iter()
+            .map(|b| b.mul(witness).into_affine())
+            .collect::<Vec<_>>();
+
+        let d = decoys.iter().map(|d| d.as_slice()).collect::<Vec<_>>();
+        for _ in 0..3 {
+            let (proof, ordering) = OneOfNProof::new_with_permutation(
+                &mut rng,
+                &actual,
+                d.clone(),
+                &instance,
+                &witness,
+                &srs,
+                &P1,
+            )
+            .unwrap();
+
+            // Ordering is a permutation of the actual and decoys
+            assert_eq!(ordering.len(), count_decoys + 1);
+            assert!(ordering.contains(&actual.as_slice()));
+            for dd in &d {
+                assert!(ordering.contains(dd));
+            }
+
+            proof
+                .verify_with_given_order(&mut rng, ordering.clone(), &instance, &srs, &P1)
+                .unwrap();
+
+            // Doesn't verify when the ordering is changed
+            let mut wrong_ordering = ordering.clone();
+            wrong_ordering.swap(0, 1);
+            assert!(proof
+                .verify_with_given_order(&mut rng, wrong_ordering, &instance, &srs, &P1)
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn one_of_n_proof_with_set_bounds() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let P1 = <Bls12_381 as Pairing>::G1Affine::rand(&mut rng);
+        let (srs, _) = OneOfNSrs::<Bls12_381>::new(&mut rng, &P1);
+
+        let size = 3;
+        let count_decoys = 9;
+        let actual = (0..size)
+            .map(|_| G2Affine::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let decoys = (0..count_decoys)
+            .map(|_| {
+                (0..size)
+                    .map(|_| G2Affine::rand(&mut rng))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let witness = Fr::rand(&mut rng);
+        let instance = actual
+            .iter()
+            .map(|b| b.mul(witness).into_affine())
+            .collect::<Vec<_>>();
+
+        let d = decoys.iter().map(|d| d.as_slice()).collect::<Vec<_>>();
+        let proof =
+            OneOfNProof::new(&mut rng, &actual, d.clone(), &instance, &witness, &srs, &P1).unwrap();
+
+        let mut possible = d.clone();
+        possible.insert(4, &actual);
+        let n = possible.len();
+
+        // Within bounds, including when the set size is equal to either bound
+        proof
+            .verify_with_set_bounds(&mut rng, possible.clone(), &instance, &srs, &P1, 5, 20)
+            .unwrap();
+        proof
+            .verify_with_set_bounds(&mut rng, possible.clone(), &instance, &srs, &P1, n, n)
+            .unwrap();
+
+        // Set smaller than the minimum
+        assert!(matches!(
+            proof.verify_with_set_bounds(&mut rng, possible.clone(), &instance, &srs, &P1, n + 1, 20),
+            Err(DelegationError::AnonymitySetTooSmall(s, m)) if s == n && m == n + 1
+        ));
+
+        // Set larger than the maximum
+        assert!(matches!(
+            proof.verify_with_set_bounds(&mut rng, possible, &instance, &srs, &P1, 1, n - 1),
+            Err(DelegationError::AnonymitySetTooLarge(s, m)) if s == n && m == n - 1
+        ));
+    }
+
+    #[test]
+    fn one_of_n_proof_with_serialized_table() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let P1 = <Bls12_381 as Pairing>::G1Affine::rand(&mut rng);
+        let (srs, _) = OneOfNSrs::<Bls12_381>::new(&mut rng, &P1);
+
+        let size = 3;
+        let actual = (0..size)
+            .map(|_| G2Affine::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let decoys = (0..5)
+            .map(|_| {
+                (0..size)
+                    .map(|_| G2Affine::rand(&mut rng))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let witness = Fr::rand(&mut rng);
+        let instance = actual
+            .iter()
+            .map(|b| b.mul(witness).into_affine())
+            .collect::<Vec<_>>();
+        let d = decoys.iter().map(|d| d.as_slice()).collect::<Vec<_>>();
+
+        // The table is created and serialized by one process and deserialized by another
+        let table = WindowTable::new(4, P1.into_group());
+        let mut table_bytes = vec![];
+        table.serialize_compressed(&mut table_bytes).unwrap();
+        let table =
+            WindowTable::<<Bls12_381 as Pairing>::G1>::deserialize_compressed(&table_bytes[..])
+                .unwrap();
+
+        let proof = OneOfNProof::new_with_table(
+            &mut rng,
+            &actual,
+            d.clone(),
+            &instance,
+            &witness,
+            &srs,
+            &P1,
+            &table,
+        )
+        .unwrap();
+        let mut possible = d.clone();
+        possible.insert(1, &actual);
+        proof
+            .verify(&mut rng, possible, &instance, &srs, &P1)
+            .unwrap();
+
+        // Table of a different group element is rejected
+        let other_table = WindowTable::new(
+            4,
+            <Bls12_381 as Pairing>::G1Affine::rand(&mut rng).into_group(),
+        );
+        assert!(matches!(
+            OneOfNProof::new_with_table(
+                &mut rng,
+                &actual,
+                d,
+                &instance,
+                &witness,
+                &srs,
+                &P1,
+                &other_table,
+            ),
+            Err(DelegationError::IncompatibleWindowTable)
+        ));
+    }
+
+    #[test]
+    fn one_of_n_proof_with_arity() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let P1 = <Bls12_381 as Pairing>::G1Affine::rand(&mut rng);
+        let (srs, _) = OneOfNSrs::<Bls12_381>::new(&mut rng, &P1);
+
+        let arity = 4;
+        let decoys = (0..5)
+            .map(|_| {
+                (0..arity)
+                    .map(|_| G2Affine::rand(&mut rng))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let d = decoys.iter().map(|d| d.as_slice()).collect::<Vec<_>>();
+        let witness = Fr::rand(&mut rng);
+
+        let actual = (0..arity)
+            .map(|_| G2Affine::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let instance = actual
+            .iter()
+            .map(|b| b.mul(witness).into_affine())
+            .collect::<Vec<_>>();
+        let proof =
+            OneOfNProof::new(&mut rng, &actual, d.clone(), &instance, &witness, &srs, &P1).unwrap();
+        let mut possible = d.clone();
+        possible.push(&actual);
+        proof
+            .verify_with_arity(&mut rng, possible.clone(), &instance, &srs, &P1, arity)
+            .unwrap();
+
+        // A shorter instance is rejected
+        assert!(matches!(
+            proof.verify_with_arity(
+                &mut rng,
+                possible,
+                &instance[..arity - 1],
+                &srs,
+                &P1,
+                arity
+            ),
+            Err(DelegationError::UnexpectedArity(l, a)) if l == arity - 1 && a == arity
+        ));
+
+        // A shorter vector padded with the identity satisfies the pairing equations but is rejected
+        let mut padded_actual = actual[..arity - 1].to_vec();
+        padded_actual.push(G2Affine::zero());
+        let padded_instance = padded_actual
+            .iter()
+            .map(|b| b.mul(witness).into_affine())
+            .collect::<Vec<_>>();
+        let proof = OneOfNProof::new(
+            &mut rng,
+            &padded_actual,
+            d.clone(),
+            &padded_instance,
+            &witness,
+            &srs,
+            &P1,
+        )
+        .unwrap();
+        let mut possible = d;
+        possible.push(&padded_actual);
+        proof
+            .verify(&mut rng, possible.clone(), &padded_instance, &srs, &P1)
+            .unwrap();
+        assert!(matches!(
+            proof.verify_with_arity(&mut rng, possible, &padded_instance, &srs, &P1, arity),
+            Err(DelegationError::IdentityElementInInstance(j)) if j == arity - 1
+        ));
+    }
+
+    #[test]
+    fn verify_many_one_of_n_proofs() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let P1 = <Bls12_381 as Pairing>::G1Affine::rand(&mut rng);
+        let srs = [
+            OneOfNSrs::<Bls12_381>::new(&mut rng, &P1).0,
+            OneOfNSrs::<Bls12_381>::new(&mut rng, &P1).0,
+        ];
+
+        let size = 3;
+        let mut proofs = vec![];
+        let mut sets = vec![];
+        let mut instances = vec![];
+        for i in 0..4 {
+            let actual = (0..size)
+                .map(|_| G2Affine::rand(&mut rng))
+                .collect::<Vec<_>>();
+            let decoys = (0..3 + i)
+                .map(|_| {
+                    (0..size)
+                        .map(|_| G2Affine::rand(&mut rng))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            let witness = Fr::rand(&mut rng);
+            let instance = actual
+                .iter()
+                .map(|b| b.mul(witness).into_affine())
+                .collect::<Vec<_>>();
+            let d = decoys.iter().map(|d| d.as_slice()).collect::<Vec<_>>();
+            proofs.push(
+                OneOfNProof::new(&mut rng, &actual, d, &instance, &witness, &srs[i % 2], &P1)
+                    .unwrap(),
+            );
+            let mut set = decoys;
+            set.push(actual);
+            sets.push(set);
+            instances.push(instance);
+        }
+
+        #[allow(clippy::type_complexity)]
+        fn batch<'a>(
+            proofs: &'a [OneOfNProof<Bls12_381>],
+            sets: &'a [Vec<Vec<G2Affine>>],
+            instances: &'a [Vec<G2Affine>],
+            srs: &'a [OneOfNSrs<Bls12_381>],
+            srs_idx: &[usize],
+        ) -> Vec<(
+            &'a OneOfNProof<Bls12_381>,
+            Vec<&'a [G2Affine]>,
+            &'a [G2Affine],
+            &'a OneOfNSrs<Bls12_381>,
+        )> {
+            (0..proofs.len())
+                .map(|i| {
+                    (
+                        &proofs[i],
+                        sets[i].iter().map(|p| p.as_slice()).collect(),
+                        instances[i].as_slice(),
+                        &srs[srs_idx[i]],
+                    )
+                })
+                .collect()
+        }
+
+        let srs_idx = [0, 1, 0, 1];
+        for (proof, possible, instance, srs) in batch(&proofs, &sets, &instances, &srs, &srs_idx) {
+            proof
+                .verify(&mut rng, possible, instance, srs, &P1)
+                .unwrap();
+        }
+        OneOfNProof::verify_many(
+            &mut rng,
+            batch(&proofs, &sets, &instances, &srs, &srs_idx),
+            &P1,
+        )
+        .unwrap();
+
+        // Fails if any proof is checked against the other SRS
+        assert!(matches!(
+            OneOfNProof::verify_many(
+                &mut rng,
+                batch(&proofs, &sets, &instances, &srs, &[0, 1, 1, 1]),
+                &P1
+            ),
+            Err(DelegationError::InvalidOneOfNProof)
+        ));
+
+        // Fails if any proof is invalid
+        let mut bad_proofs = proofs.clone();
+        bad_proofs[2].a[1][0] = G2Affine::rand(&mut rng);
+        assert!(matches!(
+            OneOfNProof::verify_many(
+                &mut rng,
+                batch(&bad_proofs, &sets, &instances, &srs, &srs_idx),
+                &P1
+            ),
+            Err(DelegationError::InvalidOneOfNProof)
+        ));
+
+        assert!(matches!(
+            OneOfNProof::<Bls12_381>::verify_many(&mut rng, vec![], &P1),
+            Err(DelegationError::NeedNonZeroSize)
+        ));
+    }
+
+    #[test]
+    fn one_of_n_proof_verification_pairing_count() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let P1 = <Bls12_381 as Pairing>::G1Affine::rand(&mut rng);
+        let (srs, _) = OneOfNSrs::<Bls12_381>::new(&mut rng, &P1);
+
+        let m = 4;
+        let count_decoys = 6;
+        let actual = (0..m).map(|_| G2Affine::rand(&mut rng)).collect::<Vec<_>>();
+        let decoys = (0..count_decoys)
+            .map(|_| (0..m).map(|_| G2Affine::rand(&mut rng)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let witness = Fr::rand(&mut rng);
+        let instance = actual
+            .iter()
+            .map(|b| b.mul(witness).into_affine())
+            .collect::<Vec<_>>();
+        let d = decoys.iter().map(|d| d.as_slice()).collect::<Vec<_>>();
+        let proof = OneOfNProof::new(&mut rng, &actual, d, &instance, &witness, &srs, &P1).unwrap();
+
+        assert_eq!(
+            proof.verification_pairing_count().unwrap(),
+            (count_decoys + 1) * m
+        );
+
+        // Malformed proofs are rejected
+        let mut bad_proof = proof.clone();
+        bad_proof.a[2].pop();
+        assert!(matches!(
+            bad_proof.verification_pairing_count(),
+            Err(DelegationError::UnequalSizeOfSequence(l, e)) if l == m - 1 && e == m
+        ));
+        let mut bad_proof = proof;
+        bad_proof.z.pop();
+        assert!(matches!(
+            bad_proof.verification_pairing_count(),
+            Err(DelegationError::UnequalSizeOfSequence(l, e)) if l == count_decoys && e == count_decoys + 1
+        ));
+    }
+
+    #[test]
+    fn trapdoor_zeroized_on_drop() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let P1 = <Bls12_381 as Pairing>::G1Affine::rand(&mut rng);
+        let (srs, trapdoor) = OneOfNSrs::<Bls12_381>::new(&mut rng, &P1);
+        assert!(!trapdoor.reveal().is_zero());
+        assert_eq!(OneOfNSrs::from_trapdoor(&P1, trapdoor.reveal()), srs);
+
+        let mut trapdoor = ManuallyDrop::new(trapdoor);
+        // SAFETY: The trapdoor is only read after being dropped to check that it was zeroized, its memory is still valid
+        // as it's owned by `ManuallyDrop`
+        unsafe { ManuallyDrop::drop(&mut trapdoor) };
+        assert!(trapdoor.reveal().is_zero());
+    }
 }
@@ -221,8 +221,10 @@ impl<E: Pairing> CredentialShowProtocolWithHiddenPublicKey<E> {
 }
 
 impl<E: Pairing> CredentialShowWithHiddenPublicKey<E> {
-    pub fn verify(
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify<R: RngCore>(
         &self,
+        rng: &mut R,
         challenge: &E::ScalarField,
         disclosed_attributes: Vec<E::ScalarField>,
         possible_public_keys: &[IssuerPublicKey<E>],
@@ -231,7 +233,12 @@ impl<E: Pairing> CredentialShowWithHiddenPublicKey<E> {
         set_comm_srs: impl Into<PreparedSetCommitmentSRS<E>>,
     ) -> Result<(), DelegationError> {
         let set_comm_srs = set_comm_srs.into();
-        self.verify_decoy_pk_proof(possible_public_keys, one_of_n_srs, set_comm_srs.get_P1())?;
+        self.verify_decoy_pk_proof(
+            rng,
+            possible_public_keys,
+            one_of_n_srs,
+            set_comm_srs.get_P1(),
+        )?;
         self.credential_show._verify(
             challenge,
             disclosed_attributes,
@@ -244,8 +251,10 @@ impl<E: Pairing> CredentialShowWithHiddenPublicKey<E> {
         )
     }
 
-    pub fn verify_with_revocation(
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_with_revocation<R: RngCore>(
         &self,
+        rng: &mut R,
         challenge: &E::ScalarField,
         disclosed_attributes: Vec<E::ScalarField>,
         possible_public_keys: &[IssuerPublicKey<E>],
@@ -257,7 +266,12 @@ impl<E: Pairing> CredentialShowWithHiddenPublicKey<E> {
         set_comm_srs: impl Into<PreparedSetCommitmentSRS<E>>,
     ) -> Result<(), DelegationError> {
         let set_comm_srs = set_comm_srs.into();
-        self.verify_decoy_pk_proof(possible_public_keys, one_of_n_srs, set_comm_srs.get_P1())?;
+        self.verify_decoy_pk_proof(
+            rng,
+            possible_public_keys,
+            one_of_n_srs,
+            set_comm_srs.get_P1(),
+        )?;
         self.credential_show._verify(
             challenge,
             disclosed_attributes,
@@ -270,14 +284,16 @@ impl<E: Pairing> CredentialShowWithHiddenPublicKey<E> {
         )
     }
 
-    fn verify_decoy_pk_proof(
+    fn verify_decoy_pk_proof<R: RngCore>(
         &self,
+        rng: &mut R,
         possible_public_keys: &[IssuerPublicKey<E>],
         one_of_n_srs: &OneOfNSrs<E>,
         P1: &E::G1Affine,
     ) -> Result<(), DelegationError> {
         let ipk = &self.pubkey_anonymity_proof.randomized_pk;
         self.pubkey_anonymity_proof.proof.verify(
+            rng,
             possible_public_keys
                 .iter()
                 .map(|p| p.public_key.0.as_slice())
@@ -308,6 +308,7 @@ pub fn show(
                 let mut possible_keys = decoy_issuer_keys.clone();
                 possible_keys.insert(0, ipk);
                 show.verify_with_revocation(
+                    rng,
                     &challenge,
                     disclosed_attrs,
                     &possible_keys,
@@ -323,6 +324,7 @@ pub fn show(
                 let mut possible_keys = decoy_issuer_keys.clone();
                 possible_keys.insert(0, ipk);
                 show.verify(
+                    rng,
                     &challenge,
                     disclosed_attrs,
                     &possible_keys,
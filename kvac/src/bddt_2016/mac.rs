@@ -7,12 +7,23 @@ use crate::{
 use ark_ec::{AffineRepr, CurveGroup};
 use ark_ff::{Field, Zero};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{collections::BTreeMap, ops::Neg, rand::RngCore, vec, vec::Vec, UniformRand};
-use digest::Digest;
-use dock_crypto_utils::{serde_utils::ArkObjectBytes, signature::MultiMessageSignatureParams};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Neg,
+    rand::RngCore,
+    vec,
+    vec::Vec,
+    UniformRand,
+};
+use digest::{Digest, DynDigest};
+use dock_crypto_utils::{
+    serde_utils::ArkObjectBytes, signature::MultiMessageSignatureParams,
+    try_iter::IndexIsOutOfBounds,
+};
 use schnorr_pok::{
     compute_random_oracle_challenge,
     discrete_log::{PokDiscreteLog, PokDiscreteLogProtocol},
+    SchnorrCommitment, SchnorrResponse,
 };
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
@@ -56,6 +67,23 @@ pub struct ProofOfValidityOfMAC<G: AffineRepr> {
     pub sc_pk: PokDiscreteLog<G>,
 }
 
+/// A proof given by the user to the issuer during blind issuance that the user knows an opening of the commitment over
+/// exactly the bases of the committed messages and the blinding base, i.e. the messages `m_i` and `blinding` such that
+/// `commitment = g * blinding + sum(g_vec_i * m_i)` for all committed indices `i`. Without it, the user could fold the
+/// base of a revealed message into the commitment and get a MAC on a different value of that message than the one the
+/// issuer saw. The proof is bound to a nonce chosen by the issuer so that it can't be replayed in another issuance.
+#[serde_as]
+#[derive(
+    Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+pub struct ProofOfKnowledgeOfCommittedMessages<G: AffineRepr> {
+    /// Commitment of the Schnorr protocol (step 1)
+    #[serde_as(as = "ArkObjectBytes")]
+    pub t: G,
+    /// Responses for the committed messages in the order of their indices, followed by the response for the blinding
+    pub response: SchnorrResponse<G>,
+}
+
 impl<G: AffineRepr> MAC<G> {
     pub fn new<R: RngCore>(
         rng: &mut R,
@@ -87,6 +115,18 @@ impl<G: AffineRepr> MAC<G> {
         })
     }
 
+    /// Same as `Self::new` but the messages are given as bytes and encoded to field elements using
+    /// `MACParams::encode_messages_to_field`. To verify the MAC, the messages must be encoded the same way.
+    pub fn new_from_bytes<R: RngCore, D: Default + DynDigest + Clone>(
+        rng: &mut R,
+        messages: &[&[u8]],
+        secret_key: &SecretKey<G::ScalarField>,
+        params: &MACParams<G>,
+    ) -> Result<Self, KVACError> {
+        let messages = MACParams::<G>::encode_messages_to_field::<D>(messages);
+        Self::new(rng, &messages, secret_key, params)
+    }
+
     /// Issuer creates a MAC on some blinded attributes.
     /// This is for "Blind Issuance" mentioned in section 4.2 of the paper with a modification, the issuer does not
     /// contribute any randomness that goes towards `s`
@@ -154,6 +194,31 @@ impl<G: AffineRepr> MAC<G> {
         Ok(())
     }
 
+    /// Verify the MAC without the secret key using the signer's public key. As the public key is in the same group as
+    /// the MAC, it can't be used to check the MAC directly, so this needs the `ProofOfValidityOfMAC` that the signer
+    /// creates for the MAC. This is a weaker check than `verify` as it only convinces that the MAC was created over
+    /// these messages by whoever knows the secret key of `public_key` and relies on the signer having given the proof.
+    /// Also, since the proof is for this specific MAC, it should not be shown to the verifier of a proof of knowledge
+    /// of the MAC as it would make such proofs linkable.
+    pub fn verify_using_public_key<D: Digest>(
+        &self,
+        messages: &[G::ScalarField],
+        proof: &ProofOfValidityOfMAC<G>,
+        public_key: &PublicKey<G>,
+        params: &MACParams<G>,
+    ) -> Result<(), KVACError> {
+        if messages.is_empty() {
+            return Err(KVACError::NoMessageGiven);
+        }
+        if messages.len() != params.supported_message_count() {
+            return Err(KVACError::MessageCountIncompatibleWithMACParams(
+                messages.len(),
+                params.supported_message_count(),
+            ));
+        }
+        proof.verify::<D>(self, messages, public_key, params)
+    }
+
     /// Used to unblind a blinded MAC from signer
     pub fn unblind(self, blinding: &G::ScalarField) -> Self {
         MAC {
@@ -223,6 +288,82 @@ impl<G: AffineRepr> ProofOfValidityOfMAC<G> {
     }
 }
 
+impl<G: AffineRepr> ProofOfKnowledgeOfCommittedMessages<G> {
+    /// Create the proof for a commitment created with `MACParams::commit_to_messages` on `committed_messages`
+    /// with `blinding`. `nonce` is the one given by the issuer.
+    pub fn new<R: RngCore, D: Digest>(
+        rng: &mut R,
+        commitment: &G,
+        committed_messages: BTreeMap<usize, &G::ScalarField>,
+        blinding: &G::ScalarField,
+        nonce: &[u8],
+        params: &MACParams<G>,
+    ) -> Result<Self, KVACError> {
+        let bases = Self::bases(committed_messages.keys().copied(), params)?;
+        let mut witnesses = committed_messages
+            .into_values()
+            .copied()
+            .chain(ark_std::iter::once(*blinding))
+            .collect::<Vec<_>>();
+        let blindings = (0..witnesses.len())
+            .map(|_| G::ScalarField::rand(rng))
+            .collect();
+        let sc = SchnorrCommitment::new(&bases, blindings);
+        let challenge = Self::challenge::<D>(&bases, commitment, &sc.t, nonce)?;
+        let response = sc.response(&witnesses, &challenge)?;
+        witnesses.zeroize();
+        Ok(Self { t: sc.t, response })
+    }
+
+    /// Verify the proof for a commitment to the messages at `committed_indices` and the issuer's `nonce`
+    pub fn verify<D: Digest>(
+        &self,
+        commitment: &G,
+        committed_indices: &BTreeSet<usize>,
+        nonce: &[u8],
+        params: &MACParams<G>,
+    ) -> Result<(), KVACError> {
+        let bases = Self::bases(committed_indices.iter().copied(), params)?;
+        let challenge = Self::challenge::<D>(&bases, commitment, &self.t, nonce)?;
+        self.response
+            .is_valid(&bases, commitment, &self.t, &challenge)
+            .map_err(|_| KVACError::InvalidCommitmentOpeningProof)
+    }
+
+    /// Bases of the committed messages sorted by index, followed by the base for the blinding
+    fn bases(
+        committed_indices: impl Iterator<Item = usize>,
+        params: &MACParams<G>,
+    ) -> Result<Vec<G>, KVACError> {
+        let mut bases = committed_indices
+            .map(|i| {
+                params.g_vec.get(i).copied().ok_or(IndexIsOutOfBounds {
+                    index: i,
+                    length: params.g_vec.len(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        bases.push(params.g);
+        Ok(bases)
+    }
+
+    fn challenge<D: Digest>(
+        bases: &[G],
+        commitment: &G,
+        t: &G,
+        nonce: &[u8],
+    ) -> Result<G::ScalarField, KVACError> {
+        let mut challenge_bytes = vec![];
+        bases.serialize_compressed(&mut challenge_bytes)?;
+        commitment.serialize_compressed(&mut challenge_bytes)?;
+        t.serialize_compressed(&mut challenge_bytes)?;
+        challenge_bytes.extend_from_slice(nonce);
+        Ok(compute_random_oracle_challenge::<G::ScalarField, D>(
+            &challenge_bytes,
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -250,6 +391,170 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn rederive_mac_after_key_rotation() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let message_count = 5;
+        let messages = (0..message_count)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let params = MACParams::<G1Affine>::new::<Blake2b512>(b"test", message_count);
+        let old_sk = SecretKey::new(&mut rng);
+        let mac = MAC::new(&mut rng, &messages, &old_sk, &params).unwrap();
+
+        let new_sk = SecretKey::new(&mut rng);
+        let new_pk = PublicKey::new(&new_sk, &params.g_0);
+        let new_mac = SecretKey::rederive_mac(&mac, &old_sk, &new_sk).unwrap();
+        new_mac.verify(&messages, &new_sk, &params).unwrap();
+        assert!(new_mac.verify(&messages, &old_sk, &params).is_err());
+        assert!(mac.verify(&messages, &new_sk, &params).is_err());
+
+        let proof = ProofOfValidityOfMAC::new::<_, Blake2b512>(
+            &mut rng, &new_mac, &new_sk, &new_pk, &params,
+        );
+        new_mac
+            .verify_using_public_key::<Blake2b512>(&messages, &proof, &new_pk, &params)
+            .unwrap();
+
+        // Rotating back gives the original MAC
+        assert_eq!(
+            SecretKey::rederive_mac(&new_mac, &new_sk, &old_sk).unwrap(),
+            mac
+        );
+
+        // Also works for a MAC on committed messages without knowing them
+        let blinding = Fr::rand(&mut rng);
+        let committed_indices = [0, 2].into_iter().collect::<BTreeSet<_>>();
+        let commitment = params
+            .commit_to_messages(
+                committed_indices.iter().map(|i| (*i, &messages[*i])),
+                &blinding,
+            )
+            .unwrap();
+        let proof = ProofOfKnowledgeOfCommittedMessages::new::<_, Blake2b512>(
+            &mut rng,
+            &commitment,
+            committed_indices.iter().map(|i| (*i, &messages[*i])).collect(),
+            &blinding,
+            b"nonce",
+            &params,
+        )
+        .unwrap();
+        let blinded_mac = old_sk
+            .generate_mac_on_commitment::<_, Blake2b512, _>(
+                &mut rng,
+                &commitment,
+                &proof,
+                b"nonce",
+                &committed_indices,
+                [1, 3, 4].into_iter().map(|i| (i, &messages[i])).collect(),
+                &params,
+            )
+            .unwrap();
+        SecretKey::rederive_mac(&blinded_mac, &old_sk, &new_sk)
+            .unwrap()
+            .unblind(&blinding)
+            .verify(&messages, &new_sk, &params)
+            .unwrap();
+
+        // Can't rederive if the new key is the negation of `e`
+        assert!(matches!(
+            SecretKey::rederive_mac(&mac, &old_sk, &SecretKey(-mac.e)),
+            Err(KVACError::CannotInvert0)
+        ));
+    }
+
+    #[test]
+    fn mac_verification_using_public_key() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let message_count = 5;
+        let messages = (0..message_count)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let params = MACParams::<G1Affine>::new::<Blake2b512>(b"test", message_count);
+        let sk = SecretKey::new(&mut rng);
+        let pk = PublicKey::new(&sk, &params.g_0);
+
+        let mac = MAC::new(&mut rng, &messages, &sk, &params).unwrap();
+        let proof = ProofOfValidityOfMAC::new::<_, Blake2b512>(&mut rng, &mac, &sk, &pk, &params);
+        mac.verify_using_public_key::<Blake2b512>(&messages, &proof, &pk, &params)
+            .unwrap();
+
+        // Tampered MAC
+        let mut tampered = mac.clone();
+        tampered.e += Fr::from(1u64);
+        assert!(matches!(
+            tampered.verify_using_public_key::<Blake2b512>(&messages, &proof, &pk, &params),
+            Err(KVACError::InvalidMACProof)
+        ));
+        let mut tampered = mac.clone();
+        tampered.A = G1Affine::rand(&mut rng);
+        assert!(tampered
+            .verify_using_public_key::<Blake2b512>(&messages, &proof, &pk, &params)
+            .is_err());
+
+        // Different messages
+        let mut other_messages = messages.clone();
+        other_messages[2] = Fr::rand(&mut rng);
+        assert!(mac
+            .verify_using_public_key::<Blake2b512>(&other_messages, &proof, &pk, &params)
+            .is_err());
+
+        // Public key of a different signer
+        let other_pk = PublicKey::new(&SecretKey::new(&mut rng), &params.g_0);
+        assert!(mac
+            .verify_using_public_key::<Blake2b512>(&messages, &proof, &other_pk, &params)
+            .is_err());
+
+        assert!(matches!(
+            mac.verify_using_public_key::<Blake2b512>(&messages[1..], &proof, &pk, &params),
+            Err(KVACError::MessageCountIncompatibleWithMACParams(4, 5))
+        ));
+        assert!(matches!(
+            mac.verify_using_public_key::<Blake2b512>(&[], &proof, &pk, &params),
+            Err(KVACError::NoMessageGiven)
+        ));
+    }
+
+    #[test]
+    fn mac_over_bytes() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        // Includes an empty message and a message longer than the field size
+        let long_msg = vec![7u8; 100];
+        let messages: Vec<&[u8]> = vec![b"name", b"", b"2024-01-01", long_msg.as_slice()];
+        let params = MACParams::<G1Affine>::new::<Blake2b512>(b"test", messages.len() as u32);
+        let sk = SecretKey::new(&mut rng);
+
+        let encoded = MACParams::<G1Affine>::encode_messages_to_field::<Blake2b512>(&messages);
+        assert_eq!(encoded.len(), messages.len());
+        // Encoding is deterministic
+        assert_eq!(
+            encoded,
+            MACParams::<G1Affine>::encode_messages_to_field::<Blake2b512>(&messages)
+        );
+        // Distinct messages have distinct encodings, even when one is a prefix of the other
+        assert_ne!(
+            encoded[3],
+            MACParams::<G1Affine>::encode_messages_to_field::<Blake2b512>(&[&long_msg[..99]])[0]
+        );
+        assert!(MACParams::<G1Affine>::encode_messages_to_field::<Blake2b512>(&[]).is_empty());
+
+        let mac = MAC::new_from_bytes::<_, Blake2b512>(&mut rng, &messages, &sk, &params).unwrap();
+        let re_encoded = MACParams::<G1Affine>::encode_messages_to_field::<Blake2b512>(&messages);
+        mac.verify(&re_encoded, &sk, &params).unwrap();
+
+        // Changing a message fails verification
+        let mut changed = messages.clone();
+        changed[1] = b"x";
+        let changed = MACParams::<G1Affine>::encode_messages_to_field::<Blake2b512>(&changed);
+        assert!(mac.verify(&changed, &sk, &params).is_err());
+
+        assert!(matches!(
+            MAC::new_from_bytes::<_, Blake2b512>(&mut rng, &[], &sk, &params),
+            Err(KVACError::NoMessageGiven)
+        ));
+    }
+
     #[test]
     fn blind_issuance() {
         let mut rng = StdRng::seed_from_u64(0u64);
@@ -299,4 +604,180 @@ mod tests {
         let mac = blinded_mac.unblind(&blinding);
         mac.verify(&messages, &sk, &params).unwrap();
     }
+
+    #[test]
+    fn blind_issuance_with_index_checks() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let message_count = 6;
+        let messages = (0..message_count)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let params = MACParams::<G1Affine>::new::<Blake2b512>(b"test", message_count);
+        let sk = SecretKey::new(&mut rng);
+
+        // Messages with indices 1 and 3 are hidden from the issuer
+        let blinding = Fr::rand(&mut rng);
+        let committed_indices = BTreeSet::from([1, 3]);
+        let commitment = params
+            .commit_to_messages(
+                committed_indices.iter().map(|i| (*i, &messages[*i])),
+                &blinding,
+            )
+            .unwrap();
+        let committed = |indices: &BTreeSet<usize>| {
+            indices
+                .iter()
+                .map(|i| (*i, &messages[*i]))
+                .collect::<BTreeMap<_, _>>()
+        };
+        let nonce = b"issuer's nonce";
+        let proof = ProofOfKnowledgeOfCommittedMessages::new::<_, Blake2b512>(
+            &mut rng,
+            &commitment,
+            committed(&committed_indices),
+            &blinding,
+            nonce,
+            &params,
+        )
+        .unwrap();
+        let revealed = |indices: &[usize]| {
+            indices
+                .iter()
+                .map(|i| (*i, &messages[*i]))
+                .collect::<BTreeMap<_, _>>()
+        };
+
+        let blinded_mac = sk
+            .generate_mac_on_commitment::<_, Blake2b512, _>(
+                &mut rng,
+                &commitment,
+                &proof,
+                nonce,
+                &committed_indices,
+                revealed(&[0, 2, 4, 5]),
+                &params,
+            )
+            .unwrap();
+        assert!(blinded_mac.verify(&messages, &sk, &params).is_err());
+        let mac = blinded_mac.unblind(&blinding);
+        mac.verify(&messages, &sk, &params).unwrap();
+
+        // Unblinding with a different blinding doesn't give a valid MAC
+        let blinded_mac = sk
+            .generate_mac_on_commitment::<_, Blake2b512, _>(
+                &mut rng,
+                &commitment,
+                &proof,
+                nonce,
+                &committed_indices,
+                revealed(&[0, 2, 4, 5]),
+                &params,
+            )
+            .unwrap();
+        assert!(blinded_mac
+            .unblind(&Fr::rand(&mut rng))
+            .verify(&messages, &sk, &params)
+            .is_err());
+
+        assert!(matches!(
+            sk.generate_mac_on_commitment::<_, Blake2b512, _>(
+                &mut rng,
+                &commitment,
+                &proof,
+                nonce,
+                &committed_indices,
+                revealed(&[0, 2, 3, 4, 5]),
+                &params,
+            ),
+            Err(KVACError::MessageIsBothCommittedAndRevealed(3))
+        ));
+        assert!(matches!(
+            sk.generate_mac_on_commitment::<_, Blake2b512, _>(
+                &mut rng,
+                &commitment,
+                &proof,
+                nonce,
+                &committed_indices,
+                revealed(&[0, 2, 5]),
+                &params,
+            ),
+            Err(KVACError::MessageNeitherCommittedNorRevealed(4))
+        ));
+        assert!(matches!(
+            sk.generate_mac_on_commitment::<_, Blake2b512, _>(
+                &mut rng,
+                &commitment,
+                &proof,
+                nonce,
+                &BTreeSet::from([1, 3, 6]),
+                revealed(&[0, 2, 4, 5]),
+                &params,
+            ),
+            Err(KVACError::MessageCountIncompatibleWithMACParams(7, 6))
+        ));
+
+        // The proof doesn't verify with a different nonce or committed indices
+        assert!(matches!(
+            sk.generate_mac_on_commitment::<_, Blake2b512, _>(
+                &mut rng,
+                &commitment,
+                &proof,
+                b"another nonce",
+                &committed_indices,
+                revealed(&[0, 2, 4, 5]),
+                &params,
+            ),
+            Err(KVACError::InvalidCommitmentOpeningProof)
+        ));
+        assert!(matches!(
+            sk.generate_mac_on_commitment::<_, Blake2b512, _>(
+                &mut rng,
+                &commitment,
+                &proof,
+                nonce,
+                &BTreeSet::from([1, 2, 3]),
+                revealed(&[0, 4, 5]),
+                &params,
+            ),
+            Err(KVACError::InvalidCommitmentOpeningProof)
+        ));
+
+        // The user folds the base of revealed message 0 into the commitment so that the MAC would be on
+        // `messages[0] + delta` rather than on the revealed `messages[0]`
+        let delta = Fr::rand(&mut rng);
+        let malicious_commitment = (commitment + params.g_vec[0] * delta).into_affine();
+        let honest_proof = ProofOfKnowledgeOfCommittedMessages::new::<_, Blake2b512>(
+            &mut rng,
+            &malicious_commitment,
+            committed(&committed_indices),
+            &blinding,
+            nonce,
+            &params,
+        )
+        .unwrap();
+        // Proving the opening including message 0 is over different bases
+        let full_proof = ProofOfKnowledgeOfCommittedMessages::new::<_, Blake2b512>(
+            &mut rng,
+            &malicious_commitment,
+            BTreeMap::from([(0, &delta), (1, &messages[1]), (3, &messages[3])]),
+            &blinding,
+            nonce,
+            &params,
+        )
+        .unwrap();
+        for p in [&honest_proof, &full_proof] {
+            assert!(matches!(
+                sk.generate_mac_on_commitment::<_, Blake2b512, _>(
+                    &mut rng,
+                    &malicious_commitment,
+                    p,
+                    nonce,
+                    &committed_indices,
+                    revealed(&[0, 2, 4, 5]),
+                    &params,
+                ),
+                Err(KVACError::InvalidCommitmentOpeningProof)
+            ));
+        }
+    }
 }
@@ -7,3 +7,4 @@ pub mod mac;
 pub mod proof;
 pub mod proof_cdh;
 pub mod setup;
+pub mod weighted_key_sharing;
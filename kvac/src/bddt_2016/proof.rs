@@ -427,6 +427,75 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn proof_of_knowledge_of_MAC_with_wrong_messages() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let message_count = 6;
+        let messages = (0..message_count)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let params = MACParams::<G1Affine>::new::<Blake2b512>(b"test", message_count);
+        let sk = SecretKey::new(&mut rng);
+        let f = G1Affine::rand(&mut rng);
+        let mac = MAC::new(&mut rng, &messages, &sk, &params).unwrap();
+
+        let revealed_indices = BTreeSet::from([1, 4]);
+        let revealed_msgs = revealed_indices
+            .iter()
+            .map(|i| (*i, messages[*i]))
+            .collect::<BTreeMap<_, _>>();
+
+        let prove = |rng: &mut StdRng, msgs: &[Fr]| {
+            let pok = PoKOfMACProtocol::init(
+                rng,
+                &mac,
+                &params,
+                msgs.iter().enumerate().map(|(idx, msg)| {
+                    if revealed_indices.contains(&idx) {
+                        MessageOrBlinding::RevealMessage(msg)
+                    } else {
+                        MessageOrBlinding::BlindMessageRandomly(msg)
+                    }
+                }),
+                f,
+            )
+            .unwrap();
+            let mut chal_bytes = vec![];
+            pok.challenge_contribution(&revealed_msgs, &params, &f, &mut chal_bytes)
+                .unwrap();
+            let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+            pok.gen_proof(&challenge).unwrap()
+        };
+        let verify = |proof: &PoKOfMAC<G1Affine>, revealed_msgs: &BTreeMap<usize, Fr>| {
+            let mut chal_bytes = vec![];
+            proof
+                .challenge_contribution(revealed_msgs, &params, &f, &mut chal_bytes)
+                .unwrap();
+            let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+            proof.verify(revealed_msgs, &challenge, &sk, &params, f)
+        };
+
+        let proof = prove(&mut rng, &messages);
+        verify(&proof, &revealed_msgs).unwrap();
+
+        // Verifier expects a different revealed message
+        let mut wrong_revealed_msgs = revealed_msgs.clone();
+        wrong_revealed_msgs.insert(4, Fr::rand(&mut rng));
+        assert!(verify(&proof, &wrong_revealed_msgs).is_err());
+
+        // Prover swaps 2 hidden messages
+        let mut swapped = messages.clone();
+        swapped.swap(0, 2);
+        let proof = prove(&mut rng, &swapped);
+        assert!(verify(&proof, &revealed_msgs).is_err());
+
+        // Prover replaces a hidden message
+        let mut replaced = messages.clone();
+        replaced[5] = Fr::rand(&mut rng);
+        let proof = prove(&mut rng, &replaced);
+        assert!(verify(&proof, &revealed_msgs).is_err());
+    }
+
     #[test]
     fn test_PoK_multiple_MACs_with_same_msg() {
         // Knowledge of 2 MACs and their corresponding messages is being proven.
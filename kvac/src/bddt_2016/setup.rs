@@ -1,20 +1,36 @@
 use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
-use ark_ff::PrimeField;
+use ark_ff::{
+    field_hashers::{DefaultFieldHasher, HashToField},
+    PrimeField,
+};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{rand::RngCore, vec::Vec};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    rand::RngCore,
+    vec,
+    vec::Vec,
+};
 use core::iter::once;
-use digest::Digest;
+use digest::{Digest, DynDigest};
 use dock_crypto_utils::{
     affine_group_element_from_byte_slices, concat_slices, join,
     misc::{n_projective_group_elements, seq_pairs_satisfy},
     serde_utils::ArkObjectBytes,
 };
 use itertools::process_results;
+use schnorr_pok::{
+    compute_random_oracle_challenge,
+    discrete_log::{PokDiscreteLog, PokDiscreteLogProtocol},
+};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
+use subtle::{Choice, ConstantTimeEq};
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
-use crate::error::KVACError;
+use crate::{
+    bddt_2016::mac::{ProofOfKnowledgeOfCommittedMessages, MAC},
+    error::KVACError,
+};
 use dock_crypto_utils::{iter::pair_valid_items_with_slice, try_iter::CheckLeft};
 
 use dock_crypto_utils::signature::MultiMessageSignatureParams;
@@ -22,6 +38,9 @@ use dock_crypto_utils::signature::MultiMessageSignatureParams;
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
+/// Domain separation tag used when encoding messages given as bytes to field elements
+pub const MESSAGE_ENCODING_DST: &[u8] = b"KVAC-BDDT16-MESSAGE-ENCODING";
+
 /// Public parameters used by the MAC creator and verifier
 #[serde_as]
 #[derive(
@@ -47,6 +66,7 @@ pub struct MACParams<G: AffineRepr> {
     pub g_vec: Vec<G>,
 }
 
+/// Secret key of the signer. Use `ct_eq` to compare secret keys, the derived `PartialEq` isn't constant time.
 #[serde_as]
 #[derive(
     Clone,
@@ -100,6 +120,32 @@ impl<G: AffineRepr> MACParams<G> {
         Self { g_0, g, h, g_vec }
     }
 
+    /// Extend params created with `Self::new` using the same `label` to support `new_message_count` messages. Only the
+    /// generators for the new messages are created so the rest of the params stay the same. Panics if
+    /// `new_message_count` is less than the number of currently supported messages.
+    pub fn extend_to<D: Digest>(&self, new_message_count: u32, label: &[u8]) -> Self {
+        let message_count = self.g_vec.len() as u32;
+        assert!(new_message_count >= message_count);
+        let mut params = self.clone();
+        let new_g: Vec<_> = n_projective_group_elements::<G, D>(
+            message_count + 1..new_message_count + 1,
+            &concat_slices!(label, b" : g_"),
+        )
+        .collect();
+        params.g_vec.append(&mut G::Group::normalize_batch(&new_g));
+        params
+    }
+
+    /// Create params from the given generators instead of hashing a label, like when the generators come from an
+    /// existing trusted setup or are shared with another scheme. None of the generators can be the identity and there
+    /// must be at least one generator for messages in `g_vec`.
+    pub fn new_using_generators(g_0: G, g: G, h: G, g_vec: Vec<G>) -> Result<Self, KVACError> {
+        if g_vec.is_empty() || [g_0, g, h].iter().chain(g_vec.iter()).any(|g| g.is_zero()) {
+            return Err(KVACError::InvalidMACParams);
+        }
+        Ok(Self { g_0, g, h, g_vec })
+    }
+
     /// Commit to given messages using the parameters and the given blinding as a Pedersen commitment.
     /// `indexed_messages_sorted_by_index` must produce items sorted by unique indices, otherwise,
     /// an error will be returned.
@@ -115,7 +161,7 @@ impl<G: AffineRepr> MACParams<G> {
     where
         MI: IntoIterator<Item = (usize, &'a G::ScalarField)>,
     {
-        let (bases, scalars): (Vec<_>, Vec<_>) = process_results(
+        let (bases, mut scalars): (Vec<G>, Vec<G::ScalarField>) = process_results(
             pair_valid_items_with_slice::<_, _, _, KVACError, _>(
                 indexed_messages_sorted_by_index,
                 CheckLeft(seq_pairs_satisfy(|a, b| a < b)),
@@ -124,7 +170,27 @@ impl<G: AffineRepr> MACParams<G> {
             |iter| iter.chain(once((&self.g, blinding))).unzip(),
         )?;
 
-        Ok(G::Group::msm_unchecked(&bases, &scalars).into_affine())
+        let commitment = G::Group::msm_unchecked(&bases, &scalars).into_affine();
+        // The scalars contain the messages and the blinding
+        scalars.zeroize();
+        Ok(commitment)
+    }
+
+    /// Encode messages given as bytes to field elements so that they can be used to create or verify a MAC. Each
+    /// message is hashed to a field element independently using the hash-to-field method of the IETF hash-to-curve
+    /// specification with `MESSAGE_ENCODING_DST` as the domain separation tag. This makes the encoding deterministic
+    /// so the issuer and verifier only need to agree on the hash function `D`. Messages can be of any length,
+    /// including empty and longer than the size of the field. Unlike hashing with try-and-increment, the time
+    /// taken does not depend on the message's value, only on its length.
+    pub fn encode_messages_to_field<D: Default + DynDigest + Clone>(
+        messages: &[&[u8]],
+    ) -> Vec<G::ScalarField> {
+        let hasher =
+            <DefaultFieldHasher<D> as HashToField<G::ScalarField>>::new(MESSAGE_ENCODING_DST);
+        messages
+            .iter()
+            .map(|m| hasher.hash_to_field(m, 1).pop().unwrap())
+            .collect()
     }
 
     /// Compute `b = A*{e+x}`
@@ -161,10 +227,269 @@ impl<F: PrimeField> SecretKey<F> {
     pub fn new<R: RngCore>(rng: &mut R) -> Self {
         Self(F::rand(rng))
     }
+
+    /// Create a MAC for blind issuance where the user gives a commitment to the messages at `committed_indices`, created
+    /// with `MACParams::commit_to_messages`, and the rest of the messages are revealed. The committed and revealed indices
+    /// must not overlap and together must cover all the messages supported by `params`. The user also gives a proof of
+    /// knowledge of the commitment's opening over exactly the committed indices, created for the issuer's `nonce`,
+    /// which is checked before creating the MAC. The returned MAC is blinded and the user gets the actual MAC with
+    /// `MAC::unblind` using the blinding of the commitment, so the issuer never learns the committed messages.
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate_mac_on_commitment<R: RngCore, D: Digest, G: AffineRepr<ScalarField = F>>(
+        &self,
+        rng: &mut R,
+        commitment: &G,
+        proof: &ProofOfKnowledgeOfCommittedMessages<G>,
+        nonce: &[u8],
+        committed_indices: &BTreeSet<usize>,
+        revealed_messages: BTreeMap<usize, &F>,
+        params: &MACParams<G>,
+    ) -> Result<MAC<G>, KVACError> {
+        if let Some(i) = committed_indices
+            .iter()
+            .find(|i| revealed_messages.contains_key(i))
+        {
+            return Err(KVACError::MessageIsBothCommittedAndRevealed(*i));
+        }
+        let count = params.supported_message_count();
+        if let Some(i) = (0..count)
+            .find(|i| !committed_indices.contains(i) && !revealed_messages.contains_key(i))
+        {
+            return Err(KVACError::MessageNeitherCommittedNorRevealed(i));
+        }
+        if committed_indices.len() + revealed_messages.len() != count {
+            return Err(KVACError::MessageCountIncompatibleWithMACParams(
+                committed_indices.len() + revealed_messages.len(),
+                count,
+            ));
+        }
+        proof.verify::<D>(commitment, committed_indices, nonce, params)?;
+        MAC::new_with_committed_messages(rng, commitment, revealed_messages, self, params)
+    }
+
+    /// Convert a MAC created with `old_key` to a MAC on the same messages valid under `new_key`, like when rotating the
+    /// secret key. As `A = b * 1/(e + x)`, the new MAC is `A * (e + x)/(e + x')` with the same `e` and `s`, so neither
+    /// the messages nor `b` need to be known, which also makes this work for MACs issued on committed messages.
+    ///
+    /// This is only meant to be run by the issuer, who knows both keys. The old MAC is not checked so an invalid one
+    /// results in an invalid new MAC. Since `e` and `s` are kept, the old and new MACs are linkable by anyone seeing
+    /// both which is fine as long as MACs themselves are only ever shown in zero knowledge.
+    pub fn rederive_mac<G: AffineRepr<ScalarField = F>>(
+        old_mac: &MAC<G>,
+        old_key: &Self,
+        new_key: &Self,
+    ) -> Result<MAC<G>, KVACError> {
+        let e_plus_new_x_inv = (old_mac.e + new_key.0)
+            .inverse()
+            .ok_or(KVACError::CannotInvert0)?;
+        let A = old_mac.A * ((old_mac.e + old_key.0) * e_plus_new_x_inv);
+        Ok(MAC {
+            A: A.into_affine(),
+            e: old_mac.e,
+            s: old_mac.s,
+        })
+    }
+
+    /// Prove knowledge of the secret key as the discrete log of the public key to base `params.g_0`, so that a
+    /// verifier can be convinced that the public key is correctly created and pin it.
+    pub fn prove_public_key_correctness<R: RngCore, D: Digest, G: AffineRepr<ScalarField = F>>(
+        &self,
+        rng: &mut R,
+        params: &MACParams<G>,
+    ) -> PokDiscreteLog<G> {
+        let public_key = PublicKey::new(self, &params.g_0);
+        let protocol = PokDiscreteLogProtocol::init(self.0, F::rand(rng), &params.g_0);
+        let mut challenge_bytes = vec![];
+        protocol
+            .challenge_contribution(&params.g_0, &public_key.0, &mut challenge_bytes)
+            .unwrap();
+        let challenge = compute_random_oracle_challenge::<F, D>(&challenge_bytes);
+        protocol.gen_proof(&challenge)
+    }
+}
+
+impl<F: PrimeField> ConstantTimeEq for SecretKey<F> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0
+            .into_bigint()
+            .as_ref()
+            .ct_eq(other.0.into_bigint().as_ref())
+    }
 }
 
 impl<G: AffineRepr> PublicKey<G> {
     pub fn new<'a>(sk: &SecretKey<G::ScalarField>, g_0: impl Into<&'a G>) -> Self {
         Self((g_0.into().mul_bigint(sk.0.into_bigint())).into_affine())
     }
+
+    /// Verify the proof created with `SecretKey::prove_public_key_correctness`
+    pub fn verify_correctness_proof<D: Digest>(
+        &self,
+        proof: &PokDiscreteLog<G>,
+        params: &MACParams<G>,
+    ) -> Result<(), KVACError> {
+        let mut challenge_bytes = vec![];
+        proof.challenge_contribution(&params.g_0, &self.0, &mut challenge_bytes)?;
+        let challenge = compute_random_oracle_challenge::<G::ScalarField, D>(&challenge_bytes);
+        if !proof.verify(&self.0, &params.g_0, &challenge) {
+            return Err(KVACError::InvalidSchnorrProof);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::{Fr, G1Affine};
+    use ark_std::{
+        rand::{rngs::StdRng, SeedableRng},
+        UniformRand,
+    };
+    use blake2::Blake2b512;
+
+    #[test]
+    fn secret_key_constant_time_equality() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let sk = SecretKey::<Fr>::new(&mut rng);
+        let other = SecretKey::<Fr>::new(&mut rng);
+        assert!(bool::from(sk.ct_eq(&sk.clone())));
+        assert!(!bool::from(sk.ct_eq(&other)));
+        assert!(!bool::from(sk.ct_eq(&SecretKey(sk.0 + Fr::from(1u64)))));
+    }
+
+    #[test]
+    fn commitment_to_messages() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let params = MACParams::<G1Affine>::new::<Blake2b512>(b"test", 5);
+        let messages = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let blinding = Fr::rand(&mut rng);
+
+        let comm = params
+            .commit_to_messages([(1, &messages[1]), (3, &messages[3])], &blinding)
+            .unwrap();
+        assert_eq!(
+            comm,
+            (params.g * blinding + params.g_vec[1] * messages[1] + params.g_vec[3] * messages[3])
+                .into_affine()
+        );
+        // The inputs are unaffected by zeroing the intermediate scalars
+        assert_eq!(
+            comm,
+            params
+                .commit_to_messages([(1, &messages[1]), (3, &messages[3])], &blinding)
+                .unwrap()
+        );
+
+        assert!(params
+            .commit_to_messages([(3, &messages[3]), (1, &messages[1])], &blinding)
+            .is_err());
+        assert!(params
+            .commit_to_messages([(5, &messages[0])], &blinding)
+            .is_err());
+    }
+
+    #[test]
+    fn extending_params() {
+        let params = MACParams::<G1Affine>::new::<Blake2b512>(b"test", 10);
+        let extended = params.extend_to::<Blake2b512>(12, b"test");
+        assert_eq!(extended.supported_message_count(), 12);
+        assert_eq!(extended.g_0, params.g_0);
+        assert_eq!(extended.g, params.g);
+        assert_eq!(extended.h, params.h);
+        assert_eq!(extended.g_vec[..10], params.g_vec[..]);
+        // Same as creating params for 12 messages directly
+        assert_eq!(
+            extended,
+            MACParams::<G1Affine>::new::<Blake2b512>(b"test", 12)
+        );
+        assert_eq!(params.extend_to::<Blake2b512>(10, b"test"), params);
+
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let messages = (0..12).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let sk = SecretKey::new(&mut rng);
+        let mac = MAC::new(&mut rng, &messages, &sk, &extended).unwrap();
+        mac.verify(&messages, &sk, &extended).unwrap();
+    }
+
+    #[test]
+    fn params_using_generators() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let hashed = MACParams::<G1Affine>::new::<Blake2b512>(b"test", 4);
+
+        let params =
+            MACParams::new_using_generators(hashed.g_0, hashed.g, hashed.h, hashed.g_vec.clone())
+                .unwrap();
+        assert_eq!(params, hashed);
+
+        let g_vec = (0..3).map(|_| G1Affine::rand(&mut rng)).collect::<Vec<_>>();
+        let params = MACParams::new_using_generators(
+            G1Affine::rand(&mut rng),
+            G1Affine::rand(&mut rng),
+            G1Affine::rand(&mut rng),
+            g_vec.clone(),
+        )
+        .unwrap();
+        assert_eq!(params.supported_message_count(), 3);
+        let messages = (0..3).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let blinding = Fr::rand(&mut rng);
+        let comm = params
+            .commit_to_messages(messages.iter().enumerate(), &blinding)
+            .unwrap();
+        assert_eq!(
+            comm,
+            (params.g * blinding
+                + g_vec[0] * messages[0]
+                + g_vec[1] * messages[1]
+                + g_vec[2] * messages[2])
+                .into_affine()
+        );
+        let sk = SecretKey::new(&mut rng);
+        let mac = MAC::new(&mut rng, &messages, &sk, &params).unwrap();
+        mac.verify(&messages, &sk, &params).unwrap();
+
+        assert!(matches!(
+            MACParams::new_using_generators(hashed.g_0, hashed.g, hashed.h, vec![]),
+            Err(KVACError::InvalidMACParams)
+        ));
+        assert!(matches!(
+            MACParams::new_using_generators(G1Affine::zero(), hashed.g, hashed.h, g_vec.clone()),
+            Err(KVACError::InvalidMACParams)
+        ));
+        assert!(matches!(
+            MACParams::new_using_generators(hashed.g_0, hashed.g, G1Affine::zero(), g_vec.clone()),
+            Err(KVACError::InvalidMACParams)
+        ));
+        let mut with_identity = g_vec;
+        with_identity[1] = G1Affine::zero();
+        assert!(matches!(
+            MACParams::new_using_generators(hashed.g_0, hashed.g, hashed.h, with_identity),
+            Err(KVACError::InvalidMACParams)
+        ));
+    }
+
+    #[test]
+    fn public_key_correctness_proof() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let params = MACParams::<G1Affine>::new::<Blake2b512>(b"test", 5);
+        let sk = SecretKey::<Fr>::new(&mut rng);
+        let pk = PublicKey::new(&sk, &params.g_0);
+
+        let proof = sk.prove_public_key_correctness::<_, Blake2b512, _>(&mut rng, &params);
+        pk.verify_correctness_proof::<Blake2b512>(&proof, &params)
+            .unwrap();
+
+        // Public key of a different secret key
+        let other_pk = PublicKey::new(&SecretKey::<Fr>::new(&mut rng), &params.g_0);
+        assert!(matches!(
+            other_pk.verify_correctness_proof::<Blake2b512>(&proof, &params),
+            Err(KVACError::InvalidSchnorrProof)
+        ));
+
+        // Different params
+        let other_params = MACParams::<G1Affine>::new::<Blake2b512>(b"test-1", 5);
+        assert!(pk
+            .verify_correctness_proof::<Blake2b512>(&proof, &other_params)
+            .is_err());
+    }
 }
@@ -0,0 +1,229 @@
+//! Weighted Shamir secret sharing of the secret key among issuer parties. Each party has an integer weight and is
+//! given as many Shamir shares of the secret key as its weight, so a party with weight 2 counts as 2 parties. The
+//! secret key can be reconstructed, and a MAC issued with it, when the summed weight of the participating parties is
+//! at least the threshold.
+//!
+//! This is not threshold issuance. The party combining the shares reconstructs and thus learns the secret key, so
+//! it must be trusted with the key. The parties don't create partial MACs from their shares.
+
+use crate::{
+    bddt_2016::{
+        mac::MAC,
+        setup::{MACParams, SecretKey},
+    },
+    error::KVACError,
+};
+use ark_ec::AffineRepr;
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{collections::BTreeSet, rand::RngCore, vec::Vec};
+use secret_sharing_and_dkg::{
+    common::{ParticipantId, Share, ShareId, Shares},
+    error::SSError,
+    shamir_ss,
+};
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Shares of the secret key held by a party. The number of shares is the party's weight.
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Zeroize,
+    ZeroizeOnDrop,
+    CanonicalSerialize,
+    CanonicalDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(bound = "")]
+pub struct WeightedSecretKeyShare<F: PrimeField> {
+    #[zeroize(skip)]
+    pub party_id: ParticipantId,
+    pub shares: Vec<Share<F>>,
+}
+
+impl<F: PrimeField> WeightedSecretKeyShare<F> {
+    pub fn weight(&self) -> ShareId {
+        self.shares.len() as ShareId
+    }
+}
+
+/// Share the secret key among parties with the given weights such that parties with summed weight of at least
+/// `threshold` can reconstruct it. Party `i` has weight `weights[i]` and gets party id `i + 1`.
+pub fn deal_weighted_secret_key_shares<R: RngCore, F: PrimeField>(
+    rng: &mut R,
+    secret_key: &SecretKey<F>,
+    weights: &[ShareId],
+    threshold: ShareId,
+) -> Result<Vec<WeightedSecretKeyShare<F>>, KVACError> {
+    if weights.contains(&0) {
+        return Err(KVACError::ZeroWeight);
+    }
+    let total = weights.iter().map(|w| *w as u64).sum::<u64>();
+    if total > ShareId::MAX as u64 {
+        return Err(KVACError::InvalidThresholdOrTotalWeight(threshold, total));
+    }
+    let (shares, _) = shamir_ss::deal_secret(rng, secret_key.0, threshold, total as ShareId)
+        .map_err(|_| KVACError::InvalidThresholdOrTotalWeight(threshold, total))?;
+    let mut shares = shares.0.into_iter();
+    Ok(weights
+        .iter()
+        .enumerate()
+        .map(|(i, w)| WeightedSecretKeyShare {
+            party_id: i as ParticipantId + 1,
+            shares: shares.by_ref().take(*w as usize).collect(),
+        })
+        .collect())
+}
+
+/// Reconstruct the secret key from the shares of the participating parties. Fails if the summed weight of the
+/// parties is less than the threshold or if a share id is given more than once.
+pub fn reconstruct_secret_key<F: PrimeField>(
+    party_shares: &[WeightedSecretKeyShare<F>],
+) -> Result<SecretKey<F>, KVACError> {
+    let mut party_ids = BTreeSet::new();
+    let mut share_ids = BTreeSet::new();
+    let mut shares = Vec::new();
+    for p in party_shares {
+        if !party_ids.insert(p.party_id) {
+            return Err(KVACError::DuplicateParty(p.party_id));
+        }
+        // Shares with the same id would be skipped during interpolation, giving a wrong key
+        for s in &p.shares {
+            if !share_ids.insert(s.id) {
+                return Err(KVACError::DuplicateShareId(s.id));
+            }
+        }
+        shares.extend(p.shares.iter().cloned());
+    }
+    let threshold = shares
+        .first()
+        .map(|s| s.threshold)
+        .ok_or(KVACError::NoShareGiven)?;
+    if shares.iter().any(|s| s.threshold != threshold) {
+        return Err(KVACError::UnequalThresholdInShares);
+    }
+    let weight = shares.len() as ShareId;
+    Shares(shares)
+        .reconstruct_secret()
+        .map(SecretKey)
+        .map_err(|e| match e {
+            SSError::BelowThreshold(t, _) => KVACError::BelowThreshold(weight, t),
+            _ => KVACError::InvalidShares,
+        })
+}
+
+impl<G: AffineRepr> MAC<G> {
+    /// Reconstruct the secret key from the shares of the participating parties and create a MAC with it. The
+    /// caller learns the secret key. Fails if the summed weight of the parties is less than the threshold.
+    pub fn new_by_reconstructing_secret_key<R: RngCore>(
+        rng: &mut R,
+        messages: &[G::ScalarField],
+        party_shares: &[WeightedSecretKeyShare<G::ScalarField>],
+        params: &MACParams<G>,
+    ) -> Result<Self, KVACError> {
+        let sk = reconstruct_secret_key(party_shares)?;
+        Self::new(rng, messages, &sk, params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::{Fr, G1Affine};
+    use ark_std::{
+        rand::{prelude::StdRng, SeedableRng},
+        UniformRand,
+    };
+    use blake2::Blake2b512;
+
+    #[test]
+    fn weighted_secret_key_sharing() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let message_count = 5;
+        let messages = (0..message_count)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let params = MACParams::<G1Affine>::new::<Blake2b512>(b"test", message_count);
+        let sk = SecretKey::new(&mut rng);
+
+        // The 1st party counts as 2 parties
+        let weights = [2, 1, 1, 1];
+        let threshold = 3;
+        let party_shares =
+            deal_weighted_secret_key_shares(&mut rng, &sk, &weights, threshold).unwrap();
+        assert_eq!(party_shares.len(), weights.len());
+        for (p, w) in party_shares.iter().zip(weights) {
+            assert_eq!(p.weight(), w);
+        }
+
+        // Weight-2 party with any other party meets the threshold
+        for i in 1..4 {
+            let participants = [party_shares[0].clone(), party_shares[i].clone()];
+            assert_eq!(reconstruct_secret_key(&participants).unwrap(), sk);
+            let mac =
+                MAC::new_by_reconstructing_secret_key(&mut rng, &messages, &participants, &params)
+                    .unwrap();
+            mac.verify(&messages, &sk, &params).unwrap();
+        }
+
+        // 3 weight-1 parties also meet the threshold
+        assert_eq!(reconstruct_secret_key(&party_shares[1..4]).unwrap(), sk);
+
+        // 2 weight-1 parties alone don't
+        for (i, j) in [(1, 2), (1, 3), (2, 3)] {
+            let participants = [party_shares[i].clone(), party_shares[j].clone()];
+            assert!(matches!(
+                reconstruct_secret_key(&participants),
+                Err(KVACError::BelowThreshold(2, 3))
+            ));
+            assert!(MAC::new_by_reconstructing_secret_key(
+                &mut rng,
+                &messages,
+                &participants,
+                &params
+            )
+            .is_err());
+        }
+
+        // Weight-2 party alone doesn't
+        assert!(matches!(
+            reconstruct_secret_key(&party_shares[0..1]),
+            Err(KVACError::BelowThreshold(2, 3))
+        ));
+
+        // A party can't be counted twice
+        let participants = [
+            party_shares[1].clone(),
+            party_shares[1].clone(),
+            party_shares[2].clone(),
+        ];
+        assert!(matches!(
+            reconstruct_secret_key(&participants),
+            Err(KVACError::DuplicateParty(2))
+        ));
+
+        // A party can't resubmit another party's share as its own
+        let mut stolen = party_shares[2].clone();
+        stolen.shares.push(party_shares[1].shares[0].clone());
+        let participants = [party_shares[1].clone(), stolen];
+        assert!(matches!(
+            reconstruct_secret_key(&participants),
+            Err(KVACError::DuplicateShareId(id)) if id == party_shares[1].shares[0].id
+        ));
+
+        // Nor repeat its own share
+        let mut repeated = party_shares[0].clone();
+        repeated.shares.push(repeated.shares[0].clone());
+        assert!(matches!(
+            reconstruct_secret_key(&[repeated]),
+            Err(KVACError::DuplicateShareId(_))
+        ));
+
+        assert!(deal_weighted_secret_key_shares(&mut rng, &sk, &[2, 0, 1], 2).is_err());
+        assert!(deal_weighted_secret_key_shares(&mut rng, &sk, &weights, 6).is_err());
+    }
+}
@@ -22,6 +22,24 @@ pub enum KVACError {
     InvalidDelegatedProof,
     InvalidSchnorrProof,
     InvalidMsgIdxForResponse(usize),
+    ZeroWeight,
+    InvalidThresholdOrTotalWeight(u16, u64),
+    NoShareGiven,
+    DuplicateParty(u16),
+    /// Id of a share given more than once, possibly by different parties
+    DuplicateShareId(u16),
+    UnequalThresholdInShares,
+    /// Summed weight of the participating parties and the threshold
+    BelowThreshold(u16, u16),
+    InvalidShares,
+    /// Index of a message both in the commitment and the revealed messages during blind issuance
+    MessageIsBothCommittedAndRevealed(usize),
+    /// Index of a message neither in the commitment nor the revealed messages during blind issuance
+    MessageNeitherCommittedNorRevealed(usize),
+    /// MAC params with an identity element or without any generators for messages
+    InvalidMACParams,
+    /// Proof of knowledge of the opening of the commitment given during blind issuance is invalid
+    InvalidCommitmentOpeningProof,
 }
 
 impl From<InvalidPair<usize>> for KVACError {
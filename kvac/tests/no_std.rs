@@ -0,0 +1,42 @@
+//! Uses the BDDT 2016 params without the standard library, only `core` and `alloc`. Build and run with
+//! `cargo test -p kvac --no-default-features` to check the crate itself doesn't need the standard library either.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use ark_bls12_381::{Fr, G1Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_std::{
+    rand::{rngs::StdRng, SeedableRng},
+    UniformRand,
+};
+use blake2::Blake2b512;
+use kvac::bddt_2016::setup::MACParams;
+
+#[test]
+fn params_and_commitment_without_std() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let params = MACParams::<G1Affine>::new::<Blake2b512>(b"test", 5);
+    let messages = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+    let blinding = Fr::rand(&mut rng);
+
+    let comm = params
+        .commit_to_messages(messages.iter().enumerate(), &blinding)
+        .unwrap();
+    assert_eq!(
+        comm,
+        (params.g * blinding
+            + params
+                .g_vec
+                .iter()
+                .zip(messages.iter())
+                .map(|(g, m)| *g * m)
+                .sum::<<G1Affine as AffineRepr>::Group>())
+        .into_affine()
+    );
+
+    let b = params.b(messages.iter().enumerate(), &blinding).unwrap();
+    assert_eq!(b, comm + params.h);
+}
@@ -105,6 +105,12 @@ impl<E: Pairing> R1CS<E> {
     ) -> Result<Self, crate::circom::CircomError> {
         Ok(R1CSFile::new_from_file(path)?.into())
     }
+
+    /// Parse the contents of a .r1cs file, like when the file can't be read from disk
+    #[cfg(feature = "std")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, crate::circom::CircomError> {
+        Ok(R1CSFile::new(std::io::Cursor::new(bytes))?.into())
+    }
 }
 
 mod serialization {
@@ -2,6 +2,7 @@ pub const COMPOSITE_PROOF_LABEL: &'static [u8; 15] = b"composite-proof";
 pub const COMPOSITE_PROOF_CHALLENGE_LABEL: &'static [u8; 25] = b"composite-proof-challenge";
 pub const NONCE_LABEL: &'static [u8; 5] = b"nonce";
 pub const CONTEXT_LABEL: &'static [u8; 7] = b"context";
+pub const CREATED_AT_LABEL: &'static [u8; 10] = b"created-at";
 pub const BBS_PLUS_LABEL: &'static [u8; 4] = b"BBS+";
 pub const BBS_23_LABEL: &'static [u8; 5] = b"BBS23";
 pub const VB_ACCUM_MEM_LABEL: &'static [u8; 25] = b"VB-accumulator-membership";
@@ -19,3 +20,6 @@ pub const KB_UNI_ACCUM_CDH_NON_MEM_LABEL: &'static [u8; 43] =
     b"KB-universal-accumulator-CDH-non-membership";
 pub const KB_POS_ACCUM_MEM_LABEL: &'static [u8; 34] = b"KB-positive-accumulator-membership";
 pub const KB_POS_ACCUM_CDH_MEM_LABEL: &'static [u8; 38] = b"KB-positive-accumulator-CDH-membership";
+pub const SCOPED_PSEUDONYM_LABEL: &'static [u8; 16] = b"scoped-pseudonym";
+pub const PED_COMM_HIDDEN_BASES_LABEL: &'static [u8; 32] = b"Pedersen-commitment-hidden-bases";
+pub const BDDT16_MAC_EXTRA_BASE_LABEL: &'static [u8; 27] = b"BDDT16-MAC-proof-extra-base";
@@ -1,8 +1,16 @@
 use ark_serialize::SerializationError;
-use ark_std::{collections::BTreeSet, fmt::Debug, string::String, vec::Vec};
+use ark_std::{
+    boxed::Box,
+    collections::BTreeSet,
+    fmt::{self, Debug, Display},
+    io,
+    string::String,
+    vec::Vec,
+};
 use bbs_plus::error::BBSPlusError;
 use bulletproofs_plus_plus::error::BulletproofsPlusPlusError;
 use dock_crypto_utils::try_iter::InvalidPair;
+use kvac::error::KVACError;
 use legogroth16::{circom::CircomError, error::Error as LegoGroth16Error};
 use saver::error::SaverError;
 use schnorr_pok::error::SchnorrError;
@@ -67,6 +75,8 @@ pub enum ProofSystemError {
     LegoGroth16ProvingKeyNotProvided,
     LegoGroth16VerifyingKeyNotProvided,
     BoundCheckMaxNotGreaterThanMin,
+    /// The sum of the 2 values doesn't fit in a u64
+    BoundCheckSumOverflow(u64, u64),
     IncompatibleR1CSSetupParamAtIndex(usize),
     CircomError(CircomError),
     R1CSInsufficientPrivateInputs(usize, usize),
@@ -103,6 +113,174 @@ pub enum ProofSystemError {
     DetachedVBAccumProofContributionFailed(u32, VBAccumulatorError),
     IncorrectEncryptedAccumulator,
     KBAccumProofContributionFailed(u32, VBAccumulatorError),
+    /// The verifier's statement can't be created from the prover's statement as it needs data the prover
+    /// doesn't have or the prover-only data is given as a reference in `SetupParams`
+    CannotDeriveVerifierStatement,
+    /// A serialized `Statement` has a tag that doesn't correspond to any known statement, like when it was
+    /// created by a newer version. Contains the tag and the largest known tag.
+    UnknownStatementTag(u8, u8),
+    UnequalCoefficientAndDisclosedCount(usize, usize),
+    /// Proof has more statement proofs than the number of statements. Contains the number of statements and
+    /// the number of statement proofs.
+    TooManyStatementProofs(usize, usize),
+    /// Proof was expected to have a creation time but doesn't
+    MissingProofCreationTime,
+    /// Proof's creation time is later than the verifier's current time. Contains the creation time and the current time.
+    ProofCreatedInFuture(u64, u64),
+    /// Proof is older than the allowed age. Contains the creation time, the current time and the maximum allowed age.
+    ProofExpired(u64, u64, u64),
+    /// Bloom filter should have a power of 2 number of bits and at least 1 hash function. Contains the number of bits
+    /// and the number of hash functions.
+    InvalidBloomFilterParams(usize, u32),
+    IncompatibleBloomMembershipSetupParamAtIndex(usize),
+    /// Witness of the statement at given index is not in the Bloom filter
+    ElementNotInBloomFilter(usize),
+    /// Proving order needs at least 2 values. Contains the number of values given.
+    InsufficientValuesForMonotonic(usize),
+    /// Statement index, number of values expected by the statement and number of values found
+    UnequalWitnessCountForMonotonic(usize, usize, usize),
+    /// Statement index and the index of the first value which is not in order with the next value
+    MonotonicValuesNotInOrder(usize, usize),
+    /// Bytes given to `serialization::from_bytes` don't have the expected header
+    InvalidSerializationHeader(InvalidSerializationHeader),
+    /// JSON given to `Statements::from_canonical_json` couldn't be decoded. Contains the reason.
+    InvalidStatementsJson(String),
+    /// Tag of the statement at given index in the JSON doesn't match the statement's variant. Contains the
+    /// statement index, the tag in the JSON and the tag of the variant.
+    StatementTagMismatchInJson(usize, u8, u8),
+    /// Statement index and the number of statements
+    InvalidStatementIndex(usize, usize),
+    /// The set for proving membership in a public set is empty
+    EmptyPublicSet,
+    /// Witness of the statement at given index is not in its public set
+    MessageNotInPublicSet(usize),
+    /// Statement index, size of the public set and the number of challenges or responses in the proof
+    UnequalPublicSetAndProofSize(usize, usize, usize),
+    PublicSetMembershipProofContributionFailed(usize),
+    /// Item at given index given for batch verification isn't a Schnorr protocol with its proof
+    UnsupportedForBatchVerification(usize),
+    /// At least one of the Schnorr proofs verified in a batch is invalid
+    BatchedSchnorrProofContributionsFailed,
+    /// Statement at given index is only used by the other role than the one the statements are meant for.
+    /// Contains the statement index and the name of its variant.
+    StatementOfWrongRole(usize, &'static str),
+    /// Aggregated bound check needs bounds for at least 1 message
+    NoBoundsForAggregatedBoundCheck,
+    /// Statement index, number of values expected by the aggregated bound check statement and number of values found
+    UnequalWitnessCountForAggregatedBoundCheck(usize, usize, usize),
+    /// Verifier's statement of a bound check with keyed verification needs the secret key
+    MissingSecretKeyForKeyedVerification,
+    /// Index of the message to hide and the number of messages
+    HiddenMessageIndexOutOfRange(usize, usize),
+    /// No public keys were given for a PS multi-signature or they support different numbers of messages
+    CannotAggregatePSPublicKeys,
+    /// Statement index, revealed position and the number of values in the partially opened Pedersen commitment
+    RevealedPositionOutOfRange(usize, usize, usize),
+    /// The witness of the partial Pedersen commitment opening at given index doesn't have exactly the values at
+    /// the hidden positions
+    IncorrectHiddenPositionsForPartialOpening(usize),
+    /// All values of the Pedersen commitment at given index are revealed but they don't open it
+    RevealedValuesDontOpenCommitment(usize),
+    /// Verifying the proof of the statement at `index` failed with `source`. `statement_kind` is the name of the
+    /// statement's variant. Failures of pairing checks deferred to the end of verification, like when using a
+    /// randomized pairing checker, aren't attributed to a statement.
+    StatementVerificationFailed {
+        index: usize,
+        statement_kind: &'static str,
+        source: Box<ProofSystemError>,
+    },
+    /// Statement index, chunk bit size of the Saver statement, number of chunks a message has with that chunk
+    /// bit size and the number of chunks in the ciphertext. Happens when the prover used a different chunk bit size
+    SaverChunkMismatch(usize, u8, usize, usize),
+    /// Statement at given index has a group element that isn't on the curve or not in the prime order subgroup
+    InvalidGroupElementInStatement(usize),
+    /// Statement index, position of a committed value said to be equal to a signed message and the number of
+    /// values in the commitment
+    CommitmentPositionOutOfRange(usize, usize, usize),
+    /// Statement index, index of the circuit's private input in a witness equality and the number of private
+    /// inputs committed by the SNARK
+    R1CSInputNotCommitted(usize, usize, usize),
+    IncompatibleBDDT16SetupParamAtIndex(usize),
+    BDDT16ProtocolInvalidMessageCount(usize, usize),
+    BDDT16ProtocolNonSequentialMessageIndices(InvalidPair<usize>),
+    BDDT16ProtocolInvalidBlindingIndex(usize),
+    BDDT16ProtocolMessageIndicesMustStartFromZero(usize),
+    KVACError(KVACError),
+    BDDT16ProofContributionFailed(u32, KVACError),
+    /// Number of bases committed to in a Pedersen commitment with hidden bases and the number of scalars or
+    /// revealed bases
+    UnequalHiddenBasesAndScalarCount(usize, usize),
+    /// Revealed bases of a Pedersen commitment with hidden bases don't match the commitment to the bases
+    RevealedBasesDontMatchCommitment,
+    /// Statement at the first index can't be removed as the statement at the second index refers to it
+    RemovedStatementIsReferenced(usize, usize),
+    /// Base of a `DisclosedLinearCombination` is the identity element, which would make the proof hold for any message
+    DisclosedLinearCombinationBaseIsIdentity,
+    /// Statement index and the index of the statement it refers to for a signed message, which isn't a statement
+    /// proving knowledge of a signature or MAC
+    NotASignatureStatement(usize, usize),
+    /// Statement index, index of the signed message it refers to and the number of messages supported by the
+    /// signature params
+    SignedMessageIndexOutOfRange(usize, usize, usize),
+}
+
+/// Returned (wrapped in `SerializationError::IoError`) when deserializing a `Statement` whose tag
+/// doesn't correspond to any known statement. Converting the `SerializationError` to `ProofSystemError`
+/// gives `ProofSystemError::UnknownStatementTag`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnknownStatementTag {
+    pub tag: u8,
+    pub max_known_tag: u8,
+}
+
+impl Display for UnknownStatementTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown statement tag {}, largest known tag is {}",
+            self.tag, self.max_known_tag
+        )
+    }
+}
+
+impl ark_std::error::Error for UnknownStatementTag {}
+
+impl From<UnknownStatementTag> for SerializationError {
+    fn from(e: UnknownStatementTag) -> Self {
+        SerializationError::IoError(io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// Returned (wrapped in `SerializationError::IoError`) by `serialization::from_bytes` when the bytes don't start
+/// with the expected header. Converting the `SerializationError` to `ProofSystemError` gives
+/// `ProofSystemError::InvalidSerializationHeader`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvalidSerializationHeader {
+    /// The bytes don't start with `serialization::MAGIC`, like when they were not created by `to_bytes`
+    MissingMagic,
+    /// The bytes were serialized with a different format version
+    UnsupportedFormatVersion { found: u8, expected: u8 },
+}
+
+impl Display for InvalidSerializationHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingMagic => write!(f, "missing or invalid serialization header"),
+            Self::UnsupportedFormatVersion { found, expected } => write!(
+                f,
+                "serialized with format version {}, expected {}",
+                found, expected
+            ),
+        }
+    }
+}
+
+impl ark_std::error::Error for InvalidSerializationHeader {}
+
+impl From<InvalidSerializationHeader> for SerializationError {
+    fn from(e: InvalidSerializationHeader) -> Self {
+        SerializationError::IoError(io::Error::new(io::ErrorKind::InvalidData, e))
+    }
 }
 
 impl From<SchnorrError> for ProofSystemError {
@@ -137,6 +315,22 @@ impl From<LegoGroth16Error> for ProofSystemError {
 
 impl From<SerializationError> for ProofSystemError {
     fn from(e: SerializationError) -> Self {
+        // Only `std` errors can be downcasted so without `std` this remains a `Serialization` error
+        #[cfg(feature = "std")]
+        if let SerializationError::IoError(err) = &e {
+            if let Some(t) = err
+                .get_ref()
+                .and_then(|inner| inner.downcast_ref::<UnknownStatementTag>())
+            {
+                return Self::UnknownStatementTag(t.tag, t.max_known_tag);
+            }
+            if let Some(h) = err
+                .get_ref()
+                .and_then(|inner| inner.downcast_ref::<InvalidSerializationHeader>())
+            {
+                return Self::InvalidSerializationHeader(*h);
+            }
+        }
         Self::Serialization(e)
     }
 }
@@ -159,6 +353,12 @@ impl From<BulletproofsPlusPlusError> for ProofSystemError {
     }
 }
 
+impl From<KVACError> for ProofSystemError {
+    fn from(e: KVACError) -> Self {
+        Self::KVACError(e)
+    }
+}
+
 impl From<SmcRangeProofError> for ProofSystemError {
     fn from(e: SmcRangeProofError) -> Self {
         Self::SetMembershipBasedRangeProof(e)
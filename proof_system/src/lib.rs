@@ -167,13 +167,17 @@ pub mod meta_statement;
 pub mod proof;
 pub mod proof_spec;
 pub mod prover;
+pub mod serialization;
 pub mod statement;
 pub mod statement_proof;
 pub mod sub_protocols;
+pub mod timings;
 pub mod verifier;
 pub mod witness;
 
 pub mod prelude {
+    #[cfg(feature = "timings")]
+    pub use crate::timings::TimingReport;
     pub use crate::{
         error::ProofSystemError, meta_statement::*, proof::*, proof_spec::*, prover::*,
         setup_params::*, statement::*, statement_proof::*,
@@ -80,6 +80,44 @@ impl MetaStatements {
         self.add(MetaStatement::WitnessEquality(item))
     }
 
+    /// Add a witness equality stating that message `msg_a` of the signature in statement `sig_a_idx` is
+    /// equal to message `msg_b` of the signature in statement `sig_b_idx`. The signatures can be from
+    /// different signers with different params and public keys, like when linking credentials issued by
+    /// different issuers. They can also be of different schemes, like a BBS+ signature (`PoKBBSSignatureG1`)
+    /// and a BBS signature (`PoKBBSSignature23G1`) when migrating credentials from one to the other, as
+    /// the equality is checked on the Schnorr responses for the messages which both schemes produce.
+    /// Returns the index of the added meta statement.
+    pub fn link_across_signatures(
+        &mut self,
+        sig_a_idx: usize,
+        msg_a: usize,
+        sig_b_idx: usize,
+        msg_b: usize,
+    ) -> usize {
+        let mut eq = BTreeSet::new();
+        eq.insert((sig_a_idx, msg_a));
+        eq.insert((sig_b_idx, msg_b));
+        self.add_witness_equality(EqualWitnesses(eq))
+    }
+
+    /// Add a witness equality stating that private input `input_idx` of the circuit in the `R1CSCircomProver` or
+    /// `R1CSCircomVerifier` statement `r1cs_st_idx` is equal to `witness`, like a message of a signature. The
+    /// private inputs are indexed in the order they are declared in the circuit, which is also the order in which
+    /// they should be set in `R1CSCircomWitness`, and an input array contributes one index per element. Only the
+    /// first `commit_witness_count` private inputs, as given when generating the SNARK proving key, can be linked.
+    /// Returns the index of the added meta statement.
+    pub fn link_r1cs_input(
+        &mut self,
+        r1cs_st_idx: usize,
+        input_idx: usize,
+        witness: WitnessRef,
+    ) -> usize {
+        let mut eq = BTreeSet::new();
+        eq.insert((r1cs_st_idx, input_idx));
+        eq.insert(witness);
+        self.add_witness_equality(EqualWitnesses(eq))
+    }
+
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
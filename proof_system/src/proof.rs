@@ -22,11 +22,18 @@ pub struct Proof<E: Pairing, G: AffineRepr> {
     // TODO: Remove this skip
     #[serde(skip)]
     pub aggregated_legogroth16: Option<Vec<AggregatedGroth16<E>>>,
+    /// Creation time of the proof as asserted by the prover. When present, it's hashed into the challenge
+    /// so it can't be changed without invalidating the proof.
+    ///
+    /// This is a breaking change to the canonical encoding of `Proof`: the field is encoded even when `None`, so
+    /// proofs serialized by versions of this crate from before the field was added can't be deserialized, and
+    /// proofs serialized now can't be deserialized by those versions.
+    pub created_at: Option<u64>,
 }
 
 impl<E: Pairing, G: AffineRepr> PartialEq for Proof<E, G> {
     fn eq(&self, other: &Self) -> bool {
-        self.statement_proofs == other.statement_proofs
+        self.statement_proofs == other.statement_proofs && self.created_at == other.created_at
         // TODO: Add remaining
     }
 }
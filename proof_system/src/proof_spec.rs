@@ -1,7 +1,7 @@
 use crate::{
     derived_params::{DerivedParamsTracker, StatementDerivedParams},
     error::ProofSystemError,
-    meta_statement::{MetaStatement, MetaStatements},
+    meta_statement::{EqualWitnesses, MetaStatement, MetaStatements, WitnessRef},
     setup_params::SetupParams,
     statement::{Statement, Statements},
 };
@@ -10,6 +10,7 @@ use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError
 use ark_std::{
     collections::{BTreeMap, BTreeSet},
     io::{Read, Write},
+    vec,
     vec::Vec,
 };
 use bbs_plus::setup::{
@@ -22,7 +23,9 @@ use coconut_crypto::setup::{
     PreparedPublicKey as PreparedPSPk, PreparedSignatureParams as PreparedPSSigParams,
     PublicKey as PSPk, SignatureParams as PSSigParams,
 };
-use dock_crypto_utils::commitment::PedersenCommitmentKey;
+use dock_crypto_utils::{
+    commitment::PedersenCommitmentKey, signature::MultiMessageSignatureParams,
+};
 use legogroth16::{
     aggregation::srs::{ProverSRS, VerifierSRS},
     PreparedVerifyingKey as LegoPreparedVerifyingKey, VerifyingKey as LegoVerifyingKey,
@@ -138,6 +141,35 @@ where
         self.meta_statements.add(meta_statement)
     }
 
+    /// Witness equalities implied by statements, like `CommittedMessageEquality`, rather than given as
+    /// meta-statements
+    pub fn implied_witness_equalities(&self) -> Vec<EqualWitnesses> {
+        self.statements
+            .0
+            .iter()
+            .enumerate()
+            .flat_map(|(i, s)| match s {
+                Statement::CommittedMessageEquality(s) => vec![s.implied_witness_equality(i)],
+                Statement::SignatureCommitmentConsistency(s) => s.implied_witness_equalities(i),
+                _ => vec![],
+            })
+            .collect()
+    }
+
+    /// All witness equalities, from both the meta-statements and the statements, such that no 2 equalities
+    /// have a common witness reference. See `MetaStatements::disjoint_witness_equalities`.
+    pub fn disjoint_witness_equalities(&self) -> Vec<EqualWitnesses> {
+        let implied = self.implied_witness_equalities();
+        if implied.is_empty() {
+            return self.meta_statements.disjoint_witness_equalities();
+        }
+        let mut meta_statements = self.meta_statements.clone();
+        for eq in implied {
+            meta_statements.add_witness_equality(eq);
+        }
+        meta_statements.disjoint_witness_equalities()
+    }
+
     /// Sanity check to ensure the proof spec is valid. This should never error as these are used
     /// by same entity creating them.
     pub fn validate(&self) -> Result<(), ProofSystemError> {
@@ -206,6 +238,40 @@ where
                         revealed_wit_refs.insert((i, *k));
                     }
                 }
+                Statement::PoKPSAggregateSignature(s) => {
+                    s.aggregated_public_key()?;
+                    for k in s.revealed_messages.keys() {
+                        revealed_wit_refs.insert((i, *k));
+                    }
+                }
+                Statement::PartialPedersenOpening(s) => {
+                    for k in s.revealed.keys() {
+                        revealed_wit_refs.insert((i, *k));
+                    }
+                }
+                Statement::PoKBDDT16MAC(s) => {
+                    for k in s.revealed_messages.keys() {
+                        revealed_wit_refs.insert((i, *k));
+                    }
+                }
+                Statement::PoKBDDT16MACFullVerifier(s) => {
+                    for k in s.revealed_messages.keys() {
+                        revealed_wit_refs.insert((i, *k));
+                    }
+                }
+                Statement::DisclosedLinearCombination(s) => s.validate()?,
+                Statement::CommittedMessageEquality(s) => self.validate_signed_message_ref(
+                    i,
+                    (s.signature_statement_index, s.message_index),
+                )?,
+                Statement::SignatureCommitmentConsistency(s) => {
+                    for msg_idx in s.message_indices.values() {
+                        self.validate_signed_message_ref(
+                            i,
+                            (s.signature_statement_index, *msg_idx),
+                        )?;
+                    }
+                }
                 _ => continue,
             }
         }
@@ -224,9 +290,104 @@ where
                 }
             }
         }
+        for w in self.implied_witness_equalities() {
+            for r in w.0.iter() {
+                if revealed_wit_refs.contains(r) {
+                    return Err(ProofSystemError::WitnessAlreadyBeingRevealed(r.0, r.1));
+                }
+            }
+        }
         Ok(())
     }
 
+    /// Check that the signed message `message_ref`, referred to by the statement at index `s_idx`, is a hidden message
+    /// of a statement proving knowledge of a signature or MAC
+    fn validate_signed_message_ref(
+        &self,
+        s_idx: usize,
+        message_ref: WitnessRef,
+    ) -> Result<(), ProofSystemError> {
+        let (sig_idx, msg_idx) = message_ref;
+        let (message_count, revealed) = match self.statements.0.get(sig_idx) {
+            Some(Statement::PoKBBSSignatureG1(s)) => (
+                s.get_sig_params(&self.setup_params, sig_idx)?
+                    .supported_message_count(),
+                &s.revealed_messages,
+            ),
+            Some(Statement::PoKBBSSignature23G1(s)) => (
+                s.get_sig_params(&self.setup_params, sig_idx)?
+                    .supported_message_count(),
+                &s.revealed_messages,
+            ),
+            Some(Statement::PoKPSSignature(s)) => (
+                s.get_sig_params(&self.setup_params, sig_idx)?
+                    .supported_message_count(),
+                &s.revealed_messages,
+            ),
+            Some(Statement::PoKPSAggregateSignature(s)) => (
+                s.get_sig_params(&self.setup_params, sig_idx)?
+                    .supported_message_count(),
+                &s.revealed_messages,
+            ),
+            Some(Statement::PoKBDDT16MAC(s)) => (
+                s.get_params(&self.setup_params, sig_idx)?
+                    .supported_message_count(),
+                &s.revealed_messages,
+            ),
+            Some(Statement::PoKBDDT16MACFullVerifier(s)) => (
+                s.get_params(&self.setup_params, sig_idx)?
+                    .supported_message_count(),
+                &s.revealed_messages,
+            ),
+            _ => return Err(ProofSystemError::NotASignatureStatement(s_idx, sig_idx)),
+        };
+        if msg_idx >= message_count {
+            return Err(ProofSystemError::SignedMessageIndexOutOfRange(
+                s_idx,
+                msg_idx,
+                message_count,
+            ));
+        }
+        if revealed.contains_key(&msg_idx) {
+            return Err(ProofSystemError::WitnessAlreadyBeingRevealed(
+                sig_idx, msg_idx,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Derive the bases of scoped pseudonyms from their scopes, keyed by statement index. These are computed once
+    /// by the prover and verifier rather than taken from the statements.
+    pub fn derive_pseudonym_bases(&self) -> BTreeMap<usize, G> {
+        self.statements
+            .0
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| match s {
+                Statement::ScopedPseudonym(s) => Some((i, s.base())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Aggregate the signers' public keys of PS multi-signature statements, keyed by statement index. These are
+    /// computed once by the prover and verifier rather than taken from the statements.
+    pub fn derive_aggregated_ps_public_keys(
+        &self,
+    ) -> Result<BTreeMap<usize, PSPk<E>>, ProofSystemError> {
+        self.statements
+            .0
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| match s {
+                Statement::PoKPSAggregateSignature(s) => {
+                    Some(s.aggregated_public_key().map(|pk| (i, pk)))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Derive commitment keys for Schnorr protocol from public params. This is done to avoid
     /// creating them if the same public params are used in multiple statements and is effectively a
     /// pre-processing step done for optimization.
@@ -287,6 +448,18 @@ where
                         .get_pedersen_commitment_key();
                     bpp_comm_keys.insert(s_idx, ck);
                 }
+                Statement::Monotonic(s) => {
+                    let ck = s
+                        .get_setup_params(&self.setup_params, s_idx)?
+                        .get_pedersen_commitment_key();
+                    bpp_comm_keys.insert(s_idx, ck);
+                }
+                Statement::AggregatedBoundCheckBpp(s) => {
+                    let ck = s
+                        .get_setup_params(&self.setup_params, s_idx)?
+                        .get_pedersen_commitment_key();
+                    bpp_comm_keys.insert(s_idx, ck);
+                }
                 _ => (),
             }
         }
@@ -308,8 +481,12 @@ where
                         .on_new_statement_idx(saver_comm_keys.get(&s_idx).unwrap(), s_idx);
                 }
 
+                // Bloom filter membership also uses LegoGroth16 with a single committed witness so
+                // the commitment key is derived the same way as for bound check
                 Statement::BoundCheckLegoGroth16Prover(_)
-                | Statement::BoundCheckLegoGroth16Verifier(_) => {
+                | Statement::BoundCheckLegoGroth16Verifier(_)
+                | Statement::BloomMembershipLegoGroth16Prover(_)
+                | Statement::BloomMembershipLegoGroth16Verifier(_) => {
                     let verifying_key = match statement {
                         Statement::BoundCheckLegoGroth16Prover(s) => {
                             &s.get_proving_key(&self.setup_params, s_idx)?.vk
@@ -317,6 +494,12 @@ where
                         Statement::BoundCheckLegoGroth16Verifier(s) => {
                             s.get_verifying_key(&self.setup_params, s_idx)?
                         }
+                        Statement::BloomMembershipLegoGroth16Prover(s) => {
+                            &s.get_proving_key(&self.setup_params, s_idx)?.vk
+                        }
+                        Statement::BloomMembershipLegoGroth16Verifier(s) => {
+                            s.get_verifying_key(&self.setup_params, s_idx)?
+                        }
                         _ => unreachable!(),
                     };
                     derived_bound_check_lego_comm.on_new_statement_idx(verifying_key, s_idx);
@@ -334,15 +517,21 @@ where
                     };
                     derived_r1cs_comm.on_new_statement_idx(verifying_key, s_idx);
                 }
-                Statement::BoundCheckBpp(_) => {
+                Statement::BoundCheckBpp(_)
+                | Statement::Monotonic(_)
+                | Statement::AggregatedBoundCheckBpp(_) => {
                     let ck = bpp_comm_keys.get(&s_idx).unwrap();
                     derived_bound_check_bpp_comm.on_new_statement_idx(ck, s_idx);
                 }
                 Statement::BoundCheckSmc(_)
                 | Statement::BoundCheckSmcWithKVProver(_)
-                | Statement::BoundCheckSmcWithKVVerifier(_) => {
+                | Statement::BoundCheckSmcWithKVVerifier(_)
+                | Statement::SetMembershipSmc(_) => {
                     let comm_key = match statement {
                         Statement::BoundCheckSmc(s) => s.get_comm_key(&self.setup_params, s_idx)?,
+                        Statement::SetMembershipSmc(s) => {
+                            s.get_comm_key(&self.setup_params, s_idx)?
+                        }
                         Statement::BoundCheckSmcWithKVProver(s) => {
                             s.get_comm_key(&self.setup_params, s_idx)?
                         }
@@ -357,6 +546,14 @@ where
                     let ck = s.get_comm_key(&self.setup_params, s_idx)?;
                     derived_ineq_comm.on_new_statement_idx(ck, s_idx);
                 }
+                Statement::PublicSetMembership(s) => {
+                    let ck = s.get_comm_key(&self.setup_params, s_idx)?;
+                    derived_ineq_comm.on_new_statement_idx(ck, s_idx);
+                }
+                Statement::CommittedInequality(s) => {
+                    let ck = s.get_comm_key(&self.setup_params, s_idx)?;
+                    derived_ineq_comm.on_new_statement_idx(ck, s_idx);
+                }
                 _ => (),
             }
         }
@@ -394,6 +591,7 @@ where
         ),
         ProofSystemError,
     > {
+        let aggregated_ps_pks = self.derive_aggregated_ps_public_keys()?;
         let mut derived_lego_vk =
             DerivedParamsTracker::<LegoVerifyingKey<E>, LegoPreparedVerifyingKey<E>, E>::new();
         let mut derived_enc_gens =
@@ -494,6 +692,10 @@ where
                     let verifying_key = s.get_verifying_key(&self.setup_params, s_idx)?;
                     derived_lego_vk.on_new_statement_idx(verifying_key, s_idx);
                 }
+                Statement::BloomMembershipLegoGroth16Verifier(s) => {
+                    let verifying_key = s.get_verifying_key(&self.setup_params, s_idx)?;
+                    derived_lego_vk.on_new_statement_idx(verifying_key, s_idx);
+                }
                 Statement::R1CSCircomVerifier(s) => {
                     let verifying_key = s.get_verifying_key(&self.setup_params, s_idx)?;
                     derived_lego_vk.on_new_statement_idx(verifying_key, s_idx);
@@ -505,10 +707,20 @@ where
                     let pk = s.get_public_key(&self.setup_params, s_idx)?;
                     derived_ps_pk.on_new_statement_idx(pk, s_idx);
                 }
+                Statement::PoKPSAggregateSignature(s) => {
+                    let params = s.get_sig_params(&self.setup_params, s_idx)?;
+                    derived_ps_p.on_new_statement_idx(params, s_idx);
+                    derived_ps_pk
+                        .on_new_statement_idx(aggregated_ps_pks.get(&s_idx).unwrap(), s_idx);
+                }
                 Statement::BoundCheckSmc(s) => {
                     let params = s.get_params_and_comm_key(&self.setup_params, s_idx)?;
                     derived_smc_p.on_new_statement_idx(params, s_idx);
                 }
+                Statement::SetMembershipSmc(s) => {
+                    let params = s.get_params_and_comm_key(&self.setup_params, s_idx)?;
+                    derived_smc_p.on_new_statement_idx(params, s_idx);
+                }
                 _ => (),
             }
         }
@@ -2,21 +2,25 @@
 
 use ark_ec::{pairing::Pairing, AffineRepr};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{collections::BTreeMap, format, rand::RngCore, vec, vec::Vec, UniformRand};
+use ark_std::{collections::BTreeMap, format, rand::RngCore, slice, vec, vec::Vec, UniformRand};
 
 use crate::{
     error::ProofSystemError,
     statement::Statement,
     sub_protocols::{ps_signature::PSSignaturePoK, SubProtocol},
+    timings::StatementTimer,
     witness::{Witness, Witnesses},
 };
+
+#[cfg(feature = "timings")]
+use crate::timings::TimingReport;
 use digest::Digest;
 use legogroth16::aggregation::srs::PreparedProverSRS;
 
 use crate::{
     constants::{
         BBS_23_LABEL, BBS_PLUS_LABEL, COMPOSITE_PROOF_CHALLENGE_LABEL, COMPOSITE_PROOF_LABEL,
-        CONTEXT_LABEL, KB_POS_ACCUM_CDH_MEM_LABEL, KB_POS_ACCUM_MEM_LABEL,
+        CONTEXT_LABEL, CREATED_AT_LABEL, KB_POS_ACCUM_CDH_MEM_LABEL, KB_POS_ACCUM_MEM_LABEL,
         KB_UNI_ACCUM_CDH_MEM_LABEL, KB_UNI_ACCUM_CDH_NON_MEM_LABEL, KB_UNI_ACCUM_MEM_LABEL,
         KB_UNI_ACCUM_NON_MEM_LABEL, NONCE_LABEL, VB_ACCUM_CDH_MEM_LABEL,
         VB_ACCUM_CDH_NON_MEM_LABEL, VB_ACCUM_MEM_LABEL, VB_ACCUM_NON_MEM_LABEL,
@@ -35,6 +39,7 @@ use crate::{
                 VBAccumulatorMembershipCDHSubProtocol, VBAccumulatorNonMembershipCDHSubProtocol,
             },
             detached::{
+                DetachedAccumulatorMembershipCDHSubProtocol,
                 DetachedAccumulatorMembershipSubProtocol,
                 DetachedAccumulatorNonMembershipSubProtocol,
             },
@@ -43,16 +48,24 @@ use crate::{
             KBUniversalAccumulatorNonMembershipSubProtocol, VBAccumulatorMembershipSubProtocol,
             VBAccumulatorNonMembershipSubProtocol,
         },
+        aggregated_bound_check_bpp::AggregatedBoundCheckBppProtocol,
         bbs_23::PoKBBSSigG1SubProtocol,
         bbs_plus::PoKBBSSigG1SubProtocol as PoKBBSPlusSigG1SubProtocol,
+        bddt16_kvac::PoKOfBDDT16MACSubProtocol,
+        bloom_membership::BloomMembershipLegoGrothProtocol,
         bound_check_bpp::BoundCheckBppProtocol,
         bound_check_legogroth16::BoundCheckLegoGrothProtocol,
         bound_check_smc::BoundCheckSmcProtocol,
         bound_check_smc_with_kv::BoundCheckSmcWithKVProtocol,
+        committed_inequality::CommittedInequalityProtocol,
         inequality::InequalityProtocol,
+        monotonic::MonotonicProtocol,
+        partial_ped_opening::PartialPedersenOpeningProtocol,
+        public_set_membership::PublicSetMembershipProtocol,
         r1cs_legogorth16::R1CSLegogroth16Protocol,
         saver::SaverProtocol,
         schnorr::SchnorrProtocol,
+        set_membership_smc::SetMembershipSmcProtocol,
     },
 };
 use dock_crypto_utils::{
@@ -106,6 +119,21 @@ impl<E: Pairing> ProverConfig<E> {
     }
 }
 
+/// Proof, the randomness used by its statements and the time taken by each statement
+type ProofWithTimer<E, G> = (
+    Proof<E, G>,
+    BTreeMap<usize, <E as Pairing>::ScalarField>,
+    StatementTimer,
+);
+
+/// Proof, the randomness used by its statements and the report of the time taken by each statement
+#[cfg(feature = "timings")]
+pub type ProofWithTimingReport<E, G> = (
+    Proof<E, G>,
+    BTreeMap<usize, <E as Pairing>::ScalarField>,
+    TimingReport,
+);
+
 macro_rules! err_incompat_witness {
     ($s_idx:ident, $s: ident, $witness: ident) => {
         return Err(ProofSystemError::WitnessIncompatibleWithStatement(
@@ -133,8 +161,52 @@ where
         proof_spec: ProofSpec<E, G>,
         witnesses: Witnesses<E>,
         nonce: Option<Vec<u8>>,
-        mut config: ProverConfig<E>,
+        config: ProverConfig<E>,
+    ) -> Result<(Self, BTreeMap<usize, E::ScalarField>), ProofSystemError> {
+        Self::_new(rng, proof_spec, witnesses, nonce, config, None).map(|(p, r, _)| (p, r))
+    }
+
+    /// Same as `Self::new` but the proof also carries its creation time `created_at` which is hashed
+    /// into the challenge. This lets the verifier reject old proofs using `Self::verify_with_max_age`
+    /// and thus limits the window in which a captured proof can be replayed. `created_at` is not
+    /// checked by the prover so it must be in the same unit as the verifier's clock, like seconds since
+    /// the Unix epoch.
+    pub fn new_with_creation_time<R: RngCore, D: Digest>(
+        rng: &mut R,
+        proof_spec: ProofSpec<E, G>,
+        witnesses: Witnesses<E>,
+        nonce: Option<Vec<u8>>,
+        config: ProverConfig<E>,
+        created_at: u64,
     ) -> Result<(Self, BTreeMap<usize, E::ScalarField>), ProofSystemError> {
+        Self::_new(rng, proof_spec, witnesses, nonce, config, Some(created_at))
+            .map(|(p, r, _)| (p, r))
+    }
+
+    /// Same as `Self::new` but also returns the time taken by each statement. Useful to find out which
+    /// statements dominate the proof creation time.
+    #[cfg(feature = "timings")]
+    pub fn new_with_timings<R: RngCore, D: Digest>(
+        rng: &mut R,
+        proof_spec: ProofSpec<E, G>,
+        witnesses: Witnesses<E>,
+        nonce: Option<Vec<u8>>,
+        config: ProverConfig<E>,
+    ) -> Result<ProofWithTimingReport<E, G>, ProofSystemError> {
+        Self::_new(rng, proof_spec, witnesses, nonce, config, None)
+            .map(|(p, r, t)| (p, r, t.finish()))
+    }
+
+    fn _new<R: RngCore>(
+        rng: &mut R,
+        proof_spec: ProofSpec<E, G>,
+        witnesses: Witnesses<E>,
+        nonce: Option<Vec<u8>>,
+        mut config: ProverConfig<E>,
+        created_at: Option<u64>,
+    ) -> Result<ProofWithTimer<E, G>, ProofSystemError> {
+        let mut timer = StatementTimer::new(proof_spec.statements.len());
+
         proof_spec.validate()?;
 
         // There should be a witness for each statement
@@ -151,14 +223,11 @@ where
         let mut blindings = BTreeMap::<WitnessRef, E::ScalarField>::new();
 
         // Prepare blindings for any witnesses that need to be proven equal.
-        if !proof_spec.meta_statements.is_empty() {
-            let disjoint_equalities = proof_spec.meta_statements.disjoint_witness_equalities();
-            for eq_wits in disjoint_equalities {
-                let blinding = E::ScalarField::rand(rng);
-                for wr in eq_wits.0 {
-                    // Duplicating the same blinding for faster search
-                    blindings.insert(wr, blinding);
-                }
+        for eq_wits in proof_spec.disjoint_witness_equalities() {
+            let blinding = E::ScalarField::rand(rng);
+            for wr in eq_wits.0 {
+                // Duplicating the same blinding for faster search
+                blindings.insert(wr, blinding);
             }
         }
 
@@ -173,6 +242,9 @@ where
             ineq_comm,
         ) = proof_spec.derive_commitment_keys()?;
 
+        let pseudonym_bases = proof_spec.derive_pseudonym_bases();
+        let aggregated_ps_pks = proof_spec.derive_aggregated_ps_public_keys()?;
+
         let mut sub_protocols =
             Vec::<SubProtocol<E, G>>::with_capacity(proof_spec.statements.0.len());
 
@@ -187,6 +259,9 @@ where
         if let Some(ctx) = &proof_spec.context {
             transcript.append_message(CONTEXT_LABEL, ctx);
         }
+        if let Some(t) = created_at.as_ref() {
+            transcript.append(CREATED_AT_LABEL, t);
+        }
 
         macro_rules! accum_protocol_init {
             ($s: ident, $s_idx: ident, $w: ident, $protocol: ident, $protocol_variant: ident, $label: ident) => {{
@@ -210,6 +285,7 @@ where
             .zip(witnesses.0.into_iter())
             .enumerate()
         {
+            timer.start();
             match statement {
                 Statement::PoKBBSSignatureG1(s) => match witness {
                     Witness::PoKBBSSignatureG1(w) => {
@@ -416,6 +492,25 @@ where
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
+                Statement::CommittedMessageEquality(s) => match witness {
+                    Witness::CommittedMessageEquality(w) => {
+                        // The blinding of the first committed value is the same as the signed message's
+                        // since they are in an implied witness equality
+                        let mut blindings_map = BTreeMap::new();
+                        for i in 0..w.len() {
+                            match blindings.remove(&(s_idx, i)) {
+                                Some(b) => blindings_map.insert(i, b),
+                                None => None,
+                            };
+                        }
+                        let comm_key = s.get_commitment_key(&proof_spec.setup_params, s_idx)?;
+                        let mut sp = SchnorrProtocol::new(s_idx, comm_key, s.commitment);
+                        sp.init(rng, blindings_map, w)?;
+                        sp.challenge_contribution(&mut transcript)?;
+                        sub_protocols.push(SubProtocol::PoKDiscreteLogs(sp));
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
                 Statement::SaverProver(s) => match witness {
                     Witness::Saver(w) => {
                         let blinding = blindings.remove(&(s_idx, 0));
@@ -504,11 +599,39 @@ where
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
+                Statement::BloomMembershipLegoGroth16Prover(s) => match witness {
+                    Witness::BloomMembershipLegoGroth16(w) => {
+                        let blinding = blindings.remove(&(s_idx, 0));
+                        let proving_key = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                        let comm_key = bound_check_lego_comm.get(s_idx).unwrap();
+
+                        let mut sp = BloomMembershipLegoGrothProtocol::new_for_prover(
+                            s_idx,
+                            &s.filter,
+                            proving_key,
+                        );
+                        sp.init(rng, comm_key, w, blinding)?;
+                        sp.challenge_contribution(&mut transcript)?;
+                        sub_protocols.push(SubProtocol::BloomMembershipLegoGroth16(sp));
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
                 Statement::R1CSCircomProver(s) => match witness {
                     Witness::R1CSLegoGroth16(w) => {
                         let proving_key = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                        let commit_witness_count = proving_key.vk.commit_witness_count as usize;
+                        if let Some(((_, i), _)) = blindings
+                            .range((s_idx, commit_witness_count)..(s_idx + 1, 0))
+                            .next()
+                        {
+                            return Err(ProofSystemError::R1CSInputNotCommitted(
+                                s_idx,
+                                *i,
+                                commit_witness_count,
+                            ));
+                        }
                         let mut blindings_map = BTreeMap::new();
-                        for i in 0..proving_key.vk.commit_witness_count as usize {
+                        for i in 0..commit_witness_count {
                             match blindings.remove(&(s_idx, i)) {
                                 Some(b) => blindings_map.insert(i, b),
                                 None => None,
@@ -572,6 +695,26 @@ where
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
+                Statement::PoKPSAggregateSignature(s) => match witness {
+                    Witness::PoKPSSignature(w) => {
+                        // Prepare blindings for this BBS+ signature proof
+                        let mut blindings_map = BTreeMap::new();
+                        for k in w.unrevealed_messages.keys() {
+                            match blindings.remove(&(s_idx, *k)) {
+                                Some(b) => blindings_map.insert(*k, b),
+                                None => None,
+                            };
+                        }
+                        let sig_params = s.get_sig_params(&proof_spec.setup_params, s_idx)?;
+                        let pk = aggregated_ps_pks.get(&s_idx).unwrap();
+                        let mut sp =
+                            PSSignaturePoK::new(s_idx, &s.revealed_messages, sig_params, pk);
+                        sp.init(rng, blindings_map, w)?;
+                        sp.challenge_contribution(&mut transcript)?;
+                        sub_protocols.push(SubProtocol::PSSignaturePoK(sp));
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
                 Statement::BoundCheckBpp(s) => match witness {
                     Witness::BoundCheckBpp(w) => {
                         let blinding = blindings.remove(&(s_idx, 0));
@@ -586,6 +729,51 @@ where
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
+                Statement::Monotonic(s) => match witness {
+                    Witness::Monotonic(w) => {
+                        let mut blindings_map = BTreeMap::new();
+                        for i in 0..s.num_values {
+                            if let Some(b) = blindings.remove(&(s_idx, i)) {
+                                blindings_map.insert(i, b);
+                            }
+                        }
+                        let bpp_setup_params =
+                            s.get_setup_params(&proof_spec.setup_params, s_idx)?;
+                        let comm_key = bound_check_bpp_comm.get(s_idx).unwrap();
+                        let mut sp = MonotonicProtocol::new(
+                            s_idx,
+                            s.num_values,
+                            s.strict,
+                            bpp_setup_params,
+                        )?;
+                        sp.init(rng, comm_key.as_slice(), w, blindings_map)?;
+                        sp.challenge_contribution(&mut transcript)?;
+                        sub_protocols.push(SubProtocol::Monotonic(sp));
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
+                Statement::AggregatedBoundCheckBpp(s) => match witness {
+                    Witness::AggregatedBoundCheckBpp(w) => {
+                        let mut blindings_map = BTreeMap::new();
+                        for i in 0..s.bounds.len() {
+                            if let Some(b) = blindings.remove(&(s_idx, i)) {
+                                blindings_map.insert(i, b);
+                            }
+                        }
+                        let bpp_setup_params =
+                            s.get_setup_params(&proof_spec.setup_params, s_idx)?;
+                        let comm_key = bound_check_bpp_comm.get(s_idx).unwrap();
+                        let mut sp = AggregatedBoundCheckBppProtocol::new(
+                            s_idx,
+                            &s.bounds,
+                            bpp_setup_params,
+                        );
+                        sp.init(rng, comm_key.as_slice(), w, blindings_map)?;
+                        sp.challenge_contribution(&mut transcript)?;
+                        sub_protocols.push(SubProtocol::AggregatedBoundCheckBpp(sp));
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
                 Statement::BoundCheckSmc(s) => match witness {
                     Witness::BoundCheckSmc(w) => {
                         let blinding = blindings.remove(&(s_idx, 0));
@@ -600,6 +788,19 @@ where
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
+                Statement::SetMembershipSmc(s) => match witness {
+                    Witness::SetMembershipSmc(w) => {
+                        let blinding = blindings.remove(&(s_idx, 0));
+                        let params_comm_key =
+                            s.get_params_and_comm_key(&proof_spec.setup_params, s_idx)?;
+                        let comm_key_as_slice = bound_check_smc_comm.get(s_idx).unwrap();
+                        let mut sp = SetMembershipSmcProtocol::new(s_idx, params_comm_key);
+                        sp.init(rng, comm_key_as_slice, w, blinding)?;
+                        sp.challenge_contribution(&mut transcript)?;
+                        sub_protocols.push(SubProtocol::SetMembershipSmc(sp));
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
                 Statement::BoundCheckSmcWithKVProver(s) => match witness {
                     Witness::BoundCheckSmcWithKV(w) => {
                         let blinding = blindings.remove(&(s_idx, 0));
@@ -630,6 +831,86 @@ where
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
+                Statement::PublicSetMembership(s) => match witness {
+                    Witness::PublicSetMembership(w) => {
+                        let blinding = blindings.remove(&(s_idx, 0));
+                        let comm_key = s.get_comm_key(&proof_spec.setup_params, s_idx)?;
+                        let mut sp = PublicSetMembershipProtocol::new(s_idx, &s.set, comm_key);
+                        sp.init(rng, ineq_comm.get(s_idx).unwrap().as_slice(), w, blinding)?;
+                        sp.challenge_contribution(&mut transcript)?;
+                        sub_protocols.push(SubProtocol::PublicSetMembership(sp));
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
+                Statement::CommittedInequality(s) => match witness {
+                    Witness::CommittedInequality(w) => {
+                        let blindings =
+                            [blindings.remove(&(s_idx, 0)), blindings.remove(&(s_idx, 1))];
+                        let comm_key = s.get_comm_key(&proof_spec.setup_params, s_idx)?;
+                        let mut sp = CommittedInequalityProtocol::new(s_idx, comm_key);
+                        sp.init(rng, ineq_comm.get(s_idx).unwrap().as_slice(), w, blindings)?;
+                        sp.challenge_contribution(&mut transcript)?;
+                        sub_protocols.push(SubProtocol::CommittedInequality(sp));
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
+                Statement::PartialPedersenOpening(s) => match witness {
+                    Witness::PartialPedersenOpening(w) => {
+                        // Blindings are keyed by the position of the hidden value in the commitment while the
+                        // protocol expects them keyed by the index among the hidden values
+                        let mut blindings_map = BTreeMap::new();
+                        for (k, i) in w.keys().enumerate() {
+                            if let Some(b) = blindings.remove(&(s_idx, *i)) {
+                                blindings_map.insert(k, b);
+                            }
+                        }
+                        let comm_key = s.get_commitment_key(&proof_spec.setup_params, s_idx)?;
+                        let mut sp = PartialPedersenOpeningProtocol::new(s_idx, s, comm_key)?;
+                        sp.init(rng, blindings_map, w)?;
+                        sp.challenge_contribution(&mut transcript)?;
+                        sub_protocols.push(SubProtocol::PartialPedersenOpening(sp));
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
+                Statement::SignatureCommitmentConsistency(s) => match witness {
+                    Witness::SignatureCommitmentConsistency(w) => {
+                        // Blindings of the committed values equal to signed messages are the same as the
+                        // messages' since they are in implied witness equalities
+                        let mut blindings_map = BTreeMap::new();
+                        for i in 0..w.len() {
+                            match blindings.remove(&(s_idx, i)) {
+                                Some(b) => blindings_map.insert(i, b),
+                                None => None,
+                            };
+                        }
+                        let comm_key = s.get_commitment_key(&proof_spec.setup_params, s_idx)?;
+                        s.check_positions(comm_key.len(), s_idx)?;
+                        let mut sp = SchnorrProtocol::new(s_idx, comm_key, s.commitment);
+                        sp.init(rng, blindings_map, w)?;
+                        sp.challenge_contribution(&mut transcript)?;
+                        sub_protocols.push(SubProtocol::PoKDiscreteLogs(sp));
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
+                Statement::PoKBDDT16MAC(s) => match witness {
+                    Witness::PoKOfBDDT16MAC(w) => {
+                        // Prepare blindings for this MAC proof
+                        let mut blindings_map = BTreeMap::new();
+                        for k in w.unrevealed_messages.keys() {
+                            match blindings.remove(&(s_idx, *k)) {
+                                Some(b) => blindings_map.insert(*k, b),
+                                None => None,
+                            };
+                        }
+                        let mac_params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let mut sp =
+                            PoKOfBDDT16MACSubProtocol::new(s_idx, &s.revealed_messages, mac_params);
+                        sp.init(rng, blindings_map, &w)?;
+                        sp.challenge_contribution(&mut transcript)?;
+                        sub_protocols.push(SubProtocol::PoKOfBDDT16MAC(sp));
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
                 Statement::DetachedAccumulatorMembershipProver(s) => match witness {
                     Witness::VBAccumulatorMembership(w) => {
                         let blinding = blindings.remove(&(s_idx, 0));
@@ -661,8 +942,52 @@ where
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
+                Statement::DetachedAccumulatorMembershipCDHProver(s) => match witness {
+                    Witness::VBAccumulatorMembership(w) => {
+                        let blinding = blindings.remove(&(s_idx, 0));
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                        let mut sp =
+                            DetachedAccumulatorMembershipCDHSubProtocol::new(s_idx, params, pk);
+                        sp.init(rng, s.accumulator_value, blinding, w)?;
+                        transcript.set_label(VB_ACCUM_CDH_MEM_LABEL);
+                        sp.challenge_contribution(&mut transcript)?;
+                        sub_protocols.push(SubProtocol::DetachedAccumulatorMembershipCDH(sp));
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
+                Statement::ScopedPseudonym(s) => match witness {
+                    Witness::ScopedPseudonym(w) => {
+                        let mut blindings_map = BTreeMap::new();
+                        if let Some(b) = blindings.remove(&(s_idx, 0)) {
+                            blindings_map.insert(0, b);
+                        }
+                        let base = pseudonym_bases.get(&s_idx).unwrap();
+                        let mut sp =
+                            SchnorrProtocol::new(s_idx, slice::from_ref(base), s.pseudonym);
+                        sp.init(rng, blindings_map, vec![w])?;
+                        sp.challenge_contribution(&mut transcript)?;
+                        sub_protocols.push(SubProtocol::PoKDiscreteLogs(sp));
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
+                Statement::DisclosedLinearCombination(s) => match witness {
+                    Witness::DisclosedLinearCombination(w) => {
+                        let mut blindings_map = BTreeMap::new();
+                        if let Some(b) = blindings.remove(&(s_idx, 0)) {
+                            blindings_map.insert(0, b);
+                        }
+                        let mut sp =
+                            SchnorrProtocol::new(s_idx, slice::from_ref(&s.base), s.commitment());
+                        sp.init(rng, blindings_map, vec![w])?;
+                        sp.challenge_contribution(&mut transcript)?;
+                        sub_protocols.push(SubProtocol::PoKDiscreteLogs(sp));
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
                 _ => return Err(ProofSystemError::InvalidStatement),
             }
+            timer.stop(s_idx);
         }
 
         // If all blindings are not consumed, it means that there was some witness equality which was
@@ -678,7 +1003,8 @@ where
 
         // Get each sub-protocol's proof
         let mut statement_proofs = Vec::with_capacity(sub_protocols.len());
-        for p in sub_protocols {
+        for (s_idx, p) in sub_protocols.into_iter().enumerate() {
+            timer.start();
             statement_proofs.push(match p {
                 SubProtocol::PoKBBSSignatureG1(mut sp) => sp.gen_proof_contribution(&challenge)?,
                 SubProtocol::VBAccumulatorMembership(mut sp) => {
@@ -695,6 +1021,9 @@ where
                 SubProtocol::R1CSLegogroth16Protocol(mut sp) => {
                     sp.gen_proof_contribution(&challenge)?
                 }
+                SubProtocol::BloomMembershipLegoGroth16(mut sp) => {
+                    sp.gen_proof_contribution(&challenge)?
+                }
                 SubProtocol::PSSignaturePoK(mut sp) => sp.gen_proof_contribution(&challenge)?,
                 SubProtocol::PoKBBSSignature23G1(mut sp) => {
                     sp.gen_proof_contribution(&challenge)?
@@ -702,17 +1031,37 @@ where
                 SubProtocol::BoundCheckBpp(mut sp) => {
                     sp.gen_proof_contribution(rng, &challenge, &mut transcript)?
                 }
+                SubProtocol::Monotonic(mut sp) => {
+                    sp.gen_proof_contribution(rng, &challenge, &mut transcript)?
+                }
+                SubProtocol::AggregatedBoundCheckBpp(mut sp) => {
+                    sp.gen_proof_contribution(rng, &challenge, &mut transcript)?
+                }
                 SubProtocol::BoundCheckSmc(mut sp) => sp.gen_proof_contribution(&challenge)?,
+                SubProtocol::SetMembershipSmc(mut sp) => sp.gen_proof_contribution(&challenge)?,
                 SubProtocol::BoundCheckSmcWithKV(mut sp) => {
                     sp.gen_proof_contribution(&challenge)?
                 }
                 SubProtocol::Inequality(mut sp) => sp.gen_proof_contribution(&challenge)?,
+                SubProtocol::CommittedInequality(mut sp) => {
+                    sp.gen_proof_contribution(&challenge)?
+                }
+                SubProtocol::PartialPedersenOpening(mut sp) => {
+                    sp.gen_proof_contribution(&challenge)?
+                }
+                SubProtocol::PoKOfBDDT16MAC(mut sp) => sp.gen_proof_contribution(&challenge)?,
+                SubProtocol::PublicSetMembership(mut sp) => {
+                    sp.gen_proof_contribution(&challenge)?
+                }
                 SubProtocol::DetachedAccumulatorMembership(mut sp) => {
                     sp.gen_proof_contribution(rng, &challenge)?
                 }
                 SubProtocol::DetachedAccumulatorNonMembership(mut sp) => {
                     sp.gen_proof_contribution(rng, &challenge)?
                 }
+                SubProtocol::DetachedAccumulatorMembershipCDH(mut sp) => {
+                    sp.gen_proof_contribution(rng, &challenge)?
+                }
                 SubProtocol::KBUniversalAccumulatorMembership(mut sp) => {
                     sp.gen_proof_contribution(&challenge)?
                 }
@@ -738,6 +1087,7 @@ where
                     sp.gen_proof_contribution(&challenge)?
                 }
             });
+            timer.stop(s_idx);
         }
 
         // TODO: Revisit - aggregating after challenge generation, is this correct?
@@ -823,8 +1173,10 @@ where
                 } else {
                     None
                 },
+                created_at,
             },
             commitment_randomness,
+            timer,
         ))
     }
 
@@ -887,6 +1239,7 @@ where
             statement_proofs,
             aggregated_groth16: self.aggregated_groth16.clone(),
             aggregated_legogroth16: self.aggregated_legogroth16.clone(),
+            created_at: self.created_at,
         }
     }
 }
@@ -0,0 +1,121 @@
+//! Helpers to serialize objects like `Proof` and `Statements` for storage along with a header identifying the
+//! format. The header is `MAGIC` followed by `FORMAT_VERSION`, which identifies the wire format of the remaining
+//! bytes. Since `CanonicalSerialize`'s output can change across major versions of arkworks or when fields are
+//! added to a type, bytes written under an incompatible format are rejected by `from_bytes` rather than being
+//! mis-decoded.
+
+use crate::error::InvalidSerializationHeader;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use ark_std::vec::Vec;
+
+/// Prefix of all bytes created by `to_bytes`
+pub const MAGIC: [u8; 4] = *b"DCPS";
+
+/// Version of the wire format. Must be updated when upgrading to an arkworks version whose serialization is
+/// incompatible with the current one or when the canonical encoding of a serialized type changes.
+pub const FORMAT_VERSION: u8 = 4;
+
+/// Length of the header prefixed to the serialized object
+pub const HEADER_LEN: usize = MAGIC.len() + 1;
+
+/// Serialize `obj` in compressed form prefixed with the header
+pub fn to_bytes<T: CanonicalSerialize>(obj: &T) -> Result<Vec<u8>, SerializationError> {
+    let mut bytes = Vec::with_capacity(HEADER_LEN + obj.compressed_size());
+    bytes.extend_from_slice(&MAGIC);
+    bytes.push(FORMAT_VERSION);
+    obj.serialize_compressed(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Deserialize an object serialized with `to_bytes`. Returns an error if the header is missing or doesn't
+/// match the one this version of the crate writes.
+pub fn from_bytes<T: CanonicalDeserialize>(bytes: &[u8]) -> Result<T, SerializationError> {
+    if bytes.len() < HEADER_LEN || bytes[..MAGIC.len()] != MAGIC {
+        return Err(InvalidSerializationHeader::MissingMagic.into());
+    }
+    let version = bytes[MAGIC.len()];
+    if version != FORMAT_VERSION {
+        return Err(InvalidSerializationHeader::UnsupportedFormatVersion {
+            found: version,
+            expected: FORMAT_VERSION,
+        }
+        .into());
+    }
+    T::deserialize_compressed(&bytes[HEADER_LEN..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::ProofSystemError,
+        statement::{bbs_plus::PoKBBSSignatureG1, Statements},
+    };
+    use ark_bls12_381::{Bls12_381, G1Affine};
+    use ark_std::{
+        collections::BTreeMap,
+        rand::{rngs::StdRng, SeedableRng},
+    };
+    use test_utils::bbs::bbs_plus_sig_setup;
+
+    type Stmts = Statements<Bls12_381, G1Affine>;
+
+    #[test]
+    fn serialization_with_header() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (_, params, keypair, _) = bbs_plus_sig_setup(&mut rng, 5);
+        let mut statements = Stmts::new();
+        statements.add(PoKBBSSignatureG1::new_statement_from_params(
+            params,
+            keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+
+        let bytes = to_bytes(&statements).unwrap();
+        assert_eq!(bytes[..MAGIC.len()], MAGIC);
+        assert_eq!(bytes[MAGIC.len()], FORMAT_VERSION);
+        assert_eq!(from_bytes::<Stmts>(&bytes).unwrap(), statements);
+
+        // Bytes without the header are rejected
+        let mut plain = Vec::new();
+        statements.serialize_compressed(&mut plain).unwrap();
+        let err = from_bytes::<Stmts>(&plain).unwrap_err();
+        assert!(matches!(
+            ProofSystemError::from(err),
+            ProofSystemError::InvalidSerializationHeader(InvalidSerializationHeader::MissingMagic)
+        ));
+        assert!(from_bytes::<Stmts>(&MAGIC).is_err());
+        assert!(from_bytes::<Stmts>(&[]).is_err());
+
+        // Bytes with the wrong magic are rejected
+        let mut wrong_magic = bytes.clone();
+        wrong_magic[0] ^= 1;
+        assert!(matches!(
+            ProofSystemError::from(from_bytes::<Stmts>(&wrong_magic).unwrap_err()),
+            ProofSystemError::InvalidSerializationHeader(InvalidSerializationHeader::MissingMagic)
+        ));
+
+        // Bytes written with a different format version are rejected
+        let mut wrong_version = bytes.clone();
+        wrong_version[MAGIC.len()] = FORMAT_VERSION + 1;
+        let err = from_bytes::<Stmts>(&wrong_version).unwrap_err();
+        assert!(matches!(
+            ProofSystemError::from(err),
+            ProofSystemError::InvalidSerializationHeader(
+                InvalidSerializationHeader::UnsupportedFormatVersion { found, expected }
+            ) if found == FORMAT_VERSION + 1 && expected == FORMAT_VERSION
+        ));
+        assert_eq!(
+            InvalidSerializationHeader::UnsupportedFormatVersion {
+                found: FORMAT_VERSION + 1,
+                expected: FORMAT_VERSION
+            }
+            .to_string(),
+            format!(
+                "serialized with format version {}, expected {}",
+                FORMAT_VERSION + 1,
+                FORMAT_VERSION
+            )
+        );
+    }
+}
@@ -17,6 +17,7 @@ use bbs_plus::prelude::{
 };
 use bulletproofs_plus_plus::setup::SetupParams as BppSetupParams;
 use dock_crypto_utils::{commitment::PedersenCommitmentKey, serde_utils::ArkObjectBytes};
+use kvac::bddt_2016::setup::MACParams as BDDT16MACParams;
 use legogroth16::{
     circom::R1CS,
     data_structures::{ProvingKey as LegoSnarkProvingKey, VerifyingKey as LegoSnarkVerifyingKey},
@@ -70,6 +71,7 @@ pub enum SetupParams<E: Pairing, G: AffineRepr> {
     BBSigProvingKey(ProvingKey<E::G1Affine>),
     KBPositiveAccumulatorParams(KBAccumParams<E>),
     KBPositiveAccumulatorPublicKey(KBAccumPublicKey<E>),
+    BDDT16MACParams(#[serde_as(as = "ArkObjectBytes")] BDDT16MACParams<E::G1Affine>),
 }
 
 macro_rules! delegate {
@@ -102,7 +104,8 @@ macro_rules! delegate {
                 CommitmentKey,
                 BBSigProvingKey,
                 KBPositiveAccumulatorParams,
-                KBPositiveAccumulatorPublicKey
+                KBPositiveAccumulatorPublicKey,
+                BDDT16MACParams
             : $($tt)+
         }
     }};
@@ -138,7 +141,8 @@ macro_rules! delegate_reverse {
                 CommitmentKey,
                 BBSigProvingKey,
                 KBPositiveAccumulatorParams,
-                KBPositiveAccumulatorPublicKey
+                KBPositiveAccumulatorPublicKey,
+                BDDT16MACParams
             : $($tt)+
         }
 
@@ -62,6 +62,19 @@ impl<E: Pairing> DetachedAccumulatorMembershipProver<E> {
         })
     }
 
+    /// Create the verifier's statement from this statement. The accumulator value is not part of
+    /// the verifier's statement as the verifier only learns it after decrypting.
+    pub fn to_verifier(&self) -> DetachedAccumulatorMembershipVerifier<E> {
+        DetachedAccumulatorMembershipVerifier {
+            params: self.params.clone(),
+            public_key: self.public_key.clone(),
+            proving_key: self.proving_key.clone(),
+            params_ref: self.params_ref,
+            public_key_ref: self.public_key_ref,
+            proving_key_ref: self.proving_key_ref,
+        }
+    }
+
     impl_getters!(
         AccumParams,
         VbAccumulatorParams,
@@ -182,6 +195,19 @@ impl<E: Pairing> DetachedAccumulatorNonMembershipProver<E> {
         })
     }
 
+    /// Create the verifier's statement from this statement. The accumulator value is not part of
+    /// the verifier's statement as the verifier only learns it after decrypting.
+    pub fn to_verifier(&self) -> DetachedAccumulatorNonMembershipVerifier<E> {
+        DetachedAccumulatorNonMembershipVerifier {
+            params: self.params.clone(),
+            public_key: self.public_key.clone(),
+            proving_key: self.proving_key.clone(),
+            params_ref: self.params_ref,
+            public_key_ref: self.public_key_ref,
+            proving_key_ref: self.proving_key_ref,
+        }
+    }
+
     impl_getters!(
         AccumParams,
         VbAccumulatorParams,
@@ -248,3 +274,117 @@ impl<E: Pairing> DetachedAccumulatorNonMembershipVerifier<E> {
         VbAccumulatorNonMemProvingKey
     );
 }
+
+/// Public values for proving membership in a VB accumulator using the CDH approach where the accumulator is
+/// randomized and its opening encrypted for the accumulator manager as in `DetachedAccumulatorMembershipProver`.
+/// Unlike that, no proving key is needed.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct DetachedAccumulatorMembershipCDHProver<E: Pairing> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub accumulator_value: E::G1Affine,
+    pub params: Option<AccumParams<E>>,
+    pub public_key: Option<PublicKey<E>>,
+    pub params_ref: Option<usize>,
+    pub public_key_ref: Option<usize>,
+}
+
+impl<E: Pairing> DetachedAccumulatorMembershipCDHProver<E> {
+    /// Create a statement by passing the accumulator params and public key directly.
+    pub fn new_statement_from_params<G: AffineRepr>(
+        params: AccumParams<E>,
+        public_key: PublicKey<E>,
+        accumulator_value: E::G1Affine,
+    ) -> Statement<E, G> {
+        Statement::DetachedAccumulatorMembershipCDHProver(Self {
+            accumulator_value,
+            params: Some(params),
+            public_key: Some(public_key),
+            params_ref: None,
+            public_key_ref: None,
+        })
+    }
+
+    /// Create a statement by passing the indices of accumulator params and public key in `SetupParams`.
+    pub fn new_statement_from_params_ref<G: AffineRepr>(
+        params_ref: usize,
+        public_key_ref: usize,
+        accumulator_value: E::G1Affine,
+    ) -> Statement<E, G> {
+        Statement::DetachedAccumulatorMembershipCDHProver(Self {
+            accumulator_value,
+            params: None,
+            public_key: None,
+            params_ref: Some(params_ref),
+            public_key_ref: Some(public_key_ref),
+        })
+    }
+
+    /// Create the verifier's statement from this statement. The accumulator value is not part of
+    /// the verifier's statement as the verifier only learns it after decrypting.
+    pub fn to_verifier(&self) -> DetachedAccumulatorMembershipCDHVerifier<E> {
+        DetachedAccumulatorMembershipCDHVerifier {
+            params: self.params.clone(),
+            public_key: self.public_key.clone(),
+            params_ref: self.params_ref,
+            public_key_ref: self.public_key_ref,
+        }
+    }
+
+    impl_pk_and_param_getters!(
+        AccumParams,
+        VbAccumulatorParams,
+        PublicKey,
+        VbAccumulatorPublicKey
+    );
+}
+
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct DetachedAccumulatorMembershipCDHVerifier<E: Pairing> {
+    pub params: Option<AccumParams<E>>,
+    pub public_key: Option<PublicKey<E>>,
+    pub params_ref: Option<usize>,
+    pub public_key_ref: Option<usize>,
+}
+
+impl<E: Pairing> DetachedAccumulatorMembershipCDHVerifier<E> {
+    /// Create a statement by passing the accumulator params and public key directly.
+    pub fn new_statement_from_params<G: AffineRepr>(
+        params: AccumParams<E>,
+        public_key: PublicKey<E>,
+    ) -> Statement<E, G> {
+        Statement::DetachedAccumulatorMembershipCDHVerifier(Self {
+            params: Some(params),
+            public_key: Some(public_key),
+            params_ref: None,
+            public_key_ref: None,
+        })
+    }
+
+    /// Create a statement by passing the indices of accumulator params and public key in `SetupParams`.
+    pub fn new_statement_from_params_ref<G: AffineRepr>(
+        params_ref: usize,
+        public_key_ref: usize,
+    ) -> Statement<E, G> {
+        Statement::DetachedAccumulatorMembershipCDHVerifier(Self {
+            params: None,
+            public_key: None,
+            params_ref: Some(params_ref),
+            public_key_ref: Some(public_key_ref),
+        })
+    }
+
+    impl_pk_and_param_getters!(
+        AccumParams,
+        VbAccumulatorParams,
+        PublicKey,
+        VbAccumulatorPublicKey
+    );
+}
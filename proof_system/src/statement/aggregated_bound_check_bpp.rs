@@ -0,0 +1,105 @@
+use crate::{
+    error::ProofSystemError,
+    meta_statement::{EqualWitnesses, WitnessRef},
+    setup_params::SetupParams,
+    statement::Statement,
+    sub_protocols::validate_bounds,
+};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{collections::BTreeSet, vec::Vec};
+use bulletproofs_plus_plus::setup::SetupParams as BppSetupParams;
+use dock_crypto_utils::serde_utils::ArkObjectBytes;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+/// Proving knowledge of messages `m_1, m_2, ..., m_k` that satisfy bounds `min_i <= m_i < max_i` using a single
+/// aggregated Bulletproofs++ proof. This is much smaller than `k` `BoundCheckBpp` proofs as the size of the
+/// Bulletproofs++ proof grows logarithmically with the number of values. The Bulletproofs++ setup params should
+/// support `k.next_power_of_two()` arbitrary range proofs of 64-bit values. The messages are the witnesses of
+/// this statement in the order of the bounds and are linked to signed messages using witness equalities,
+/// see `Self::witness_equalities_with_signature`.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct AggregatedBoundCheckBpp<G: AffineRepr> {
+    /// Bounds `(min, max)` for each message
+    pub bounds: Vec<(u64, u64)>,
+    #[serde_as(as = "Option<ArkObjectBytes>")]
+    pub params: Option<BppSetupParams<G>>,
+    pub params_ref: Option<usize>,
+}
+
+impl<G: AffineRepr> AggregatedBoundCheckBpp<G> {
+    pub fn new_statement_from_params<E: Pairing>(
+        bounds: Vec<(u64, u64)>,
+        params: BppSetupParams<G>,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        Self::validate_bounds(&bounds)?;
+        Ok(Statement::AggregatedBoundCheckBpp(Self {
+            bounds,
+            params: Some(params),
+            params_ref: None,
+        }))
+    }
+
+    pub fn new_statement_from_params_ref<E: Pairing>(
+        bounds: Vec<(u64, u64)>,
+        params_ref: usize,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        Self::validate_bounds(&bounds)?;
+        Ok(Statement::AggregatedBoundCheckBpp(Self {
+            bounds,
+            params: None,
+            params_ref: Some(params_ref),
+        }))
+    }
+
+    pub fn get_setup_params<'a, E: Pairing>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a BppSetupParams<G>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.params,
+            self.params_ref,
+            BppSetupParams,
+            IncompatibleBoundCheckSetupParamAtIndex,
+            st_idx
+        )
+    }
+
+    /// Witness equalities linking the messages at indices `message_indices` of the signature in statement
+    /// `sig_statement_idx` to the witnesses of this statement at index `statement_idx`, i.e. the message at
+    /// `message_indices[i]` should satisfy the `i`-th bounds.
+    pub fn witness_equalities_with_signature(
+        sig_statement_idx: usize,
+        message_indices: &[usize],
+        statement_idx: usize,
+    ) -> Vec<EqualWitnesses> {
+        message_indices
+            .iter()
+            .enumerate()
+            .map(|(i, m_idx)| {
+                EqualWitnesses(
+                    [(sig_statement_idx, *m_idx), (statement_idx, i)]
+                        .into_iter()
+                        .collect::<BTreeSet<WitnessRef>>(),
+                )
+            })
+            .collect()
+    }
+
+    fn validate_bounds(bounds: &[(u64, u64)]) -> Result<(), ProofSystemError> {
+        if bounds.is_empty() {
+            return Err(ProofSystemError::NoBoundsForAggregatedBoundCheck);
+        }
+        for (min, max) in bounds {
+            validate_bounds(*min, *max)?;
+        }
+        Ok(())
+    }
+}
@@ -61,6 +61,33 @@ macro_rules! impl_bbs_statement {
             })
         }
 
+        /// Create a statement by passing the signature parameters and public key directly where all of
+        /// `messages` are revealed except the ones at `hidden_indices`.
+        pub fn new_statement_revealing_all_except<G: AffineRepr>(
+            signature_params: $params<E>,
+            public_key: PublicKeyG2<E>,
+            messages: &[E::ScalarField],
+            hidden_indices: &ark_std::collections::BTreeSet<usize>,
+        ) -> Result<Statement<E, G>, ProofSystemError> {
+            if let Some(i) = hidden_indices.iter().find(|i| **i >= messages.len()) {
+                return Err(ProofSystemError::HiddenMessageIndexOutOfRange(
+                    *i,
+                    messages.len(),
+                ));
+            }
+            let revealed_messages = messages
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !hidden_indices.contains(i))
+                .map(|(i, m)| (i, *m))
+                .collect();
+            Ok(Self::new_statement_from_params(
+                signature_params,
+                public_key,
+                revealed_messages,
+            ))
+        }
+
         /// Get signature params for the statement index `s_idx` either from `self` or from given `setup_params`.
         pub fn get_sig_params<'a, G: AffineRepr>(
             &'a self,
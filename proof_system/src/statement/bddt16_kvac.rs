@@ -0,0 +1,134 @@
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{collections::BTreeMap, vec::Vec};
+use kvac::bddt_2016::setup::{MACParams, SecretKey};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, Same};
+
+use crate::{error::ProofSystemError, setup_params::SetupParams, statement::Statement};
+use dock_crypto_utils::serde_utils::ArkObjectBytes;
+
+/// Public values like setup params and revealed messages for proving knowledge of a BDDT16 MAC. Used by the prover.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct PoKBDDT16MAC<E: Pairing> {
+    /// Messages being revealed.
+    #[serde_as(as = "BTreeMap<Same, ArkObjectBytes>")]
+    pub revealed_messages: BTreeMap<usize, E::ScalarField>,
+    /// If the statement was created by passing the MAC params directly, then it will not be None
+    #[serde_as(as = "Option<ArkObjectBytes>")]
+    pub mac_params: Option<MACParams<E::G1Affine>>,
+    /// If the statement was created by passing the index of MAC params in `SetupParams`, then it will not be None
+    pub mac_params_ref: Option<usize>,
+}
+
+/// Public values like setup params and revealed messages for verifying the proof of knowledge of a BDDT16 MAC. Used by
+/// the verifier as it knows the secret key of the MAC issuer. Should not be shared with the prover.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct PoKBDDT16MACFullVerifier<E: Pairing> {
+    /// Messages being revealed.
+    #[serde_as(as = "BTreeMap<Same, ArkObjectBytes>")]
+    pub revealed_messages: BTreeMap<usize, E::ScalarField>,
+    /// If the statement was created by passing the MAC params directly, then it will not be None
+    #[serde_as(as = "Option<ArkObjectBytes>")]
+    pub mac_params: Option<MACParams<E::G1Affine>>,
+    /// If the statement was created by passing the index of MAC params in `SetupParams`, then it will not be None
+    pub mac_params_ref: Option<usize>,
+    #[serde_as(as = "ArkObjectBytes")]
+    pub secret_key: SecretKey<E::ScalarField>,
+}
+
+impl<E: Pairing> PoKBDDT16MAC<E> {
+    /// Create a statement by passing the MAC parameters directly.
+    pub fn new_statement_from_params<G: AffineRepr>(
+        mac_params: MACParams<E::G1Affine>,
+        revealed_messages: BTreeMap<usize, E::ScalarField>,
+    ) -> Statement<E, G> {
+        Statement::PoKBDDT16MAC(Self {
+            revealed_messages,
+            mac_params: Some(mac_params),
+            mac_params_ref: None,
+        })
+    }
+
+    /// Create a statement by passing the index of MAC parameters in `SetupParams`.
+    pub fn new_statement_from_params_ref<G: AffineRepr>(
+        mac_params_ref: usize,
+        revealed_messages: BTreeMap<usize, E::ScalarField>,
+    ) -> Statement<E, G> {
+        Statement::PoKBDDT16MAC(Self {
+            revealed_messages,
+            mac_params: None,
+            mac_params_ref: Some(mac_params_ref),
+        })
+    }
+
+    /// Get MAC params for the statement index `s_idx` either from `self` or from given `setup_params`.
+    pub fn get_params<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a MACParams<E::G1Affine>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.mac_params,
+            self.mac_params_ref,
+            BDDT16MACParams,
+            IncompatibleBDDT16SetupParamAtIndex,
+            st_idx
+        )
+    }
+}
+
+impl<E: Pairing> PoKBDDT16MACFullVerifier<E> {
+    /// Create a statement by passing the MAC parameters directly.
+    pub fn new_statement_from_params<G: AffineRepr>(
+        mac_params: MACParams<E::G1Affine>,
+        secret_key: SecretKey<E::ScalarField>,
+        revealed_messages: BTreeMap<usize, E::ScalarField>,
+    ) -> Statement<E, G> {
+        Statement::PoKBDDT16MACFullVerifier(Self {
+            revealed_messages,
+            mac_params: Some(mac_params),
+            mac_params_ref: None,
+            secret_key,
+        })
+    }
+
+    /// Create a statement by passing the index of MAC parameters in `SetupParams`.
+    pub fn new_statement_from_params_ref<G: AffineRepr>(
+        mac_params_ref: usize,
+        secret_key: SecretKey<E::ScalarField>,
+        revealed_messages: BTreeMap<usize, E::ScalarField>,
+    ) -> Statement<E, G> {
+        Statement::PoKBDDT16MACFullVerifier(Self {
+            revealed_messages,
+            mac_params: None,
+            mac_params_ref: Some(mac_params_ref),
+            secret_key,
+        })
+    }
+
+    /// Get MAC params for the statement index `s_idx` either from `self` or from given `setup_params`.
+    pub fn get_params<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a MACParams<E::G1Affine>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.mac_params,
+            self.mac_params_ref,
+            BDDT16MACParams,
+            IncompatibleBDDT16SetupParamAtIndex,
+            st_idx
+        )
+    }
+}
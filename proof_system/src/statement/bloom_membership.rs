@@ -0,0 +1,168 @@
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::vec::Vec;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+pub use legogroth16::{ProvingKey, VerifyingKey};
+
+use crate::{
+    error::ProofSystemError,
+    setup_params::SetupParams,
+    statement::Statement,
+    sub_protocols::bloom_membership::{BloomFilter, BloomMembershipLegoGrothProtocol},
+};
+use dock_crypto_utils::serde_utils::ArkObjectBytes;
+
+/// Proving that a message is present in a public Bloom filter using LegoGroth16. Since Bloom filters have false
+/// positives, this proves that either the message was added to the filter or that all its bits are set due to
+/// other elements.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct BloomMembershipLegoGroth16Prover<E: Pairing> {
+    pub filter: BloomFilter,
+    #[serde_as(as = "Option<ArkObjectBytes>")]
+    pub snark_proving_key: Option<ProvingKey<E>>,
+    pub snark_proving_key_ref: Option<usize>,
+}
+
+/// Verifying that a message is present in a public Bloom filter using LegoGroth16.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct BloomMembershipLegoGroth16Verifier<E: Pairing> {
+    pub filter: BloomFilter,
+    #[serde_as(as = "Option<ArkObjectBytes>")]
+    pub snark_verifying_key: Option<VerifyingKey<E>>,
+    pub snark_verifying_key_ref: Option<usize>,
+}
+
+impl<E: Pairing> BloomMembershipLegoGroth16Prover<E> {
+    pub fn new_statement_from_params<G: AffineRepr>(
+        filter: BloomFilter,
+        snark_proving_key: ProvingKey<E>,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        BloomFilter::validate_params(filter.num_bits(), filter.num_hashes)?;
+        BloomMembershipLegoGrothProtocol::validate_verification_key(
+            &snark_proving_key.vk,
+            &filter,
+        )?;
+        Ok(Statement::BloomMembershipLegoGroth16Prover(Self {
+            filter,
+            snark_proving_key: Some(snark_proving_key),
+            snark_proving_key_ref: None,
+        }))
+    }
+
+    pub fn new_statement_from_params_ref<G: AffineRepr>(
+        filter: BloomFilter,
+        snark_proving_key_ref: usize,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        BloomFilter::validate_params(filter.num_bits(), filter.num_hashes)?;
+        Ok(Statement::BloomMembershipLegoGroth16Prover(Self {
+            filter,
+            snark_proving_key: None,
+            snark_proving_key_ref: Some(snark_proving_key_ref),
+        }))
+    }
+
+    /// Get the proving key, given directly or as a reference in `SetupParams`, after checking it against the
+    /// filter
+    pub fn get_proving_key<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a ProvingKey<E>, ProofSystemError> {
+        let pk = self.extract_proving_key(setup_params, st_idx)?;
+        BloomMembershipLegoGrothProtocol::validate_verification_key(&pk.vk, &self.filter)?;
+        Ok(pk)
+    }
+
+    fn extract_proving_key<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a ProvingKey<E>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.snark_proving_key,
+            self.snark_proving_key_ref,
+            LegoSnarkProvingKey,
+            IncompatibleBloomMembershipSetupParamAtIndex,
+            st_idx
+        )
+    }
+
+    /// Create the verifier's statement from this statement. Fails if the proving key is given as a
+    /// reference in `SetupParams`.
+    pub fn to_verifier(&self) -> Result<BloomMembershipLegoGroth16Verifier<E>, ProofSystemError> {
+        let snark_pk = self
+            .snark_proving_key
+            .as_ref()
+            .ok_or(ProofSystemError::CannotDeriveVerifierStatement)?;
+        Ok(BloomMembershipLegoGroth16Verifier {
+            filter: self.filter.clone(),
+            snark_verifying_key: Some(snark_pk.vk.clone()),
+            snark_verifying_key_ref: None,
+        })
+    }
+}
+
+impl<E: Pairing> BloomMembershipLegoGroth16Verifier<E> {
+    pub fn new_statement_from_params<G: AffineRepr>(
+        filter: BloomFilter,
+        snark_verifying_key: VerifyingKey<E>,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        BloomFilter::validate_params(filter.num_bits(), filter.num_hashes)?;
+        BloomMembershipLegoGrothProtocol::validate_verification_key(&snark_verifying_key, &filter)?;
+        Ok(Statement::BloomMembershipLegoGroth16Verifier(Self {
+            filter,
+            snark_verifying_key: Some(snark_verifying_key),
+            snark_verifying_key_ref: None,
+        }))
+    }
+
+    pub fn new_statement_from_params_ref<G: AffineRepr>(
+        filter: BloomFilter,
+        snark_verifying_key_ref: usize,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        BloomFilter::validate_params(filter.num_bits(), filter.num_hashes)?;
+        Ok(Statement::BloomMembershipLegoGroth16Verifier(Self {
+            filter,
+            snark_verifying_key: None,
+            snark_verifying_key_ref: Some(snark_verifying_key_ref),
+        }))
+    }
+
+    /// Get the verifying key, given directly or as a reference in `SetupParams`, after checking it against the
+    /// filter
+    pub fn get_verifying_key<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a VerifyingKey<E>, ProofSystemError> {
+        let vk = self.extract_verifying_key(setup_params, st_idx)?;
+        BloomMembershipLegoGrothProtocol::validate_verification_key(vk, &self.filter)?;
+        Ok(vk)
+    }
+
+    fn extract_verifying_key<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a VerifyingKey<E>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.snark_verifying_key,
+            self.snark_verifying_key_ref,
+            LegoSnarkVerifyingKey,
+            IncompatibleBloomMembershipSetupParamAtIndex,
+            st_idx
+        )
+    }
+}
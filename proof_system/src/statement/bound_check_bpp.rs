@@ -1,10 +1,13 @@
 use crate::{
-    error::ProofSystemError, setup_params::SetupParams, statement::Statement,
+    error::ProofSystemError,
+    meta_statement::{EqualWitnesses, MetaStatements, WitnessRef},
+    setup_params::SetupParams,
+    statement::{ped_comm::PedersenCommitment, Statement, Statements},
     sub_protocols::validate_bounds,
 };
 use ark_ec::{pairing::Pairing, AffineRepr};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::vec::Vec;
+use ark_std::{collections::BTreeSet, ops::Neg, vec, vec::Vec};
 use bulletproofs_plus_plus::setup::SetupParams as BppSetupParams;
 use dock_crypto_utils::serde_utils::ArkObjectBytes;
 use serde::{Deserialize, Serialize};
@@ -52,6 +55,44 @@ impl<G: AffineRepr> BoundCheckBpp<G> {
         }))
     }
 
+    /// Add statements to prove that the sum of 2 hidden messages, like messages of signatures, satisfies
+    /// `min <= m_1 + m_2 < max`. `msg_1` and `msg_2` refer to the messages as `(statement index, witness index)`.
+    ///
+    /// A Pedersen commitment statement with key `[g, g, -g]` and commitment as the identity element is added
+    /// which proves knowledge of `m_1`, `m_2` and `s` such that `g * m_1 + g * m_2 - g * s = 0`, i.e.
+    /// `s = m_1 + m_2`. A bound check statement is added for `s`. Witness equalities bind `m_1` and `m_2`
+    /// to the referenced messages so the same Schnorr responses are used for them. Returns the indices of
+    /// the Pedersen commitment and the bound check statements. Use `Witnesses::add_for_bound_check_bpp_of_sum`
+    /// to add the corresponding witnesses.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_statements_for_sum<E: Pairing>(
+        statements: &mut Statements<E, G>,
+        meta_statements: &mut MetaStatements,
+        min: u64,
+        max: u64,
+        params: BppSetupParams<G>,
+        g: G,
+        msg_1: WitnessRef,
+        msg_2: WitnessRef,
+    ) -> Result<(usize, usize), ProofSystemError> {
+        let bound_check = Self::new_statement_from_params(min, max, params)?;
+        let ped_comm_idx = statements.add(PedersenCommitment::new_statement_from_params(
+            vec![g, g, g.into_group().neg().into()],
+            G::zero(),
+        ));
+        let bound_check_idx = statements.add(bound_check);
+        for eq in [
+            [msg_1, (ped_comm_idx, 0)],
+            [msg_2, (ped_comm_idx, 1)],
+            [(ped_comm_idx, 2), (bound_check_idx, 0)],
+        ] {
+            meta_statements.add_witness_equality(EqualWitnesses(
+                eq.into_iter().collect::<BTreeSet<WitnessRef>>(),
+            ));
+        }
+        Ok((ped_comm_idx, bound_check_idx))
+    }
+
     pub fn get_setup_params<'a, E: Pairing>(
         &'a self,
         setup_params: &'a [SetupParams<E, G>],
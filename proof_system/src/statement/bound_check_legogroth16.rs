@@ -15,6 +15,12 @@ use crate::{
 use dock_crypto_utils::serde_utils::ArkObjectBytes;
 
 /// Proving knowledge of message that satisfies given bounds [min, max), i.e. `min <= message < max` using LegoGroth16.
+///
+/// The proving key is large (about 900 KB for the bound check circuit over BLS12-381) so when several statements
+/// use the same key, it should be loaded once, added to the `ProofSpec`'s setup params as
+/// `SetupParams::LegoSnarkProvingKey` and referred to by index using `Self::new_statement_from_params_ref`, rather
+/// than cloned into each statement. `StatementsBuilder` does this for statements created with the key inline. The
+/// same applies to the verifying key of `BoundCheckLegoGroth16Verifier`.
 #[serde_as]
 #[derive(
     Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
@@ -87,6 +93,22 @@ impl<E: Pairing> BoundCheckLegoGroth16Prover<E> {
             st_idx
         )
     }
+
+    /// Create the verifier's statement from this statement. The verifier's statement has the SNARK
+    /// verifying key instead of the proving key. Fails if the proving key is given as a reference in
+    /// `SetupParams` as the verifying key can't be extracted without the setup params.
+    pub fn to_verifier(&self) -> Result<BoundCheckLegoGroth16Verifier<E>, ProofSystemError> {
+        let snark_pk = self
+            .snark_proving_key
+            .as_ref()
+            .ok_or(ProofSystemError::CannotDeriveVerifierStatement)?;
+        Ok(BoundCheckLegoGroth16Verifier {
+            min: self.min,
+            max: self.max,
+            snark_verifying_key: Some(snark_pk.vk.clone()),
+            snark_verifying_key_ref: None,
+        })
+    }
 }
 
 impl<E: Pairing> BoundCheckLegoGroth16Verifier<E> {
@@ -44,6 +44,20 @@ impl<E: Pairing> SmcParamsAndCommitmentKey<E> {
         (Self { params, comm_key }, sk)
     }
 
+    /// Create params for proving membership in an arbitrary set of values, used with `SetMembershipSmc`. The set
+    /// is sorted and duplicates are removed before signing each member.
+    pub fn new_for_set<R: RngCore, D: Digest>(
+        rng: &mut R,
+        label: &[u8],
+        mut set: Vec<E::ScalarField>,
+    ) -> (Self, SecretKey<E::ScalarField>) {
+        set.sort();
+        set.dedup();
+        let (params, sk) = SetMembershipCheckParams::new::<R, D>(rng, label, set);
+        let comm_key = MemberCommitmentKey::new::<D>(label);
+        (Self { params, comm_key }, sk)
+    }
+
     pub fn verify(&self) -> Result<(), ProofSystemError> {
         self.params.verify()?;
         Ok(())
@@ -1,8 +1,12 @@
 use crate::{
     error::ProofSystemError,
     setup_params::SetupParams,
-    statement::{bound_check_smc::SmcParamsAndCommitmentKey, Statement},
+    statement::{
+        bound_check_smc::{BoundCheckSmc, SmcParamsAndCommitmentKey},
+        Statement,
+    },
     sub_protocols::validate_bounds,
+    witness::Witness,
 };
 use ark_ec::{pairing::Pairing, AffineRepr};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
@@ -73,6 +77,64 @@ pub struct BoundCheckSmcWithKVVerifier<E: Pairing> {
     pub params_ref: Option<usize>,
 }
 
+/// Whether a set-membership check based bound check is publicly verifiable, i.e. uses `BoundCheckSmc`, or has keyed
+/// verification, i.e. uses `BoundCheckSmcWithKVProver` and `BoundCheckSmcWithKVVerifier`, where only the holder of
+/// the secret key of the params can verify the proof. Lets the caller create the statements and witness for either
+/// mode without branching on it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VerificationMode {
+    Public,
+    Keyed,
+}
+
+impl VerificationMode {
+    /// Statement used by the prover to prove `min <= message < max`. The prover uses the same params in both modes.
+    pub fn prover_statement<E: Pairing, G: AffineRepr>(
+        &self,
+        min: u64,
+        max: u64,
+        params: SmcParamsAndCommitmentKey<E>,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        match self {
+            Self::Public => BoundCheckSmc::new_statement_from_params(min, max, params),
+            Self::Keyed => BoundCheckSmcWithKVProver::new_statement_from_params(min, max, params),
+        }
+    }
+
+    /// Statement used by the verifier to verify that `min <= message < max`. The secret key is only used in the
+    /// keyed mode and is required there.
+    pub fn verifier_statement<E: Pairing, G: AffineRepr>(
+        &self,
+        min: u64,
+        max: u64,
+        params: SmcParamsAndCommitmentKey<E>,
+        sk: Option<SecretKey<E::ScalarField>>,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        match self {
+            Self::Public => BoundCheckSmc::new_statement_from_params(min, max, params),
+            Self::Keyed => {
+                let sk = sk.ok_or(ProofSystemError::MissingSecretKeyForKeyedVerification)?;
+                BoundCheckSmcWithKVVerifier::new_statement_from_params(
+                    min,
+                    max,
+                    SmcParamsAndCommitmentKeyAndSecretKey {
+                        params_and_comm_key: params,
+                        sk,
+                    },
+                )
+            }
+        }
+    }
+
+    /// Witness for the prover's statement
+    pub fn witness<E: Pairing>(&self, message: E::ScalarField) -> Witness<E> {
+        match self {
+            Self::Public => Witness::BoundCheckSmc(message),
+            Self::Keyed => Witness::BoundCheckSmcWithKV(message),
+        }
+    }
+}
+
 impl<E: Pairing> BoundCheckSmcWithKVProver<E> {
     pub fn new_statement_from_params<G: AffineRepr>(
         min: u64,
@@ -0,0 +1,198 @@
+//! Builder for `Statements` that moves the setup params (signature params, public keys, SNARK keys) of the statements
+//! into a list of `SetupParams` and makes the statements refer to them by index. Equal params are stored
+//! only once so when several statements use the same params, the `Statements` and the `SetupParams` together
+//! are much smaller than the statements with inline params.
+
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_std::vec::Vec;
+
+use crate::{
+    setup_params::SetupParams,
+    statement::{Statement, Statements},
+};
+
+/// Builds `Statements` along with the `SetupParams` they refer to. Params passed inline in a statement
+/// are interned, i.e. if an equal param has already been seen, its index is reused.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatementsBuilder<E: Pairing, G: AffineRepr> {
+    statements: Statements<E, G>,
+    setup_params: Vec<SetupParams<E, G>>,
+}
+
+impl<E: Pairing, G: AffineRepr> Default for StatementsBuilder<E, G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Pairing, G: AffineRepr> StatementsBuilder<E, G> {
+    pub fn new() -> Self {
+        Self {
+            statements: Statements::new(),
+            setup_params: Vec::new(),
+        }
+    }
+
+    /// Start with already existing setup params. Statements referring to these should use the indices
+    /// of this list.
+    pub fn with_setup_params(setup_params: Vec<SetupParams<E, G>>) -> Self {
+        Self {
+            statements: Statements::new(),
+            setup_params,
+        }
+    }
+
+    /// Add the setup param if an equal one isn't already present and return its index.
+    pub fn intern(&mut self, param: SetupParams<E, G>) -> usize {
+        match self.setup_params.iter().position(|p| *p == param) {
+            Some(idx) => idx,
+            None => {
+                self.setup_params.push(param);
+                self.setup_params.len() - 1
+            }
+        }
+    }
+
+    /// Add a statement and return its index. If the statement carries its signature params and public key,
+    /// or its LegoGroth16 proving or verifying key for bound checks, inline, they are interned and the statement
+    /// is changed to refer to them. Statements of other types are added unchanged.
+    pub fn add(&mut self, statement: Statement<E, G>) -> usize {
+        let statement = match statement {
+            Statement::PoKBBSSignatureG1(mut s) => {
+                if let Some(p) = s.signature_params.take() {
+                    s.signature_params_ref =
+                        Some(self.intern(SetupParams::BBSPlusSignatureParams(p)));
+                }
+                if let Some(pk) = s.public_key.take() {
+                    s.public_key_ref = Some(self.intern(SetupParams::BBSPlusPublicKey(pk)));
+                }
+                Statement::PoKBBSSignatureG1(s)
+            }
+            Statement::PoKBBSSignature23G1(mut s) => {
+                if let Some(p) = s.signature_params.take() {
+                    s.signature_params_ref =
+                        Some(self.intern(SetupParams::BBSSignatureParams23(p)));
+                }
+                if let Some(pk) = s.public_key.take() {
+                    s.public_key_ref = Some(self.intern(SetupParams::BBSPlusPublicKey(pk)));
+                }
+                Statement::PoKBBSSignature23G1(s)
+            }
+            Statement::PoKPSSignature(mut s) => {
+                if let Some(p) = s.signature_params.take() {
+                    s.signature_params_ref = Some(self.intern(SetupParams::PSSignatureParams(p)));
+                }
+                if let Some(pk) = s.public_key.take() {
+                    s.public_key_ref = Some(self.intern(SetupParams::PSSignaturePublicKey(pk)));
+                }
+                Statement::PoKPSSignature(s)
+            }
+            Statement::PoKPSAggregateSignature(mut s) => {
+                if let Some(p) = s.signature_params.take() {
+                    s.signature_params_ref = Some(self.intern(SetupParams::PSSignatureParams(p)));
+                }
+                Statement::PoKPSAggregateSignature(s)
+            }
+            Statement::BoundCheckLegoGroth16Prover(mut s) => {
+                if let Some(pk) = s.snark_proving_key.take() {
+                    s.snark_proving_key_ref =
+                        Some(self.intern(SetupParams::LegoSnarkProvingKey(pk)));
+                }
+                Statement::BoundCheckLegoGroth16Prover(s)
+            }
+            Statement::BoundCheckLegoGroth16Verifier(mut s) => {
+                if let Some(vk) = s.snark_verifying_key.take() {
+                    s.snark_verifying_key_ref =
+                        Some(self.intern(SetupParams::LegoSnarkVerifyingKey(vk)));
+                }
+                Statement::BoundCheckLegoGroth16Verifier(s)
+            }
+            s => s,
+        };
+        self.statements.add(statement)
+    }
+
+    pub fn len(&self) -> usize {
+        self.statements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.statements.is_empty()
+    }
+
+    /// Return the statements and the setup params they refer to. Both should be passed to the `ProofSpec`.
+    pub fn build(self) -> (Statements<E, G>, Vec<SetupParams<E, G>>) {
+        (self.statements, self.setup_params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statement::bbs_plus::PoKBBSSignatureG1;
+    use ark_bls12_381::{Bls12_381, G1Affine};
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use ark_std::{
+        collections::BTreeMap,
+        rand::{rngs::StdRng, SeedableRng},
+    };
+    use test_utils::bbs::bbs_plus_sig_setup;
+
+    #[test]
+    fn builder_interns_shared_params() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (msgs, params, keypair, _) = bbs_plus_sig_setup(&mut rng, 10);
+        let count = 50;
+
+        let mut independent = Statements::<Bls12_381, G1Affine>::new();
+        let mut builder = StatementsBuilder::<Bls12_381, G1Affine>::new();
+        for i in 0..count {
+            let mut revealed = BTreeMap::new();
+            revealed.insert(i % 10, msgs[i % 10]);
+            let stmt = PoKBBSSignatureG1::new_statement_from_params(
+                params.clone(),
+                keypair.public_key.clone(),
+                revealed,
+            );
+            independent.add(stmt.clone());
+            assert_eq!(builder.add(stmt), i);
+        }
+        assert_eq!(builder.len(), count);
+        let (statements, setup_params) = builder.build();
+        assert_eq!(setup_params.len(), 2);
+
+        let mut independent_bytes = vec![];
+        independent
+            .serialize_compressed(&mut independent_bytes)
+            .unwrap();
+        let mut stmt_bytes = vec![];
+        statements.serialize_compressed(&mut stmt_bytes).unwrap();
+        let mut params_bytes = vec![];
+        setup_params
+            .serialize_compressed(&mut params_bytes)
+            .unwrap();
+        assert!((stmt_bytes.len() + params_bytes.len()) * 5 < independent_bytes.len());
+
+        let statements =
+            Statements::<Bls12_381, G1Affine>::deserialize_compressed(&stmt_bytes[..]).unwrap();
+        let setup_params =
+            Vec::<SetupParams<Bls12_381, G1Affine>>::deserialize_compressed(&params_bytes[..])
+                .unwrap();
+        for (i, (s, ind)) in statements.0.iter().zip(independent.0.iter()).enumerate() {
+            match (s, ind) {
+                (Statement::PoKBBSSignatureG1(s), Statement::PoKBBSSignatureG1(ind)) => {
+                    assert_eq!(s.revealed_messages, ind.revealed_messages);
+                    assert_eq!(
+                        s.get_sig_params(&setup_params, i).unwrap(),
+                        ind.signature_params.as_ref().unwrap()
+                    );
+                    assert_eq!(
+                        s.get_public_key(&setup_params, i).unwrap(),
+                        ind.public_key.as_ref().unwrap()
+                    );
+                }
+                _ => panic!("Unexpected statement"),
+            }
+        }
+    }
+}
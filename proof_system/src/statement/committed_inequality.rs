@@ -0,0 +1,87 @@
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{collections::BTreeSet, vec::Vec};
+use dock_crypto_utils::{commitment::PedersenCommitmentKey, serde_utils::*};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::{
+    error::ProofSystemError,
+    meta_statement::{EqualWitnesses, WitnessRef},
+    setup_params::SetupParams,
+    statement::Statement,
+};
+
+/// Statement for proving that 2 hidden messages are not equal, like that the holder's id in one credential
+/// differs from the id in another. The statement has 2 witnesses, the messages at index 0 and 1, which are
+/// linked to signed messages with witness equalities, see `Self::witness_equalities_with_signatures`.
+///
+/// Both messages are committed to and the inequality of the committed values is proved using a
+/// `DiscreteLogInequalityProtocol` on the difference of the commitments. The verifier learns nothing
+/// about the messages other than that they differ.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct CommittedInequality<G: AffineRepr> {
+    #[serde_as(as = "Option<ArkObjectBytes>")]
+    pub comm_key: Option<PedersenCommitmentKey<G>>,
+    pub comm_key_ref: Option<usize>,
+}
+
+impl<G: AffineRepr> CommittedInequality<G> {
+    pub fn new_statement_from_params<E: Pairing>(
+        comm_key: PedersenCommitmentKey<G>,
+    ) -> Statement<E, G> {
+        Statement::CommittedInequality(Self {
+            comm_key: Some(comm_key),
+            comm_key_ref: None,
+        })
+    }
+
+    pub fn new_statement_from_params_ref<E: Pairing>(comm_key_ref: usize) -> Statement<E, G> {
+        Statement::CommittedInequality(Self {
+            comm_key: None,
+            comm_key_ref: Some(comm_key_ref),
+        })
+    }
+
+    /// Witness equalities linking the message at index `message_index_1` of the signature in statement
+    /// `sig_statement_idx_1` and the message at index `message_index_2` of the signature in statement
+    /// `sig_statement_idx_2` to the witnesses of this statement at index `statement_idx`. The signatures
+    /// can be in the same statement.
+    pub fn witness_equalities_with_signatures(
+        (sig_statement_idx_1, message_index_1): WitnessRef,
+        (sig_statement_idx_2, message_index_2): WitnessRef,
+        statement_idx: usize,
+    ) -> [EqualWitnesses; 2] {
+        [
+            EqualWitnesses(
+                [(sig_statement_idx_1, message_index_1), (statement_idx, 0)]
+                    .into_iter()
+                    .collect::<BTreeSet<WitnessRef>>(),
+            ),
+            EqualWitnesses(
+                [(sig_statement_idx_2, message_index_2), (statement_idx, 1)]
+                    .into_iter()
+                    .collect::<BTreeSet<WitnessRef>>(),
+            ),
+        ]
+    }
+
+    pub fn get_comm_key<'a, E: Pairing>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a PedersenCommitmentKey<G>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.comm_key,
+            self.comm_key_ref,
+            CommitmentKey,
+            IncompatibleBoundCheckSetupParamAtIndex,
+            st_idx
+        )
+    }
+}
@@ -0,0 +1,97 @@
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{collections::BTreeSet, vec::Vec};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::{
+    error::ProofSystemError,
+    meta_statement::{EqualWitnesses, WitnessRef},
+    setup_params::SetupParams,
+    statement::Statement,
+};
+use dock_crypto_utils::serde_utils::*;
+
+/// Proving knowledge of scalars `s_i` in Pedersen commitment `g_0 * s_0 + g_1 * s_1 + ... + g_{n-1} * s_{n-1} = C`
+/// where `s_0` is the message at index `message_index` of the signature in statement `signature_statement_index`.
+/// Unlike a `PedersenCommitment` statement, the equality with the signed message doesn't need a witness equality
+/// in the meta-statements as it's implied by this statement so the Schnorr responses for `s_0` and the message
+/// are always the same. The witness of this statement is the opening of the commitment, i.e. `[s_0, s_1, ...]`.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct CommittedMessageEquality<G: AffineRepr> {
+    /// The Pedersen commitment `C` in `g_0 * s_0 + g_1 * s_1 + ... + g_{n-1} * s_{n-1} = C`
+    #[serde_as(as = "ArkObjectBytes")]
+    pub commitment: G,
+    /// Commitment key `g_i` in `g_0 * s_0 + g_1 * s_1 + ... + g_{n-1} * s_{n-1} = C`
+    #[serde_as(as = "Option<Vec<ArkObjectBytes>>")]
+    pub key: Option<Vec<G>>,
+    pub key_ref: Option<usize>,
+    /// Index of the signature's statement in the `ProofSpec`
+    pub signature_statement_index: usize,
+    /// Index of the committed message in the signature
+    pub message_index: usize,
+}
+
+impl<G: AffineRepr> CommittedMessageEquality<G> {
+    pub fn new_statement_from_params<E: Pairing>(
+        key: Vec<G>,
+        commitment: G,
+        signature_statement_index: usize,
+        message_index: usize,
+    ) -> Statement<E, G> {
+        Statement::CommittedMessageEquality(Self {
+            commitment,
+            key: Some(key),
+            key_ref: None,
+            signature_statement_index,
+            message_index,
+        })
+    }
+
+    pub fn new_statement_from_params_refs<E: Pairing>(
+        key_ref: usize,
+        commitment: G,
+        signature_statement_index: usize,
+        message_index: usize,
+    ) -> Statement<E, G> {
+        Statement::CommittedMessageEquality(Self {
+            commitment,
+            key: None,
+            key_ref: Some(key_ref),
+            signature_statement_index,
+            message_index,
+        })
+    }
+
+    pub fn get_commitment_key<'a, E: Pairing>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a Vec<G>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.key,
+            self.key_ref,
+            PedersenCommitmentKey,
+            IncompatiblePedCommSetupParamAtIndex,
+            st_idx
+        )
+    }
+
+    /// The witness equality implied by this statement when it's at index `statement_idx`, i.e. the first
+    /// committed value is equal to the signed message
+    pub fn implied_witness_equality(&self, statement_idx: usize) -> EqualWitnesses {
+        EqualWitnesses(
+            [
+                (self.signature_statement_index, self.message_index),
+                (statement_idx, 0),
+            ]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+        )
+    }
+}
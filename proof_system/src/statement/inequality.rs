@@ -1,11 +1,17 @@
 use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_ff::Zero;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::vec::Vec;
+use ark_std::{collections::BTreeSet, vec::Vec};
 use dock_crypto_utils::{commitment::PedersenCommitmentKey, serde_utils::*};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
-use crate::{error::ProofSystemError, setup_params::SetupParams, statement::Statement};
+use crate::{
+    error::ProofSystemError,
+    meta_statement::{EqualWitnesses, WitnessRef},
+    setup_params::SetupParams,
+    statement::Statement,
+};
 
 #[serde_as]
 #[derive(
@@ -44,6 +50,36 @@ impl<G: AffineRepr> PublicInequality<G> {
         })
     }
 
+    /// Statement for proving that a message is not zero, like that a required attribute is filled when absent
+    /// attributes are encoded as zero. The signed message at `message_index` is linked to this statement with
+    /// `Self::witness_equality_with_signature`.
+    ///
+    /// The verifier learns that the message is not the field element zero and nothing else. This only tells
+    /// something about the attribute if its encoding maps the "empty" value to zero; attributes encoded by
+    /// hashing, like strings, are never zero in practice, even when empty, so proving them nonzero is meaningless.
+    pub fn new_nonzero<E: Pairing>(comm_key: PedersenCommitmentKey<G>) -> Statement<E, G> {
+        Self::new_statement_from_params(G::ScalarField::zero(), comm_key)
+    }
+
+    /// Same as `Self::new_nonzero` but the commitment key is at index `comm_key_ref` of the setup params
+    pub fn new_nonzero_from_params_ref<E: Pairing>(comm_key_ref: usize) -> Statement<E, G> {
+        Self::new_statement_from_params_ref(G::ScalarField::zero(), comm_key_ref)
+    }
+
+    /// Witness equality linking the message at index `message_index` of the signature in statement
+    /// `sig_statement_idx` to the witness of this statement at index `statement_idx`
+    pub fn witness_equality_with_signature(
+        sig_statement_idx: usize,
+        message_index: usize,
+        statement_idx: usize,
+    ) -> EqualWitnesses {
+        EqualWitnesses(
+            [(sig_statement_idx, message_index), (statement_idx, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        )
+    }
+
     pub fn get_comm_key<'a, E: Pairing>(
         &'a self,
         setup_params: &'a [SetupParams<E, G>],
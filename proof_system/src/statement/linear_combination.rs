@@ -0,0 +1,78 @@
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::vec::Vec;
+use dock_crypto_utils::serde_utils::ArkObjectBytes;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::{error::ProofSystemError, statement::Statement};
+
+/// Proving that a hidden message `m` equals a public linear combination of disclosed values, i.e.
+/// `m = a_0 * d_0 + a_1 * d_1 + ... + a_{n-1} * d_{n-1} + c` where the coefficients `a_i`, the values `d_i`
+/// (usually revealed messages of a signature) and the constant `c` are public. `m` is proven equal to a signed
+/// message using a witness equality. This is done by proving knowledge of `m` in `base * m` where the verifier
+/// computes `base * m` from the public values.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct DisclosedLinearCombination<G: AffineRepr> {
+    /// Coefficients `a_i`
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub coefficients: Vec<G::ScalarField>,
+    /// Disclosed values `d_i`
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub disclosed: Vec<G::ScalarField>,
+    /// The constant `c`
+    #[serde_as(as = "ArkObjectBytes")]
+    pub constant: G::ScalarField,
+    #[serde_as(as = "ArkObjectBytes")]
+    pub base: G,
+}
+
+impl<G: AffineRepr> DisclosedLinearCombination<G> {
+    pub fn new_statement_from_params<E: Pairing>(
+        coefficients: Vec<G::ScalarField>,
+        disclosed: Vec<G::ScalarField>,
+        constant: G::ScalarField,
+        base: G,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        let s = Self {
+            coefficients,
+            disclosed,
+            constant,
+            base,
+        };
+        s.validate()?;
+        Ok(Statement::DisclosedLinearCombination(s))
+    }
+
+    /// Check that there is a coefficient for each disclosed value and that the base isn't the identity. Since the
+    /// fields are public and the statement can be deserialized, this is also done when validating the `ProofSpec`.
+    pub fn validate(&self) -> Result<(), ProofSystemError> {
+        if self.coefficients.len() != self.disclosed.len() {
+            return Err(ProofSystemError::UnequalCoefficientAndDisclosedCount(
+                self.coefficients.len(),
+                self.disclosed.len(),
+            ));
+        }
+        if self.base.is_zero() {
+            return Err(ProofSystemError::DisclosedLinearCombinationBaseIsIdentity);
+        }
+        Ok(())
+    }
+
+    /// The expected value of the hidden message, `a_0 * d_0 + a_1 * d_1 + ... + a_{n-1} * d_{n-1} + c`
+    pub fn value(&self) -> G::ScalarField {
+        self.coefficients
+            .iter()
+            .zip(self.disclosed.iter())
+            .fold(self.constant, |acc, (a, d)| acc + *a * d)
+    }
+
+    /// `base * m` where `m` is the expected value
+    pub fn commitment(&self) -> G {
+        (self.base * self.value()).into()
+    }
+}
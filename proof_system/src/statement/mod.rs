@@ -1,24 +1,44 @@
 use ark_ec::{pairing::Pairing, AffineRepr};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError, Valid};
 use ark_std::{
+    collections::BTreeMap,
     io::{Read, Write},
+    string::{String, ToString},
     vec::Vec,
 };
+use blake2::{digest::consts::U32, Blake2b};
+use dock_crypto_utils::transcript::DigestWriter;
 use serde::{Deserialize, Serialize};
 
+use crate::error::ProofSystemError;
+
 pub mod accumulator;
+pub mod aggregated_bound_check_bpp;
 pub mod bbs_23;
 #[macro_use]
 pub mod bbs_plus;
+pub mod bddt16_kvac;
+pub mod bloom_membership;
 pub mod bound_check_bpp;
 pub mod bound_check_legogroth16;
 pub mod bound_check_smc;
 pub mod bound_check_smc_with_kv;
+pub mod builder;
+pub mod committed_inequality;
+pub mod committed_message_equality;
 pub mod inequality;
+pub mod linear_combination;
+pub mod monotonic;
+pub mod partial_ped_opening;
 pub mod ped_comm;
 pub mod ps_signature;
+pub mod public_set_membership;
 pub mod r1cs_legogroth16;
 pub mod saver;
+pub mod scoped_pseudonym;
+pub mod set_membership_smc;
+pub mod signature_commitment_consistency;
+mod size_estimate;
 
 /// Type of relation being proved and the public values for the relation
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -86,9 +106,64 @@ pub enum Statement<E: Pairing, G: AffineRepr> {
     ),
     KBPositiveAccumulatorMembership(accumulator::KBPositiveAccumulatorMembership<E>),
     KBPositiveAccumulatorMembershipCDH(accumulator::cdh::KBPositiveAccumulatorMembershipCDH<E>),
+    /// For proof of knowledge of the secret in a pseudonym for a public scope
+    ScopedPseudonym(scoped_pseudonym::ScopedPseudonym<G>),
+    /// For proving that a hidden message equals a public linear combination of disclosed values
+    DisclosedLinearCombination(linear_combination::DisclosedLinearCombination<G>),
+    /// Used by prover to create proof that witness is present in a public Bloom filter using LegoGroth16
+    BloomMembershipLegoGroth16Prover(bloom_membership::BloomMembershipLegoGroth16Prover<E>),
+    /// Used by verifier to verify proof that witness is present in a public Bloom filter using LegoGroth16
+    BloomMembershipLegoGroth16Verifier(bloom_membership::BloomMembershipLegoGroth16Verifier<E>),
+    /// For proving that hidden messages are in order using Bulletproofs++
+    Monotonic(monotonic::Monotonic<G>),
+    /// For proving that a value committed in a Pedersen commitment is equal to a signed message
+    CommittedMessageEquality(committed_message_equality::CommittedMessageEquality<G>),
+    /// For proving that a signed message is one of the values in a public set
+    PublicSetMembership(public_set_membership::PublicSetMembership<G>),
+    /// Used by prover to create proof of membership in a VB accumulator using the CDH approach where the
+    /// accumulator is only revealed to the accumulator manager
+    DetachedAccumulatorMembershipCDHProver(accumulator::DetachedAccumulatorMembershipCDHProver<E>),
+    /// Used by verifier to verify proof of membership in a VB accumulator using the CDH approach where the
+    /// accumulator is only revealed to the accumulator manager
+    DetachedAccumulatorMembershipCDHVerifier(
+        accumulator::DetachedAccumulatorMembershipCDHVerifier<E>,
+    ),
+    /// For proving that several hidden messages satisfy their bounds using a single Bulletproofs++ proof
+    AggregatedBoundCheckBpp(aggregated_bound_check_bpp::AggregatedBoundCheckBpp<G>),
+    /// For proving that a signed message is one of the values in a public set using set-membership check
+    SetMembershipSmc(set_membership_smc::SetMembershipSmc<E>),
+    /// For proving that 2 hidden messages are not equal
+    CommittedInequality(committed_inequality::CommittedInequality<G>),
+    /// For proof of knowledge of a PS signature aggregated from signatures of several signers
+    PoKPSAggregateSignature(ps_signature::PoKPSAggregateSignatureStatement<E>),
+    /// For proving knowledge of the hidden values in a Pedersen commitment while revealing the others
+    PartialPedersenOpening(partial_ped_opening::PartialPedersenOpening<G>),
+    /// For proving that several values committed in a Pedersen commitment are equal to signed messages
+    SignatureCommitmentConsistency(
+        signature_commitment_consistency::SignatureCommitmentConsistency<G>,
+    ),
+    /// Used by prover to create proof of knowledge of a BDDT16 MAC
+    PoKBDDT16MAC(bddt16_kvac::PoKBDDT16MAC<E>),
+    /// Used by verifier to verify proof of knowledge of a BDDT16 MAC using the secret key of the MAC issuer
+    PoKBDDT16MACFullVerifier(bddt16_kvac::PoKBDDT16MACFullVerifier<E>),
 }
 
-/// A collection of statements
+/// Whether statements are meant for creating or verifying a proof
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProverOrVerifier {
+    Prover,
+    Verifier,
+}
+
+/// Statement removed by `Statements::remove_and_reindex` and the map from old to new indices of the
+/// remaining statements
+pub type RemovedStatement<E, G> = (Statement<E, G>, BTreeMap<usize, usize>);
+
+/// A collection of statements.
+///
+/// The compressed serialization is the number of statements as a `u64` followed by each statement, and
+/// `serialize_compressed` writes it to the writer one statement at a time without buffering, so the
+/// writer can be a file or socket when there are many large statements.
 #[derive(
     Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
 )]
@@ -119,6 +194,251 @@ where
     pub fn len(&self) -> usize {
         self.0.len()
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Statement<E, G>> {
+        self.0.iter()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Statement<E, G>> {
+        self.0.get(index)
+    }
+
+    /// Replace the statement at `index` with `item`, returning the old statement. Indices of other statements
+    /// are unchanged.
+    pub fn replace(
+        &mut self,
+        index: usize,
+        item: Statement<E, G>,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        let len = self.len();
+        let s = self
+            .0
+            .get_mut(index)
+            .ok_or(ProofSystemError::InvalidStatementIndex(index, len))?;
+        Ok(core::mem::replace(s, item))
+    }
+
+    /// Remove the statement at `index`, shifting later statements down. Returns the removed statement and
+    /// a map from the old index of each remaining statement to its new index so that references to statements,
+    /// like in meta-statements, can be updated. References to signature statements inside the remaining
+    /// statements, like in `CommittedMessageEquality` and `SignatureCommitmentConsistency`, are updated using
+    /// the map. Fails if a remaining
+    /// statement refers to the statement being removed.
+    pub fn remove_and_reindex(
+        &mut self,
+        index: usize,
+    ) -> Result<RemovedStatement<E, G>, ProofSystemError> {
+        if index >= self.len() {
+            return Err(ProofSystemError::InvalidStatementIndex(index, self.len()));
+        }
+        for (i, s) in self.0.iter_mut().enumerate() {
+            if i != index && matches!(s.signature_statement_index_mut(), Some(r) if *r == index) {
+                return Err(ProofSystemError::RemovedStatementIsReferenced(index, i));
+            }
+        }
+        let removed = self.0.remove(index);
+        let reindex: BTreeMap<usize, usize> = (0..=self.len())
+            .filter(|i| *i != index)
+            .map(|i| (i, if i > index { i - 1 } else { i }))
+            .collect();
+        for s in self.0.iter_mut() {
+            // An out of range index is left as it is and fails when the proof spec is validated
+            if let Some(sig_idx) = s.signature_statement_index_mut() {
+                if let Some(new_idx) = reindex.get(sig_idx) {
+                    *sig_idx = *new_idx;
+                }
+            }
+        }
+        Ok((removed, reindex))
+    }
+
+    /// Check that none of the statements is used only by the other role, like a `SaverVerifier` among
+    /// statements for the prover. Statements used by both prover and verifier are always accepted.
+    pub fn validate_roles(&self, role: ProverOrVerifier) -> Result<(), ProofSystemError> {
+        for (i, s) in self.0.iter().enumerate() {
+            if let Some((r, name)) = s.role_with_name() {
+                if r != role {
+                    return Err(ProofSystemError::StatementOfWrongRole(i, name));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Serialize to JSON where each statement is written as an object with its `tag`, i.e. the variant's
+    /// index used by `CanonicalSerialize`, and the `statement` itself. This lets the JSON and canonical byte
+    /// representations be matched by tag.
+    pub fn to_canonical_json(&self) -> Result<String, ProofSystemError> {
+        let tagged = self
+            .0
+            .iter()
+            .map(|s| TaggedStatementRef {
+                tag: s.tag(),
+                statement: s,
+            })
+            .collect::<Vec<_>>();
+        serde_json::to_string(&tagged)
+            .map_err(|e| ProofSystemError::InvalidStatementsJson(e.to_string()))
+    }
+
+    /// Deserialize from JSON created by `Self::to_canonical_json`. Fails if a statement has an unknown tag,
+    /// like when it was created by a newer version, or a tag that doesn't match the statement.
+    pub fn from_canonical_json(json: &str) -> Result<Self, ProofSystemError> {
+        let tagged: Vec<TaggedStatement> = serde_json::from_str(json)
+            .map_err(|e| ProofSystemError::InvalidStatementsJson(e.to_string()))?;
+        let mut statements = Self::new();
+        for (i, t) in tagged.into_iter().enumerate() {
+            if t.tag > MAX_STATEMENT_TAG {
+                return Err(ProofSystemError::UnknownStatementTag(
+                    t.tag,
+                    MAX_STATEMENT_TAG,
+                ));
+            }
+            let statement: Statement<E, G> = serde_json::from_value(t.statement)
+                .map_err(|e| ProofSystemError::InvalidStatementsJson(e.to_string()))?;
+            if statement.tag() != t.tag {
+                return Err(ProofSystemError::StatementTagMismatchInJson(
+                    i,
+                    t.tag,
+                    statement.tag(),
+                ));
+            }
+            statements.add(statement);
+        }
+        Ok(statements)
+    }
+
+    /// Check that every group element in the statements is on the curve and in the prime order subgroup, returning
+    /// the index of the first statement failing the check. These are the checks of `Valid::check` (arkworks checks
+    /// subgroup membership of affine points there) which no variant skips. They already run when deserializing
+    /// with validation, including with serde, so this is for statements deserialized with the `_unchecked`
+    /// methods or created from untrusted group elements.
+    pub fn verify_subgroup_membership(&self) -> Result<(), ProofSystemError> {
+        for (i, s) in self.0.iter().enumerate() {
+            s.check()
+                .map_err(|_| ProofSystemError::InvalidGroupElementInStatement(i))?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: Pairing, G: AffineRepr> IntoIterator for Statements<E, G> {
+    type Item = Statement<E, G>;
+    type IntoIter = ark_std::vec::IntoIter<Statement<E, G>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, E: Pairing, G: AffineRepr> IntoIterator for &'a Statements<E, G> {
+    type Item = &'a Statement<E, G>;
+    type IntoIter = core::slice::Iter<'a, Statement<E, G>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+#[derive(Serialize)]
+#[serde(bound = "")]
+struct TaggedStatementRef<'a, E: Pairing, G: AffineRepr> {
+    tag: u8,
+    statement: &'a Statement<E, G>,
+}
+
+/// The statement is decoded only after its tag is checked so that an unknown tag gives a clear error
+#[derive(Deserialize)]
+struct TaggedStatement {
+    tag: u8,
+    statement: serde_json::Value,
+}
+
+impl<E, G> Statement<E, G>
+where
+    E: Pairing,
+    G: AffineRepr,
+{
+    /// Convert a statement used by the prover to the statement that the verifier would use, like
+    /// `SaverProver` to `SaverVerifier`, removing data only needed by the prover. Statements used by
+    /// both prover and verifier are returned unchanged. Fails for prover statements whose verifier
+    /// statement needs data the prover doesn't have, like the public inputs of an R1CS or the
+    /// verifier's secret key.
+    pub fn to_verifier_statement(&self) -> Result<Statement<E, G>, ProofSystemError> {
+        match self {
+            Statement::SaverProver(s) => Ok(Statement::SaverVerifier(s.to_verifier()?)),
+            Statement::BoundCheckLegoGroth16Prover(s) => {
+                Ok(Statement::BoundCheckLegoGroth16Verifier(s.to_verifier()?))
+            }
+            Statement::BloomMembershipLegoGroth16Prover(s) => Ok(
+                Statement::BloomMembershipLegoGroth16Verifier(s.to_verifier()?),
+            ),
+            Statement::DetachedAccumulatorMembershipProver(s) => Ok(
+                Statement::DetachedAccumulatorMembershipVerifier(s.to_verifier()),
+            ),
+            Statement::DetachedAccumulatorNonMembershipProver(s) => Ok(
+                Statement::DetachedAccumulatorNonMembershipVerifier(s.to_verifier()),
+            ),
+            Statement::DetachedAccumulatorMembershipCDHProver(s) => Ok(
+                Statement::DetachedAccumulatorMembershipCDHVerifier(s.to_verifier()),
+            ),
+            Statement::R1CSCircomProver(_)
+            | Statement::BoundCheckSmcWithKVProver(_)
+            | Statement::VBAccumulatorMembershipCDHProver(_)
+            | Statement::VBAccumulatorNonMembershipCDHProver(_)
+            | Statement::KBUniversalAccumulatorMembershipCDHProver(_)
+            | Statement::KBUniversalAccumulatorNonMembershipCDHProver(_)
+            | Statement::PoKBDDT16MAC(_) => Err(ProofSystemError::CannotDeriveVerifierStatement),
+            s => Ok(s.clone()),
+        }
+    }
+
+    /// Whether the statement is used only by the prover or only by the verifier. Returns `None` for
+    /// statements used by both.
+    pub fn role(&self) -> Option<ProverOrVerifier> {
+        self.role_with_name().map(|(r, _)| r)
+    }
+
+    fn role_with_name(&self) -> Option<(ProverOrVerifier, &'static str)> {
+        macro_rules! roles {
+            ($($prover: ident / $verifier: ident),+) => {
+                match self {
+                    $(
+                        Statement::$prover(_) => Some((ProverOrVerifier::Prover, stringify!($prover))),
+                        Statement::$verifier(_) => {
+                            Some((ProverOrVerifier::Verifier, stringify!($verifier)))
+                        }
+                    )+
+                    _ => None,
+                }
+            };
+        }
+        roles!(
+            SaverProver / SaverVerifier,
+            BoundCheckLegoGroth16Prover / BoundCheckLegoGroth16Verifier,
+            R1CSCircomProver / R1CSCircomVerifier,
+            BoundCheckSmcWithKVProver / BoundCheckSmcWithKVVerifier,
+            DetachedAccumulatorMembershipProver / DetachedAccumulatorMembershipVerifier,
+            DetachedAccumulatorNonMembershipProver / DetachedAccumulatorNonMembershipVerifier,
+            VBAccumulatorMembershipCDHProver / VBAccumulatorMembershipCDHVerifier,
+            VBAccumulatorNonMembershipCDHProver / VBAccumulatorNonMembershipCDHVerifier,
+            KBUniversalAccumulatorMembershipCDHProver / KBUniversalAccumulatorMembershipCDHVerifier,
+            KBUniversalAccumulatorNonMembershipCDHProver
+                / KBUniversalAccumulatorNonMembershipCDHVerifier,
+            BloomMembershipLegoGroth16Prover / BloomMembershipLegoGroth16Verifier,
+            DetachedAccumulatorMembershipCDHProver / DetachedAccumulatorMembershipCDHVerifier,
+            PoKBDDT16MAC / PoKBDDT16MACFullVerifier
+        )
+    }
+
+    /// Index of the signature's statement for statements that refer to another statement by its index
+    fn signature_statement_index_mut(&mut self) -> Option<&mut usize> {
+        match self {
+            Statement::CommittedMessageEquality(s) => Some(&mut s.signature_statement_index),
+            Statement::SignatureCommitmentConsistency(s) => Some(&mut s.signature_statement_index),
+            _ => None,
+        }
+    }
 }
 
 macro_rules! delegate {
@@ -157,7 +477,24 @@ macro_rules! delegate {
                 KBUniversalAccumulatorNonMembershipCDHProver,
                 KBUniversalAccumulatorNonMembershipCDHVerifier,
                 KBPositiveAccumulatorMembership,
-                KBPositiveAccumulatorMembershipCDH
+                KBPositiveAccumulatorMembershipCDH,
+                ScopedPseudonym,
+                DisclosedLinearCombination,
+                BloomMembershipLegoGroth16Prover,
+                BloomMembershipLegoGroth16Verifier,
+                Monotonic,
+                CommittedMessageEquality,
+                PublicSetMembership,
+                DetachedAccumulatorMembershipCDHProver,
+                DetachedAccumulatorMembershipCDHVerifier,
+                AggregatedBoundCheckBpp,
+                SetMembershipSmc,
+                CommittedInequality,
+                PoKPSAggregateSignature,
+                PartialPedersenOpening,
+                SignatureCommitmentConsistency,
+                PoKBDDT16MAC,
+                PoKBDDT16MACFullVerifier
             : $($tt)+
         }
     }}
@@ -199,7 +536,24 @@ macro_rules! delegate_reverse {
                 KBUniversalAccumulatorNonMembershipCDHProver,
                 KBUniversalAccumulatorNonMembershipCDHVerifier,
                 KBPositiveAccumulatorMembership,
-                KBPositiveAccumulatorMembershipCDH
+                KBPositiveAccumulatorMembershipCDH,
+                ScopedPseudonym,
+                DisclosedLinearCombination,
+                BloomMembershipLegoGroth16Prover,
+                BloomMembershipLegoGroth16Verifier,
+                Monotonic,
+                CommittedMessageEquality,
+                PublicSetMembership,
+                DetachedAccumulatorMembershipCDHProver,
+                DetachedAccumulatorMembershipCDHVerifier,
+                AggregatedBoundCheckBpp,
+                SetMembershipSmc,
+                CommittedInequality,
+                PoKPSAggregateSignature,
+                PartialPedersenOpening,
+                SignatureCommitmentConsistency,
+                PoKBDDT16MAC,
+                PoKBDDT16MACFullVerifier
             : $($tt)+
         }
 
@@ -207,9 +561,93 @@ macro_rules! delegate_reverse {
     }}
 }
 
+/// Tag of the last variant of `Statement` when serialized. Must be updated when a variant is added.
+const MAX_STATEMENT_TAG: u8 = 49;
+
+impl<E: Pairing, G: AffineRepr> Statement<E, G> {
+    /// Tag of the statement's variant as used when serializing with `CanonicalSerialize`
+    pub fn tag(&self) -> u8 {
+        delegate!([index]self with variant as _s index)
+    }
+
+    /// Name of the statement's variant, like `PoKBBSSignatureG1`
+    pub fn name(&self) -> &'static str {
+        macro_rules! names {
+            ($($variant: ident),+) => {
+                match self {
+                    $(Statement::$variant(_) => stringify!($variant)),+
+                }
+            };
+        }
+        names!(
+            PoKBBSSignatureG1,
+            VBAccumulatorMembership,
+            VBAccumulatorNonMembership,
+            PedersenCommitment,
+            SaverProver,
+            SaverVerifier,
+            BoundCheckLegoGroth16Prover,
+            BoundCheckLegoGroth16Verifier,
+            R1CSCircomProver,
+            R1CSCircomVerifier,
+            PoKPSSignature,
+            PoKBBSSignature23G1,
+            BoundCheckBpp,
+            BoundCheckSmc,
+            BoundCheckSmcWithKVProver,
+            BoundCheckSmcWithKVVerifier,
+            PublicInequality,
+            DetachedAccumulatorMembershipProver,
+            DetachedAccumulatorMembershipVerifier,
+            DetachedAccumulatorNonMembershipProver,
+            DetachedAccumulatorNonMembershipVerifier,
+            KBUniversalAccumulatorMembership,
+            KBUniversalAccumulatorNonMembership,
+            VBAccumulatorMembershipCDHProver,
+            VBAccumulatorMembershipCDHVerifier,
+            VBAccumulatorNonMembershipCDHProver,
+            VBAccumulatorNonMembershipCDHVerifier,
+            KBUniversalAccumulatorMembershipCDHProver,
+            KBUniversalAccumulatorMembershipCDHVerifier,
+            KBUniversalAccumulatorNonMembershipCDHProver,
+            KBUniversalAccumulatorNonMembershipCDHVerifier,
+            KBPositiveAccumulatorMembership,
+            KBPositiveAccumulatorMembershipCDH,
+            ScopedPseudonym,
+            DisclosedLinearCombination,
+            BloomMembershipLegoGroth16Prover,
+            BloomMembershipLegoGroth16Verifier,
+            Monotonic,
+            CommittedMessageEquality,
+            PublicSetMembership,
+            DetachedAccumulatorMembershipCDHProver,
+            DetachedAccumulatorMembershipCDHVerifier,
+            AggregatedBoundCheckBpp,
+            SetMembershipSmc,
+            CommittedInequality,
+            PoKPSAggregateSignature,
+            PartialPedersenOpening,
+            SignatureCommitmentConsistency,
+            PoKBDDT16MAC,
+            PoKBDDT16MACFullVerifier
+        )
+    }
+
+    /// Blake2b hash of the compressed canonical serialization of the statement, usable as a key for caching
+    /// data generated for the statement. Since the serialization is canonical and includes the variant's tag,
+    /// identical statements have the same fingerprint across runs and platforms.
+    pub fn fingerprint(&self) -> [u8; 32] {
+        let mut writer = DigestWriter::<Blake2b<U32>>::new();
+        self.serialize_compressed(&mut writer)
+            .expect("serializing into a digest should not fail");
+        writer.finalize().into()
+    }
+}
+
 mod serialization {
     use super::*;
-    use ark_serialize::{Compress, Valid, Validate};
+    use crate::error::UnknownStatementTag;
+    use ark_serialize::{Compress, Validate};
 
     impl<E: Pairing, G: AffineRepr> Valid for Statement<E, G> {
         fn check(&self) -> Result<(), SerializationError> {
@@ -248,7 +686,11 @@ mod serialization {
                 CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
 
             delegate_reverse!(
-                idx or else Err(SerializationError::InvalidData) => with variant as build
+                idx or else Err(UnknownStatementTag {
+                    tag: idx,
+                    max_known_tag: MAX_STATEMENT_TAG,
+                }
+                .into()) => with variant as build
                 CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate).map(build)
             )
         }
@@ -258,10 +700,11 @@ mod serialization {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sub_protocols::bound_check_legogroth16::generate_snark_srs_bound_check;
     use ark_bls12_381::{fr::Fr, g1::G1Projective as G1Proj, Bls12_381};
     use ark_ec::{CurveGroup, VariableBaseMSM};
     use ark_std::{
-        collections::BTreeMap,
+        collections::BTreeSet,
         rand::{rngs::StdRng, SeedableRng},
         UniformRand,
     };
@@ -345,5 +788,510 @@ mod tests {
 
         statements.add(stmt_5);
         test_serialization!(Statements<Bls12_381, <Bls12_381 as Pairing>::G1Affine>, statements);
+
+        // JSON with tags round-trips and its tags match the ones in the canonical bytes
+        let json = statements.to_canonical_json().unwrap();
+        let from_json =
+            Statements::<Bls12_381, <Bls12_381 as Pairing>::G1Affine>::from_canonical_json(&json)
+                .unwrap();
+        assert_eq!(from_json, statements);
+        let mut bytes = vec![];
+        from_json.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(
+            Statements::<Bls12_381, <Bls12_381 as Pairing>::G1Affine>::deserialize_compressed(
+                &bytes[..]
+            )
+            .unwrap(),
+            statements
+        );
+        let json_tags = serde_json::from_str::<serde_json::Value>(&json)
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v["tag"].as_u64().unwrap() as u8)
+            .collect::<Vec<_>>();
+        let byte_tags = statements
+            .0
+            .iter()
+            .map(|s| {
+                let mut b = vec![];
+                s.serialize_compressed(&mut b).unwrap();
+                b[0]
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(json_tags, byte_tags);
+        assert_eq!(json_tags, vec![0, 1, 2, 3, 11]);
+
+        // Unknown tag is rejected
+        let mut value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+        value[0]["tag"] = (MAX_STATEMENT_TAG + 1).into();
+        assert!(matches!(
+            Statements::<Bls12_381, <Bls12_381 as Pairing>::G1Affine>::from_canonical_json(
+                &value.to_string()
+            ),
+            Err(ProofSystemError::UnknownStatementTag(t, MAX_STATEMENT_TAG)) if t == MAX_STATEMENT_TAG + 1
+        ));
+
+        // Tag not matching the statement is rejected
+        value[0]["tag"] = 4.into();
+        assert!(matches!(
+            Statements::<Bls12_381, <Bls12_381 as Pairing>::G1Affine>::from_canonical_json(
+                &value.to_string()
+            ),
+            Err(ProofSystemError::StatementTagMismatchInJson(0, 4, 0))
+        ));
+
+        // Statement without tag is rejected
+        value[0].as_object_mut().unwrap().remove("tag");
+        assert!(matches!(
+            Statements::<Bls12_381, <Bls12_381 as Pairing>::G1Affine>::from_canonical_json(
+                &value.to_string()
+            ),
+            Err(ProofSystemError::InvalidStatementsJson(_))
+        ));
+    }
+
+    #[test]
+    fn replacing_and_removing_statements() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (_, params, keypair, _) = bbs_plus_sig_setup(&mut rng, 5);
+        let snark_pk = generate_snark_srs_bound_check::<Bls12_381, _>(&mut rng).unwrap();
+
+        let sig_stmt = bbs_plus::PoKBBSSignatureG1::new_statement_from_params(
+            params,
+            keypair.public_key.clone(),
+            BTreeMap::new(),
+        );
+        let prover_stmt =
+            bound_check_legogroth16::BoundCheckLegoGroth16Prover::new_statement_from_params(
+                10,
+                100,
+                snark_pk.clone(),
+            )
+            .unwrap();
+        let mut statements: Statements<Bls12_381, <Bls12_381 as Pairing>::G1Affine> =
+            Statements::new();
+        statements.add(sig_stmt.clone());
+        statements.add(prover_stmt.clone());
+        statements.add(sig_stmt.clone());
+
+        // Replace the prover statement with its verifier counterpart
+        let verifier_stmt = prover_stmt.to_verifier_statement().unwrap();
+        let old = statements.replace(1, verifier_stmt.clone()).unwrap();
+        assert_eq!(old, prover_stmt);
+        assert_eq!(statements.len(), 3);
+        assert_eq!(statements.0[1], verifier_stmt);
+        test_serialization!(Statements<Bls12_381, <Bls12_381 as Pairing>::G1Affine>, statements);
+
+        assert!(matches!(
+            statements.replace(3, prover_stmt.clone()),
+            Err(ProofSystemError::InvalidStatementIndex(3, 3))
+        ));
+
+        // Removing shifts the later statements
+        let (removed, reindex) = statements.remove_and_reindex(1).unwrap();
+        assert_eq!(removed, verifier_stmt);
+        assert_eq!(statements.len(), 2);
+        assert_eq!(
+            reindex,
+            [(0, 0), (2, 1)].into_iter().collect::<BTreeMap<_, _>>()
+        );
+        assert_eq!(statements.0, vec![sig_stmt.clone(), sig_stmt]);
+        test_serialization!(Statements<Bls12_381, <Bls12_381 as Pairing>::G1Affine>, statements);
+
+        let (_, reindex) = statements.remove_and_reindex(1).unwrap();
+        assert_eq!(reindex, [(0, 0)].into_iter().collect::<BTreeMap<_, _>>());
+        assert!(matches!(
+            statements.remove_and_reindex(1),
+            Err(ProofSystemError::InvalidStatementIndex(1, 1))
+        ));
+    }
+
+    #[test]
+    fn removing_statements_updates_signature_references() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (_, params, keypair, _) = bbs_plus_sig_setup(&mut rng, 5);
+        let bases = (0..3)
+            .map(|_| G1Proj::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let commitment = G1Proj::rand(&mut rng).into_affine();
+
+        let sig_stmt = bbs_plus::PoKBBSSignatureG1::new_statement_from_params(
+            params,
+            keypair.public_key.clone(),
+            BTreeMap::new(),
+        );
+        let ped_comm_stmt =
+            ped_comm::PedersenCommitment::new_statement_from_params(bases.clone(), commitment);
+        let mut statements: Statements<Bls12_381, <Bls12_381 as Pairing>::G1Affine> =
+            Statements::new();
+        statements.add(ped_comm_stmt.clone());
+        statements.add(sig_stmt.clone());
+        statements.add(
+            committed_message_equality::CommittedMessageEquality::new_statement_from_params(
+                bases.clone(),
+                commitment,
+                1,
+                2,
+            ),
+        );
+        statements.add(
+            signature_commitment_consistency::SignatureCommitmentConsistency::new_statement_from_params(
+                bases.clone(),
+                commitment,
+                1,
+                [(0, 1), (1, 3)].into_iter().collect(),
+            ),
+        );
+
+        // The signature statement can't be removed while referred to
+        assert!(matches!(
+            statements.remove_and_reindex(1),
+            Err(ProofSystemError::RemovedStatementIsReferenced(1, 2))
+        ));
+        assert_eq!(statements.len(), 4);
+
+        // Removing a statement before the signature updates the reference to it
+        let (removed, reindex) = statements.remove_and_reindex(0).unwrap();
+        assert_eq!(removed, ped_comm_stmt);
+        assert_eq!(reindex[&1], 0);
+        match &statements.0[1] {
+            Statement::CommittedMessageEquality(s) => {
+                assert_eq!(s.signature_statement_index, 0);
+                assert_eq!(s.message_index, 2);
+            }
+            _ => panic!("expected a CommittedMessageEquality statement"),
+        }
+        match &statements.0[2] {
+            Statement::SignatureCommitmentConsistency(s) => {
+                assert_eq!(s.signature_statement_index, 0);
+                assert_eq!(
+                    s.message_indices,
+                    [(0, 1), (1, 3)].into_iter().collect::<BTreeMap<_, _>>()
+                );
+            }
+            _ => panic!("expected a SignatureCommitmentConsistency statement"),
+        }
+    }
+
+    #[test]
+    fn iterating_statements() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (_, params, keypair, _) = bbs_plus_sig_setup(&mut rng, 5);
+        let snark_pk = generate_snark_srs_bound_check::<Bls12_381, _>(&mut rng).unwrap();
+
+        let sig_stmt = bbs_plus::PoKBBSSignatureG1::new_statement_from_params(
+            params,
+            keypair.public_key.clone(),
+            BTreeMap::new(),
+        );
+        let prover_stmt =
+            bound_check_legogroth16::BoundCheckLegoGroth16Prover::new_statement_from_params(
+                10,
+                100,
+                snark_pk.clone(),
+            )
+            .unwrap();
+        let verifier_stmt = prover_stmt.to_verifier_statement().unwrap();
+        let mut statements: Statements<Bls12_381, <Bls12_381 as Pairing>::G1Affine> =
+            Statements::new();
+        statements.add(sig_stmt.clone());
+        statements.add(prover_stmt.clone());
+        statements.add(sig_stmt.clone());
+        statements.add(verifier_stmt.clone());
+
+        let count = |pred: fn(&Statement<Bls12_381, <Bls12_381 as Pairing>::G1Affine>) -> bool| {
+            statements.iter().filter(|s| pred(s)).count()
+        };
+        assert_eq!(count(|s| matches!(s, Statement::PoKBBSSignatureG1(_))), 2);
+        assert_eq!(
+            count(|s| matches!(s, Statement::BoundCheckLegoGroth16Prover(_))),
+            1
+        );
+        assert_eq!(
+            count(|s| matches!(s, Statement::BoundCheckLegoGroth16Verifier(_))),
+            1
+        );
+
+        let mut tags = BTreeMap::new();
+        for s in &statements {
+            *tags.entry(s.tag()).or_insert(0) += 1;
+        }
+        assert_eq!(tags.values().sum::<usize>(), statements.len());
+        assert_eq!(tags.len(), 3);
+
+        assert_eq!(statements.get(1), Some(&prover_stmt));
+        assert_eq!(statements.get(4), None);
+        assert_eq!(
+            statements.into_iter().collect::<Vec<_>>(),
+            vec![sig_stmt.clone(), prover_stmt, sig_stmt, verifier_stmt]
+        );
+    }
+
+    #[test]
+    fn validating_statement_roles() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (_, params, keypair, _) = bbs_plus_sig_setup(&mut rng, 5);
+        let snark_pk = generate_snark_srs_bound_check::<Bls12_381, _>(&mut rng).unwrap();
+
+        let sig_stmt = bbs_plus::PoKBBSSignatureG1::new_statement_from_params(
+            params,
+            keypair.public_key.clone(),
+            BTreeMap::new(),
+        );
+        let prover_stmt =
+            bound_check_legogroth16::BoundCheckLegoGroth16Prover::new_statement_from_params(
+                10,
+                100,
+                snark_pk.clone(),
+            )
+            .unwrap();
+        let verifier_stmt = prover_stmt.to_verifier_statement().unwrap();
+        assert_eq!(sig_stmt.role(), None);
+        assert_eq!(prover_stmt.role(), Some(ProverOrVerifier::Prover));
+        assert_eq!(verifier_stmt.role(), Some(ProverOrVerifier::Verifier));
+
+        let mut statements: Statements<Bls12_381, <Bls12_381 as Pairing>::G1Affine> =
+            Statements::new();
+        statements.add(sig_stmt.clone());
+        statements.add(prover_stmt.clone());
+        statements.validate_roles(ProverOrVerifier::Prover).unwrap();
+        assert!(matches!(
+            statements.validate_roles(ProverOrVerifier::Verifier),
+            Err(ProofSystemError::StatementOfWrongRole(
+                1,
+                "BoundCheckLegoGroth16Prover"
+            ))
+        ));
+
+        // Verifier statement mistakenly added among the prover's statements
+        statements.add(sig_stmt);
+        statements.add(verifier_stmt);
+        assert!(matches!(
+            statements.validate_roles(ProverOrVerifier::Prover),
+            Err(ProofSystemError::StatementOfWrongRole(
+                3,
+                "BoundCheckLegoGroth16Verifier"
+            ))
+        ));
+
+        // Statements used by both roles are valid for either
+        statements.remove_and_reindex(3).unwrap();
+        statements.remove_and_reindex(1).unwrap();
+        statements.validate_roles(ProverOrVerifier::Prover).unwrap();
+        statements
+            .validate_roles(ProverOrVerifier::Verifier)
+            .unwrap();
+    }
+
+    #[test]
+    fn deserializing_unknown_statement_tag() {
+        let bytes = [MAX_STATEMENT_TAG + 1];
+        let err = Statement::<Bls12_381, <Bls12_381 as Pairing>::G1Affine>::deserialize_compressed(
+            &bytes[..],
+        )
+        .unwrap_err();
+        match ProofSystemError::from(err) {
+            ProofSystemError::UnknownStatementTag(tag, max_known_tag) => {
+                assert_eq!(tag, MAX_STATEMENT_TAG + 1);
+                assert_eq!(max_known_tag, MAX_STATEMENT_TAG);
+            }
+            e => panic!("Unexpected error {:?}", e),
+        }
+
+        // The largest known tag is recognized and fails only because the statement's bytes are missing
+        let bytes = [MAX_STATEMENT_TAG];
+        let err = Statement::<Bls12_381, <Bls12_381 as Pairing>::G1Affine>::deserialize_compressed(
+            &bytes[..],
+        )
+        .unwrap_err();
+        assert!(!matches!(
+            ProofSystemError::from(err),
+            ProofSystemError::UnknownStatementTag(..)
+        ));
+    }
+
+    #[test]
+    fn statements_with_group_element_outside_subgroup() {
+        use ark_bls12_381::{Fq, G1Affine};
+
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let bases = (0..5)
+            .map(|_| G1Proj::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let scalars = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let commitment = G1Proj::msm_unchecked(&bases, &scalars).into_affine();
+
+        // A point on the curve but not in the prime order subgroup
+        let off_subgroup = loop {
+            if let Some(p) = G1Affine::get_point_from_x_unchecked(Fq::rand(&mut rng), true) {
+                if !p.is_in_correct_subgroup_assuming_on_curve() {
+                    break p;
+                }
+            }
+        };
+        assert!(off_subgroup.is_on_curve());
+
+        let mut statements: Statements<Bls12_381, G1Affine> = Statements::new();
+        statements.add(ped_comm::PedersenCommitment::new_statement_from_params(
+            bases.clone(),
+            commitment,
+        ));
+        statements.verify_subgroup_membership().unwrap();
+
+        let mut bad_bases = bases.clone();
+        bad_bases[2] = off_subgroup;
+        statements.add(ped_comm::PedersenCommitment::new_statement_from_params(
+            bad_bases, commitment,
+        ));
+        assert!(matches!(
+            statements.verify_subgroup_membership(),
+            Err(ProofSystemError::InvalidGroupElementInStatement(1))
+        ));
+
+        // Such statements are rejected when deserializing with validation but not without it
+        let mut bytes = vec![];
+        statements.serialize_compressed(&mut bytes).unwrap();
+        assert!(Statements::<Bls12_381, G1Affine>::deserialize_compressed(&bytes[..]).is_err());
+        let unchecked =
+            Statements::<Bls12_381, G1Affine>::deserialize_compressed_unchecked(&bytes[..])
+                .unwrap();
+        assert!(matches!(
+            unchecked.verify_subgroup_membership(),
+            Err(ProofSystemError::InvalidGroupElementInStatement(1))
+        ));
+
+        let mut statements: Statements<Bls12_381, G1Affine> = Statements::new();
+        statements.add(ped_comm::PedersenCommitment::new_statement_from_params(
+            bases,
+            off_subgroup,
+        ));
+        assert!(matches!(
+            statements.verify_subgroup_membership(),
+            Err(ProofSystemError::InvalidGroupElementInStatement(0))
+        ));
+    }
+
+    #[test]
+    fn statements_serialization_layout() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (_, params_1, keypair_1, _) = bbs_plus_sig_setup(&mut rng, 5);
+
+        let mut statements: Statements<Bls12_381, <Bls12_381 as Pairing>::G1Affine> =
+            Statements::new();
+
+        let check = |statements: &Statements<Bls12_381, <Bls12_381 as Pairing>::G1Affine>| {
+            let mut bytes = vec![];
+            statements.serialize_compressed(&mut bytes).unwrap();
+            let mut expected = vec![];
+            (statements.len() as u64)
+                .serialize_compressed(&mut expected)
+                .unwrap();
+            for s in &statements.0 {
+                s.serialize_compressed(&mut expected).unwrap();
+            }
+            assert_eq!(bytes, expected);
+            assert_eq!(
+                Statements::deserialize_compressed(&bytes[..]).unwrap(),
+                *statements
+            );
+        };
+
+        check(&statements);
+
+        statements.add(bbs_plus::PoKBBSSignatureG1::new_statement_from_params(
+            params_1,
+            keypair_1.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        for _ in 0..3 {
+            let bases = (0..5)
+                .map(|_| G1Proj::rand(&mut rng).into_affine())
+                .collect::<Vec<_>>();
+            let scalars = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+            let commitment = G1Proj::msm_unchecked(&bases, &scalars).into_affine();
+            statements.add(ped_comm::PedersenCommitment::new_statement_from_params(
+                bases, commitment,
+            ));
+        }
+        check(&statements);
+    }
+
+    #[test]
+    fn statement_fingerprint() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let bases = (0..5)
+            .map(|_| G1Proj::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let scalars = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let commitment = G1Proj::msm_unchecked(&bases, &scalars).into_affine();
+        let other_commitment = G1Proj::rand(&mut rng).into_affine();
+
+        let stmt = ped_comm::PedersenCommitment::new_statement_from_params::<Bls12_381>(
+            bases.clone(),
+            commitment,
+        );
+        let same_stmt = ped_comm::PedersenCommitment::new_statement_from_params::<Bls12_381>(
+            bases.clone(),
+            commitment,
+        );
+        let different_stmt = ped_comm::PedersenCommitment::new_statement_from_params::<Bls12_381>(
+            bases.clone(),
+            other_commitment,
+        );
+        // Same data but a different statement type
+        let different_type_stmt =
+            partial_ped_opening::PartialPedersenOpening::new_statement_from_params::<Bls12_381>(
+                bases,
+                commitment,
+                BTreeMap::new(),
+            );
+
+        assert_eq!(stmt.fingerprint(), stmt.clone().fingerprint());
+        assert_eq!(stmt.fingerprint(), same_stmt.fingerprint());
+        assert_ne!(stmt.fingerprint(), different_stmt.fingerprint());
+        assert_ne!(stmt.fingerprint(), different_type_stmt.fingerprint());
+
+        // Deserialized statement has the same fingerprint
+        let mut bytes = vec![];
+        stmt.serialize_compressed(&mut bytes).unwrap();
+        let deserialized =
+            Statement::<Bls12_381, <Bls12_381 as Pairing>::G1Affine>::deserialize_compressed(
+                &bytes[..],
+            )
+            .unwrap();
+        assert_eq!(stmt.fingerprint(), deserialized.fingerprint());
+    }
+
+    #[test]
+    fn bbs_statement_revealing_all_except() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (msgs, params, keypair, _) = bbs_sig_setup(&mut rng, 6);
+
+        let hidden = [1, 4].into_iter().collect::<BTreeSet<_>>();
+        let stmt = bbs_23::PoKBBSSignature23G1::new_statement_revealing_all_except::<
+            <Bls12_381 as Pairing>::G1Affine,
+        >(params.clone(), keypair.public_key.clone(), &msgs, &hidden)
+        .unwrap();
+        let Statement::PoKBBSSignature23G1(stmt) = stmt else {
+            panic!("expected a BBS statement")
+        };
+        assert_eq!(stmt.revealed_messages.len(), 4);
+        for (i, m) in msgs.iter().enumerate() {
+            if hidden.contains(&i) {
+                assert!(!stmt.revealed_messages.contains_key(&i));
+            } else {
+                assert_eq!(stmt.revealed_messages.get(&i), Some(m));
+            }
+        }
+
+        let hidden = [1, 6].into_iter().collect::<BTreeSet<_>>();
+        assert!(matches!(
+            bbs_23::PoKBBSSignature23G1::new_statement_revealing_all_except::<
+                <Bls12_381 as Pairing>::G1Affine,
+            >(params, keypair.public_key.clone(), &msgs, &hidden),
+            Err(ProofSystemError::HiddenMessageIndexOutOfRange(6, 6))
+        ));
     }
 }
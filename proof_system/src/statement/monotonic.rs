@@ -0,0 +1,107 @@
+use crate::{
+    error::ProofSystemError,
+    meta_statement::{EqualWitnesses, WitnessRef},
+    setup_params::SetupParams,
+    statement::Statement,
+};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{collections::BTreeSet, vec::Vec};
+use bulletproofs_plus_plus::setup::SetupParams as BppSetupParams;
+use dock_crypto_utils::serde_utils::ArkObjectBytes;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+/// Proving knowledge of messages `m_1, m_2, ..., m_k` that are in order, i.e. `m_1 <= m_2 <= ... <= m_k` or
+/// `m_1 < m_2 < ... < m_k` when `strict` is true, using Bulletproofs++. Each consecutive difference, i.e.
+/// `m_{i+1} - m_i` (or `m_{i+1} - m_i - 1` when `strict`), is proven to be in `[0, 2^64)` so the messages should
+/// be integers such that the difference between consecutive ones fits in 64 bits. The Bulletproofs++ setup params
+/// should support `(num_values - 1).next_power_of_two()` proofs of 64-bit values. The messages are the witnesses
+/// of this statement in the given order and are linked to signed messages using witness equalities,
+/// see `Self::witness_equalities_with_signature`.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct Monotonic<G: AffineRepr> {
+    /// Number of messages whose order is proven
+    pub num_values: usize,
+    pub strict: bool,
+    #[serde_as(as = "Option<ArkObjectBytes>")]
+    pub params: Option<BppSetupParams<G>>,
+    pub params_ref: Option<usize>,
+}
+
+impl<G: AffineRepr> Monotonic<G> {
+    pub fn new_statement_from_params<E: Pairing>(
+        num_values: usize,
+        strict: bool,
+        params: BppSetupParams<G>,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        Self::validate_num_values(num_values)?;
+        Ok(Statement::Monotonic(Self {
+            num_values,
+            strict,
+            params: Some(params),
+            params_ref: None,
+        }))
+    }
+
+    pub fn new_statement_from_params_ref<E: Pairing>(
+        num_values: usize,
+        strict: bool,
+        params_ref: usize,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        Self::validate_num_values(num_values)?;
+        Ok(Statement::Monotonic(Self {
+            num_values,
+            strict,
+            params: None,
+            params_ref: Some(params_ref),
+        }))
+    }
+
+    pub fn get_setup_params<'a, E: Pairing>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a BppSetupParams<G>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.params,
+            self.params_ref,
+            BppSetupParams,
+            IncompatibleBoundCheckSetupParamAtIndex,
+            st_idx
+        )
+    }
+
+    /// Witness equalities linking the messages at indices `message_indices` of the signature in statement
+    /// `sig_statement_idx` to the witnesses of this statement at index `statement_idx`, i.e. the message at
+    /// `message_indices[i]` should be the `i`-th value in the order.
+    pub fn witness_equalities_with_signature(
+        sig_statement_idx: usize,
+        message_indices: &[usize],
+        statement_idx: usize,
+    ) -> Vec<EqualWitnesses> {
+        message_indices
+            .iter()
+            .enumerate()
+            .map(|(i, m_idx)| {
+                EqualWitnesses(
+                    [(sig_statement_idx, *m_idx), (statement_idx, i)]
+                        .into_iter()
+                        .collect::<BTreeSet<WitnessRef>>(),
+                )
+            })
+            .collect()
+    }
+
+    fn validate_num_values(num_values: usize) -> Result<(), ProofSystemError> {
+        if num_values < 2 {
+            return Err(ProofSystemError::InsufficientValuesForMonotonic(num_values));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{collections::BTreeMap, vec::Vec};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, Same};
+
+use crate::{error::ProofSystemError, setup_params::SetupParams, statement::Statement};
+use dock_crypto_utils::serde_utils::*;
+
+/// Proving knowledge of the hidden scalars `s_i` in Pedersen commitment `g_0 * s_0 + g_1 * s_1 + ... + g_{n-1} * s_{n-1} = C`
+/// when some of the scalars are revealed. The witness indices of the hidden scalars are their positions in the
+/// commitment so witness equalities refer to them like to the messages of a signature.
+///
+/// The verifier subtracts the revealed scalars times their bases from `C` and the prover proves knowledge of the
+/// hidden scalars in the result. When all scalars are revealed, nothing is hidden and the verifier only checks that
+/// the revealed scalars open `C`.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct PartialPedersenOpening<G: AffineRepr> {
+    /// The Pedersen commitment `C` in `g_0 * s_0 + g_1 * s_1 + ... + g_{n-1} * s_{n-1} = C`
+    #[serde_as(as = "ArkObjectBytes")]
+    pub commitment: G,
+    /// Scalars being revealed, keyed by their position in the commitment
+    #[serde_as(as = "BTreeMap<Same, ArkObjectBytes>")]
+    pub revealed: BTreeMap<usize, G::ScalarField>,
+    /// Commitment key `g_i` in `g_0 * s_0 + g_1 * s_1 + ... + g_{n-1} * s_{n-1} = C`
+    #[serde_as(as = "Option<Vec<ArkObjectBytes>>")]
+    pub key: Option<Vec<G>>,
+    pub key_ref: Option<usize>,
+}
+
+impl<G: AffineRepr> PartialPedersenOpening<G> {
+    pub fn new_statement_from_params<E: Pairing>(
+        key: Vec<G>,
+        commitment: G,
+        revealed: BTreeMap<usize, G::ScalarField>,
+    ) -> Statement<E, G> {
+        Statement::PartialPedersenOpening(Self {
+            commitment,
+            revealed,
+            key: Some(key),
+            key_ref: None,
+        })
+    }
+
+    pub fn new_statement_from_params_refs<E: Pairing>(
+        key_ref: usize,
+        commitment: G,
+        revealed: BTreeMap<usize, G::ScalarField>,
+    ) -> Statement<E, G> {
+        Statement::PartialPedersenOpening(Self {
+            commitment,
+            revealed,
+            key: None,
+            key_ref: Some(key_ref),
+        })
+    }
+
+    pub fn get_commitment_key<'a, E: Pairing>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a Vec<G>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.key,
+            self.key_ref,
+            PedersenCommitmentKey,
+            IncompatiblePedCommSetupParamAtIndex,
+            st_idx
+        )
+    }
+
+    /// Positions of the hidden scalars in increasing order. The response for the `k`-th of these is at index `k`
+    /// in the proof.
+    pub fn hidden_positions(&self, key_len: usize) -> Vec<usize> {
+        (0..key_len)
+            .filter(|i| !self.revealed.contains_key(i))
+            .collect()
+    }
+
+    /// Bases of the hidden scalars, in the order of their positions, and the commitment to only the hidden scalars,
+    /// i.e. `C` minus the revealed scalars times their bases
+    pub fn hidden_bases_and_commitment(
+        &self,
+        key: &[G],
+        st_idx: usize,
+    ) -> Result<(Vec<G>, G), ProofSystemError> {
+        if let Some((i, _)) = self.revealed.range(key.len()..).next() {
+            return Err(ProofSystemError::RevealedPositionOutOfRange(
+                st_idx,
+                *i,
+                key.len(),
+            ));
+        }
+        let hidden_bases = self
+            .hidden_positions(key.len())
+            .into_iter()
+            .map(|i| key[i])
+            .collect::<Vec<_>>();
+        let revealed_bases = self.revealed.keys().map(|i| key[*i]).collect::<Vec<_>>();
+        let revealed_scalars = self.revealed.values().cloned().collect::<Vec<_>>();
+        let reduced = self.commitment.into_group()
+            - G::Group::msm_unchecked(&revealed_bases, &revealed_scalars);
+        Ok((hidden_bases, reduced.into_affine()))
+    }
+}
@@ -1,13 +1,26 @@
-use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::vec::Vec;
+use ark_std::{rand::RngCore, vec::Vec};
+use digest::Digest;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
-use crate::{error::ProofSystemError, setup_params::SetupParams, statement::Statement};
-use dock_crypto_utils::serde_utils::*;
+use crate::{
+    constants::PED_COMM_HIDDEN_BASES_LABEL, error::ProofSystemError, setup_params::SetupParams,
+    statement::Statement, statement_proof::PedersenCommitmentProof,
+};
+use dock_crypto_utils::{
+    concat_slices, misc::n_projective_group_elements, serde_utils::*, transcript::DigestWriter,
+};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 /// Proving knowledge of scalars `s_i` in Pedersen commitment `g_0 * s_0 + g_1 * s_1 + ... + g_{n-1} * s_{n-1} = C`
+///
+/// The commitment can be in either group of the pairing, like G2 for commitments created by protocols working in
+/// G2, by using that group as `G` of the `Statement`. All statements generic over `G` in a proof are then over that
+/// group while statements like signatures are over the pairing's groups as usual.
 #[serde_as]
 #[derive(
     Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
@@ -44,6 +57,15 @@ impl<G: AffineRepr> PedersenCommitment<G> {
         })
     }
 
+    /// Create `count` bases for the commitment key by hashing `label`||`g_i` for i in 0 to `count` so that
+    /// different parties can derive the same key independently.
+    pub fn bases_from_label<D: Digest>(label: &[u8], count: u32) -> Vec<G> {
+        let bases: Vec<_> =
+            n_projective_group_elements::<G, D>(0..count, &concat_slices!(label, b" : g_"))
+                .collect();
+        G::Group::normalize_batch(&bases)
+    }
+
     pub fn get_commitment_key<'a, E: Pairing>(
         &'a self,
         setup_params: &'a [SetupParams<E, G>],
@@ -59,3 +81,88 @@ impl<G: AffineRepr> PedersenCommitment<G> {
         )
     }
 }
+
+/// A Pedersen commitment `C` whose commitment key `g_i` is hidden from the verifier until a later phase. Only a hash
+/// of the commitment key with a random opening is given to the verifier. The prover creates the proof using the
+/// `PedersenCommitment` statement over the actual key as usual. Once the key and the opening are revealed,
+/// `Self::reveal_bases` checks them against the hash and returns the `PedersenCommitment` statement to verify
+/// the proof with.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct PedersenCommitmentWithHiddenBases<G: AffineRepr> {
+    /// The Pedersen commitment `C` in `g_0 * s_0 + g_1 * s_1 + ... + g_{n-1} * s_{n-1} = C`
+    #[serde_as(as = "ArkObjectBytes")]
+    pub commitment: G,
+    /// Hash of the commitment key `g_i` and the opening
+    pub committed_bases: Vec<u8>,
+    /// Number of bases in the commitment key which is also the number of committed scalars
+    pub num_bases: u32,
+}
+
+impl<G: AffineRepr> PedersenCommitmentWithHiddenBases<G> {
+    /// Commit to the commitment key `bases` of the Pedersen commitment `commitment`. Returns the opening to be
+    /// given to the verifier along with `bases` when revealing them.
+    pub fn new<R: RngCore, D: Digest>(rng: &mut R, bases: &[G], commitment: G) -> (Self, [u8; 32]) {
+        let mut opening = [0; 32];
+        rng.fill_bytes(&mut opening);
+        let committed_bases = Self::hash_bases::<D>(bases, &opening);
+        (
+            Self {
+                commitment,
+                committed_bases,
+                num_bases: bases.len() as u32,
+            },
+            opening,
+        )
+    }
+
+    /// Check that the proof of knowledge of the committed scalars has as many responses as the number of
+    /// committed bases. This can be done before the bases are revealed.
+    pub fn check_scalar_count(
+        &self,
+        proof: &PedersenCommitmentProof<G>,
+    ) -> Result<(), ProofSystemError> {
+        if proof.response.len() != self.num_bases as usize {
+            return Err(ProofSystemError::UnequalHiddenBasesAndScalarCount(
+                self.num_bases as usize,
+                proof.response.len(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check the revealed commitment key `bases` and `opening` against the committed bases and create the
+    /// `Statement` for verifying the proof of knowledge of the committed scalars.
+    pub fn reveal_bases<E: Pairing, D: Digest>(
+        &self,
+        bases: Vec<G>,
+        opening: &[u8; 32],
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        if bases.len() != self.num_bases as usize {
+            return Err(ProofSystemError::UnequalHiddenBasesAndScalarCount(
+                self.num_bases as usize,
+                bases.len(),
+            ));
+        }
+        if Self::hash_bases::<D>(&bases, opening) != self.committed_bases {
+            return Err(ProofSystemError::RevealedBasesDontMatchCommitment);
+        }
+        Ok(PedersenCommitment::new_statement_from_params(
+            bases,
+            self.commitment,
+        ))
+    }
+
+    fn hash_bases<D: Digest>(bases: &[G], opening: &[u8; 32]) -> Vec<u8> {
+        let mut writer = DigestWriter::<D>::new();
+        writer.0.update(PED_COMM_HIDDEN_BASES_LABEL);
+        bases
+            .serialize_compressed(&mut writer)
+            .expect("serializing into a digest should not fail");
+        writer.0.update(opening);
+        writer.finalize().to_vec()
+    }
+}
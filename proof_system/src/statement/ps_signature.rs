@@ -9,6 +9,10 @@ use coconut_crypto::{proof::*, setup::*};
 use dock_crypto_utils::serde_utils::ArkObjectBytes;
 
 /// Public values like setup params, public key and revealed messages for proving knowledge of PS signature.
+///
+/// The signature is randomized with fresh randomness for each proof, so proofs created from the same signature
+/// can't be linked to each other or to the signature except through the revealed messages. Rerandomizing the
+/// signature with `Signature::rerandomize` before proving isn't needed for this.
 #[serde_as]
 #[derive(
     Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
@@ -91,3 +95,83 @@ impl<E: Pairing> PoKPSSignatureStatement<E> {
         )
     }
 }
+
+/// Public values for proving knowledge of a PS multi-signature, i.e. a signature over the same messages by
+/// several signers aggregated with `AggregatedSignature::new_multi_signature`. The proof is the same as for a
+/// single PS signature but with the aggregate of the signers' public keys. The witness is
+/// `Witness::PoKPSSignature` with the aggregated signature and the proof is `StatementProof::PoKPSSignature`.
+///
+/// As aggregating public keys is vulnerable to rogue key attacks, the verifier must only use public keys it
+/// trusts, see `PublicKey::aggregate`.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct PoKPSAggregateSignatureStatement<E: Pairing> {
+    /// Messages being revealed.
+    #[serde_as(as = "BTreeMap<Same, ArkObjectBytes>")]
+    pub revealed_messages: BTreeMap<usize, E::ScalarField>,
+    /// If the statement was created by passing the signature params directly, then it will not be None
+    pub signature_params: Option<SignatureParams<E>>,
+    /// If the statement was created by passing the index of signature params in `SetupParams`, then it will not be None
+    pub signature_params_ref: Option<usize>,
+    /// Public keys of the signers
+    pub public_keys: Vec<PublicKey<E>>,
+}
+
+impl<E: Pairing> PoKPSAggregateSignatureStatement<E> {
+    /// Create a statement by passing the signature parameters and signers' public keys directly.
+    pub fn new_statement_from_params<G: AffineRepr>(
+        signature_params: SignatureParams<E>,
+        public_keys: Vec<PublicKey<E>>,
+        revealed_messages: BTreeMap<usize, E::ScalarField>,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        let statement = Self {
+            revealed_messages,
+            signature_params: Some(signature_params),
+            signature_params_ref: None,
+            public_keys,
+        };
+        statement.aggregated_public_key()?;
+        Ok(Statement::PoKPSAggregateSignature(statement))
+    }
+
+    /// Create a statement by passing the index of signature parameters in `SetupParams` and the signers'
+    /// public keys.
+    pub fn new_statement_from_params_ref<G: AffineRepr>(
+        signature_params_ref: usize,
+        public_keys: Vec<PublicKey<E>>,
+        revealed_messages: BTreeMap<usize, E::ScalarField>,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        let statement = Self {
+            revealed_messages,
+            signature_params: None,
+            signature_params_ref: Some(signature_params_ref),
+            public_keys,
+        };
+        statement.aggregated_public_key()?;
+        Ok(Statement::PoKPSAggregateSignature(statement))
+    }
+
+    /// Get signature params for the statement index `s_idx` either from `self` or from given `setup_params`.
+    pub fn get_sig_params<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a SignatureParams<E>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.signature_params,
+            self.signature_params_ref,
+            PSSignatureParams,
+            IncompatiblePSSetupParamAtIndex,
+            st_idx
+        )
+    }
+
+    /// Aggregate of the signers' public keys which the proof is verified against
+    pub fn aggregated_public_key(&self) -> Result<PublicKey<E>, ProofSystemError> {
+        PublicKey::aggregate(&self.public_keys).ok_or(ProofSystemError::CannotAggregatePSPublicKeys)
+    }
+}
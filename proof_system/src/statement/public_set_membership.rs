@@ -0,0 +1,74 @@
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::vec::Vec;
+use dock_crypto_utils::{commitment::PedersenCommitmentKey, serde_utils::*};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::{error::ProofSystemError, setup_params::SetupParams, statement::Statement};
+
+/// Proving that a signed message is one of the values in a small public set, like a country code being one of
+/// a few allowed ones. The message is committed in a Pedersen commitment and a one-of-many Schnorr (OR) proof
+/// shows that the commitment is to one of the set's values so the proof size is linear in the size of the set.
+/// For large sets, an accumulator should be used instead.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct PublicSetMembership<G: AffineRepr> {
+    /// The public values, one of which is equal to the message
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub set: Vec<G::ScalarField>,
+    #[serde_as(as = "Option<ArkObjectBytes>")]
+    pub comm_key: Option<PedersenCommitmentKey<G>>,
+    pub comm_key_ref: Option<usize>,
+}
+
+impl<G: AffineRepr> PublicSetMembership<G> {
+    pub fn new_statement_from_params<E: Pairing>(
+        set: Vec<G::ScalarField>,
+        comm_key: PedersenCommitmentKey<G>,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        Self::validate_set(&set)?;
+        Ok(Statement::PublicSetMembership(Self {
+            set,
+            comm_key: Some(comm_key),
+            comm_key_ref: None,
+        }))
+    }
+
+    pub fn new_statement_from_params_ref<E: Pairing>(
+        set: Vec<G::ScalarField>,
+        comm_key_ref: usize,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        Self::validate_set(&set)?;
+        Ok(Statement::PublicSetMembership(Self {
+            set,
+            comm_key: None,
+            comm_key_ref: Some(comm_key_ref),
+        }))
+    }
+
+    pub fn get_comm_key<'a, E: Pairing>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a PedersenCommitmentKey<G>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.comm_key,
+            self.comm_key_ref,
+            CommitmentKey,
+            IncompatiblePedCommSetupParamAtIndex,
+            st_idx
+        )
+    }
+
+    fn validate_set(set: &[G::ScalarField]) -> Result<(), ProofSystemError> {
+        if set.is_empty() {
+            return Err(ProofSystemError::EmptyPublicSet);
+        }
+        Ok(())
+    }
+}
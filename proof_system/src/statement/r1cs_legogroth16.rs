@@ -1,7 +1,11 @@
 use ark_ec::{pairing::Pairing, AffineRepr};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+#[cfg(feature = "std")]
+use ark_std::string::ToString;
 use ark_std::vec::Vec;
 use dock_crypto_utils::serde_utils::ArkObjectBytes;
+#[cfg(feature = "std")]
+use legogroth16::circom::CircomError;
 pub use legogroth16::{circom::R1CS, PreparedVerifyingKey, ProvingKey, VerifyingKey};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
@@ -54,6 +58,26 @@ impl<E: Pairing> R1CSCircomProver<E> {
         }))
     }
 
+    /// Create the statement from the contents of the .r1cs and .wasm files generated by Circom and the compressed
+    /// serialization of the proving key, like when the files can't be read from disk.
+    #[cfg(feature = "std")]
+    pub fn new_from_bytes<G: AffineRepr>(
+        r1cs_bytes: &[u8],
+        wasm_bytes: &[u8],
+        snark_proving_key_bytes: &[u8],
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        let r1cs = R1CS::from_bytes(r1cs_bytes)?;
+        // The WASM module is only compiled when creating the proof so check just its magic number here
+        if !wasm_bytes.starts_with(b"\0asm") {
+            return Err(CircomError::UnableToLoadWasmModuleFromBytes(
+                "Invalid magic number".to_string(),
+            )
+            .into());
+        }
+        let snark_proving_key = ProvingKey::deserialize_compressed(snark_proving_key_bytes)?;
+        Self::new_statement_from_params(r1cs, wasm_bytes.to_vec(), snark_proving_key)
+    }
+
     pub fn new_statement_from_params_ref<G: AffineRepr>(
         r1cs_ref: usize,
         wasm_bytes_ref: usize,
@@ -154,6 +178,15 @@ impl<E: Pairing> R1CSCircomVerifier<E> {
         }))
     }
 
+    /// Create the statement from the compressed serialization of the verifying key
+    pub fn new_from_bytes<G: AffineRepr>(
+        public_inputs: Vec<E::ScalarField>,
+        snark_verifying_key_bytes: &[u8],
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        let snark_verifying_key = VerifyingKey::deserialize_compressed(snark_verifying_key_bytes)?;
+        Self::new_statement_from_params(public_inputs, snark_verifying_key)
+    }
+
     pub fn new_statement_from_params_ref<G: AffineRepr>(
         public_inputs_ref: usize,
         snark_verifying_key_ref: usize,
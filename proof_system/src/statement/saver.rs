@@ -9,11 +9,17 @@ use crate::{
     sub_protocols::saver::SaverProtocol,
 };
 use dock_crypto_utils::serde_utils::ArkObjectBytes;
-use saver::prelude::{
-    ChunkedCommitmentGens, EncryptionGens, EncryptionKey, ProvingKey, VerifyingKey,
+use saver::{
+    encryption::Ciphertext,
+    prelude::{
+        ChunkedCommitmentGens, DecryptionKey, EncryptionGens, EncryptionKey, PreparedDecryptionKey,
+        ProvingKey, SecretKey, VerifyingKey,
+    },
 };
 
-/// Proving knowledge of correctly encrypted message
+/// Proving knowledge of correctly encrypted message. To prove that ciphertexts for different decryptors encrypt
+/// the same message, like a signed message, add one such statement for each decryptor and a witness equality
+/// between the statements' witnesses and the message.
 #[derive(
     Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
 )]
@@ -150,6 +156,27 @@ impl<E: Pairing> SaverProver<E> {
             st_idx
         )
     }
+
+    /// Create the verifier's statement from this statement. The verifier's statement has the SNARK
+    /// verifying key instead of the proving key. Fails if the proving key is given as a reference in
+    /// `SetupParams` as the verifying key can't be extracted without the setup params.
+    pub fn to_verifier(&self) -> Result<SaverVerifier<E>, ProofSystemError> {
+        let snark_pk = self
+            .snark_proving_key
+            .as_ref()
+            .ok_or(ProofSystemError::CannotDeriveVerifierStatement)?;
+        Ok(SaverVerifier {
+            chunk_bit_size: self.chunk_bit_size,
+            encryption_gens: self.encryption_gens.clone(),
+            chunked_commitment_gens: self.chunked_commitment_gens.clone(),
+            encryption_key: self.encryption_key.clone(),
+            snark_verifying_key: Some(snark_pk.pk.vk.clone()),
+            encryption_gens_ref: self.encryption_gens_ref,
+            chunked_commitment_gens_ref: self.chunked_commitment_gens_ref,
+            encryption_key_ref: self.encryption_key_ref,
+            snark_verifying_key_ref: None,
+        })
+    }
 }
 
 impl<E: Pairing> SaverVerifier<E> {
@@ -253,4 +280,57 @@ impl<E: Pairing> SaverVerifier<E> {
             st_idx
         )
     }
+
+    /// For the decryptor to check that its secret key `sk` and decryption key `dk` correspond to the encryption
+    /// key and generators of this statement before attempting to decrypt the ciphertext in a proof
+    pub fn verify_decryption_key<G: AffineRepr>(
+        &self,
+        sk: &SecretKey<E::ScalarField>,
+        dk: &DecryptionKey<E>,
+        setup_params: &[SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<(), ProofSystemError> {
+        let encryption_gens = self.get_encryption_gens(setup_params, st_idx)?;
+        let encryption_key = self.get_encryption_key(setup_params, st_idx)?;
+        dk.verify_against_encryption_key(sk, encryption_key, encryption_gens)?;
+        Ok(())
+    }
+
+    /// For the decryptor to decrypt the ciphertext in the proof of this statement. Returns the message and `nu`,
+    /// the proof of correct decryption which can be checked with `Self::verify_decryption` using the decryption
+    /// key without the secret key. Fails if the ciphertext doesn't decrypt to a message.
+    pub fn decrypt_and_prove<G: AffineRepr>(
+        &self,
+        ciphertext: &Ciphertext<E>,
+        sk: &SecretKey<E::ScalarField>,
+        dk: impl Into<PreparedDecryptionKey<E>>,
+        setup_params: &[SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<(E::ScalarField, E::G1Affine), ProofSystemError> {
+        let snark_vk = self.get_snark_verifying_key(setup_params, st_idx)?;
+        Ok(ciphertext.decrypt_given_groth16_vk(sk, dk, snark_vk, self.chunk_bit_size)?)
+    }
+
+    /// Verify that `message` is the decryption of the ciphertext given `nu` returned by `Self::decrypt_and_prove`
+    pub fn verify_decryption<G: AffineRepr>(
+        &self,
+        message: &E::ScalarField,
+        nu: &E::G1Affine,
+        ciphertext: &Ciphertext<E>,
+        dk: impl Into<PreparedDecryptionKey<E>>,
+        setup_params: &[SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<(), ProofSystemError> {
+        let encryption_gens = self.get_encryption_gens(setup_params, st_idx)?;
+        let snark_vk = self.get_snark_verifying_key(setup_params, st_idx)?;
+        ciphertext.verify_decryption_given_groth16_vk(
+            message,
+            nu,
+            self.chunk_bit_size,
+            dk,
+            snark_vk,
+            encryption_gens.clone(),
+        )?;
+        Ok(())
+    }
 }
@@ -0,0 +1,56 @@
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::vec::Vec;
+use blake2::Blake2b512;
+use dock_crypto_utils::{
+    concat_slices, hashing_utils::affine_group_elem_from_try_and_incr, serde_utils::ArkObjectBytes,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::{constants::SCOPED_PSEUDONYM_LABEL, statement::Statement};
+
+/// Proving knowledge of a secret `s` in the pseudonym `nym = H(scope) * s` where `scope` is public. The secret
+/// is usually a hidden message of a signature (like a holder's secret in a credential) and is proven equal to it
+/// using a witness equality. The same secret gives the same pseudonym for a scope but pseudonyms for different
+/// scopes can't be linked to each other.
+///
+/// `H(scope)` isn't part of the statement but is computed from `scope` by both the prover and the verifier so a
+/// statement can't pair a scope with a base of its choice.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct ScopedPseudonym<G: AffineRepr> {
+    /// The public scope, like a verifier's or a service's identifier
+    pub scope: Vec<u8>,
+    /// The pseudonym `nym` in `nym = H(scope) * s`
+    #[serde_as(as = "ArkObjectBytes")]
+    pub pseudonym: G,
+}
+
+impl<G: AffineRepr> ScopedPseudonym<G> {
+    /// Create a `Statement` variant for proving knowledge of the secret in the pseudonym for the given scope
+    pub fn new_statement_from_params<E: Pairing>(scope: Vec<u8>, pseudonym: G) -> Statement<E, G> {
+        Statement::ScopedPseudonym(Self { scope, pseudonym })
+    }
+
+    /// The base of the pseudonym, i.e. `H(scope)`
+    pub fn base(&self) -> G {
+        Self::base_for_scope(&self.scope)
+    }
+
+    /// Hash the scope to get the base of the pseudonym
+    pub fn base_for_scope(scope: &[u8]) -> G {
+        affine_group_elem_from_try_and_incr::<G, Blake2b512>(&concat_slices!(
+            SCOPED_PSEUDONYM_LABEL,
+            scope
+        ))
+    }
+
+    /// Create the pseudonym of `secret` for the given scope
+    pub fn pseudonym_for_scope(scope: &[u8], secret: &G::ScalarField) -> G {
+        (Self::base_for_scope(scope) * secret).into()
+    }
+}
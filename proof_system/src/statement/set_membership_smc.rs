@@ -0,0 +1,85 @@
+use crate::{
+    error::ProofSystemError,
+    setup_params::SetupParams,
+    statement::{bound_check_smc::SmcParamsAndCommitmentKey, Statement},
+};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::vec::Vec;
+use dock_crypto_utils::serde_utils::ArkObjectBytes;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use smc_range_proof::prelude::MemberCommitmentKey;
+
+/// Proving knowledge of message that is one of the values of a public set using set-membership based check. Unlike
+/// `BoundCheckSmc` where the set is the digits of a base, the set here is arbitrary and is part of the params, which
+/// should be created with `SmcParamsAndCommitmentKey::new_for_set` by the verifier as it holds the secret key used to
+/// sign each set member.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct SetMembershipSmc<E: Pairing> {
+    #[serde_as(as = "Option<ArkObjectBytes>")]
+    pub params_and_comm_key: Option<SmcParamsAndCommitmentKey<E>>,
+    pub params_and_comm_key_ref: Option<usize>,
+}
+
+impl<E: Pairing> SetMembershipSmc<E> {
+    pub fn new_statement_from_params<G: AffineRepr>(
+        params: SmcParamsAndCommitmentKey<E>,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        if params.params.set.is_empty() {
+            return Err(ProofSystemError::EmptyPublicSet);
+        }
+        Ok(Statement::SetMembershipSmc(Self {
+            params_and_comm_key: Some(params),
+            params_and_comm_key_ref: None,
+        }))
+    }
+
+    pub fn new_statement_from_params_ref<G: AffineRepr>(
+        params_ref: usize,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        Ok(Statement::SetMembershipSmc(Self {
+            params_and_comm_key: None,
+            params_and_comm_key_ref: Some(params_ref),
+        }))
+    }
+
+    pub fn get_params_and_comm_key<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a SmcParamsAndCommitmentKey<E>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.params_and_comm_key,
+            self.params_and_comm_key_ref,
+            SmcParamsAndCommKey,
+            IncompatibleBoundCheckSetupParamAtIndex,
+            st_idx
+        )
+    }
+
+    pub fn get_comm_key<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a MemberCommitmentKey<E::G1Affine>, ProofSystemError> {
+        Ok(&self.get_params_and_comm_key(setup_params, st_idx)?.comm_key)
+    }
+
+    /// The set of values the message is proven to be a member of
+    pub fn get_set<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a [E::ScalarField], ProofSystemError> {
+        Ok(&self
+            .get_params_and_comm_key(setup_params, st_idx)?
+            .params
+            .set)
+    }
+}
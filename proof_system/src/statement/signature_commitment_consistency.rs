@@ -0,0 +1,115 @@
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::{
+    error::ProofSystemError,
+    meta_statement::{EqualWitnesses, WitnessRef},
+    setup_params::SetupParams,
+    statement::Statement,
+};
+use dock_crypto_utils::serde_utils::*;
+
+/// Proving knowledge of scalars `s_i` in Pedersen commitment `g_0 * s_0 + g_1 * s_1 + ... + g_{n-1} * s_{n-1} = C`
+/// where some of the `s_i` are messages of the signature in statement `signature_statement_index`. Like
+/// `CommittedMessageEquality` but for several committed values, the equalities with the signed messages are
+/// implied by this statement rather than given as witness equalities in the meta-statements. The witness of this
+/// statement is the opening of the commitment, i.e. `[s_0, s_1, ...]`.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct SignatureCommitmentConsistency<G: AffineRepr> {
+    /// The Pedersen commitment `C` in `g_0 * s_0 + g_1 * s_1 + ... + g_{n-1} * s_{n-1} = C`
+    #[serde_as(as = "ArkObjectBytes")]
+    pub commitment: G,
+    /// Commitment key `g_i` in `g_0 * s_0 + g_1 * s_1 + ... + g_{n-1} * s_{n-1} = C`
+    #[serde_as(as = "Option<Vec<ArkObjectBytes>>")]
+    pub key: Option<Vec<G>>,
+    pub key_ref: Option<usize>,
+    /// Index of the signature's statement in the `ProofSpec`
+    pub signature_statement_index: usize,
+    /// Index of the message in the signature, keyed by the position of the committed value equal to it
+    pub message_indices: BTreeMap<usize, usize>,
+}
+
+impl<G: AffineRepr> SignatureCommitmentConsistency<G> {
+    pub fn new_statement_from_params<E: Pairing>(
+        key: Vec<G>,
+        commitment: G,
+        signature_statement_index: usize,
+        message_indices: BTreeMap<usize, usize>,
+    ) -> Statement<E, G> {
+        Statement::SignatureCommitmentConsistency(Self {
+            commitment,
+            key: Some(key),
+            key_ref: None,
+            signature_statement_index,
+            message_indices,
+        })
+    }
+
+    pub fn new_statement_from_params_refs<E: Pairing>(
+        key_ref: usize,
+        commitment: G,
+        signature_statement_index: usize,
+        message_indices: BTreeMap<usize, usize>,
+    ) -> Statement<E, G> {
+        Statement::SignatureCommitmentConsistency(Self {
+            commitment,
+            key: None,
+            key_ref: Some(key_ref),
+            signature_statement_index,
+            message_indices,
+        })
+    }
+
+    pub fn get_commitment_key<'a, E: Pairing>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a Vec<G>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.key,
+            self.key_ref,
+            PedersenCommitmentKey,
+            IncompatiblePedCommSetupParamAtIndex,
+            st_idx
+        )
+    }
+
+    /// Check that each committed value mapped to a signed message is in the commitment
+    pub fn check_positions(&self, key_len: usize, st_idx: usize) -> Result<(), ProofSystemError> {
+        if let Some((i, _)) = self.message_indices.range(key_len..).next() {
+            return Err(ProofSystemError::CommitmentPositionOutOfRange(
+                st_idx, *i, key_len,
+            ));
+        }
+        Ok(())
+    }
+
+    /// The witness equalities implied by this statement when it's at index `statement_idx`, i.e. each committed
+    /// value in `message_indices` is equal to its signed message
+    pub fn implied_witness_equalities(&self, statement_idx: usize) -> Vec<EqualWitnesses> {
+        self.message_indices
+            .iter()
+            .map(|(pos, msg_idx)| {
+                EqualWitnesses(
+                    [
+                        (self.signature_statement_index, *msg_idx),
+                        (statement_idx, *pos),
+                    ]
+                    .into_iter()
+                    .collect::<BTreeSet<WitnessRef>>(),
+                )
+            })
+            .collect()
+    }
+}
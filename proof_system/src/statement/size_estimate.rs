@@ -0,0 +1,416 @@
+//! Estimating the size of a proof before creating it, like to check that a presentation won't exceed the size
+//! limit of the transport. The estimates are of the compressed serialization and are upper bounds for proofs
+//! created without aggregating the SNARK proofs.
+
+use crate::{
+    proof_spec::ProofSpec,
+    setup_params::SetupParams,
+    statement::{Statement, Statements},
+};
+use ark_ec::{
+    pairing::{Pairing, PairingOutput},
+    AffineRepr,
+};
+use ark_ff::{PrimeField, Zero};
+use ark_serialize::CanonicalSerialize;
+use bulletproofs_plus_plus::setup::SetupParams as BppSetupParams;
+use smc_range_proof::prelude::SetMembershipCheckParams;
+
+/// Size of the length prefix of a serialized vector
+const VEC_LEN_SIZE: usize = 8;
+
+/// Number of bits in the values whose range is proven by the Bulletproofs++ based statements
+const BPP_NUM_BITS: usize = 64;
+
+/// Base assumed when the params, and thus the base, of a range proof aren't available. Base 2 gives the
+/// largest proofs.
+const DEFAULT_BASE: u16 = 2;
+
+/// Compressed sizes of the elements that proofs are made of
+struct ElementSizes {
+    g1: usize,
+    g2: usize,
+    gt: usize,
+    fr: usize,
+    g: usize,
+}
+
+impl ElementSizes {
+    fn new<E: Pairing, G: AffineRepr>() -> Self {
+        Self {
+            g1: E::G1Affine::zero().compressed_size(),
+            g2: E::G2Affine::zero().compressed_size(),
+            gt: PairingOutput::<E>::zero().compressed_size(),
+            fr: E::ScalarField::zero().compressed_size(),
+            g: G::zero().compressed_size(),
+        }
+    }
+
+    /// Schnorr response for `n` witnesses
+    fn schnorr_response(&self, n: usize) -> usize {
+        VEC_LEN_SIZE + n * self.fr
+    }
+
+    /// `PedersenCommitmentProof` over group `G` for `n` witnesses
+    fn ped_comm_proof(&self, n: usize) -> usize {
+        self.g + self.schnorr_response(n)
+    }
+
+    /// `PedersenCommitmentProof` over group `G1` for `n` witnesses
+    fn ped_comm_proof_g1(&self, n: usize) -> usize {
+        self.g1 + self.schnorr_response(n)
+    }
+
+    /// Proof of knowledge of the signature with `hidden` undisclosed messages
+    fn bbs_plus_proof(&self, hidden: usize) -> usize {
+        4 * self.g1 + self.g1 + 2 * self.fr + self.schnorr_response(hidden + 2)
+    }
+
+    fn bbs_23_proof(&self, hidden: usize) -> usize {
+        4 * self.g1 + self.g1 + 2 * self.fr + self.schnorr_response(hidden + 1)
+    }
+
+    fn ps_proof(&self, hidden: usize) -> usize {
+        2 * self.g2 + self.schnorr_response(hidden + 1) + 2 * VEC_LEN_SIZE + 2 * self.g1
+    }
+
+    /// Proof of knowledge of the MAC with `hidden` undisclosed messages
+    fn bddt16_proof(&self, hidden: usize) -> usize {
+        6 * self.g1 + 4 * self.fr + self.schnorr_response(hidden + 3)
+    }
+
+    fn vb_membership_proof(&self) -> usize {
+        7 * self.g1 + self.gt + 5 * self.fr
+    }
+
+    fn vb_non_membership_proof(&self) -> usize {
+        11 * self.g1 + self.gt + 8 * self.fr
+    }
+
+    fn cdh_membership_proof(&self) -> usize {
+        3 * self.g1 + 2 * self.fr
+    }
+
+    fn cdh_non_membership_proof(&self) -> usize {
+        5 * self.g1 + self.schnorr_response(3) + self.fr
+    }
+
+    /// The accumulator, the challenge and the encrypted opening of the accumulator
+    fn detached_accumulator_extra(&self) -> usize {
+        let opening = self.g1 + self.fr + 1;
+        // Ephemeral public key, nonce, and ciphertext with its authentication tag
+        let encryption = self.g2 + 24 + VEC_LEN_SIZE + opening + 16;
+        self.g1 + self.fr + encryption
+    }
+
+    fn legogroth16_proof(&self) -> usize {
+        3 * self.g1 + self.g2
+    }
+
+    /// Bulletproofs++ proof for `num_proofs` values. The number of rounds of the norm argument is the log of the
+    /// size of the params. Without params, base 2 is assumed, which needs the largest params.
+    fn bpp_proof<G: AffineRepr>(
+        &self,
+        num_proofs: usize,
+        params: Option<&BppSetupParams<G>>,
+    ) -> usize {
+        let vec_len = match params {
+            Some(p) => core::cmp::max(p.G_vec.len(), p.H_vec.len()),
+            None => core::cmp::max(BPP_NUM_BITS, DEFAULT_BASE as usize) * num_proofs,
+        };
+        let rounds = vec_len.next_power_of_two().trailing_zeros() as usize;
+        // Base, the commitments of 3 rounds and the norm argument
+        2 + 4 * self.g + 2 * (VEC_LEN_SIZE + rounds * self.g) + 2 * (VEC_LEN_SIZE + self.fr)
+    }
+
+    /// Bulletproofs++ proof for `num_values` values in arbitrary ranges
+    fn bpp_arbitrary_range_proof<G: AffineRepr>(
+        &self,
+        num_values: usize,
+        params: Option<&BppSetupParams<G>>,
+    ) -> usize {
+        VEC_LEN_SIZE + 2 * num_values * self.g + self.bpp_proof(2 * num_values, params)
+    }
+
+    /// Set-membership check based range proof, with `pairing_output_size` as the size of the elements that are
+    /// pairing outputs when publicly verifiable and group elements with keyed verification. The number of digits
+    /// is an upper bound for both CLS and CCS.
+    fn smc_range_proof(&self, min: u64, max: u64, base: u16, pairing_output_size: usize) -> usize {
+        let l = num_digits(max, base) + 1;
+        let proof = if crate::sub_protocols::should_use_cls(min, max) {
+            2 + 2 * VEC_LEN_SIZE
+                + l * (self.g1 + pairing_output_size)
+                + self.g1
+                + 2 * (VEC_LEN_SIZE + l * self.fr)
+                + self.fr
+        } else {
+            2 + 4 * VEC_LEN_SIZE
+                + 2 * l * (self.g1 + pairing_output_size)
+                + self.g1
+                + 4 * (VEC_LEN_SIZE + l * self.fr)
+                + self.fr
+        };
+        // Tag of the proof type, the commitment and the Schnorr proof for it
+        1 + proof + self.g1 + self.ped_comm_proof_g1(2)
+    }
+}
+
+/// Number of digits of `value` in `base`
+fn num_digits(value: u64, base: u16) -> usize {
+    let mut power = 1_u128;
+    let mut l = 0;
+    while power <= value as u128 {
+        power *= base as u128;
+        l += 1;
+    }
+    l
+}
+
+fn smc_base<E: Pairing>(params: Option<&SetMembershipCheckParams<E>>) -> u16 {
+    params
+        .map(|p| p.get_supported_base_for_range_proof())
+        .unwrap_or(DEFAULT_BASE)
+}
+
+impl<E: Pairing, G: AffineRepr> Statement<E, G> {
+    /// Upper bound on the size of the statement's proof. The statement's params are taken from `self` or from
+    /// `setup_params` when referenced. When params referenced by the statement aren't in `setup_params`, the parts
+    /// of the proof whose size depends on them, like the responses for the undisclosed messages of a signature
+    /// or the number of values in a Pedersen commitment, are not counted.
+    pub fn estimated_proof_size(&self, setup_params: &[SetupParams<E, G>], st_idx: usize) -> usize {
+        let sz = ElementSizes::new::<E, G>();
+        // Tag of the statement proof
+        1 + match self {
+            Statement::PoKBBSSignatureG1(s) => {
+                let hidden = s
+                    .get_sig_params(setup_params, st_idx)
+                    .map(|p| p.h.len().saturating_sub(s.revealed_messages.len()))
+                    .unwrap_or(0);
+                sz.bbs_plus_proof(hidden)
+            }
+            Statement::PoKBBSSignature23G1(s) => {
+                let hidden = s
+                    .get_sig_params(setup_params, st_idx)
+                    .map(|p| p.h.len().saturating_sub(s.revealed_messages.len()))
+                    .unwrap_or(0);
+                sz.bbs_23_proof(hidden)
+            }
+            Statement::PoKPSSignature(s) => {
+                let hidden = s
+                    .get_sig_params(setup_params, st_idx)
+                    .map(|p| {
+                        p.supported_message_count()
+                            .saturating_sub(s.revealed_messages.len())
+                    })
+                    .unwrap_or(0);
+                sz.ps_proof(hidden)
+            }
+            Statement::PoKPSAggregateSignature(s) => {
+                let hidden = s
+                    .get_sig_params(setup_params, st_idx)
+                    .map(|p| {
+                        p.supported_message_count()
+                            .saturating_sub(s.revealed_messages.len())
+                    })
+                    .unwrap_or(0);
+                sz.ps_proof(hidden)
+            }
+            Statement::PoKBDDT16MAC(s) => {
+                let hidden = s
+                    .get_params(setup_params, st_idx)
+                    .map(|p| p.g_vec.len().saturating_sub(s.revealed_messages.len()))
+                    .unwrap_or(0);
+                sz.bddt16_proof(hidden)
+            }
+            Statement::PoKBDDT16MACFullVerifier(s) => {
+                let hidden = s
+                    .get_params(setup_params, st_idx)
+                    .map(|p| p.g_vec.len().saturating_sub(s.revealed_messages.len()))
+                    .unwrap_or(0);
+                sz.bddt16_proof(hidden)
+            }
+            Statement::PedersenCommitment(s) => {
+                let n = s
+                    .get_commitment_key(setup_params, st_idx)
+                    .map(|k| k.len())
+                    .unwrap_or(0);
+                sz.ped_comm_proof(n)
+            }
+            Statement::PartialPedersenOpening(s) => {
+                let n = s
+                    .get_commitment_key(setup_params, st_idx)
+                    .map(|k| k.len().saturating_sub(s.revealed.len()))
+                    .unwrap_or(0);
+                sz.ped_comm_proof(n)
+            }
+            Statement::CommittedMessageEquality(s) => {
+                let n = s
+                    .get_commitment_key(setup_params, st_idx)
+                    .map(|k| k.len())
+                    .unwrap_or(0);
+                sz.ped_comm_proof(n)
+            }
+            Statement::SignatureCommitmentConsistency(s) => {
+                let n = s
+                    .get_commitment_key(setup_params, st_idx)
+                    .map(|k| k.len())
+                    .unwrap_or(0);
+                sz.ped_comm_proof(n)
+            }
+            Statement::ScopedPseudonym(_) | Statement::DisclosedLinearCombination(_) => {
+                sz.ped_comm_proof(1)
+            }
+            Statement::VBAccumulatorMembership(_)
+            | Statement::KBUniversalAccumulatorMembership(_)
+            | Statement::KBUniversalAccumulatorNonMembership(_) => sz.vb_membership_proof(),
+            Statement::VBAccumulatorNonMembership(_) => sz.vb_non_membership_proof(),
+            Statement::DetachedAccumulatorMembershipProver(_)
+            | Statement::DetachedAccumulatorMembershipVerifier(_) => {
+                sz.vb_membership_proof() + sz.detached_accumulator_extra()
+            }
+            Statement::DetachedAccumulatorNonMembershipProver(_)
+            | Statement::DetachedAccumulatorNonMembershipVerifier(_) => {
+                sz.vb_non_membership_proof() + sz.detached_accumulator_extra()
+            }
+            Statement::DetachedAccumulatorMembershipCDHProver(_)
+            | Statement::DetachedAccumulatorMembershipCDHVerifier(_) => {
+                sz.cdh_membership_proof() + sz.detached_accumulator_extra()
+            }
+            Statement::VBAccumulatorMembershipCDHProver(_)
+            | Statement::VBAccumulatorMembershipCDHVerifier(_)
+            | Statement::KBUniversalAccumulatorMembershipCDHProver(_)
+            | Statement::KBUniversalAccumulatorMembershipCDHVerifier(_)
+            | Statement::KBUniversalAccumulatorNonMembershipCDHProver(_)
+            | Statement::KBUniversalAccumulatorNonMembershipCDHVerifier(_) => {
+                sz.cdh_membership_proof()
+            }
+            Statement::VBAccumulatorNonMembershipCDHProver(_)
+            | Statement::VBAccumulatorNonMembershipCDHVerifier(_) => sz.cdh_non_membership_proof(),
+            // Proof of knowledge of BB signature and the membership proof
+            Statement::KBPositiveAccumulatorMembership(_) => {
+                9 * sz.g1 + 10 * sz.fr + sz.gt + sz.vb_membership_proof()
+            }
+            Statement::KBPositiveAccumulatorMembershipCDH(_) => {
+                9 * sz.g1 + 10 * sz.fr + sz.gt + sz.cdh_membership_proof()
+            }
+            Statement::SaverProver(s) => saver_proof_size::<E::ScalarField>(&sz, s.chunk_bit_size),
+            Statement::SaverVerifier(s) => {
+                saver_proof_size::<E::ScalarField>(&sz, s.chunk_bit_size)
+            }
+            Statement::BoundCheckLegoGroth16Prover(_)
+            | Statement::BoundCheckLegoGroth16Verifier(_)
+            | Statement::BloomMembershipLegoGroth16Prover(_)
+            | Statement::BloomMembershipLegoGroth16Verifier(_) => {
+                sz.legogroth16_proof() + sz.ped_comm_proof_g1(2)
+            }
+            Statement::R1CSCircomProver(s) => {
+                let n = s
+                    .get_proving_key(setup_params, st_idx)
+                    .map(|k| k.vk.commit_witness_count as usize)
+                    .unwrap_or(0);
+                sz.legogroth16_proof() + sz.ped_comm_proof_g1(n + 1)
+            }
+            Statement::R1CSCircomVerifier(s) => {
+                let n = s
+                    .get_verifying_key(setup_params, st_idx)
+                    .map(|k| k.commit_witness_count as usize)
+                    .unwrap_or(0);
+                sz.legogroth16_proof() + sz.ped_comm_proof_g1(n + 1)
+            }
+            Statement::BoundCheckBpp(s) => {
+                let params = s.get_setup_params(setup_params, st_idx).ok();
+                sz.bpp_arbitrary_range_proof(1, params) + 2 * sz.ped_comm_proof(2)
+            }
+            Statement::AggregatedBoundCheckBpp(s) => {
+                let params = s.get_setup_params(setup_params, st_idx).ok();
+                let n = s.bounds.len();
+                sz.bpp_arbitrary_range_proof(n.next_power_of_two(), params)
+                    + 2 * (VEC_LEN_SIZE + n * sz.ped_comm_proof(2))
+            }
+            Statement::Monotonic(s) => {
+                let params = s.get_setup_params(setup_params, st_idx).ok();
+                let n = s.num_values;
+                VEC_LEN_SIZE
+                    + n * sz.g
+                    + sz.bpp_proof(n.saturating_sub(1).next_power_of_two(), params)
+                    + VEC_LEN_SIZE
+                    + n * sz.ped_comm_proof(2)
+            }
+            Statement::BoundCheckSmc(s) => {
+                let params = s.get_params_and_comm_key(setup_params, st_idx).ok();
+                let base = smc_base(params.map(|p| &p.params));
+                sz.smc_range_proof(s.min, s.max, base, sz.gt)
+            }
+            Statement::BoundCheckSmcWithKVProver(s) => {
+                let params = s.get_params_and_comm_key(setup_params, st_idx).ok();
+                let base = smc_base(params.map(|p| &p.params));
+                sz.smc_range_proof(s.min, s.max, base, sz.g1)
+            }
+            Statement::BoundCheckSmcWithKVVerifier(s) => {
+                let params = s.get_params_and_comm_key_and_sk(setup_params, st_idx).ok();
+                let base = smc_base(params.map(|p| &p.params_and_comm_key.params));
+                sz.smc_range_proof(s.min, s.max, base, sz.g1)
+            }
+            Statement::SetMembershipSmc(_) => {
+                2 * sz.g1 + sz.gt + 3 * sz.fr + sz.g1 + sz.ped_comm_proof_g1(2)
+            }
+            Statement::PublicInequality(_) => {
+                let proof = 4 * sz.g + sz.fr + 2 * sz.schnorr_response(2);
+                proof + sz.g + sz.ped_comm_proof(2)
+            }
+            Statement::PublicSetMembership(s) => {
+                sz.g + 2 * (VEC_LEN_SIZE + s.set.len() * sz.fr) + sz.ped_comm_proof(2)
+            }
+            Statement::CommittedInequality(_) => {
+                let proof = 4 * sz.g + sz.fr + 2 * sz.schnorr_response(2);
+                proof + 2 * (sz.g + sz.ped_comm_proof(2))
+            }
+        }
+    }
+}
+
+/// SAVER proof for a message encrypted in chunks of `chunk_bit_size` bits
+fn saver_proof_size<F: PrimeField>(sz: &ElementSizes, chunk_bit_size: u8) -> usize {
+    let chunks = saver::utils::chunks_count::<F>(chunk_bit_size) as usize;
+    let ciphertext = sz.g1 + VEC_LEN_SIZE + chunks * sz.g1 + sz.g1;
+    let snark_proof = 2 * sz.g1 + sz.g2;
+    ciphertext
+        + snark_proof
+        + 2 * sz.g1
+        + 2 * sz.ped_comm_proof_g1(chunks + 1)
+        + sz.ped_comm_proof_g1(2)
+}
+
+impl<E: Pairing, G: AffineRepr> Statements<E, G> {
+    /// Upper bound on the size of the compressed serialization of the proof for these statements. Params that
+    /// the statements reference in the setup params aren't available here, so use
+    /// `Self::estimated_proof_size_with_setup_params` or `ProofSpec::estimated_proof_size` for such statements.
+    pub fn estimated_proof_size(&self) -> usize {
+        self.estimated_proof_size_with_setup_params(&[])
+    }
+
+    /// Like `Self::estimated_proof_size` but takes the params referenced by the statements from `setup_params`
+    pub fn estimated_proof_size_with_setup_params(
+        &self,
+        setup_params: &[SetupParams<E, G>],
+    ) -> usize {
+        // Length of the statement proofs, the absent aggregated proofs and the creation time
+        let overhead = VEC_LEN_SIZE + 1 + 1 + 1 + 8;
+        overhead
+            + self
+                .0
+                .iter()
+                .enumerate()
+                .map(|(i, s)| s.estimated_proof_size(setup_params, i))
+                .sum::<usize>()
+    }
+}
+
+impl<E: Pairing, G: AffineRepr> ProofSpec<E, G> {
+    /// Upper bound on the size of the compressed serialization of the proof for this proof spec. Assumes that the
+    /// SNARK proofs aren't aggregated.
+    pub fn estimated_proof_size(&self) -> usize {
+        self.statements
+            .estimated_proof_size_with_setup_params(&self.setup_params)
+    }
+}
@@ -5,9 +5,10 @@ use ark_std::{
     vec::Vec,
 };
 use bbs_plus::prelude::{PoKOfSignature23G1Proof, PoKOfSignatureG1Proof};
-use bulletproofs_plus_plus::prelude::ProofArbitraryRange;
+use bulletproofs_plus_plus::prelude::{Proof as BppProof, ProofArbitraryRange};
 use coconut_crypto::SignaturePoK as PSSignaturePoK;
 use dock_crypto_utils::{ecies, serde_utils::ArkObjectBytes};
+use kvac::bddt_2016::proof::PoKOfMAC;
 use saver::encryption::Ciphertext;
 use schnorr_pok::SchnorrResponse;
 use serde::{Deserialize, Serialize};
@@ -56,7 +57,14 @@ pub enum StatementProof<E: Pairing, G: AffineRepr> {
     KBUniversalAccumulatorNonMembershipCDH(vb_accumulator::kb_universal_accumulator::proofs_cdh::KBUniversalAccumulatorNonMembershipProof<E>),
     KBPositiveAccumulatorMembership(#[serde_as(as = "ArkObjectBytes")] KBPositiveAccumulatorMembershipProof<E>),
     KBPositiveAccumulatorMembershipCDH(#[serde_as(as = "ArkObjectBytes")] KBPositiveAccumulatorMembershipProofCDH<E>),
-
+    BloomMembershipLegoGroth16(BoundCheckLegoGroth16Proof<E>),
+    Monotonic(MonotonicProof<G>),
+    PublicSetMembership(PublicSetMembershipProof<G>),
+    DetachedAccumulatorMembershipCDH(DetachedAccumulatorMembershipCDHProof<E>),
+    AggregatedBoundCheckBpp(AggregatedBoundCheckBppProof<G>),
+    SetMembershipSmc(SetMembershipSmcProof<E>),
+    CommittedInequality(CommittedInequalityProof<G>),
+    PoKOfBDDT16MAC(#[serde_as(as = "ArkObjectBytes")] PoKOfMAC<E::G1Affine>),
 }
 
 macro_rules! delegate {
@@ -88,7 +96,15 @@ macro_rules! delegate {
                 KBUniversalAccumulatorMembershipCDH,
                 KBUniversalAccumulatorNonMembershipCDH,
                 KBPositiveAccumulatorMembership,
-                KBPositiveAccumulatorMembershipCDH
+                KBPositiveAccumulatorMembershipCDH,
+                BloomMembershipLegoGroth16,
+                Monotonic,
+                PublicSetMembership,
+                DetachedAccumulatorMembershipCDH,
+                AggregatedBoundCheckBpp,
+                SetMembershipSmc,
+                CommittedInequality,
+                PoKOfBDDT16MAC
             : $($tt)+
         }
     }};
@@ -123,7 +139,15 @@ macro_rules! delegate_reverse {
                 KBUniversalAccumulatorMembershipCDH,
                 KBUniversalAccumulatorNonMembershipCDH,
                 KBPositiveAccumulatorMembership,
-                KBPositiveAccumulatorMembershipCDH
+                KBPositiveAccumulatorMembershipCDH,
+                BloomMembershipLegoGroth16,
+                Monotonic,
+                PublicSetMembership,
+                DetachedAccumulatorMembershipCDH,
+                AggregatedBoundCheckBpp,
+                SetMembershipSmc,
+                CommittedInequality,
+                PoKOfBDDT16MAC
             : $($tt)+
         }
 
@@ -327,6 +351,76 @@ impl<G: AffineRepr> BoundCheckBppProof<G> {
     }
 }
 
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct MonotonicProof<G: AffineRepr> {
+    /// Commitment to each value
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub commitments: Vec<G>,
+    /// Proof that the differences of consecutive values are non-negative
+    #[serde_as(as = "ArkObjectBytes")]
+    pub bpp_proof: BppProof<G>,
+    /// Proof of knowledge of the value in each commitment
+    pub sp: Vec<PedersenCommitmentProof<G>>,
+}
+
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct AggregatedBoundCheckBppProof<G: AffineRepr> {
+    /// Proof that all the messages satisfy their bounds
+    #[serde_as(as = "ArkObjectBytes")]
+    pub bpp_proof: ProofArbitraryRange<G>,
+    /// Proof of knowledge of each message in the commitment to `message - min`
+    pub sp1: Vec<PedersenCommitmentProof<G>>,
+    /// Proof of knowledge of each message in the commitment to `max - 1 - message`
+    pub sp2: Vec<PedersenCommitmentProof<G>>,
+}
+
+impl<G: AffineRepr> AggregatedBoundCheckBppProof<G> {
+    pub fn get_schnorr_response_for_message(
+        &self,
+        index: usize,
+    ) -> Result<&G::ScalarField, ProofSystemError> {
+        self.sp1
+            .get(index)
+            .ok_or(ProofSystemError::InvalidWitnessEquality)?
+            .response
+            .get_response(0)
+            .map_err(|e| e.into())
+    }
+
+    /// For the proof to be correct, both responses of Schnorr protocols for each message should be correct as
+    /// both are proving the knowledge of same committed message
+    pub fn check_schnorr_responses_consistency(&self) -> Result<bool, ProofSystemError> {
+        for (sp1, sp2) in self.sp1.iter().zip(self.sp2.iter()) {
+            if sp1.response.get_response(0)? != sp2.response.get_response(0)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+impl<G: AffineRepr> MonotonicProof<G> {
+    pub fn get_schnorr_response_for_message(
+        &self,
+        index: usize,
+    ) -> Result<&G::ScalarField, ProofSystemError> {
+        self.sp
+            .get(index)
+            .ok_or(ProofSystemError::InvalidWitnessEquality)?
+            .response
+            .get_response(0)
+            .map_err(|e| e.into())
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum BoundCheckSmcInnerProof<E: Pairing> {
     CCS(smc_range_proof::prelude::CCSArbitraryRangeProof<E>),
@@ -358,6 +452,25 @@ impl<E: Pairing> BoundCheckSmcProof<E> {
     }
 }
 
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct SetMembershipSmcProof<E: Pairing> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub proof: smc_range_proof::ccs_set_membership::single_member::SetMembershipCheckProof<E>,
+    #[serde_as(as = "ArkObjectBytes")]
+    pub comm: E::G1Affine,
+    pub sp: PedersenCommitmentProof<E::G1Affine>,
+}
+
+impl<E: Pairing> SetMembershipSmcProof<E> {
+    pub fn get_schnorr_response_for_message(&self) -> Result<&E::ScalarField, ProofSystemError> {
+        self.sp.response.get_response(0).map_err(|e| e.into())
+    }
+}
+
 #[serde_as]
 #[derive(
     Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
@@ -396,6 +509,65 @@ impl<G: AffineRepr> InequalityProof<G> {
     }
 }
 
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct CommittedInequalityProof<G: AffineRepr> {
+    /// Proof that the values committed in `comm1` and `comm2` are not equal
+    #[serde_as(as = "ArkObjectBytes")]
+    pub proof: schnorr_pok::inequality::InequalityProof<G>,
+    /// Commitment to the first message
+    #[serde_as(as = "ArkObjectBytes")]
+    pub comm1: G,
+    /// Commitment to the second message
+    #[serde_as(as = "ArkObjectBytes")]
+    pub comm2: G,
+    pub sp1: PedersenCommitmentProof<G>,
+    pub sp2: PedersenCommitmentProof<G>,
+}
+
+impl<G: AffineRepr> CommittedInequalityProof<G> {
+    pub fn get_schnorr_response_for_message(
+        &self,
+        index: usize,
+    ) -> Result<&G::ScalarField, ProofSystemError> {
+        match index {
+            0 => &self.sp1,
+            1 => &self.sp2,
+            _ => return Err(ProofSystemError::InvalidWitnessEquality),
+        }
+        .response
+        .get_response(0)
+        .map_err(|e| e.into())
+    }
+}
+
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct PublicSetMembershipProof<G: AffineRepr> {
+    /// Commitment to the message
+    #[serde_as(as = "ArkObjectBytes")]
+    pub comm: G,
+    /// Challenge for the proof of each value in the set. These add up to the proof's challenge.
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub challenges: Vec<G::ScalarField>,
+    /// Response for the proof of each value in the set
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub responses: Vec<G::ScalarField>,
+    pub sp: PedersenCommitmentProof<G>,
+}
+
+impl<G: AffineRepr> PublicSetMembershipProof<G> {
+    pub fn get_schnorr_response_for_message(&self) -> Result<&G::ScalarField, ProofSystemError> {
+        self.sp.response.get_response(0).map_err(|e| e.into())
+    }
+}
+
 #[serde_as]
 #[derive(
     Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
@@ -430,6 +602,22 @@ pub struct DetachedAccumulatorNonMembershipProof<E: Pairing> {
     pub encrypted: ecies::Encryption<E::G2Affine, 32, 24>,
 }
 
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct DetachedAccumulatorMembershipCDHProof<E: Pairing> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub accumulator: E::G1Affine,
+    pub accum_proof: vb_accumulator::proofs_cdh::MembershipProof<E>,
+    #[serde_as(as = "ArkObjectBytes")]
+    pub challenge: E::ScalarField,
+    /// Encrypted opening
+    #[serde_as(as = "ArkObjectBytes")]
+    pub encrypted: ecies::Encryption<E::G2Affine, 32, 24>,
+}
+
 mod serialization {
     use super::{
         AffineRepr, CanonicalDeserialize, CanonicalSerialize, Pairing, Read, SerializationError,
@@ -1,7 +1,8 @@
 use crate::{
     error::ProofSystemError,
     prelude::{
-        DetachedAccumulatorMembershipProof, DetachedAccumulatorNonMembershipProof, StatementProof,
+        DetachedAccumulatorMembershipCDHProof, DetachedAccumulatorMembershipProof,
+        DetachedAccumulatorNonMembershipProof, StatementProof,
     },
 };
 use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
@@ -9,10 +10,13 @@ use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{io::Write, rand::RngCore, vec, vec::Vec, UniformRand};
 use chacha20poly1305::XChaCha20Poly1305;
 use dock_crypto_utils::ecies;
-use vb_accumulator::prelude::{
-    MembershipProofProtocol, MembershipProvingKey, NonMembershipProofProtocol,
-    NonMembershipProvingKey, PreparedPublicKey, PreparedSetupParams, PublicKey,
-    SetupParams as AccumParams,
+use vb_accumulator::{
+    prelude::{
+        MembershipProofProtocol, MembershipProvingKey, NonMembershipProofProtocol,
+        NonMembershipProvingKey, PreparedPublicKey, PreparedSetupParams, PublicKey,
+        SetupParams as AccumParams,
+    },
+    proofs_cdh::MembershipProofProtocol as CDHMembershipProofProtocol,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -39,6 +43,17 @@ pub struct DetachedAccumulatorNonMembershipSubProtocol<'a, E: Pairing> {
     pub protocol: Option<NonMembershipProofProtocol<E>>,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DetachedAccumulatorMembershipCDHSubProtocol<'a, E: Pairing> {
+    pub id: usize,
+    pub params: &'a AccumParams<E>,
+    pub public_key: &'a PublicKey<E>,
+    pub original_accumulator_value: Option<E::G1Affine>,
+    pub randomized_accumulator_value: Option<E::G1Affine>,
+    pub randomizer: Option<E::ScalarField>,
+    pub protocol: Option<CDHMembershipProofProtocol<E>>,
+}
+
 #[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Opening<G: AffineRepr> {
     pub original_accumulator: G,
@@ -321,3 +336,124 @@ impl<'a, E: Pairing> DetachedAccumulatorNonMembershipSubProtocol<'a, E> {
         }
     }
 }
+
+impl<'a, E: Pairing> DetachedAccumulatorMembershipCDHSubProtocol<'a, E> {
+    pub fn new(id: usize, params: &'a AccumParams<E>, public_key: &'a PublicKey<E>) -> Self {
+        Self {
+            id,
+            params,
+            public_key,
+            original_accumulator_value: None,
+            randomizer: None,
+            randomized_accumulator_value: None,
+            protocol: None,
+        }
+    }
+
+    pub fn init<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        accumulator_value: E::G1Affine,
+        blinding: Option<E::ScalarField>,
+        witness: crate::witness::Membership<E>,
+    ) -> Result<(), ProofSystemError> {
+        if self.protocol.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        // Randomize the accumulator and witness with the same value
+        let randomizer = E::ScalarField::rand(rng);
+        let randomized_accumulator_value = (accumulator_value * randomizer).into_affine();
+        let randomized_accum_witness = witness.witness.randomize(&randomizer);
+        let protocol = CDHMembershipProofProtocol::init(
+            rng,
+            witness.element,
+            blinding,
+            randomized_accumulator_value,
+            &randomized_accum_witness,
+        );
+        self.protocol = Some(protocol);
+        self.original_accumulator_value = Some(accumulator_value);
+        self.randomizer = Some(randomizer);
+        self.randomized_accumulator_value = Some(randomized_accumulator_value);
+        Ok(())
+    }
+
+    pub fn challenge_contribution<W: Write>(&self, writer: W) -> Result<(), ProofSystemError> {
+        if self.protocol.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            ));
+        }
+        self.protocol
+            .as_ref()
+            .unwrap()
+            .challenge_contribution(self.randomized_accumulator_value.unwrap(), writer)?;
+        Ok(())
+    }
+
+    pub fn gen_proof_contribution<G: AffineRepr, R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        challenge: &E::ScalarField,
+    ) -> Result<StatementProof<E, G>, ProofSystemError> {
+        if self.protocol.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ));
+        }
+        let protocol = self.protocol.take().unwrap();
+        let accum_proof = protocol.gen_proof(challenge)?;
+        // Encrypt the original accumulator value and the randomizer
+        let opening = Opening {
+            original_accumulator: self.original_accumulator_value.unwrap(),
+            randomizer: self.randomizer.unwrap(),
+            extra: None,
+        };
+        let mut opening_bytes = vec![];
+        opening.serialize_compressed(&mut opening_bytes).unwrap();
+        let encrypted = ecies::Encryption::encrypt::<R, XChaCha20Poly1305>(
+            rng,
+            &opening_bytes,
+            &self.public_key.0,
+            &self.params.P_tilde,
+            None,
+            None,
+        );
+        Ok(StatementProof::DetachedAccumulatorMembershipCDH(
+            DetachedAccumulatorMembershipCDHProof {
+                accumulator: self.randomized_accumulator_value.unwrap(),
+                accum_proof,
+                challenge: *challenge,
+                encrypted,
+            },
+        ))
+    }
+
+    pub fn verify_proof_contribution(
+        &self,
+        proof: &DetachedAccumulatorMembershipCDHProof<E>,
+        sk: &vb_accumulator::setup::SecretKey<E::ScalarField>,
+        pk: impl Into<PreparedPublicKey<E>>,
+        params: impl Into<PreparedSetupParams<E>>,
+    ) -> Result<(), ProofSystemError> {
+        // Decrypt the opening
+        let decrypted = proof
+            .encrypted
+            .clone()
+            .decrypt::<XChaCha20Poly1305>(&sk.0, None, None);
+        let opening: Opening<E::G1Affine> =
+            CanonicalDeserialize::deserialize_compressed(decrypted.as_slice()).unwrap();
+        proof
+            .accum_proof
+            .verify(proof.accumulator, &proof.challenge, pk, params)
+            .map_err(|e| {
+                ProofSystemError::DetachedVBAccumProofContributionFailed(self.id as u32, e)
+            })?;
+        // Check that the randomized accumulator is consistent with the original accumulator
+        if (opening.original_accumulator * opening.randomizer).into_affine() != proof.accumulator {
+            Err(ProofSystemError::IncorrectEncryptedAccumulator)
+        } else {
+            Ok(())
+        }
+    }
+}
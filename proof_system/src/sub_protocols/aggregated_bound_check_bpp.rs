@@ -0,0 +1,288 @@
+use crate::{
+    error::ProofSystemError,
+    prelude::StatementProof,
+    statement_proof::{AggregatedBoundCheckBppProof, PedersenCommitmentProof},
+    sub_protocols::{enforce_and_get_u64, schnorr::SchnorrProtocol},
+};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_ff::Zero;
+use ark_serialize::CanonicalSerialize;
+use ark_std::{collections::BTreeMap, io::Write, rand::RngCore, vec, vec::Vec, UniformRand};
+use bulletproofs_plus_plus::{
+    prelude::{ProofArbitraryRange, Prover},
+    setup::SetupParams,
+};
+use dock_crypto_utils::transcript::Transcript;
+
+/// Number of bits in the values proven to be in range
+const NUM_BITS: u16 = 64;
+
+/// Runs a single Bulletproofs++ protocol for proving bounds of several witnesses and, for each witness, 2
+/// Schnorr protocols for proving knowledge of the witness committed in the commitments accompanying the proof.
+/// As Bulletproofs++ needs the number of aggregated proofs to be a power of 2, values 0 with bounds `(0, 1)` and
+/// randomness 0 are added as padding so the commitments for the padding are the identity element and the setup
+/// params should support `num_values.next_power_of_two()` arbitrary range proofs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AggregatedBoundCheckBppProtocol<'a, G: AffineRepr> {
+    pub id: usize,
+    pub bounds: &'a [(u64, u64)],
+    pub setup_params: &'a SetupParams<G>,
+    pub commitments: Option<Vec<G>>,
+    pub bpp_randomness: Option<Vec<G::ScalarField>>,
+    pub values: Option<Vec<u64>>,
+    pub sp1: Option<Vec<SchnorrProtocol<'a, G>>>,
+    pub sp2: Option<Vec<SchnorrProtocol<'a, G>>>,
+}
+
+impl<'a, G: AffineRepr> AggregatedBoundCheckBppProtocol<'a, G> {
+    pub fn new(id: usize, bounds: &'a [(u64, u64)], setup_params: &'a SetupParams<G>) -> Self {
+        Self {
+            id,
+            bounds,
+            setup_params,
+            commitments: None,
+            bpp_randomness: None,
+            values: None,
+            sp1: None,
+            sp2: None,
+        }
+    }
+
+    /// `comm_key` is the Pedersen commitment key from the Bulletproofs++ setup params. `blindings` has the blinding
+    /// to use for a message when it's being proven equal to another witness, keyed by the message's index.
+    pub fn init<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        comm_key: &'a [G],
+        messages: Vec<G::ScalarField>,
+        mut blindings: BTreeMap<usize, G::ScalarField>,
+    ) -> Result<(), ProofSystemError> {
+        if self.sp1.is_some() || self.sp2.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        if messages.len() != self.bounds.len() {
+            return Err(
+                ProofSystemError::UnequalWitnessCountForAggregatedBoundCheck(
+                    self.id,
+                    self.bounds.len(),
+                    messages.len(),
+                ),
+            );
+        }
+        let mut values_and_bounds = Vec::with_capacity(Self::num_proofs(self.bounds.len()));
+        for (m, (min, max)) in messages.iter().zip(self.bounds.iter()) {
+            values_and_bounds.push((enforce_and_get_u64::<G::ScalarField>(m)?, *min, *max));
+        }
+        values_and_bounds.resize(Self::num_proofs(self.bounds.len()), (0, 0, 1));
+
+        // 2 blindings for each message's commitments in the Bulletproofs++ proof, for `(message - min)` and
+        // `(max - 1 - message)`. The padding uses randomness 0.
+        let mut bpp_randomness = (0..2 * self.bounds.len())
+            .map(|_| G::ScalarField::rand(rng))
+            .collect::<Vec<_>>();
+        bpp_randomness.resize(2 * values_and_bounds.len(), G::ScalarField::zero());
+        let (commitments, values) = ProofArbitraryRange::compute_commitments_and_values(
+            values_and_bounds,
+            &bpp_randomness,
+            self.setup_params,
+        )?;
+
+        let comms =
+            ProofArbitraryRange::get_commitments_to_values_given_transformed_commitments_and_g(
+                &commitments,
+                Self::padded_bounds(self.bounds),
+                &self.setup_params.G,
+            )?;
+        let mut sp1 = Vec::with_capacity(self.bounds.len());
+        let mut sp2 = Vec::with_capacity(self.bounds.len());
+        for (i, (message, (comm_1, comm_2))) in messages.into_iter().zip(comms).enumerate() {
+            // Same blinding is used for the message in both Schnorr protocols and when the message is proven
+            // equal to another witness, the caller ensures that the blinding is the same as the one used there.
+            let blinding = blindings
+                .remove(&i)
+                .unwrap_or_else(|| G::ScalarField::rand(rng));
+            let mut b = BTreeMap::new();
+            b.insert(0, blinding);
+            // NOTE: value of id is dummy
+            let mut sp_1 = SchnorrProtocol::new(10000, comm_key, comm_1);
+            let mut sp_2 = SchnorrProtocol::new(10000, comm_key, comm_2);
+            sp_1.init(rng, b.clone(), vec![message, bpp_randomness[2 * i]])?;
+            sp_2.init(rng, b, vec![message, -bpp_randomness[2 * i + 1]])?;
+            sp1.push(sp_1);
+            sp2.push(sp_2);
+        }
+        self.values = Some(values);
+        self.commitments = Some(commitments);
+        self.bpp_randomness = Some(bpp_randomness);
+        self.sp1 = Some(sp1);
+        self.sp2 = Some(sp2);
+        Ok(())
+    }
+
+    /// Generate challenge contribution for all the Schnorr protocols
+    pub fn challenge_contribution<W: Write>(&self, mut writer: W) -> Result<(), ProofSystemError> {
+        if self.sp1.is_none() || self.sp2.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            ));
+        }
+        for (sp1, sp2) in self
+            .sp1
+            .as_ref()
+            .unwrap()
+            .iter()
+            .zip(self.sp2.as_ref().unwrap().iter())
+        {
+            sp1.challenge_contribution(&mut writer)?;
+            sp2.challenge_contribution(&mut writer)?;
+        }
+        Ok(())
+    }
+
+    /// Generate the Bulletproofs++ proof and responses for all the Schnorr protocols
+    pub fn gen_proof_contribution<E: Pairing, R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        challenge: &G::ScalarField,
+        transcript: &mut impl Transcript,
+    ) -> Result<StatementProof<E, G>, ProofSystemError> {
+        if self.sp1.is_none() || self.sp2.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ));
+        }
+        let commitments = self.commitments.take().unwrap();
+        let prover = Prover::new(
+            NUM_BITS,
+            commitments.clone(),
+            self.values.take().unwrap(),
+            self.bpp_randomness.take().unwrap(),
+        )?;
+        let proof = prover.prove(rng, self.setup_params.clone(), transcript)?;
+        let sp1 = self
+            .sp1
+            .take()
+            .unwrap()
+            .iter_mut()
+            .map(|sp| sp.gen_proof_contribution_as_struct(challenge))
+            .collect::<Result<Vec<PedersenCommitmentProof<G>>, _>>()?;
+        let sp2 = self
+            .sp2
+            .take()
+            .unwrap()
+            .iter_mut()
+            .map(|sp| sp.gen_proof_contribution_as_struct(challenge))
+            .collect::<Result<Vec<PedersenCommitmentProof<G>>, _>>()?;
+        Ok(StatementProof::AggregatedBoundCheckBpp(
+            AggregatedBoundCheckBppProof {
+                bpp_proof: ProofArbitraryRange {
+                    proof,
+                    V: commitments,
+                },
+                sp1,
+                sp2,
+            },
+        ))
+    }
+
+    pub fn verify_proof_contribution(
+        &self,
+        challenge: &G::ScalarField,
+        proof: &AggregatedBoundCheckBppProof<G>,
+        comm_key: &[G],
+        transcript: &mut impl Transcript,
+    ) -> Result<(), ProofSystemError> {
+        Self::validate_proof(self.id, self.bounds.len(), proof)?;
+        proof
+            .bpp_proof
+            .verify(NUM_BITS, self.setup_params, transcript)
+            .map_err(|e| {
+                ProofSystemError::BulletproofsPlusPlusProofContributionFailed(self.id as u32, e)
+            })?;
+        if !proof.check_schnorr_responses_consistency()? {
+            return Err(ProofSystemError::DifferentResponsesForSchnorrProtocolInBpp(
+                self.id,
+            ));
+        }
+        let comms = proof
+            .bpp_proof
+            .get_commitments_to_values(Self::padded_bounds(self.bounds), self.setup_params)?;
+        for ((comm_1, comm_2), (sp1_proof, sp2_proof)) in comms
+            .into_iter()
+            .zip(proof.sp1.iter().zip(proof.sp2.iter()))
+        {
+            // NOTE: value of id is dummy
+            let sp1 = SchnorrProtocol::new(10000, comm_key, comm_1);
+            let sp2 = SchnorrProtocol::new(10000, comm_key, comm_2);
+            sp1.verify_proof_contribution(challenge, sp1_proof)
+                .map_err(|e| ProofSystemError::SchnorrProofContributionFailed(self.id as u32, e))?;
+            sp2.verify_proof_contribution(challenge, sp2_proof)
+                .map_err(|e| ProofSystemError::SchnorrProofContributionFailed(self.id as u32, e))?;
+        }
+        Ok(())
+    }
+
+    pub fn compute_challenge_contribution<W: Write>(
+        id: usize,
+        bounds: &[(u64, u64)],
+        comm_key: &[G],
+        proof: &AggregatedBoundCheckBppProof<G>,
+        mut writer: W,
+    ) -> Result<(), ProofSystemError> {
+        Self::validate_proof(id, bounds.len(), proof)?;
+        let comms = proof
+            .bpp_proof
+            .get_commitments_to_values_given_g(Self::padded_bounds(bounds), &comm_key[0])?;
+        for ((comm_1, comm_2), (sp1, sp2)) in comms
+            .into_iter()
+            .zip(proof.sp1.iter().zip(proof.sp2.iter()))
+        {
+            comm_key.serialize_compressed(&mut writer)?;
+            comm_1.serialize_compressed(&mut writer)?;
+            sp1.t.serialize_compressed(&mut writer)?;
+            comm_key.serialize_compressed(&mut writer)?;
+            comm_2.serialize_compressed(&mut writer)?;
+            sp2.t.serialize_compressed(&mut writer)?;
+        }
+        Ok(())
+    }
+
+    /// Bounds of the messages followed by the bounds of the padding
+    fn padded_bounds(bounds: &[(u64, u64)]) -> Vec<(u64, u64)> {
+        let mut padded = bounds.to_vec();
+        padded.resize(Self::num_proofs(bounds.len()), (0, 1));
+        padded
+    }
+
+    /// Number of aggregated arbitrary range proofs including the padding
+    fn num_proofs(num_values: usize) -> usize {
+        num_values.next_power_of_two()
+    }
+
+    fn validate_proof(
+        id: usize,
+        num_values: usize,
+        proof: &AggregatedBoundCheckBppProof<G>,
+    ) -> Result<(), ProofSystemError> {
+        let num_proofs = proof.bpp_proof.num_proofs() as usize;
+        if num_proofs != Self::num_proofs(num_values) {
+            return Err(
+                ProofSystemError::UnequalWitnessCountForAggregatedBoundCheck(
+                    id,
+                    Self::num_proofs(num_values),
+                    num_proofs,
+                ),
+            );
+        }
+        for found in [proof.sp1.len(), proof.sp2.len()] {
+            if found != num_values {
+                return Err(
+                    ProofSystemError::UnequalWitnessCountForAggregatedBoundCheck(
+                        id, num_values, found,
+                    ),
+                );
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_std::{collections::BTreeMap, io::Write, rand::RngCore};
+use blake2::Blake2b512;
+use dock_crypto_utils::{
+    hashing_utils::affine_group_elem_from_try_and_incr,
+    iter::take_while_satisfy,
+    misc::seq_inc_by_n_from,
+    signature::{MessageOrBlinding, MultiMessageSignatureParams},
+    try_iter::CheckLeft,
+};
+use itertools::Itertools;
+use kvac::bddt_2016::{
+    proof::{PoKOfMAC, PoKOfMACProtocol},
+    setup::{MACParams, SecretKey},
+};
+
+use crate::{
+    constants::BDDT16_MAC_EXTRA_BASE_LABEL, error::ProofSystemError,
+    statement_proof::StatementProof,
+};
+
+use super::merge_indexed_messages_with_blindings;
+
+/// Proof of knowledge of a BDDT16 MAC. The extra base `f` of the protocol is created by hashing a label so that it's
+/// independent of the MAC params' bases, including `g_0` which is the public key's base.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoKOfBDDT16MACSubProtocol<'a, E: Pairing> {
+    pub id: usize,
+    pub revealed_messages: &'a BTreeMap<usize, E::ScalarField>,
+    pub mac_params: &'a MACParams<E::G1Affine>,
+    /// The extra base `f` of the protocol
+    pub f: E::G1Affine,
+    pub protocol: Option<PoKOfMACProtocol<E::G1Affine>>,
+}
+
+impl<'a, E: Pairing> PoKOfBDDT16MACSubProtocol<'a, E> {
+    pub fn new(
+        id: usize,
+        revealed_messages: &'a BTreeMap<usize, E::ScalarField>,
+        mac_params: &'a MACParams<E::G1Affine>,
+    ) -> Self {
+        Self {
+            id,
+            revealed_messages,
+            mac_params,
+            f: Self::extra_base(),
+            protocol: None,
+        }
+    }
+
+    /// The extra base `f` of the protocol, created by hashing `BDDT16_MAC_EXTRA_BASE_LABEL`
+    pub fn extra_base() -> E::G1Affine {
+        affine_group_elem_from_try_and_incr::<E::G1Affine, Blake2b512>(BDDT16_MAC_EXTRA_BASE_LABEL)
+    }
+
+    pub fn init<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        blindings: BTreeMap<usize, E::ScalarField>,
+        witness: &crate::witness::PoKOfBDDT16MAC<E>,
+    ) -> Result<(), ProofSystemError> {
+        if self.protocol.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        let total_message_count = self.revealed_messages.len() + witness.unrevealed_messages.len();
+        if total_message_count != self.mac_params.supported_message_count() {
+            Err(ProofSystemError::BDDT16ProtocolInvalidMessageCount(
+                total_message_count,
+                self.mac_params.supported_message_count(),
+            ))?
+        }
+
+        // Create messages from revealed messages in statement and unrevealed in witness
+        let mut invalid_blinding_idx = None;
+        let messages_to_commit = merge_indexed_messages_with_blindings(
+            &witness.unrevealed_messages,
+            blindings,
+            MessageOrBlinding::BlindMessageRandomly,
+            MessageOrBlinding::blind_message_with,
+            &mut invalid_blinding_idx,
+        );
+        let mut non_seq_idx = None;
+        let all_messages = take_while_satisfy(
+            messages_to_commit.merge_by(
+                self.revealed_messages
+                    .iter()
+                    .map(|(idx, msg)| (*idx, MessageOrBlinding::RevealMessage(msg))),
+                |(a, _), (b, _)| a < b,
+            ),
+            CheckLeft(seq_inc_by_n_from(1, 0)),
+            &mut non_seq_idx,
+        )
+        .map(|(_, msg)| msg);
+
+        let protocol =
+            PoKOfMACProtocol::init(rng, &witness.mac, self.mac_params, all_messages, self.f);
+        if let Some(idx) = invalid_blinding_idx {
+            Err(ProofSystemError::BDDT16ProtocolInvalidBlindingIndex(idx))?
+        } else if let Some(invalid) = non_seq_idx {
+            Err(invalid.over(
+                ProofSystemError::BDDT16ProtocolMessageIndicesMustStartFromZero,
+                ProofSystemError::BDDT16ProtocolNonSequentialMessageIndices,
+            ))?
+        }
+
+        self.protocol = Some(protocol?);
+        Ok(())
+    }
+
+    pub fn challenge_contribution<W: Write>(&self, writer: W) -> Result<(), ProofSystemError> {
+        self.protocol
+            .as_ref()
+            .ok_or(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            ))?
+            .challenge_contribution(self.revealed_messages, self.mac_params, &self.f, writer)?;
+        Ok(())
+    }
+
+    pub fn gen_proof_contribution<G: AffineRepr>(
+        &mut self,
+        challenge: &E::ScalarField,
+    ) -> Result<StatementProof<E, G>, ProofSystemError> {
+        let proof = self
+            .protocol
+            .take()
+            .ok_or(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ))?
+            .gen_proof(challenge)?;
+        Ok(StatementProof::PoKOfBDDT16MAC(proof))
+    }
+
+    /// Verify the proof using the secret key of the MAC issuer
+    pub fn verify_proof_contribution(
+        &self,
+        challenge: &E::ScalarField,
+        proof: &PoKOfMAC<E::G1Affine>,
+        secret_key: &SecretKey<E::ScalarField>,
+    ) -> Result<(), ProofSystemError> {
+        proof
+            .verify(
+                self.revealed_messages,
+                challenge,
+                secret_key,
+                self.mac_params,
+                self.f,
+            )
+            .map_err(|e| ProofSystemError::BDDT16ProofContributionFailed(self.id as u32, e))
+    }
+}
@@ -0,0 +1,445 @@
+//! Proving that a hidden element is present in a public Bloom filter using LegoGroth16.
+//!
+//! The filter has `m` bits, where `m` is a power of 2, and uses `k` hash functions. The `i`-th hash of element `x`
+//! is given by the `log2(m)` least significant bits of `(x + i + 1)^5` and the element is in the filter if the bits
+//! at all its `k` hashes are set. The circuit computes these hashes for the committed element and checks that the
+//! filter's bits at those indices are 1. The public inputs of the circuit are the offsets `i + 1` of the `k` hashes
+//! followed by the filter's bits so the SNARK parameters only depend on `m` and `k` and not on the filter's contents.
+//! Having an input for each hash makes the verification key's size depend on `k` so that a key generated for a
+//! different number of hashes is rejected.
+//!
+//! A Bloom filter has false positives, i.e. an element which was never added to the filter can still have all its
+//! bits set, in which case the proof will succeed. The false positive rate depends on `m`, `k` and the number of
+//! elements added and should be chosen according to the application. Thus, this should only be used when admitting
+//! a small fraction of elements not in the allowlist is acceptable, else use an accumulator.
+
+use crate::{
+    error::ProofSystemError,
+    statement_proof::{BoundCheckLegoGroth16Proof, StatementProof},
+    sub_protocols::schnorr::SchnorrProtocol,
+};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_ff::{BigInteger, PrimeField};
+use ark_r1cs_std::{
+    fields::fp::FpVar,
+    prelude::{AllocVar, AllocationMode, CondSelectGadget, EqGadget, FieldVar, ToBitsGadget},
+};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::BTreeMap,
+    io::Write,
+    rand::{Rng, RngCore},
+    vec,
+    vec::Vec,
+    UniformRand,
+};
+use dock_crypto_utils::randomized_pairing_check::RandomizedPairingChecker;
+use legogroth16::{
+    calculate_d, create_random_proof, generate_random_parameters, verify_proof,
+    PreparedVerifyingKey, Proof, ProvingKey, VerifyingKey,
+};
+use serde::{Deserialize, Serialize};
+
+/// A Bloom filter with `bits.len()` bits and `num_hashes` hash functions. The hash functions work over field elements
+/// and are chosen such that they can be efficiently computed in a circuit.
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+pub struct BloomFilter {
+    pub bits: Vec<bool>,
+    pub num_hashes: u32,
+}
+
+impl BloomFilter {
+    /// Create an empty filter with `num_bits` bits and `num_hashes` hash functions. `num_bits` must be a power of 2
+    /// and at least 2
+    pub fn new(num_bits: usize, num_hashes: u32) -> Result<Self, ProofSystemError> {
+        Self::validate_params(num_bits, num_hashes)?;
+        Ok(Self {
+            bits: vec![false; num_bits],
+            num_hashes,
+        })
+    }
+
+    pub fn validate_params(num_bits: usize, num_hashes: u32) -> Result<(), ProofSystemError> {
+        if num_bits < 2 || !num_bits.is_power_of_two() || num_hashes == 0 {
+            return Err(ProofSystemError::InvalidBloomFilterParams(
+                num_bits, num_hashes,
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn num_bits(&self) -> usize {
+        self.bits.len()
+    }
+
+    pub fn insert<F: PrimeField>(&mut self, element: &F) {
+        for i in self.indices(element) {
+            self.bits[i] = true;
+        }
+    }
+
+    /// Returns true if the element might be in the filter and false if its definitely not
+    pub fn contains<F: PrimeField>(&self, element: &F) -> bool {
+        self.indices(element).into_iter().all(|i| self.bits[i])
+    }
+
+    /// Indices of the filter's bits for the given element, one for each hash function.
+    pub fn indices<F: PrimeField>(&self, element: &F) -> Vec<usize> {
+        let index_bit_size = self.num_bits().trailing_zeros() as usize;
+        (0..self.num_hashes)
+            .map(|i| {
+                let bits = Self::hash(element, i).into_bigint().to_bits_le();
+                bits[..index_bit_size]
+                    .iter()
+                    .rev()
+                    .fold(0, |acc, b| (acc << 1) | (*b as usize))
+            })
+            .collect()
+    }
+
+    /// The filter's bits as field elements
+    pub fn bits_as_field_elements<F: PrimeField>(&self) -> Vec<F> {
+        self.bits.iter().map(|b| F::from(*b)).collect()
+    }
+
+    /// The public inputs to the SNARK, i.e. the offset of each hash function followed by the filter's bits
+    pub fn public_inputs<F: PrimeField>(&self) -> Vec<F> {
+        (0..self.num_hashes)
+            .map(Self::hash_offset)
+            .chain(self.bits.iter().map(|b| F::from(*b)))
+            .collect()
+    }
+
+    /// `i`-th hash function, `(x + i + 1)^5`
+    fn hash<F: PrimeField>(element: &F, i: u32) -> F {
+        (*element + Self::hash_offset::<F>(i)).pow([5])
+    }
+
+    fn hash_offset<F: PrimeField>(i: u32) -> F {
+        F::from(i as u64 + 1)
+    }
+}
+
+/// Runs the LegoGroth16 protocol for proving membership of a witness in a Bloom filter and a Schnorr protocol for
+/// proving knowledge of the witness committed in the LegoGroth16 proof.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BloomMembershipLegoGrothProtocol<'a, E: Pairing> {
+    pub id: usize,
+    pub filter: &'a BloomFilter,
+    /// The SNARK proving key, will be `None` if invoked by verifier.
+    pub proving_key: Option<&'a ProvingKey<E>>,
+    /// The SNARK verifying key, will be `None` if invoked by prover.
+    pub verifying_key: Option<&'a VerifyingKey<E>>,
+    pub snark_proof: Option<Proof<E>>,
+    pub sp: Option<SchnorrProtocol<'a, E::G1Affine>>,
+}
+
+impl<'a, E: Pairing> BloomMembershipLegoGrothProtocol<'a, E> {
+    /// Create an instance of this protocol for the prover.
+    pub fn new_for_prover(
+        id: usize,
+        filter: &'a BloomFilter,
+        proving_key: &'a ProvingKey<E>,
+    ) -> Self {
+        Self {
+            id,
+            filter,
+            proving_key: Some(proving_key),
+            verifying_key: None,
+            snark_proof: None,
+            sp: None,
+        }
+    }
+
+    /// Create an instance of this protocol for the verifier.
+    pub fn new_for_verifier(
+        id: usize,
+        filter: &'a BloomFilter,
+        verifying_key: &'a VerifyingKey<E>,
+    ) -> Self {
+        Self {
+            id,
+            filter,
+            proving_key: None,
+            verifying_key: Some(verifying_key),
+            snark_proof: None,
+            sp: None,
+        }
+    }
+
+    /// Runs the LegoGroth16 protocol to prove that the message is in the filter and initialize a Schnorr proof of
+    /// knowledge protocol to prove knowledge of the committed message
+    pub fn init<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        comm_key: &'a [E::G1Affine],
+        message: E::ScalarField,
+        blinding: Option<E::ScalarField>,
+    ) -> Result<(), ProofSystemError> {
+        if self.sp.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        let proving_key = self
+            .proving_key
+            .ok_or(ProofSystemError::LegoGroth16ProvingKeyNotProvided)?;
+        if !self.filter.contains(&message) {
+            return Err(ProofSystemError::ElementNotInBloomFilter(self.id));
+        }
+
+        // blinding for the commitment in the snark proof
+        let v = E::ScalarField::rand(rng);
+
+        let circuit = BloomMembershipCircuit {
+            num_bits: self.filter.num_bits(),
+            num_hashes: self.filter.num_hashes,
+            bits: Some(self.filter.bits_as_field_elements()),
+            value: Some(message),
+        };
+        let snark_proof = create_random_proof(circuit, v, proving_key, rng)?;
+
+        // blinding used to prove knowledge of message in `snark_proof.d`. The caller of this method ensures
+        // that this will be same as the one used proving knowledge of the corresponding message in the
+        // signature, thus allowing them to be proved equal.
+        let blinding = blinding.unwrap_or_else(|| E::ScalarField::rand(rng));
+        // NOTE: value of id is dummy
+        let mut sp = SchnorrProtocol::new(10000, comm_key, snark_proof.d);
+        let mut blindings = BTreeMap::new();
+        blindings.insert(0, blinding);
+        sp.init(rng, blindings, vec![message, v])?;
+        self.snark_proof = Some(snark_proof);
+        self.sp = Some(sp);
+        Ok(())
+    }
+
+    /// Generate challenge contribution for the Schnorr protocol
+    pub fn challenge_contribution<W: Write>(&self, mut writer: W) -> Result<(), ProofSystemError> {
+        if self.sp.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            ));
+        }
+        self.sp
+            .as_ref()
+            .unwrap()
+            .challenge_contribution(&mut writer)?;
+        Ok(())
+    }
+
+    /// Generate responses for the Schnorr protocol
+    pub fn gen_proof_contribution<G: AffineRepr>(
+        &mut self,
+        challenge: &E::ScalarField,
+    ) -> Result<StatementProof<E, G>, ProofSystemError> {
+        if self.sp.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ));
+        }
+        Ok(StatementProof::BloomMembershipLegoGroth16(
+            BoundCheckLegoGroth16Proof {
+                snark_proof: self.snark_proof.take().unwrap(),
+                sp: self
+                    .sp
+                    .take()
+                    .unwrap()
+                    .gen_proof_contribution_as_struct(challenge)?,
+            },
+        ))
+    }
+
+    /// Verify that the snark proof and the Schnorr proof are valid.
+    pub fn verify_proof_contribution(
+        &self,
+        challenge: &E::ScalarField,
+        proof: &BoundCheckLegoGroth16Proof<E>,
+        comm_key: &[E::G1Affine],
+        pvk: &PreparedVerifyingKey<E>,
+        pairing_checker: &mut Option<RandomizedPairingChecker<E>>,
+    ) -> Result<(), ProofSystemError> {
+        let pub_inp = self.filter.public_inputs::<E::ScalarField>();
+        let snark_proof = &proof.snark_proof;
+        match pairing_checker {
+            Some(c) => {
+                let d = calculate_d(pvk, snark_proof, &pub_inp)?;
+                c.add_multiple_sources_and_target(
+                    &[snark_proof.a, snark_proof.c, d],
+                    [
+                        snark_proof.b.into(),
+                        pvk.delta_g2_neg_pc.clone(),
+                        pvk.gamma_g2_neg_pc.clone(),
+                    ],
+                    &pvk.alpha_g1_beta_g2,
+                );
+            }
+            None => verify_proof(pvk, snark_proof, &pub_inp).map_err(|e| {
+                ProofSystemError::LegoSnarkProofContributionFailed(self.id as u32, e)
+            })?,
+        }
+
+        // NOTE: value of id is dummy
+        let sp = SchnorrProtocol::new(10000, comm_key, proof.snark_proof.d);
+
+        sp.verify_proof_contribution(challenge, &proof.sp)
+            .map_err(|e| ProofSystemError::SchnorrProofContributionFailed(self.id as u32, e))
+    }
+
+    pub fn compute_challenge_contribution<W: Write>(
+        comm_key: &[E::G1Affine],
+        proof: &BoundCheckLegoGroth16Proof<E>,
+        mut writer: W,
+    ) -> Result<(), ProofSystemError> {
+        comm_key.serialize_compressed(&mut writer)?;
+        proof.snark_proof.d.serialize_compressed(&mut writer)?;
+        proof.sp.t.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+
+    /// Check that the verification key is for a circuit with as many public inputs as the filter's hashes and bits
+    /// and a single committed witness
+    pub fn validate_verification_key(
+        vk: &VerifyingKey<E>,
+        filter: &BloomFilter,
+    ) -> Result<(), ProofSystemError> {
+        if vk.gamma_abc_g1.len() != filter.num_hashes as usize + filter.num_bits() + 2 {
+            return Err(ProofSystemError::LegoGroth16Error(
+                legogroth16::error::Error::SynthesisError(SynthesisError::MalformedVerifyingKey),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Enforce that the bits of the filter at indices given by the hashes of value are set
+#[derive(Clone)]
+pub struct BloomMembershipCircuit<F: PrimeField> {
+    num_bits: usize,
+    num_hashes: u32,
+    bits: Option<Vec<F>>,
+    value: Option<F>,
+}
+
+impl<ConstraintF: PrimeField> ConstraintSynthesizer<ConstraintF>
+    for BloomMembershipCircuit<ConstraintF>
+{
+    fn generate_constraints(
+        self,
+        cs: ConstraintSystemRef<ConstraintF>,
+    ) -> Result<(), SynthesisError> {
+        // The value must be the first witness as its the committed witness
+        let val = FpVar::new_variable(
+            cs.clone(),
+            || self.value.ok_or(SynthesisError::AssignmentMissing),
+            AllocationMode::Witness,
+        )?;
+
+        // Offsets of the hashes are public inputs, given by the verifier, so that the verification key depends on
+        // the number of hashes
+        let mut offsets = Vec::with_capacity(self.num_hashes as usize);
+        for i in 0..self.num_hashes {
+            offsets.push(FpVar::new_variable(
+                cs.clone(),
+                || Ok(BloomFilter::hash_offset::<ConstraintF>(i)),
+                AllocationMode::Input,
+            )?);
+        }
+
+        let mut bits = Vec::with_capacity(self.num_bits);
+        for i in 0..self.num_bits {
+            bits.push(FpVar::new_variable(
+                cs.clone(),
+                || {
+                    self.bits
+                        .as_ref()
+                        .map(|b| b[i])
+                        .ok_or(SynthesisError::AssignmentMissing)
+                },
+                AllocationMode::Input,
+            )?);
+        }
+
+        let index_bit_size = self.num_bits.trailing_zeros() as usize;
+        for offset in &offsets {
+            // hash = (val + i + 1)^5
+            let t = &val + offset;
+            let t_sqr = t.square()?;
+            let hash = t_sqr.square()? * &t;
+            // Index is formed by the least significant bits of the hash. The selector expects big-endian index
+            let mut index = hash.to_bits_le()?;
+            index.truncate(index_bit_size);
+            index.reverse();
+            let bit = FpVar::conditionally_select_power_of_two_vector(&index, &bits)?;
+            bit.enforce_equal(&FpVar::one())?;
+        }
+        Ok(())
+    }
+}
+
+/// Generate SNARK proving key and verification key for a circuit that checks that given a witness `w` and public
+/// inputs as the hash offsets and bits of a Bloom filter with `num_bits` bits and `num_hashes` hash functions, `w` is
+/// in the filter
+pub fn generate_snark_srs_bloom_membership<E, R>(
+    rng: &mut R,
+    num_bits: usize,
+    num_hashes: u32,
+) -> Result<ProvingKey<E>, ProofSystemError>
+where
+    E: Pairing,
+    R: Rng,
+{
+    BloomFilter::validate_params(num_bits, num_hashes)?;
+    let circuit = BloomMembershipCircuit::<E::ScalarField> {
+        num_bits,
+        num_hashes,
+        bits: None,
+        value: None,
+    };
+    generate_random_parameters::<E, _, R>(circuit, 1, rng).map_err(|e| e.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::Fr;
+    use ark_relations::r1cs::ConstraintSystem;
+    use ark_std::rand::{prelude::StdRng, SeedableRng};
+
+    #[test]
+    fn bloom_membership_circuit() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        assert!(BloomFilter::new(100, 3).is_err());
+        assert!(BloomFilter::new(128, 0).is_err());
+
+        let mut filter = BloomFilter::new(128, 3).unwrap();
+        let members = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        for m in &members {
+            filter.insert(m);
+        }
+        for m in &members {
+            assert!(filter.contains(m));
+        }
+
+        let check = |value: Fr| {
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let circuit = BloomMembershipCircuit {
+                num_bits: filter.num_bits(),
+                num_hashes: filter.num_hashes,
+                bits: Some(filter.bits_as_field_elements()),
+                value: Some(value),
+            };
+            circuit.generate_constraints(cs.clone()).unwrap();
+            cs.is_satisfied().unwrap()
+        };
+
+        for m in &members {
+            assert!(check(*m));
+        }
+        // Circuit agrees with the filter for non-members
+        for _ in 0..10 {
+            let v = Fr::rand(&mut rng);
+            assert_eq!(check(v), filter.contains(&v));
+        }
+    }
+}
@@ -6,13 +6,62 @@ use crate::{
 };
 use ark_ec::{pairing::Pairing, AffineRepr};
 use ark_serialize::CanonicalSerialize;
-use ark_std::{collections::BTreeMap, io::Write, rand::RngCore, vec, vec::Vec, UniformRand};
+use ark_std::{
+    collections::BTreeMap, io::Write, marker::PhantomData, rand::RngCore, vec, vec::Vec,
+    UniformRand,
+};
 use bulletproofs_plus_plus::{
     prelude::{ProofArbitraryRange, Prover},
     setup::SetupParams,
 };
+use digest::Digest;
 use dock_crypto_utils::transcript::Transcript;
 
+/// Cache of Bulletproofs++ setup params for proving bounds of a single value, keyed by the number of bits
+/// of the value. Creating the params requires hashing to the curve with `D` for each generator so when many bound
+/// check statements of the same bit width are created, the params should be created once and shared.
+/// All params in the cache use the same label, base and hash function.
+#[derive(Debug)]
+pub struct BppSetupParamsCache<G: AffineRepr, D: Digest> {
+    pub label: Vec<u8>,
+    pub base: u16,
+    params: BTreeMap<u16, SetupParams<G>>,
+    phantom: PhantomData<D>,
+}
+
+impl<G: AffineRepr, D: Digest> BppSetupParamsCache<G, D> {
+    pub fn new(label: &[u8], base: u16) -> Self {
+        Self {
+            label: label.to_vec(),
+            base,
+            params: BTreeMap::new(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Get the setup params for values of `num_value_bits` bits, creating them if not already cached
+    pub fn get(&mut self, num_value_bits: u16) -> &SetupParams<G> {
+        let (label, base) = (&self.label, self.base);
+        self.params.entry(num_value_bits).or_insert_with(|| {
+            SetupParams::new_for_arbitrary_range_proof::<D>(label, base, num_value_bits, 1)
+        })
+    }
+
+    /// Returns true if params for values of `num_value_bits` bits are cached
+    pub fn contains(&self, num_value_bits: u16) -> bool {
+        self.params.contains_key(&num_value_bits)
+    }
+
+    /// Number of cached params
+    pub fn len(&self) -> usize {
+        self.params.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty()
+    }
+}
+
 /// Runs the Bulletproofs++ protocol for proving bounds of a witness and a Schnorr protocol for proving
 /// knowledge of the witness committed in the commitments accompanying the proof.
 #[derive(Clone, Debug, PartialEq)]
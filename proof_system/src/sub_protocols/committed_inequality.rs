@@ -0,0 +1,195 @@
+use crate::{
+    error::ProofSystemError,
+    statement_proof::{CommittedInequalityProof, StatementProof},
+    sub_protocols::schnorr::SchnorrProtocol,
+};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::CanonicalSerialize;
+use ark_std::{collections::BTreeMap, io::Write, rand::RngCore, vec, UniformRand};
+use dock_crypto_utils::commitment::PedersenCommitmentKey;
+use schnorr_pok::inequality::DiscreteLogInequalityProtocol;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CommittedInequalityProtocol<'a, G: AffineRepr> {
+    pub id: usize,
+    pub comm_key: &'a PedersenCommitmentKey<G>,
+    /// Commitments to both messages
+    pub comms: Option<[G; 2]>,
+    pub inequality_protocol: Option<DiscreteLogInequalityProtocol<G>>,
+    /// Proofs of knowledge of the opening of each commitment
+    pub sp: Option<[SchnorrProtocol<'a, G>; 2]>,
+}
+
+impl<'a, G: AffineRepr> CommittedInequalityProtocol<'a, G> {
+    pub fn new(id: usize, comm_key: &'a PedersenCommitmentKey<G>) -> Self {
+        Self {
+            id,
+            comm_key,
+            comms: None,
+            inequality_protocol: None,
+            sp: None,
+        }
+    }
+
+    pub fn init<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        comm_key_as_slice: &'a [G],
+        messages: (G::ScalarField, G::ScalarField),
+        blindings: [Option<G::ScalarField>; 2],
+    ) -> Result<(), ProofSystemError> {
+        if self.sp.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        let (message1, message2) = messages;
+        let randomness1 = G::ScalarField::rand(rng);
+        let randomness2 = G::ScalarField::rand(rng);
+        let comm1 = self.comm_key.commit(&message1, &randomness1);
+        let comm2 = self.comm_key.commit(&message2, &randomness2);
+        self.inequality_protocol = Some(
+            DiscreteLogInequalityProtocol::new_for_inequality_with_committed_value(
+                rng,
+                message1,
+                randomness1,
+                &comm1,
+                message2,
+                randomness2,
+                &comm2,
+                self.comm_key,
+            )?,
+        );
+        let [blinding1, blinding2] = blindings;
+        let sp1 = Self::init_schnorr_protocol(
+            rng,
+            comm_key_as_slice,
+            comm1,
+            message1,
+            blinding1,
+            randomness1,
+        )?;
+        let sp2 = Self::init_schnorr_protocol(
+            rng,
+            comm_key_as_slice,
+            comm2,
+            message2,
+            blinding2,
+            randomness2,
+        )?;
+        self.comms = Some([comm1, comm2]);
+        self.sp = Some([sp1, sp2]);
+        Ok(())
+    }
+
+    fn init_schnorr_protocol<R: RngCore>(
+        rng: &mut R,
+        comm_key: &'a [G],
+        comm: G,
+        message: G::ScalarField,
+        blinding: Option<G::ScalarField>,
+        randomness: G::ScalarField,
+    ) -> Result<SchnorrProtocol<'a, G>, ProofSystemError> {
+        let blinding = blinding.unwrap_or_else(|| G::ScalarField::rand(rng));
+        let mut blindings = BTreeMap::new();
+        blindings.insert(0, blinding);
+
+        // NOTE: value of id is dummy
+        let mut sp = SchnorrProtocol::new(10000, comm_key, comm);
+        sp.init(rng, blindings, vec![message, randomness])?;
+        Ok(sp)
+    }
+
+    pub fn challenge_contribution<W: Write>(&self, mut writer: W) -> Result<(), ProofSystemError> {
+        if self.sp.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            ));
+        }
+        let [comm1, comm2] = self.comms.as_ref().unwrap();
+        self.inequality_protocol
+            .as_ref()
+            .unwrap()
+            .challenge_contribution_for_committed_inequality(
+                comm1,
+                comm2,
+                self.comm_key,
+                &mut writer,
+            )?;
+        for sp in self.sp.as_ref().unwrap() {
+            sp.challenge_contribution(&mut writer)?;
+        }
+        Ok(())
+    }
+
+    pub fn gen_proof_contribution<E: Pairing>(
+        &mut self,
+        challenge: &G::ScalarField,
+    ) -> Result<StatementProof<E, G>, ProofSystemError> {
+        if self.sp.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ));
+        }
+        let proof = self
+            .inequality_protocol
+            .take()
+            .unwrap()
+            .gen_proof(challenge)?;
+        let [comm1, comm2] = self.comms.take().unwrap();
+        let [mut sp1, mut sp2] = self.sp.take().unwrap();
+        Ok(StatementProof::CommittedInequality(
+            CommittedInequalityProof {
+                proof,
+                comm1,
+                comm2,
+                sp1: sp1.gen_proof_contribution_as_struct(challenge)?,
+                sp2: sp2.gen_proof_contribution_as_struct(challenge)?,
+            },
+        ))
+    }
+
+    pub fn verify_proof_contribution(
+        &self,
+        challenge: &G::ScalarField,
+        proof: &CommittedInequalityProof<G>,
+        comm_key_as_slice: &[G],
+    ) -> Result<(), ProofSystemError> {
+        proof
+            .proof
+            .verify_for_inequality_with_committed_value(
+                &proof.comm1,
+                &proof.comm2,
+                challenge,
+                self.comm_key,
+            )
+            .map_err(|e| ProofSystemError::SchnorrProofContributionFailed(self.id as u32, e))?;
+        for (comm, sp_proof) in [(proof.comm1, &proof.sp1), (proof.comm2, &proof.sp2)] {
+            // NOTE: value of id is dummy
+            let sp = SchnorrProtocol::new(10000, comm_key_as_slice, comm);
+            sp.verify_proof_contribution(challenge, sp_proof)
+                .map_err(|e| ProofSystemError::SchnorrProofContributionFailed(self.id as u32, e))?;
+        }
+        Ok(())
+    }
+
+    pub fn compute_challenge_contribution<W: Write>(
+        comm_key_as_slice: &[G],
+        proof: &CommittedInequalityProof<G>,
+        comm_key: &PedersenCommitmentKey<G>,
+        mut writer: W,
+    ) -> Result<(), ProofSystemError> {
+        proof
+            .proof
+            .challenge_contribution_for_committed_inequality(
+                &proof.comm1,
+                &proof.comm2,
+                comm_key,
+                &mut writer,
+            )?;
+        for (comm, sp_proof) in [(&proof.comm1, &proof.sp1), (&proof.comm2, &proof.sp2)] {
+            comm_key_as_slice.serialize_compressed(&mut writer)?;
+            comm.serialize_compressed(&mut writer)?;
+            sp_proof.t.serialize_compressed(&mut writer)?;
+        }
+        Ok(())
+    }
+}
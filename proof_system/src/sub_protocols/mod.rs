@@ -1,24 +1,33 @@
 pub mod accumulator;
+pub mod aggregated_bound_check_bpp;
 #[macro_use]
 pub mod bbs_plus;
 pub mod bbs_23;
+pub mod bddt16_kvac;
+pub mod bloom_membership;
 pub mod bound_check_bpp;
 pub mod bound_check_legogroth16;
 pub mod bound_check_smc;
 pub mod bound_check_smc_with_kv;
+pub mod committed_inequality;
 pub mod inequality;
+pub mod monotonic;
+pub mod partial_ped_opening;
 pub mod ps_signature;
+pub mod public_set_membership;
 pub mod r1cs_legogorth16;
 pub mod saver;
 pub mod schnorr;
+pub mod set_membership_smc;
 
 use core::borrow::Borrow;
 
-use crate::error::ProofSystemError;
-use ark_ec::{pairing::Pairing, AffineRepr};
-use ark_ff::PrimeField;
-use ark_std::{format, io::Write};
+use crate::{error::ProofSystemError, statement_proof::StatementProof};
+use ark_ec::{pairing::Pairing, AffineRepr, VariableBaseMSM};
+use ark_ff::{PrimeField, Zero};
+use ark_std::{format, io::Write, rand::RngCore, vec::Vec, UniformRand};
 use itertools::{EitherOrBoth, Itertools};
+use schnorr_pok::error::SchnorrError;
 
 use crate::sub_protocols::{
     accumulator::{
@@ -31,16 +40,24 @@ use crate::sub_protocols::{
         KBPositiveAccumulatorMembershipSubProtocol, KBUniversalAccumulatorMembershipSubProtocol,
         KBUniversalAccumulatorNonMembershipSubProtocol,
     },
+    aggregated_bound_check_bpp::AggregatedBoundCheckBppProtocol,
+    bloom_membership::BloomMembershipLegoGrothProtocol,
     bound_check_bpp::BoundCheckBppProtocol,
     bound_check_legogroth16::BoundCheckLegoGrothProtocol,
     bound_check_smc::BoundCheckSmcProtocol,
     bound_check_smc_with_kv::BoundCheckSmcWithKVProtocol,
+    committed_inequality::CommittedInequalityProtocol,
     inequality::InequalityProtocol,
+    monotonic::MonotonicProtocol,
+    partial_ped_opening::PartialPedersenOpeningProtocol,
+    public_set_membership::PublicSetMembershipProtocol,
     r1cs_legogorth16::R1CSLegogroth16Protocol,
+    set_membership_smc::SetMembershipSmcProtocol,
 };
 use accumulator::{
     detached::{
-        DetachedAccumulatorMembershipSubProtocol, DetachedAccumulatorNonMembershipSubProtocol,
+        DetachedAccumulatorMembershipCDHSubProtocol, DetachedAccumulatorMembershipSubProtocol,
+        DetachedAccumulatorNonMembershipSubProtocol,
     },
     VBAccumulatorMembershipSubProtocol, VBAccumulatorNonMembershipSubProtocol,
 };
@@ -82,6 +99,24 @@ pub enum SubProtocol<'a, E: Pairing, G: AffineRepr> {
     ),
     KBPositiveAccumulatorMembership(KBPositiveAccumulatorMembershipSubProtocol<'a, E>),
     KBPositiveAccumulatorMembershipCDH(KBPositiveAccumulatorMembershipCDHSubProtocol<'a, E>),
+    /// For proving membership in a Bloom filter using LegoGroth16
+    BloomMembershipLegoGroth16(BloomMembershipLegoGrothProtocol<'a, E>),
+    /// For proving order of witnesses using Bulletproofs++
+    Monotonic(MonotonicProtocol<'a, G>),
+    /// For proving that a witness is one of the values in a public set
+    PublicSetMembership(PublicSetMembershipProtocol<'a, G>),
+    /// For proving membership in a VB accumulator using the CDH approach with the accumulator encrypted for
+    /// the accumulator manager
+    DetachedAccumulatorMembershipCDH(DetachedAccumulatorMembershipCDHSubProtocol<'a, E>),
+    /// For proving bounds of several witnesses using a single Bulletproofs++ proof
+    AggregatedBoundCheckBpp(AggregatedBoundCheckBppProtocol<'a, G>),
+    /// For proving membership in a public set using set-membership check
+    SetMembershipSmc(SetMembershipSmcProtocol<'a, E>),
+    /// To prove inequality of 2 hidden messages
+    CommittedInequality(CommittedInequalityProtocol<'a, G>),
+    PartialPedersenOpening(PartialPedersenOpeningProtocol<'a, G>),
+    /// For proving knowledge of a BDDT16 MAC
+    PoKOfBDDT16MAC(bddt16_kvac::PoKOfBDDT16MACSubProtocol<'a, E>),
 }
 
 macro_rules! delegate {
@@ -110,7 +145,16 @@ macro_rules! delegate {
                 KBUniversalAccumulatorMembershipCDH,
                 KBUniversalAccumulatorNonMembershipCDH,
                 KBPositiveAccumulatorMembership,
-                KBPositiveAccumulatorMembershipCDH
+                KBPositiveAccumulatorMembershipCDH,
+                BloomMembershipLegoGroth16,
+                Monotonic,
+                PublicSetMembership,
+                DetachedAccumulatorMembershipCDH,
+                AggregatedBoundCheckBpp,
+                SetMembershipSmc,
+                CommittedInequality,
+                PartialPedersenOpening,
+                PoKOfBDDT16MAC
             : $($tt)+
         }
     }};
@@ -120,6 +164,50 @@ impl<'a, E: Pairing, G: AffineRepr<ScalarField = E::ScalarField>> SubProtocol<'a
     pub fn challenge_contribution<W: Write>(&self, writer: W) -> Result<(), ProofSystemError> {
         delegate!(self.challenge_contribution(writer))
     }
+
+    /// Verify the proofs of several Schnorr protocols for the same challenge using a single multi-scalar
+    /// multiplication. A Schnorr proof is valid when `sum(bases[i] * responses[i]) - y * challenge - t` is 0,
+    /// so all proofs are checked at once by checking that a linear combination of these with random
+    /// coefficients is 0. Only `PoKDiscreteLogs` sub-protocols with `PedersenCommitment` proofs are supported.
+    /// Unlike verifying each proof, a failure doesn't tell which proof is invalid.
+    pub fn verify_proof_contributions_batched<R: RngCore>(
+        items: &[(&Self, &StatementProof<E, G>)],
+        challenge: &G::ScalarField,
+        rng: &mut R,
+    ) -> Result<(), ProofSystemError> {
+        let mut bases = Vec::new();
+        let mut scalars = Vec::new();
+        for (i, (sp, proof)) in items.iter().enumerate() {
+            let (sp, proof) = match (sp, proof) {
+                (SubProtocol::PoKDiscreteLogs(sp), StatementProof::PedersenCommitment(p)) => {
+                    (sp, p)
+                }
+                _ => return Err(ProofSystemError::UnsupportedForBatchVerification(i)),
+            };
+            let responses = &proof.response.0;
+            if responses.len() != sp.commitment_key.len() {
+                return Err(ProofSystemError::SchnorrProofContributionFailed(
+                    sp.id as u32,
+                    SchnorrError::ExpectedSameSizeSequences(
+                        responses.len(),
+                        sp.commitment_key.len(),
+                    ),
+                ));
+            }
+            let r = G::ScalarField::rand(rng);
+            bases.extend_from_slice(sp.commitment_key);
+            scalars.extend(responses.iter().map(|z| r * z));
+            bases.push(sp.commitment);
+            scalars.push(-(r * challenge));
+            bases.push(proof.t);
+            scalars.push(-r);
+        }
+        if G::Group::msm_unchecked(&bases, &scalars).is_zero() {
+            Ok(())
+        } else {
+            Err(ProofSystemError::BatchedSchnorrProofContributionsFailed)
+        }
+    }
 }
 
 /// Merges indexed messages sorted by index with indexed blindings sorted by index.
@@ -188,3 +276,126 @@ pub fn should_use_cls(min: u64, max: u64) -> bool {
     let bits = diff.ilog2();
     bits < 20
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sub_protocols::schnorr::SchnorrProtocol;
+    use ark_bls12_381::{Bls12_381, G1Affine, G1Projective};
+    use ark_ec::{CurveGroup, VariableBaseMSM};
+    use ark_std::{
+        collections::BTreeMap,
+        rand::{rngs::StdRng, SeedableRng},
+        UniformRand,
+    };
+    use test_utils::Fr;
+
+    #[test]
+    fn batched_schnorr_verification() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let challenge = Fr::rand(&mut rng);
+
+        let comm_keys = (0..10)
+            .map(|i| {
+                (0..i + 1)
+                    .map(|_| G1Projective::rand(&mut rng).into_affine())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let mut commitments = vec![];
+        let mut proofs = vec![];
+        for (i, comm_key) in comm_keys.iter().enumerate() {
+            let wits = (0..i + 1).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+            let comm = G1Projective::msm_unchecked(comm_key, &wits).into_affine();
+            let mut sp = SchnorrProtocol::new(i, comm_key, comm);
+            sp.init(&mut rng, BTreeMap::new(), wits).unwrap();
+            proofs.push(sp.gen_proof_contribution::<Bls12_381>(&challenge).unwrap());
+            commitments.push(comm);
+        }
+        let verifiers = comm_keys
+            .iter()
+            .zip(commitments.iter())
+            .enumerate()
+            .map(|(i, (k, c))| {
+                SubProtocol::<Bls12_381, G1Affine>::PoKDiscreteLogs(SchnorrProtocol::new(i, k, *c))
+            })
+            .collect::<Vec<_>>();
+
+        let verify_each = |proofs: &[StatementProof<Bls12_381, G1Affine>]| {
+            verifiers
+                .iter()
+                .zip(proofs.iter())
+                .all(|(v, p)| match (v, p) {
+                    (SubProtocol::PoKDiscreteLogs(sp), StatementProof::PedersenCommitment(p)) => {
+                        sp.verify_proof_contribution(&challenge, p).is_ok()
+                    }
+                    _ => false,
+                })
+        };
+        type Item<'a, 'b> = (
+            &'a SubProtocol<'b, Bls12_381, G1Affine>,
+            &'a StatementProof<Bls12_381, G1Affine>,
+        );
+        fn items<'a, 'b>(
+            verifiers: &'a [SubProtocol<'b, Bls12_381, G1Affine>],
+            proofs: &'a [StatementProof<Bls12_381, G1Affine>],
+        ) -> Vec<Item<'a, 'b>> {
+            verifiers.iter().zip(proofs.iter()).collect()
+        }
+
+        assert!(verify_each(&proofs));
+        SubProtocol::verify_proof_contributions_batched(
+            &items(&verifiers, &proofs),
+            &challenge,
+            &mut rng,
+        )
+        .unwrap();
+
+        // Wrong challenge
+        assert!(SubProtocol::verify_proof_contributions_batched(
+            &items(&verifiers, &proofs),
+            &Fr::rand(&mut rng),
+            &mut rng
+        )
+        .is_err());
+
+        // One of the proofs is corrupted
+        let mut corrupted = proofs.clone();
+        if let StatementProof::PedersenCommitment(p) = &mut corrupted[4] {
+            p.response.0[2] += Fr::from(1u64);
+        }
+        assert!(!verify_each(&corrupted));
+        assert!(matches!(
+            SubProtocol::verify_proof_contributions_batched(
+                &items(&verifiers, &corrupted),
+                &challenge,
+                &mut rng
+            ),
+            Err(ProofSystemError::BatchedSchnorrProofContributionsFailed)
+        ));
+
+        // Proof with fewer responses than bases
+        let mut corrupted = proofs.clone();
+        if let StatementProof::PedersenCommitment(p) = &mut corrupted[3] {
+            p.response.0.pop();
+        }
+        assert!(matches!(
+            SubProtocol::verify_proof_contributions_batched(
+                &items(&verifiers, &corrupted),
+                &challenge,
+                &mut rng
+            ),
+            Err(ProofSystemError::SchnorrProofContributionFailed(3, _))
+        ));
+
+        // Proofs swapped between statements
+        let mut swapped = proofs.clone();
+        swapped.swap(2, 3);
+        assert!(SubProtocol::verify_proof_contributions_batched(
+            &items(&verifiers, &swapped),
+            &challenge,
+            &mut rng
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,250 @@
+use crate::{
+    error::ProofSystemError,
+    prelude::StatementProof,
+    statement_proof::{MonotonicProof, PedersenCommitmentProof},
+    sub_protocols::{enforce_and_get_u64, schnorr::SchnorrProtocol},
+};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::Zero;
+use ark_std::{collections::BTreeMap, io::Write, rand::RngCore, vec, vec::Vec, UniformRand};
+use bulletproofs_plus_plus::{prelude::Prover, setup::SetupParams};
+use dock_crypto_utils::transcript::Transcript;
+
+/// Number of bits in the difference of consecutive values
+const NUM_BITS: u16 = 64;
+
+/// Commits to each value as `g * value + h * randomness` and proves knowledge of the value and randomness in each
+/// commitment using a Schnorr protocol. The commitment to the difference of consecutive values is then computed by
+/// subtracting consecutive commitments, and a single aggregated Bulletproofs++ proof proves that all the
+/// differences are in `[0, 2^64)`. As Bulletproofs++ needs the number of aggregated proofs to be a power of 2,
+/// commitments to 0 with randomness 0, i.e. the identity element, are added as padding and the setup params
+/// should support `(num_values - 1).next_power_of_two()` proofs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonotonicProtocol<'a, G: AffineRepr> {
+    pub id: usize,
+    pub num_values: usize,
+    pub strict: bool,
+    pub setup_params: &'a SetupParams<G>,
+    pub commitments: Option<Vec<G>>,
+    /// Differences of consecutive values and the randomness in their commitments
+    pub differences: Option<Vec<u64>>,
+    pub differences_randomness: Option<Vec<G::ScalarField>>,
+    pub sps: Option<Vec<SchnorrProtocol<'a, G>>>,
+}
+
+impl<'a, G: AffineRepr> MonotonicProtocol<'a, G> {
+    pub fn new(
+        id: usize,
+        num_values: usize,
+        strict: bool,
+        setup_params: &'a SetupParams<G>,
+    ) -> Result<Self, ProofSystemError> {
+        Self::validate_num_values(num_values)?;
+        Ok(Self {
+            id,
+            num_values,
+            strict,
+            setup_params,
+            commitments: None,
+            differences: None,
+            differences_randomness: None,
+            sps: None,
+        })
+    }
+
+    /// `comm_key` is the Pedersen commitment key from the Bulletproofs++ setup params. `blindings` has the blinding
+    /// to use for a value when it's being proven equal to another witness, keyed by the value's index.
+    pub fn init<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        comm_key: &'a [G],
+        values: Vec<G::ScalarField>,
+        mut blindings: BTreeMap<usize, G::ScalarField>,
+    ) -> Result<(), ProofSystemError> {
+        if self.sps.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        Self::validate_num_values(self.num_values)?;
+        if values.len() != self.num_values {
+            return Err(ProofSystemError::UnequalWitnessCountForMonotonic(
+                self.id,
+                self.num_values,
+                values.len(),
+            ));
+        }
+        let one = G::ScalarField::from(self.strict as u64);
+        let mut differences = Vec::with_capacity(self.num_values - 1);
+        for i in 0..self.num_values - 1 {
+            let d = values[i + 1] - values[i] - one;
+            let d = enforce_and_get_u64::<G::ScalarField>(&d)
+                .map_err(|_| ProofSystemError::MonotonicValuesNotInOrder(self.id, i))?;
+            differences.push(d);
+        }
+
+        let randomness = (0..self.num_values)
+            .map(|_| G::ScalarField::rand(rng))
+            .collect::<Vec<_>>();
+        let mut commitments = Vec::with_capacity(self.num_values);
+        let mut sps = Vec::with_capacity(self.num_values);
+        for (i, (v, r)) in values.into_iter().zip(randomness.iter()).enumerate() {
+            let comm = (comm_key[0] * v + comm_key[1] * r).into_affine();
+            let mut b = BTreeMap::new();
+            if let Some(blinding) = blindings.remove(&i) {
+                b.insert(0, blinding);
+            }
+            // NOTE: value of id is dummy
+            let mut sp = SchnorrProtocol::new(10000, comm_key, comm);
+            sp.init(rng, b, vec![v, *r])?;
+            commitments.push(comm);
+            sps.push(sp);
+        }
+        let mut differences_randomness = (0..self.num_values - 1)
+            .map(|i| randomness[i + 1] - randomness[i])
+            .collect::<Vec<_>>();
+        let padded_len = Self::num_proofs(self.num_values);
+        differences.resize(padded_len, 0);
+        differences_randomness.resize(padded_len, G::ScalarField::zero());
+        self.differences_randomness = Some(differences_randomness);
+        self.differences = Some(differences);
+        self.commitments = Some(commitments);
+        self.sps = Some(sps);
+        Ok(())
+    }
+
+    /// Generate challenge contribution for all the Schnorr protocols
+    pub fn challenge_contribution<W: Write>(&self, mut writer: W) -> Result<(), ProofSystemError> {
+        if self.sps.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            ));
+        }
+        for sp in self.sps.as_ref().unwrap() {
+            sp.challenge_contribution(&mut writer)?;
+        }
+        Ok(())
+    }
+
+    /// Generate the Bulletproofs++ proof and responses for all the Schnorr protocols
+    pub fn gen_proof_contribution<E: Pairing, R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        challenge: &G::ScalarField,
+        transcript: &mut impl Transcript,
+    ) -> Result<StatementProof<E, G>, ProofSystemError> {
+        if self.sps.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ));
+        }
+        let commitments = self.commitments.take().unwrap();
+        let prover = Prover::new(
+            NUM_BITS,
+            self.commitments_to_differences(&commitments),
+            self.differences.take().unwrap(),
+            self.differences_randomness.take().unwrap(),
+        )?;
+        let bpp_proof = prover.prove(rng, self.setup_params.clone(), transcript)?;
+        let sp = self
+            .sps
+            .take()
+            .unwrap()
+            .iter_mut()
+            .map(|sp| sp.gen_proof_contribution_as_struct(challenge))
+            .collect::<Result<Vec<PedersenCommitmentProof<G>>, _>>()?;
+        Ok(StatementProof::Monotonic(MonotonicProof {
+            commitments,
+            bpp_proof,
+            sp,
+        }))
+    }
+
+    pub fn verify_proof_contribution(
+        &self,
+        challenge: &G::ScalarField,
+        proof: &MonotonicProof<G>,
+        comm_key: &[G],
+        transcript: &mut impl Transcript,
+    ) -> Result<(), ProofSystemError> {
+        Self::validate_proof(self.id, self.num_values, proof)?;
+        proof
+            .bpp_proof
+            .verify(
+                NUM_BITS,
+                &self.commitments_to_differences(&proof.commitments),
+                self.setup_params,
+                transcript,
+            )
+            .map_err(|e| {
+                ProofSystemError::BulletproofsPlusPlusProofContributionFailed(self.id as u32, e)
+            })?;
+        for (comm, sp_proof) in proof.commitments.iter().zip(proof.sp.iter()) {
+            // NOTE: value of id is dummy
+            let sp = SchnorrProtocol::new(10000, comm_key, *comm);
+            sp.verify_proof_contribution(challenge, sp_proof)
+                .map_err(|e| ProofSystemError::SchnorrProofContributionFailed(self.id as u32, e))?;
+        }
+        Ok(())
+    }
+
+    pub fn compute_challenge_contribution<W: Write>(
+        id: usize,
+        num_values: usize,
+        comm_key: &[G],
+        proof: &MonotonicProof<G>,
+        mut writer: W,
+    ) -> Result<(), ProofSystemError> {
+        Self::validate_proof(id, num_values, proof)?;
+        for (comm, sp) in proof.commitments.iter().zip(proof.sp.iter()) {
+            SchnorrProtocol::compute_challenge_contribution(comm_key, comm, &sp.t, &mut writer)?;
+        }
+        Ok(())
+    }
+
+    /// Commitments to differences of consecutive values, i.e. `C_{i+1} - C_i` or `C_{i+1} - C_i - g` when `strict`,
+    /// followed by the padding
+    fn commitments_to_differences(&self, commitments: &[G]) -> Vec<G> {
+        let g = self.setup_params.G.into_group();
+        let mut diffs = (0..commitments.len() - 1)
+            .map(|i| {
+                let d = commitments[i + 1].into_group() - commitments[i];
+                if self.strict {
+                    d - g
+                } else {
+                    d
+                }
+            })
+            .collect::<Vec<_>>();
+        diffs.resize(Self::num_proofs(commitments.len()), G::Group::zero());
+        G::Group::normalize_batch(&diffs)
+    }
+
+    /// Number of aggregated Bulletproofs++ proofs including the padding
+    fn num_proofs(num_values: usize) -> usize {
+        (num_values - 1).next_power_of_two()
+    }
+
+    fn validate_proof(
+        id: usize,
+        num_values: usize,
+        proof: &MonotonicProof<G>,
+    ) -> Result<(), ProofSystemError> {
+        Self::validate_num_values(num_values)?;
+        for found in [proof.commitments.len(), proof.sp.len()] {
+            if found != num_values {
+                return Err(ProofSystemError::UnequalWitnessCountForMonotonic(
+                    id, num_values, found,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// The number of values is public and deserializable so it's checked again here as fewer than 2 values would
+    /// make the number of differences underflow
+    fn validate_num_values(num_values: usize) -> Result<(), ProofSystemError> {
+        if num_values < 2 {
+            return Err(ProofSystemError::InsufficientValuesForMonotonic(num_values));
+        }
+        Ok(())
+    }
+}
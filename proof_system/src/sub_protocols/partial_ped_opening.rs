@@ -0,0 +1,151 @@
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::CanonicalSerialize;
+use ark_std::{collections::BTreeMap, io::Write, rand::RngCore, vec::Vec, UniformRand};
+use schnorr_pok::SchnorrCommitment;
+
+use crate::{
+    error::ProofSystemError,
+    statement::partial_ped_opening::PartialPedersenOpening,
+    statement_proof::{PedersenCommitmentProof, StatementProof},
+};
+
+/// Proof of knowledge of the hidden scalars of a `PartialPedersenOpening`. This is a Schnorr protocol over the
+/// bases of the hidden scalars for the commitment with the revealed scalars removed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartialPedersenOpeningProtocol<'a, G: AffineRepr> {
+    pub id: usize,
+    pub statement: &'a PartialPedersenOpening<G>,
+    pub comm_key: &'a [G],
+    /// Bases of the hidden scalars in the order of their positions
+    pub hidden_bases: Vec<G>,
+    /// Commitment to only the hidden scalars
+    pub reduced_commitment: G,
+    pub commitment_to_randomness: Option<SchnorrCommitment<G>>,
+    pub witnesses: Option<Vec<G::ScalarField>>,
+}
+
+impl<'a, G: AffineRepr> PartialPedersenOpeningProtocol<'a, G> {
+    pub fn new(
+        id: usize,
+        statement: &'a PartialPedersenOpening<G>,
+        comm_key: &'a [G],
+    ) -> Result<Self, ProofSystemError> {
+        let (hidden_bases, reduced_commitment) =
+            statement.hidden_bases_and_commitment(comm_key, id)?;
+        Ok(Self {
+            id,
+            statement,
+            comm_key,
+            hidden_bases,
+            reduced_commitment,
+            commitment_to_randomness: None,
+            witnesses: None,
+        })
+    }
+
+    /// `witness` contains the hidden scalars keyed by their position and `blindings` is keyed by the index of
+    /// the hidden scalar among the hidden scalars.
+    pub fn init<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        mut blindings: BTreeMap<usize, G::ScalarField>,
+        witness: BTreeMap<usize, G::ScalarField>,
+    ) -> Result<(), ProofSystemError> {
+        if self.commitment_to_randomness.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        if !witness
+            .keys()
+            .cloned()
+            .eq(self.statement.hidden_positions(self.comm_key.len()))
+        {
+            return Err(ProofSystemError::IncorrectHiddenPositionsForPartialOpening(
+                self.id,
+            ));
+        }
+        if self.hidden_bases.is_empty() && !self.reduced_commitment.is_zero() {
+            return Err(ProofSystemError::RevealedValuesDontOpenCommitment(self.id));
+        }
+        let blindings = (0..witness.len())
+            .map(|i| {
+                blindings
+                    .remove(&i)
+                    .unwrap_or_else(|| G::ScalarField::rand(rng))
+            })
+            .collect::<Vec<_>>();
+        self.commitment_to_randomness = Some(SchnorrCommitment::new(&self.hidden_bases, blindings));
+        self.witnesses = Some(witness.into_values().collect());
+        Ok(())
+    }
+
+    pub fn challenge_contribution<W: Write>(&self, writer: W) -> Result<(), ProofSystemError> {
+        if self.commitment_to_randomness.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            ));
+        }
+        Self::compute_challenge_contribution(
+            self.comm_key,
+            self.statement,
+            &self.commitment_to_randomness.as_ref().unwrap().t,
+            writer,
+        )
+    }
+
+    pub fn gen_proof_contribution<E: Pairing>(
+        &mut self,
+        challenge: &G::ScalarField,
+    ) -> Result<StatementProof<E, G>, ProofSystemError> {
+        if self.commitment_to_randomness.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ));
+        }
+        let commitment = self.commitment_to_randomness.take().unwrap();
+        let responses = commitment.response(self.witnesses.as_ref().unwrap(), challenge)?;
+        Ok(StatementProof::PedersenCommitment(
+            PedersenCommitmentProof::new(commitment.t, responses),
+        ))
+    }
+
+    /// When all scalars are revealed, this only checks that they open the commitment.
+    pub fn verify_proof_contribution(
+        &self,
+        challenge: &G::ScalarField,
+        proof: &PedersenCommitmentProof<G>,
+    ) -> Result<(), ProofSystemError> {
+        if self.hidden_bases.is_empty() {
+            if !self.reduced_commitment.is_zero() {
+                return Err(ProofSystemError::RevealedValuesDontOpenCommitment(self.id));
+            }
+            if !proof.response.0.is_empty() {
+                return Err(ProofSystemError::IncorrectHiddenPositionsForPartialOpening(
+                    self.id,
+                ));
+            }
+            return Ok(());
+        }
+        proof
+            .response
+            .is_valid(
+                &self.hidden_bases,
+                &self.reduced_commitment,
+                &proof.t,
+                challenge,
+            )
+            .map_err(|e| ProofSystemError::SchnorrProofContributionFailed(self.id as u32, e))
+    }
+
+    pub fn compute_challenge_contribution<W: Write>(
+        comm_key: &[G],
+        statement: &PartialPedersenOpening<G>,
+        t: &G,
+        mut writer: W,
+    ) -> Result<(), ProofSystemError> {
+        comm_key.serialize_compressed(&mut writer)?;
+        statement.commitment.serialize_compressed(&mut writer)?;
+        statement.revealed.serialize_compressed(&mut writer)?;
+        t.serialize_compressed(writer)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,263 @@
+use crate::{
+    error::ProofSystemError,
+    statement_proof::{PublicSetMembershipProof, StatementProof},
+    sub_protocols::schnorr::SchnorrProtocol,
+};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::Zero;
+use ark_serialize::CanonicalSerialize;
+use ark_std::{collections::BTreeMap, io::Write, rand::RngCore, vec, vec::Vec, UniformRand};
+use dock_crypto_utils::commitment::PedersenCommitmentKey;
+
+/// Commits to the message as `C = g * message + h * r` and proves knowledge of the message and `r` using a
+/// Schnorr protocol whose response for the message is used for witness equality. Then proves using a
+/// one-of-many Schnorr proof (an OR of discrete log proofs as in Cramer-Damgård-Schoenmakers) that
+/// `C - g * s_i = h * r` for some value `s_i` in the set. The proofs for all values except the actual one
+/// are simulated by choosing their challenges and responses and the challenges must add up to the proof's
+/// challenge. The proof has a challenge and a response for each value in the set.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PublicSetMembershipProtocol<'a, G: AffineRepr> {
+    pub id: usize,
+    pub set: &'a [G::ScalarField],
+    pub comm_key: &'a PedersenCommitmentKey<G>,
+    pub comm: Option<G>,
+    /// Index of the message in the set
+    pub member_index: Option<usize>,
+    pub randomness: Option<G::ScalarField>,
+    /// Blinding for the proof of the actual value
+    pub blinding: Option<G::ScalarField>,
+    /// Commitments to the randomness of the proof of each value. All except the actual one are simulated.
+    pub t: Option<Vec<G>>,
+    /// Challenges for the simulated proofs with 0 in place of the actual one's
+    pub challenges: Option<Vec<G::ScalarField>>,
+    /// Responses for the simulated proofs with 0 in place of the actual one's
+    pub responses: Option<Vec<G::ScalarField>>,
+    pub sp: Option<SchnorrProtocol<'a, G>>,
+}
+
+impl<'a, G: AffineRepr> PublicSetMembershipProtocol<'a, G> {
+    pub fn new(
+        id: usize,
+        set: &'a [G::ScalarField],
+        comm_key: &'a PedersenCommitmentKey<G>,
+    ) -> Self {
+        Self {
+            id,
+            set,
+            comm_key,
+            comm: None,
+            member_index: None,
+            randomness: None,
+            blinding: None,
+            t: None,
+            challenges: None,
+            responses: None,
+            sp: None,
+        }
+    }
+
+    pub fn init<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        comm_key_as_slice: &'a [G],
+        message: G::ScalarField,
+        blinding: Option<G::ScalarField>,
+    ) -> Result<(), ProofSystemError> {
+        if self.sp.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        let member_index = self
+            .set
+            .iter()
+            .position(|s| *s == message)
+            .ok_or(ProofSystemError::MessageNotInPublicSet(self.id))?;
+        let randomness = G::ScalarField::rand(rng);
+        let comm = self.comm_key.commit(&message, &randomness);
+
+        let or_blinding = G::ScalarField::rand(rng);
+        let mut challenges = vec![G::ScalarField::zero(); self.set.len()];
+        let mut responses = vec![G::ScalarField::zero(); self.set.len()];
+        let mut t = Vec::with_capacity(self.set.len());
+        for (i, s) in self.set.iter().enumerate() {
+            if i == member_index {
+                t.push(self.comm_key.h * or_blinding);
+            } else {
+                challenges[i] = G::ScalarField::rand(rng);
+                responses[i] = G::ScalarField::rand(rng);
+                t.push(Self::simulated_t(
+                    self.comm_key,
+                    &comm,
+                    s,
+                    &challenges[i],
+                    &responses[i],
+                ));
+            }
+        }
+
+        let mut blindings = BTreeMap::new();
+        if let Some(b) = blinding {
+            blindings.insert(0, b);
+        }
+        // NOTE: value of id is dummy
+        let mut sp = SchnorrProtocol::new(10000, comm_key_as_slice, comm);
+        sp.init(rng, blindings, vec![message, randomness])?;
+
+        self.comm = Some(comm);
+        self.member_index = Some(member_index);
+        self.randomness = Some(randomness);
+        self.blinding = Some(or_blinding);
+        self.t = Some(G::Group::normalize_batch(&t));
+        self.challenges = Some(challenges);
+        self.responses = Some(responses);
+        self.sp = Some(sp);
+        Ok(())
+    }
+
+    pub fn challenge_contribution<W: Write>(&self, mut writer: W) -> Result<(), ProofSystemError> {
+        if self.sp.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            ));
+        }
+        Self::write_or_proof_challenge_contribution(
+            self.comm_key,
+            self.set,
+            self.comm.as_ref().unwrap(),
+            self.t.as_ref().unwrap(),
+            &mut writer,
+        )?;
+        self.sp
+            .as_ref()
+            .unwrap()
+            .challenge_contribution(&mut writer)?;
+        Ok(())
+    }
+
+    pub fn gen_proof_contribution<E: Pairing>(
+        &mut self,
+        challenge: &G::ScalarField,
+    ) -> Result<StatementProof<E, G>, ProofSystemError> {
+        if self.sp.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ));
+        }
+        let member_index = self.member_index.take().unwrap();
+        let mut challenges = self.challenges.take().unwrap();
+        let mut responses = self.responses.take().unwrap();
+        // Challenge of the actual proof is such that all challenges add up to the given challenge
+        let c = challenges.iter().fold(*challenge, |acc, c_i| acc - c_i);
+        challenges[member_index] = c;
+        responses[member_index] =
+            self.blinding.take().unwrap() + c * self.randomness.take().unwrap();
+        self.t = None;
+        Ok(StatementProof::PublicSetMembership(
+            PublicSetMembershipProof {
+                comm: self.comm.take().unwrap(),
+                challenges,
+                responses,
+                sp: self
+                    .sp
+                    .take()
+                    .unwrap()
+                    .gen_proof_contribution_as_struct(challenge)?,
+            },
+        ))
+    }
+
+    pub fn verify_proof_contribution(
+        &self,
+        challenge: &G::ScalarField,
+        proof: &PublicSetMembershipProof<G>,
+        comm_key_as_slice: &[G],
+    ) -> Result<(), ProofSystemError> {
+        Self::validate_proof(self.id, self.set, proof)?;
+        // The `t` of each proof was recomputed from the challenges and responses for the challenge contribution
+        // so only need to check that the challenges add up to the proof's challenge
+        let sum = proof
+            .challenges
+            .iter()
+            .fold(G::ScalarField::zero(), |acc, c| acc + c);
+        if sum != *challenge {
+            return Err(ProofSystemError::PublicSetMembershipProofContributionFailed(self.id));
+        }
+        // NOTE: value of id is dummy
+        let sp = SchnorrProtocol::new(10000, comm_key_as_slice, proof.comm);
+        sp.verify_proof_contribution(challenge, &proof.sp)
+            .map_err(|e| ProofSystemError::SchnorrProofContributionFailed(self.id as u32, e))
+    }
+
+    pub fn compute_challenge_contribution<W: Write>(
+        id: usize,
+        comm_key_as_slice: &[G],
+        proof: &PublicSetMembershipProof<G>,
+        set: &[G::ScalarField],
+        comm_key: &PedersenCommitmentKey<G>,
+        mut writer: W,
+    ) -> Result<(), ProofSystemError> {
+        Self::validate_proof(id, set, proof)?;
+        // Recompute the `t` of each proof as `h * response - (C - g * s_i) * challenge`
+        let t = set
+            .iter()
+            .zip(proof.challenges.iter().zip(proof.responses.iter()))
+            .map(|(s, (c, z))| Self::simulated_t(comm_key, &proof.comm, s, c, z))
+            .collect::<Vec<_>>();
+        Self::write_or_proof_challenge_contribution(
+            comm_key,
+            set,
+            &proof.comm,
+            &G::Group::normalize_batch(&t),
+            &mut writer,
+        )?;
+        SchnorrProtocol::compute_challenge_contribution(
+            comm_key_as_slice,
+            &proof.comm,
+            &proof.sp.t,
+            &mut writer,
+        )
+    }
+
+    /// `h * response - (C - g * value) * challenge`
+    fn simulated_t(
+        comm_key: &PedersenCommitmentKey<G>,
+        comm: &G,
+        value: &G::ScalarField,
+        challenge: &G::ScalarField,
+        response: &G::ScalarField,
+    ) -> G::Group {
+        let y = comm.into_group() - comm_key.g * value;
+        comm_key.h * response - y * challenge
+    }
+
+    fn write_or_proof_challenge_contribution<W: Write>(
+        comm_key: &PedersenCommitmentKey<G>,
+        set: &[G::ScalarField],
+        comm: &G,
+        t: &[G],
+        mut writer: W,
+    ) -> Result<(), ProofSystemError> {
+        comm_key.g.serialize_compressed(&mut writer)?;
+        comm_key.h.serialize_compressed(&mut writer)?;
+        set.serialize_compressed(&mut writer)?;
+        comm.serialize_compressed(&mut writer)?;
+        t.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+
+    fn validate_proof(
+        id: usize,
+        set: &[G::ScalarField],
+        proof: &PublicSetMembershipProof<G>,
+    ) -> Result<(), ProofSystemError> {
+        for found in [proof.challenges.len(), proof.responses.len()] {
+            if found != set.len() {
+                return Err(ProofSystemError::UnequalPublicSetAndProofSize(
+                    id,
+                    set.len(),
+                    found,
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -248,6 +248,7 @@ impl<'a, E: Pairing> SaverProtocol<'a, E> {
         pek: impl Into<PreparedEncryptionKey<E>>,
         pairing_checker: &mut Option<RandomizedPairingChecker<E>>,
     ) -> Result<(), ProofSystemError> {
+        self.check_chunks_count(&proof.ciphertext)?;
         let pek = pek.into();
         let pgens = pgens.into();
         let expected_count = pek.supported_chunks_count()? as usize;
@@ -313,6 +314,7 @@ impl<'a, E: Pairing> SaverProtocol<'a, E> {
         ck_comm_chunks: &[E::G1Affine],
         ck_comm_combined: &[E::G1Affine],
     ) -> Result<(), ProofSystemError> {
+        self.check_chunks_count(ciphertext)?;
         // NOTE: value of id is dummy
         let sp_ciphertext = SchnorrProtocol::new(10000, ck_comm_ct, ciphertext.commitment);
         let sp_chunks = SchnorrProtocol::new(10000, ck_comm_chunks, comm_chunks);
@@ -421,6 +423,18 @@ impl<'a, E: Pairing> SaverProtocol<'a, E> {
         Ok(())
     }
 
+    /// Check that the ciphertext is well formed, i.e. its commitment is consistent with the encrypted chunks
+    /// as per the encryption key and generators. This doesn't verify the SNARK or the Schnorr proofs and thus
+    /// can be used by a decryptor to check a ciphertext received separately from the proof before decrypting it.
+    pub fn verify_ciphertext_commitment(
+        ciphertext: &Ciphertext<E>,
+        pgens: impl Into<PreparedEncryptionGens<E>>,
+        pek: impl Into<PreparedEncryptionKey<E>>,
+    ) -> Result<(), ProofSystemError> {
+        ciphertext.verify_commitment(pek, pgens)?;
+        Ok(())
+    }
+
     pub fn validate_encryption_key(
         chunk_bit_size: u8,
         encryption_key: &EncryptionKey<E>,
@@ -439,6 +453,23 @@ impl<'a, E: Pairing> SaverProtocol<'a, E> {
         }
     }
 
+    /// Check that the ciphertext has as many chunks as a message decomposed into chunks of `self.chunk_bit_size`
+    /// bits. Fails early with a descriptive error when prover and verifier disagree on the chunk bit size rather
+    /// than in the pairing or Schnorr checks.
+    pub fn check_chunks_count(&self, ciphertext: &Ciphertext<E>) -> Result<(), ProofSystemError> {
+        let expected_count =
+            saver::utils::chunks_count::<E::ScalarField>(self.chunk_bit_size) as usize;
+        if ciphertext.enc_chunks.len() != expected_count {
+            return Err(ProofSystemError::SaverChunkMismatch(
+                self.id,
+                self.chunk_bit_size,
+                expected_count,
+                ciphertext.enc_chunks.len(),
+            ));
+        }
+        Ok(())
+    }
+
     /// Commitment key for the commitment in ciphertext
     pub fn encryption_comm_key(encryption_key: &EncryptionKey<E>) -> Vec<E::G1Affine> {
         encryption_key.commitment_key()
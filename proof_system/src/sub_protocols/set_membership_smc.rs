@@ -0,0 +1,151 @@
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::CanonicalSerialize;
+use ark_std::{collections::BTreeMap, io::Write, rand::RngCore, vec, UniformRand};
+
+use crate::{
+    error::ProofSystemError,
+    prelude::bound_check_smc::SmcParamsWithPairingAndCommitmentKey,
+    statement::bound_check_smc::SmcParamsAndCommitmentKey,
+    statement_proof::{SetMembershipSmcProof, StatementProof},
+    sub_protocols::schnorr::SchnorrProtocol,
+};
+use smc_range_proof::{
+    ccs_set_membership::single_member::SetMembershipCheckProtocol,
+    prelude::SetMembershipCheckParamsWithPairing,
+};
+
+/// Runs the set-membership check protocol for proving that a witness is a member of a public set and a Schnorr
+/// protocol for proving knowledge of the witness committed in the commitment accompanying the proof.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SetMembershipSmcProtocol<'a, E: Pairing> {
+    pub id: usize,
+    pub params_and_comm_key: &'a SmcParamsAndCommitmentKey<E>,
+    pub comm: Option<E::G1Affine>,
+    pub smc_protocol: Option<SetMembershipCheckProtocol<E>>,
+    pub sp: Option<SchnorrProtocol<'a, E::G1Affine>>,
+}
+
+impl<'a, E: Pairing> SetMembershipSmcProtocol<'a, E> {
+    pub fn new(id: usize, params: &'a SmcParamsAndCommitmentKey<E>) -> Self {
+        Self {
+            id,
+            params_and_comm_key: params,
+            comm: None,
+            smc_protocol: None,
+            sp: None,
+        }
+    }
+
+    pub fn init<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        comm_key_as_slice: &'a [E::G1Affine],
+        message: E::ScalarField,
+        blinding: Option<E::ScalarField>,
+    ) -> Result<(), ProofSystemError> {
+        if self.sp.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        if !self.params_and_comm_key.params.set.contains(&message) {
+            return Err(ProofSystemError::MessageNotInPublicSet(self.id));
+        }
+        let randomness = E::ScalarField::rand(rng);
+        let params_with_pairing =
+            SetMembershipCheckParamsWithPairing::from(self.params_and_comm_key.params.clone());
+        let comm_key = &self.params_and_comm_key.comm_key;
+        self.comm = Some(comm_key.commit(&message, &randomness));
+        self.smc_protocol = Some(SetMembershipCheckProtocol::init(
+            rng,
+            message,
+            randomness,
+            comm_key,
+            params_with_pairing,
+        )?);
+
+        let blinding = blinding.unwrap_or_else(|| E::ScalarField::rand(rng));
+        let mut blindings = BTreeMap::new();
+        blindings.insert(0, blinding);
+        // NOTE: value of id is dummy
+        let mut sp = SchnorrProtocol::new(10000, comm_key_as_slice, self.comm.unwrap());
+        sp.init(rng, blindings, vec![message, randomness])?;
+        self.sp = Some(sp);
+        Ok(())
+    }
+
+    pub fn challenge_contribution<W: Write>(&self, mut writer: W) -> Result<(), ProofSystemError> {
+        if self.sp.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            ));
+        }
+        let params_with_pairing =
+            SetMembershipCheckParamsWithPairing::from(self.params_and_comm_key.params.clone());
+        self.smc_protocol.as_ref().unwrap().challenge_contribution(
+            self.comm.as_ref().unwrap(),
+            &self.params_and_comm_key.comm_key,
+            params_with_pairing,
+            &mut writer,
+        )?;
+        self.sp
+            .as_ref()
+            .unwrap()
+            .challenge_contribution(&mut writer)?;
+        Ok(())
+    }
+
+    pub fn gen_proof_contribution<G: AffineRepr>(
+        &mut self,
+        challenge: &E::ScalarField,
+    ) -> Result<StatementProof<E, G>, ProofSystemError> {
+        if self.sp.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ));
+        }
+        Ok(StatementProof::SetMembershipSmc(SetMembershipSmcProof {
+            proof: self.smc_protocol.take().unwrap().gen_proof(challenge),
+            comm: self.comm.take().unwrap(),
+            sp: self
+                .sp
+                .take()
+                .unwrap()
+                .gen_proof_contribution_as_struct(challenge)?,
+        }))
+    }
+
+    pub fn verify_proof_contribution(
+        &self,
+        challenge: &E::ScalarField,
+        proof: &SetMembershipSmcProof<E>,
+        comm_key_as_slice: &[E::G1Affine],
+        params: SmcParamsWithPairingAndCommitmentKey<E>,
+    ) -> Result<(), ProofSystemError> {
+        proof
+            .proof
+            .verify(&proof.comm, challenge, &params.comm_key, params.params)
+            .map_err(|e| ProofSystemError::SmcRangeProofContributionFailed(self.id as u32, e))?;
+
+        // NOTE: value of id is dummy
+        let sp = SchnorrProtocol::new(10000, comm_key_as_slice, proof.comm);
+        sp.verify_proof_contribution(challenge, &proof.sp)
+            .map_err(|e| ProofSystemError::SchnorrProofContributionFailed(self.id as u32, e))
+    }
+
+    pub fn compute_challenge_contribution<W: Write>(
+        comm_key_as_slice: &[E::G1Affine],
+        proof: &SetMembershipSmcProof<E>,
+        params: SmcParamsWithPairingAndCommitmentKey<E>,
+        mut writer: W,
+    ) -> Result<(), ProofSystemError> {
+        proof.proof.challenge_contribution(
+            &proof.comm,
+            &params.comm_key,
+            params.params,
+            &mut writer,
+        )?;
+        comm_key_as_slice.serialize_compressed(&mut writer)?;
+        proof.comm.serialize_compressed(&mut writer)?;
+        proof.sp.t.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+//! Per-statement timing of proof creation and verification. The timings are only recorded when the
+//! `timings` feature is enabled, otherwise `StatementTimer` does nothing.
+
+#[cfg(feature = "timings")]
+use ark_std::{
+    time::{Duration, Instant},
+    vec,
+    vec::Vec,
+};
+
+/// Wall-clock time taken by each statement while creating or verifying a proof.
+#[cfg(feature = "timings")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TimingReport {
+    /// Time taken by each statement, indexed by the statement's index in the `ProofSpec`. For the prover, this
+    /// is the time taken to initialize the statement's sub-protocol and to generate its proof. For the
+    /// verifier, this is the time taken to compute the statement's challenge contribution and to verify its
+    /// proof. Work shared by all statements like deriving parameters or checking aggregated SNARK proofs is
+    /// not counted towards any statement.
+    pub statements: Vec<Duration>,
+    /// Total time taken to create or verify the proof
+    pub total: Duration,
+}
+
+/// Records the time taken by each statement in a `TimingReport`
+#[derive(Default)]
+pub(crate) struct StatementTimer {
+    #[cfg(feature = "timings")]
+    report: TimingReport,
+    #[cfg(feature = "timings")]
+    started_at: Option<Instant>,
+    #[cfg(feature = "timings")]
+    created_at: Option<Instant>,
+}
+
+#[cfg_attr(not(feature = "timings"), allow(unused_variables))]
+impl StatementTimer {
+    pub fn new(num_statements: usize) -> Self {
+        #[cfg(feature = "timings")]
+        return Self {
+            report: TimingReport {
+                statements: vec![Duration::ZERO; num_statements],
+                total: Duration::ZERO,
+            },
+            started_at: None,
+            created_at: Some(Instant::now()),
+        };
+        #[cfg(not(feature = "timings"))]
+        Self::default()
+    }
+
+    /// Start timing a statement
+    pub fn start(&mut self) {
+        #[cfg(feature = "timings")]
+        {
+            self.started_at = Some(Instant::now());
+        }
+    }
+
+    /// Stop timing and add the time since the last call to `start` to the statement at index `s_idx`
+    pub fn stop(&mut self, s_idx: usize) {
+        #[cfg(feature = "timings")]
+        if let Some(start) = self.started_at.take() {
+            self.report.statements[s_idx] += start.elapsed();
+        }
+    }
+
+    #[cfg(feature = "timings")]
+    pub fn finish(mut self) -> TimingReport {
+        if let Some(c) = self.created_at {
+            self.report.total = c.elapsed();
+        }
+        self.report
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     constants::{
         BBS_23_LABEL, BBS_PLUS_LABEL, COMPOSITE_PROOF_CHALLENGE_LABEL, COMPOSITE_PROOF_LABEL,
-        CONTEXT_LABEL, KB_POS_ACCUM_CDH_MEM_LABEL, KB_POS_ACCUM_MEM_LABEL,
+        CONTEXT_LABEL, CREATED_AT_LABEL, KB_POS_ACCUM_CDH_MEM_LABEL, KB_POS_ACCUM_MEM_LABEL,
         KB_UNI_ACCUM_CDH_MEM_LABEL, KB_UNI_ACCUM_CDH_NON_MEM_LABEL, KB_UNI_ACCUM_MEM_LABEL,
         KB_UNI_ACCUM_NON_MEM_LABEL, NONCE_LABEL, VB_ACCUM_CDH_MEM_LABEL,
         VB_ACCUM_CDH_NON_MEM_LABEL, VB_ACCUM_MEM_LABEL, VB_ACCUM_NON_MEM_LABEL,
@@ -24,22 +24,38 @@ use crate::{
             KBUniversalAccumulatorNonMembershipSubProtocol, VBAccumulatorMembershipSubProtocol,
             VBAccumulatorNonMembershipSubProtocol,
         },
+        aggregated_bound_check_bpp::AggregatedBoundCheckBppProtocol,
         bbs_23::PoKBBSSigG1SubProtocol as PoKBBSSig23G1SubProtocol,
         bbs_plus::PoKBBSSigG1SubProtocol,
+        bddt16_kvac::PoKOfBDDT16MACSubProtocol,
+        bloom_membership::BloomMembershipLegoGrothProtocol,
         bound_check_bpp::BoundCheckBppProtocol,
         bound_check_legogroth16::BoundCheckLegoGrothProtocol,
         bound_check_smc::BoundCheckSmcProtocol,
         bound_check_smc_with_kv::BoundCheckSmcWithKVProtocol,
+        committed_inequality::CommittedInequalityProtocol,
         inequality::InequalityProtocol,
+        monotonic::MonotonicProtocol,
+        partial_ped_opening::PartialPedersenOpeningProtocol,
         ps_signature::PSSignaturePoK,
+        public_set_membership::PublicSetMembershipProtocol,
         r1cs_legogorth16::R1CSLegogroth16Protocol,
         saver::SaverProtocol,
         schnorr::SchnorrProtocol,
+        set_membership_smc::SetMembershipSmcProtocol,
     },
+    timings::StatementTimer,
 };
 use ark_ec::{pairing::Pairing, AffineRepr};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{collections::BTreeMap, format, rand::RngCore, vec, vec::Vec};
+use ark_std::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    format,
+    rand::RngCore,
+    slice, vec,
+    vec::Vec,
+};
 use digest::Digest;
 use dock_crypto_utils::{
     randomized_pairing_check::RandomizedPairingChecker,
@@ -48,6 +64,9 @@ use dock_crypto_utils::{
 };
 use saver::encryption::Ciphertext;
 
+#[cfg(feature = "timings")]
+use crate::timings::TimingReport;
+
 /// Passed to the verifier during proof verification
 #[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize, Default)]
 pub struct VerifierConfig {
@@ -109,7 +128,14 @@ where
     E: Pairing,
     G: AffineRepr<ScalarField = E::ScalarField>,
 {
-    /// Verify the `Proof` given the `ProofSpec`, `nonce` and `config`
+    /// Verify the `Proof` given the `ProofSpec`, `nonce` and `config`. The `nonce` and the `ProofSpec`'s
+    /// `context` are hashed into the challenge so verification fails unless they are the same as when the
+    /// proof was created. A verifier wanting replay protection gives the prover a fresh nonce for each
+    /// session and verifies with that nonce.
+    ///
+    /// The challenge is always recomputed from the proof and the `ProofSpec`. A challenge cached from an earlier
+    /// verification can't be trusted without rebuilding the transcript it was derived from, which is what computing
+    /// it costs. That cost is small next to the pairings, which `config` can speed up by batching them.
     pub fn verify<R: RngCore, D: Digest>(
         self,
         rng: &mut R,
@@ -117,6 +143,57 @@ where
         nonce: Option<Vec<u8>>,
         config: VerifierConfig,
     ) -> Result<(), ProofSystemError> {
+        self.verify_and_time::<R, D>(rng, proof_spec, nonce, config)
+            .map(|_| ())
+    }
+
+    /// Same as `Self::verify` but also returns the time taken by each statement. Useful to find out which
+    /// statements dominate the verification time. When `config` enables lazy randomized pairing checks, the
+    /// time taken by the deferred pairing check is only counted in the total.
+    #[cfg(feature = "timings")]
+    pub fn verify_with_timings<R: RngCore, D: Digest>(
+        self,
+        rng: &mut R,
+        proof_spec: ProofSpec<E, G>,
+        nonce: Option<Vec<u8>>,
+        config: VerifierConfig,
+    ) -> Result<TimingReport, ProofSystemError> {
+        self.verify_and_time::<R, D>(rng, proof_spec, nonce, config)
+            .map(|t| t.finish())
+    }
+
+    /// Same as `Self::verify` but also rejects the proof if it wasn't created with a creation time or was
+    /// created more than `max_age` before `now`. `now` and `max_age` must be in the same unit as the
+    /// proof's creation time. As the creation time is part of the challenge, a proof whose creation time
+    /// was changed after its creation fails verification.
+    pub fn verify_with_max_age<R: RngCore, D: Digest>(
+        self,
+        rng: &mut R,
+        proof_spec: ProofSpec<E, G>,
+        nonce: Option<Vec<u8>>,
+        config: VerifierConfig,
+        now: u64,
+        max_age: u64,
+    ) -> Result<(), ProofSystemError> {
+        let created_at = self
+            .created_at
+            .ok_or(ProofSystemError::MissingProofCreationTime)?;
+        if created_at > now {
+            return Err(ProofSystemError::ProofCreatedInFuture(created_at, now));
+        }
+        if now - created_at > max_age {
+            return Err(ProofSystemError::ProofExpired(created_at, now, max_age));
+        }
+        self.verify::<R, D>(rng, proof_spec, nonce, config)
+    }
+
+    fn verify_and_time<R: RngCore, D: Digest>(
+        self,
+        rng: &mut R,
+        proof_spec: ProofSpec<E, G>,
+        nonce: Option<Vec<u8>>,
+        config: VerifierConfig,
+    ) -> Result<StatementTimer, ProofSystemError> {
         match config.use_lazy_randomized_pairing_checks {
             Some(b) => {
                 let pairing_checker = RandomizedPairingChecker::new_using_rng(rng, b);
@@ -132,17 +209,13 @@ where
         proof_spec: ProofSpec<E, G>,
         nonce: Option<Vec<u8>>,
         mut pairing_checker: Option<RandomizedPairingChecker<E>>,
-    ) -> Result<(), ProofSystemError> {
+    ) -> Result<StatementTimer, ProofSystemError> {
+        let mut timer = StatementTimer::new(proof_spec.statements.len());
+
         proof_spec.validate()?;
 
-        // Number of statement proofs is less than number of statements which means some statements
-        // are not satisfied.
-        if proof_spec.statements.len() > self.statement_proofs.len() {
-            return Err(ProofSystemError::UnsatisfiedStatements(
-                proof_spec.statements.len(),
-                self.statement_proofs.len(),
-            ));
-        }
+        // Reject malformed proofs before doing any expensive work like preparing parameters for pairings
+        self.check_structure(&proof_spec)?;
 
         let mut transcript = MerlinTranscript::new(COMPOSITE_PROOF_LABEL);
 
@@ -186,6 +259,8 @@ where
             bound_check_smc_comm,
             ineq_comm,
         ) = proof_spec.derive_commitment_keys()?;
+        let pseudonym_bases = proof_spec.derive_pseudonym_bases();
+        let aggregated_ps_pks = proof_spec.derive_aggregated_ps_public_keys()?;
 
         // Prepare required parameters for pairings
         let (
@@ -206,14 +281,11 @@ where
         ) = proof_spec.derive_prepared_parameters()?;
 
         // All the distinct equalities in `ProofSpec`
-        let mut witness_equalities = vec![];
-
-        if !proof_spec.meta_statements.is_empty() {
-            let disjoint_equalities = proof_spec.meta_statements.disjoint_witness_equalities();
-            for eq_wits in disjoint_equalities {
-                witness_equalities.push(eq_wits.0);
-            }
-        }
+        let witness_equalities = proof_spec
+            .disjoint_witness_equalities()
+            .into_iter()
+            .map(|eq_wits| eq_wits.0)
+            .collect::<Vec<_>>();
 
         // This will hold the response for each witness equality. If there is no response for some witness
         // equality, it will contain `None` corresponding to that.
@@ -227,6 +299,9 @@ where
         if let Some(ctx) = &proof_spec.context {
             transcript.append_message(CONTEXT_LABEL, ctx);
         }
+        if let Some(t) = self.created_at.as_ref() {
+            transcript.append(CREATED_AT_LABEL, t);
+        }
 
         // Get challenge contribution for each statement and check if response is equal for all witnesses.
         for (s_idx, (statement, proof)) in proof_spec
@@ -236,6 +311,7 @@ where
             .zip(self.statement_proofs.iter())
             .enumerate()
         {
+            timer.start();
             match statement {
                 Statement::PoKBBSSignatureG1(s) => match proof {
                     StatementProof::PoKBBSSignatureG1(p) => {
@@ -533,6 +609,34 @@ where
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
+                Statement::CommittedMessageEquality(s) => match proof {
+                    StatementProof::PedersenCommitment(p) => {
+                        let comm_key = s.get_commitment_key(&proof_spec.setup_params, s_idx)?;
+                        // Check witness equalities for this statement, including the implied one.
+                        for i in 0..comm_key.len() {
+                            for (j, eq) in witness_equalities.iter().enumerate() {
+                                if eq.contains(&(s_idx, i)) {
+                                    let r = p.response.get_response(i)?;
+                                    Self::check_response_for_equality(
+                                        s_idx,
+                                        i,
+                                        j,
+                                        &mut responses_for_equalities,
+                                        r,
+                                    )?;
+                                }
+                            }
+                        }
+
+                        SchnorrProtocol::compute_challenge_contribution(
+                            comm_key,
+                            &s.commitment,
+                            &p.t,
+                            &mut transcript,
+                        )?;
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
                 Statement::SaverVerifier(s) => match proof {
                     StatementProof::Saver(p) => {
                         check_resp_for_equalities_with_err!(
@@ -611,8 +715,41 @@ where
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
+                Statement::BloomMembershipLegoGroth16Verifier(s) => match proof {
+                    StatementProof::BloomMembershipLegoGroth16(p) => {
+                        check_resp_for_equalities_with_err!(
+                            witness_equalities,
+                            s_idx,
+                            p,
+                            get_schnorr_response_for_message,
+                            Self,
+                            responses_for_equalities
+                        );
+
+                        let comm_key = bound_check_comm.get(s_idx).unwrap();
+                        BloomMembershipLegoGrothProtocol::compute_challenge_contribution(
+                            comm_key,
+                            p,
+                            &mut transcript,
+                        )?;
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
                 Statement::R1CSCircomVerifier(s) => {
                     let verifying_key = s.get_verifying_key(&proof_spec.setup_params, s_idx)?;
+                    // Only the committed inputs have responses so equality of any other input can't be checked
+                    let commit_witness_count = verifying_key.commit_witness_count as usize;
+                    if let Some((_, i)) = witness_equalities
+                        .iter()
+                        .flatten()
+                        .find(|(i, j)| *i == s_idx && *j >= commit_witness_count)
+                    {
+                        return Err(ProofSystemError::R1CSInputNotCommitted(
+                            s_idx,
+                            *i,
+                            commit_witness_count,
+                        ));
+                    }
                     match proof {
                         StatementProof::R1CSLegoGroth16(p) => {
                             for i in 0..witness_equalities.len() {
@@ -690,6 +827,35 @@ where
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
+                Statement::PoKPSAggregateSignature(s) => match proof {
+                    StatementProof::PoKPSSignature(p) => {
+                        let revealed_msg_ids: Vec<_> =
+                            s.revealed_messages.keys().copied().collect();
+                        let sig_params = s.get_sig_params(&proof_spec.setup_params, s_idx)?;
+                        let pk = aggregated_ps_pks.get(&s_idx).unwrap();
+                        // Check witness equalities for this statement.
+                        for i in 0..sig_params.supported_message_count() {
+                            let w_ref = (s_idx, i);
+                            for (j, eq) in witness_equalities.iter().enumerate() {
+                                if eq.contains(&w_ref) {
+                                    let resp = p.response_for_message(
+                                        i,
+                                        revealed_msg_ids.iter().copied(),
+                                    )?;
+                                    Self::check_response_for_equality(
+                                        s_idx,
+                                        i,
+                                        j,
+                                        &mut responses_for_equalities,
+                                        resp,
+                                    )?;
+                                }
+                            }
+                        }
+                        p.challenge_contribution(&mut transcript, pk, sig_params)?;
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
                 Statement::BoundCheckBpp(s) => match proof {
                     StatementProof::BoundCheckBpp(p) => {
                         check_resp_for_equalities_with_err!(
@@ -712,6 +878,62 @@ where
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
+                Statement::Monotonic(s) => match proof {
+                    StatementProof::Monotonic(p) => {
+                        let comm_key = bound_check_bpp_comm.get(s_idx).unwrap();
+                        MonotonicProtocol::<G>::compute_challenge_contribution(
+                            s_idx,
+                            s.num_values,
+                            comm_key.as_slice(),
+                            p,
+                            &mut transcript,
+                        )?;
+                        // Check witness equalities for this statement.
+                        for i in 0..s.num_values {
+                            for (j, eq) in witness_equalities.iter().enumerate() {
+                                if eq.contains(&(s_idx, i)) {
+                                    let resp = p.get_schnorr_response_for_message(i)?;
+                                    Self::check_response_for_equality(
+                                        s_idx,
+                                        i,
+                                        j,
+                                        &mut responses_for_equalities,
+                                        resp,
+                                    )?;
+                                }
+                            }
+                        }
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::AggregatedBoundCheckBpp(s) => match proof {
+                    StatementProof::AggregatedBoundCheckBpp(p) => {
+                        let comm_key = bound_check_bpp_comm.get(s_idx).unwrap();
+                        AggregatedBoundCheckBppProtocol::<G>::compute_challenge_contribution(
+                            s_idx,
+                            &s.bounds,
+                            comm_key.as_slice(),
+                            p,
+                            &mut transcript,
+                        )?;
+                        // Check witness equalities for this statement.
+                        for i in 0..s.bounds.len() {
+                            for (j, eq) in witness_equalities.iter().enumerate() {
+                                if eq.contains(&(s_idx, i)) {
+                                    let resp = p.get_schnorr_response_for_message(i)?;
+                                    Self::check_response_for_equality(
+                                        s_idx,
+                                        i,
+                                        j,
+                                        &mut responses_for_equalities,
+                                        resp,
+                                    )?;
+                                }
+                            }
+                        }
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
                 Statement::BoundCheckSmc(s) => match proof {
                     StatementProof::BoundCheckSmc(p) => {
                         check_resp_for_equalities_with_err!(
@@ -733,6 +955,27 @@ where
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
+                Statement::SetMembershipSmc(s) => match proof {
+                    StatementProof::SetMembershipSmc(p) => {
+                        check_resp_for_equalities_with_err!(
+                            witness_equalities,
+                            s_idx,
+                            p,
+                            get_schnorr_response_for_message,
+                            Self,
+                            responses_for_equalities
+                        );
+
+                        let comm_key_slice = bound_check_smc_comm.get(s_idx).unwrap();
+                        SetMembershipSmcProtocol::compute_challenge_contribution(
+                            comm_key_slice.as_slice(),
+                            p,
+                            derived_smc_param.get(s_idx).unwrap().clone(),
+                            &mut transcript,
+                        )?;
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
                 Statement::BoundCheckSmcWithKVVerifier(s) => match proof {
                     StatementProof::BoundCheckSmcWithKV(p) => {
                         check_resp_for_equalities_with_err!(
@@ -776,567 +1019,966 @@ where
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::DetachedAccumulatorMembershipVerifier(s) => match proof {
-                    StatementProof::DetachedAccumulatorMembership(p) => {
-                        check_resp_for_equalities!(
+                Statement::PublicSetMembership(s) => match proof {
+                    StatementProof::PublicSetMembership(p) => {
+                        check_resp_for_equalities_with_err!(
                             witness_equalities,
                             s_idx,
-                            p.accum_proof,
-                            get_schnorr_response_for_element,
+                            p,
+                            get_schnorr_response_for_message,
                             Self,
                             responses_for_equalities
                         );
-                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
-                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
-                        let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
-                        transcript.set_label(VB_ACCUM_MEM_LABEL);
-                        p.accum_proof.challenge_contribution(
-                            &p.accumulator,
-                            pk,
-                            params,
-                            prk,
+
+                        let comm_key_slice = ineq_comm.get(s_idx).unwrap();
+                        PublicSetMembershipProtocol::compute_challenge_contribution(
+                            s_idx,
+                            comm_key_slice.as_slice(),
+                            p,
+                            &s.set,
+                            s.get_comm_key(&proof_spec.setup_params, s_idx)?,
                             &mut transcript,
                         )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::DetachedAccumulatorNonMembershipVerifier(s) => match proof {
-                    StatementProof::DetachedAccumulatorNonMembership(p) => {
-                        check_resp_for_equalities!(
-                            witness_equalities,
-                            s_idx,
-                            p.accum_proof,
-                            get_schnorr_response_for_element,
-                            Self,
-                            responses_for_equalities
-                        );
-                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
-                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
-                        let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
-                        transcript.set_label(VB_ACCUM_NON_MEM_LABEL);
-                        p.accum_proof.challenge_contribution(
-                            &p.accumulator,
-                            pk,
-                            params,
-                            prk,
+                Statement::CommittedInequality(s) => match proof {
+                    StatementProof::CommittedInequality(p) => {
+                        // Check witness equalities for this statement.
+                        for i in 0..2 {
+                            for (j, eq) in witness_equalities.iter().enumerate() {
+                                if eq.contains(&(s_idx, i)) {
+                                    let resp = p.get_schnorr_response_for_message(i)?;
+                                    Self::check_response_for_equality(
+                                        s_idx,
+                                        i,
+                                        j,
+                                        &mut responses_for_equalities,
+                                        resp,
+                                    )?;
+                                }
+                            }
+                        }
+
+                        let comm_key_slice = ineq_comm.get(s_idx).unwrap();
+                        CommittedInequalityProtocol::compute_challenge_contribution(
+                            comm_key_slice.as_slice(),
+                            p,
+                            s.get_comm_key(&proof_spec.setup_params, s_idx)?,
                             &mut transcript,
                         )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                _ => return Err(ProofSystemError::InvalidStatement),
-            }
-        }
-
-        // If even one of witness equality had no corresponding response, it means that wasn't satisfied
-        // and proof should not verify
-        if responses_for_equalities.iter().any(|r| r.is_none()) {
-            return Err(ProofSystemError::UnsatisfiedWitnessEqualities(
-                responses_for_equalities
-                    .iter()
-                    .enumerate()
-                    .filter_map(|(i, r)| match r {
-                        None => Some(witness_equalities[i].clone()),
-                        _ => None,
-                    })
-                    .collect::<Vec<_>>(),
-            ));
-        }
+                Statement::PartialPedersenOpening(s) => match proof {
+                    StatementProof::PedersenCommitment(p) => {
+                        let comm_key = s.get_commitment_key(&proof_spec.setup_params, s_idx)?;
+                        // Check witness equalities for this statement. The response for a hidden value is
+                        // at its index among the hidden values.
+                        for (k, i) in s.hidden_positions(comm_key.len()).into_iter().enumerate() {
+                            for (j, eq) in witness_equalities.iter().enumerate() {
+                                if eq.contains(&(s_idx, i)) {
+                                    let r = p.response.get_response(k)?;
+                                    Self::check_response_for_equality(
+                                        s_idx,
+                                        i,
+                                        j,
+                                        &mut responses_for_equalities,
+                                        r,
+                                    )?;
+                                }
+                            }
+                        }
 
-        // Verifier independently generates challenge
-        let challenge = transcript.challenge_scalar(COMPOSITE_PROOF_CHALLENGE_LABEL);
+                        PartialPedersenOpeningProtocol::compute_challenge_contribution(
+                            comm_key,
+                            s,
+                            &p.t,
+                            &mut transcript,
+                        )?;
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::SignatureCommitmentConsistency(s) => match proof {
+                    StatementProof::PedersenCommitment(p) => {
+                        let comm_key = s.get_commitment_key(&proof_spec.setup_params, s_idx)?;
+                        s.check_positions(comm_key.len(), s_idx)?;
+                        // Check witness equalities for this statement, including the implied ones.
+                        for i in 0..comm_key.len() {
+                            for (j, eq) in witness_equalities.iter().enumerate() {
+                                if eq.contains(&(s_idx, i)) {
+                                    let r = p.response.get_response(i)?;
+                                    Self::check_response_for_equality(
+                                        s_idx,
+                                        i,
+                                        j,
+                                        &mut responses_for_equalities,
+                                        r,
+                                    )?;
+                                }
+                            }
+                        }
 
-        // Verify the proof for each statement
-        for (s_idx, (statement, proof)) in proof_spec
-            .statements
-            .0
-            .iter()
-            .zip(self.statement_proofs.into_iter())
-            .enumerate()
-        {
-            match statement {
-                Statement::PoKBBSSignatureG1(s) => match proof {
-                    StatementProof::PoKBBSSignatureG1(ref p) => {
-                        let sig_params = s.get_sig_params(&proof_spec.setup_params, s_idx)?;
-                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = PoKBBSSigG1SubProtocol::new(
-                            s_idx,
-                            &s.revealed_messages,
-                            sig_params,
-                            pk,
-                        );
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            derived_bbs_pk.get(s_idx).unwrap().clone(),
-                            derived_bbs_plus_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                        )
-                        .map_err(|e| {
-                            ProofSystemError::BBSPlusProofContributionFailed(s_idx as u32, e)
-                        })?
+                        SchnorrProtocol::compute_challenge_contribution(
+                            comm_key,
+                            &s.commitment,
+                            &p.t,
+                            &mut transcript,
+                        )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::PoKBBSSignature23G1(s) => match proof {
-                    StatementProof::PoKBBSSignature23G1(ref p) => {
-                        let sig_params = s.get_sig_params(&proof_spec.setup_params, s_idx)?;
-                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = PoKBBSSig23G1SubProtocol::new(
-                            s_idx,
+                Statement::PoKBDDT16MACFullVerifier(s) => match proof {
+                    StatementProof::PoKOfBDDT16MAC(p) => {
+                        let revealed_msg_ids =
+                            s.revealed_messages.keys().copied().collect::<BTreeSet<_>>();
+                        let mac_params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        // Check witness equalities for this statement.
+                        for i in 0..mac_params.supported_message_count() {
+                            let w_ref = (s_idx, i);
+                            for (j, eq) in witness_equalities.iter().enumerate() {
+                                if eq.contains(&w_ref) {
+                                    let resp = p.get_resp_for_message(i, &revealed_msg_ids)?;
+                                    Self::check_response_for_equality(
+                                        s_idx,
+                                        i,
+                                        j,
+                                        &mut responses_for_equalities,
+                                        resp,
+                                    )?;
+                                }
+                            }
+                        }
+                        p.challenge_contribution(
                             &s.revealed_messages,
-                            sig_params,
-                            pk,
-                        );
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            derived_bbs_pk.get(s_idx).unwrap().clone(),
-                            derived_bbs_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                        )
-                        .map_err(|e| {
-                            ProofSystemError::BBSProofContributionFailed(s_idx as u32, e)
-                        })?
+                            mac_params,
+                            &PoKOfBDDT16MACSubProtocol::<E>::extra_base(),
+                            &mut transcript,
+                        )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::VBAccumulatorMembership(s) => match proof {
-                    StatementProof::VBAccumulatorMembership(ref p) => {
-                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
-                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
-                        let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = VBAccumulatorMembershipSubProtocol::new(
+                Statement::DetachedAccumulatorMembershipVerifier(s) => match proof {
+                    StatementProof::DetachedAccumulatorMembership(p) => {
+                        check_resp_for_equalities!(
+                            witness_equalities,
                             s_idx,
-                            params,
-                            pk,
-                            prk,
-                            s.accumulator_value,
+                            p.accum_proof,
+                            get_schnorr_response_for_element,
+                            Self,
+                            responses_for_equalities
                         );
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            derived_accum_pk.get(s_idx).unwrap().clone(),
-                            derived_accum_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                        )?
-                    }
-                    _ => err_incompat_proof!(s_idx, s, proof),
-                },
-                Statement::VBAccumulatorNonMembership(s) => match proof {
-                    StatementProof::VBAccumulatorNonMembership(ref p) => {
                         let params = s.get_params(&proof_spec.setup_params, s_idx)?;
                         let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
                         let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = VBAccumulatorNonMembershipSubProtocol::new(
-                            s_idx,
-                            params,
+                        transcript.set_label(VB_ACCUM_MEM_LABEL);
+                        p.accum_proof.challenge_contribution(
+                            &p.accumulator,
                             pk,
+                            params,
                             prk,
-                            s.accumulator_value,
-                        );
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            derived_accum_pk.get(s_idx).unwrap().clone(),
-                            derived_accum_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                        )?
+                            &mut transcript,
+                        )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::KBUniversalAccumulatorMembership(s) => match proof {
-                    StatementProof::KBUniversalAccumulatorMembership(ref p) => {
-                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
-                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
-                        let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = KBUniversalAccumulatorMembershipSubProtocol::new(
+                Statement::DetachedAccumulatorNonMembershipVerifier(s) => match proof {
+                    StatementProof::DetachedAccumulatorNonMembership(p) => {
+                        check_resp_for_equalities!(
+                            witness_equalities,
                             s_idx,
-                            params,
-                            pk,
-                            prk,
-                            s.accumulator_value,
+                            p.accum_proof,
+                            get_schnorr_response_for_element,
+                            Self,
+                            responses_for_equalities
                         );
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            derived_accum_pk.get(s_idx).unwrap().clone(),
-                            derived_accum_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                        )?
-                    }
-                    _ => err_incompat_proof!(s_idx, s, proof),
-                },
-                Statement::KBUniversalAccumulatorNonMembership(s) => match proof {
-                    StatementProof::KBUniversalAccumulatorNonMembership(ref p) => {
                         let params = s.get_params(&proof_spec.setup_params, s_idx)?;
                         let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
                         let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = KBUniversalAccumulatorNonMembershipSubProtocol::new(
-                            s_idx,
-                            params,
+                        transcript.set_label(VB_ACCUM_NON_MEM_LABEL);
+                        p.accum_proof.challenge_contribution(
+                            &p.accumulator,
                             pk,
+                            params,
                             prk,
-                            s.accumulator_value,
-                        );
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            derived_accum_pk.get(s_idx).unwrap().clone(),
-                            derived_accum_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                        )?
+                            &mut transcript,
+                        )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::VBAccumulatorMembershipCDHVerifier(s) => match proof {
-                    StatementProof::VBAccumulatorMembershipCDH(ref p) => {
-                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
-                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = VBAccumulatorMembershipCDHSubProtocol::new_for_verifier(
+                Statement::DetachedAccumulatorMembershipCDHVerifier(s) => match proof {
+                    StatementProof::DetachedAccumulatorMembershipCDH(p) => {
+                        check_resp_for_equalities!(
+                            witness_equalities,
                             s_idx,
-                            s.accumulator_value,
-                            params,
-                            pk,
+                            p.accum_proof,
+                            get_schnorr_response_for_element,
+                            Self,
+                            responses_for_equalities
                         );
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            derived_accum_pk.get(s_idx).unwrap().clone(),
-                            derived_accum_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                        )?
+                        transcript.set_label(VB_ACCUM_CDH_MEM_LABEL);
+                        p.accum_proof
+                            .challenge_contribution(p.accumulator, &mut transcript)?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::VBAccumulatorNonMembershipCDHVerifier(s) => match proof {
-                    StatementProof::VBAccumulatorNonMembershipCDH(ref p) => {
-                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
-                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = VBAccumulatorNonMembershipCDHSubProtocol::new_for_verifier(
-                            s_idx,
-                            s.accumulator_value,
-                            s.Q,
-                            params,
-                            pk,
-                        );
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            derived_accum_pk.get(s_idx).unwrap().clone(),
-                            derived_accum_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                        )?
+                Statement::ScopedPseudonym(s) => match proof {
+                    StatementProof::PedersenCommitment(p) => {
+                        let r = p.response.get_response(0)?;
+                        for (j, eq) in witness_equalities.iter().enumerate() {
+                            if eq.contains(&(s_idx, 0)) {
+                                Self::check_response_for_equality(
+                                    s_idx,
+                                    0,
+                                    j,
+                                    &mut responses_for_equalities,
+                                    r,
+                                )?;
+                            }
+                        }
+                        SchnorrProtocol::compute_challenge_contribution(
+                            slice::from_ref(pseudonym_bases.get(&s_idx).unwrap()),
+                            &s.pseudonym,
+                            &p.t,
+                            &mut transcript,
+                        )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::KBUniversalAccumulatorMembershipCDHVerifier(s) => match proof {
-                    StatementProof::KBUniversalAccumulatorMembershipCDH(ref p) => {
-                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
-                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = KBUniversalAccumulatorMembershipCDHSubProtocol::new_for_verifier(
-                            s_idx,
-                            s.accumulator_value,
-                            params,
-                            pk,
-                        );
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            derived_accum_pk.get(s_idx).unwrap().clone(),
-                            derived_accum_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                        )?
+                Statement::DisclosedLinearCombination(s) => match proof {
+                    StatementProof::PedersenCommitment(p) => {
+                        let r = p.response.get_response(0)?;
+                        for (j, eq) in witness_equalities.iter().enumerate() {
+                            if eq.contains(&(s_idx, 0)) {
+                                Self::check_response_for_equality(
+                                    s_idx,
+                                    0,
+                                    j,
+                                    &mut responses_for_equalities,
+                                    r,
+                                )?;
+                            }
+                        }
+                        SchnorrProtocol::compute_challenge_contribution(
+                            slice::from_ref(&s.base),
+                            &s.commitment(),
+                            &p.t,
+                            &mut transcript,
+                        )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::KBUniversalAccumulatorNonMembershipCDHVerifier(s) => match proof {
-                    StatementProof::KBUniversalAccumulatorNonMembershipCDH(ref p) => {
-                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
-                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
-                        let sp =
-                            KBUniversalAccumulatorNonMembershipCDHSubProtocol::new_for_verifier(
+                _ => return Err(ProofSystemError::InvalidStatement),
+            }
+            timer.stop(s_idx);
+        }
+
+        // If even one of witness equality had no corresponding response, it means that wasn't satisfied
+        // and proof should not verify
+        if responses_for_equalities.iter().any(|r| r.is_none()) {
+            return Err(ProofSystemError::UnsatisfiedWitnessEqualities(
+                responses_for_equalities
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, r)| match r {
+                        None => Some(witness_equalities[i].clone()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>(),
+            ));
+        }
+
+        // Verifier independently generates challenge
+        let challenge = transcript.challenge_scalar(COMPOSITE_PROOF_CHALLENGE_LABEL);
+
+        // Verify the proof for each statement
+        for (s_idx, (statement, proof)) in proof_spec
+            .statements
+            .0
+            .iter()
+            .zip(self.statement_proofs.into_iter())
+            .enumerate()
+        {
+            timer.start();
+            // Any error while verifying the proof of this statement is wrapped with the statement's index and kind
+            let mut verify_statement_proof = || -> Result<(), ProofSystemError> {
+                match statement {
+                    Statement::PoKBBSSignatureG1(s) => match proof {
+                        StatementProof::PoKBBSSignatureG1(ref p) => {
+                            let sig_params = s.get_sig_params(&proof_spec.setup_params, s_idx)?;
+                            let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                            let sp = PoKBBSSigG1SubProtocol::new(
+                                s_idx,
+                                &s.revealed_messages,
+                                sig_params,
+                                pk,
+                            );
+                            sp.verify_proof_contribution(
+                                &challenge,
+                                p,
+                                derived_bbs_pk.get(s_idx).unwrap().clone(),
+                                derived_bbs_plus_param.get(s_idx).unwrap().clone(),
+                                &mut pairing_checker,
+                            )
+                            .map_err(|e| {
+                                ProofSystemError::BBSPlusProofContributionFailed(s_idx as u32, e)
+                            })?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::PoKBBSSignature23G1(s) => match proof {
+                        StatementProof::PoKBBSSignature23G1(ref p) => {
+                            let sig_params = s.get_sig_params(&proof_spec.setup_params, s_idx)?;
+                            let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                            let sp = PoKBBSSig23G1SubProtocol::new(
+                                s_idx,
+                                &s.revealed_messages,
+                                sig_params,
+                                pk,
+                            );
+                            sp.verify_proof_contribution(
+                                &challenge,
+                                p,
+                                derived_bbs_pk.get(s_idx).unwrap().clone(),
+                                derived_bbs_param.get(s_idx).unwrap().clone(),
+                                &mut pairing_checker,
+                            )
+                            .map_err(|e| {
+                                ProofSystemError::BBSProofContributionFailed(s_idx as u32, e)
+                            })?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::VBAccumulatorMembership(s) => match proof {
+                        StatementProof::VBAccumulatorMembership(ref p) => {
+                            let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                            let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                            let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                            let sp = VBAccumulatorMembershipSubProtocol::new(
+                                s_idx,
+                                params,
+                                pk,
+                                prk,
+                                s.accumulator_value,
+                            );
+                            sp.verify_proof_contribution(
+                                &challenge,
+                                p,
+                                derived_accum_pk.get(s_idx).unwrap().clone(),
+                                derived_accum_param.get(s_idx).unwrap().clone(),
+                                &mut pairing_checker,
+                            )?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::VBAccumulatorNonMembership(s) => match proof {
+                        StatementProof::VBAccumulatorNonMembership(ref p) => {
+                            let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                            let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                            let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                            let sp = VBAccumulatorNonMembershipSubProtocol::new(
+                                s_idx,
+                                params,
+                                pk,
+                                prk,
+                                s.accumulator_value,
+                            );
+                            sp.verify_proof_contribution(
+                                &challenge,
+                                p,
+                                derived_accum_pk.get(s_idx).unwrap().clone(),
+                                derived_accum_param.get(s_idx).unwrap().clone(),
+                                &mut pairing_checker,
+                            )?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::KBUniversalAccumulatorMembership(s) => match proof {
+                        StatementProof::KBUniversalAccumulatorMembership(ref p) => {
+                            let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                            let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                            let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                            let sp = KBUniversalAccumulatorMembershipSubProtocol::new(
+                                s_idx,
+                                params,
+                                pk,
+                                prk,
+                                s.accumulator_value,
+                            );
+                            sp.verify_proof_contribution(
+                                &challenge,
+                                p,
+                                derived_accum_pk.get(s_idx).unwrap().clone(),
+                                derived_accum_param.get(s_idx).unwrap().clone(),
+                                &mut pairing_checker,
+                            )?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::KBUniversalAccumulatorNonMembership(s) => match proof {
+                        StatementProof::KBUniversalAccumulatorNonMembership(ref p) => {
+                            let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                            let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                            let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                            let sp = KBUniversalAccumulatorNonMembershipSubProtocol::new(
+                                s_idx,
+                                params,
+                                pk,
+                                prk,
+                                s.accumulator_value,
+                            );
+                            sp.verify_proof_contribution(
+                                &challenge,
+                                p,
+                                derived_accum_pk.get(s_idx).unwrap().clone(),
+                                derived_accum_param.get(s_idx).unwrap().clone(),
+                                &mut pairing_checker,
+                            )?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::VBAccumulatorMembershipCDHVerifier(s) => match proof {
+                        StatementProof::VBAccumulatorMembershipCDH(ref p) => {
+                            let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                            let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                            let sp = VBAccumulatorMembershipCDHSubProtocol::new_for_verifier(
+                                s_idx,
+                                s.accumulator_value,
+                                params,
+                                pk,
+                            );
+                            sp.verify_proof_contribution(
+                                &challenge,
+                                p,
+                                derived_accum_pk.get(s_idx).unwrap().clone(),
+                                derived_accum_param.get(s_idx).unwrap().clone(),
+                                &mut pairing_checker,
+                            )?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::VBAccumulatorNonMembershipCDHVerifier(s) => match proof {
+                        StatementProof::VBAccumulatorNonMembershipCDH(ref p) => {
+                            let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                            let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                            let sp = VBAccumulatorNonMembershipCDHSubProtocol::new_for_verifier(
+                                s_idx,
+                                s.accumulator_value,
+                                s.Q,
+                                params,
+                                pk,
+                            );
+                            sp.verify_proof_contribution(
+                                &challenge,
+                                p,
+                                derived_accum_pk.get(s_idx).unwrap().clone(),
+                                derived_accum_param.get(s_idx).unwrap().clone(),
+                                &mut pairing_checker,
+                            )?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::KBUniversalAccumulatorMembershipCDHVerifier(s) => match proof {
+                        StatementProof::KBUniversalAccumulatorMembershipCDH(ref p) => {
+                            let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                            let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                            let sp =
+                                KBUniversalAccumulatorMembershipCDHSubProtocol::new_for_verifier(
+                                    s_idx,
+                                    s.accumulator_value,
+                                    params,
+                                    pk,
+                                );
+                            sp.verify_proof_contribution(
+                                &challenge,
+                                p,
+                                derived_accum_pk.get(s_idx).unwrap().clone(),
+                                derived_accum_param.get(s_idx).unwrap().clone(),
+                                &mut pairing_checker,
+                            )?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::KBUniversalAccumulatorNonMembershipCDHVerifier(s) => match proof {
+                        StatementProof::KBUniversalAccumulatorNonMembershipCDH(ref p) => {
+                            let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                            let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                            let sp =
+                                KBUniversalAccumulatorNonMembershipCDHSubProtocol::new_for_verifier(
+                                    s_idx,
+                                    s.accumulator_value,
+                                    params,
+                                    pk,
+                                );
+                            sp.verify_proof_contribution(
+                                &challenge,
+                                p,
+                                derived_accum_pk.get(s_idx).unwrap().clone(),
+                                derived_accum_param.get(s_idx).unwrap().clone(),
+                                &mut pairing_checker,
+                            )?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::KBPositiveAccumulatorMembership(s) => match proof {
+                        StatementProof::KBPositiveAccumulatorMembership(ref p) => {
+                            let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                            let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                            let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                            let sp = KBPositiveAccumulatorMembershipSubProtocol::new(
                                 s_idx,
+                                params,
+                                pk,
+                                prk,
                                 s.accumulator_value,
+                            );
+                            sp.verify_proof_contribution(
+                                &challenge,
+                                p,
+                                derived_kb_accum_pk.get(s_idx).unwrap().clone(),
+                                derived_kb_accum_param.get(s_idx).unwrap().clone(),
+                                &mut pairing_checker,
+                            )?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::KBPositiveAccumulatorMembershipCDH(s) => match proof {
+                        StatementProof::KBPositiveAccumulatorMembershipCDH(ref p) => {
+                            let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                            let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                            let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                            let sp = KBPositiveAccumulatorMembershipCDHSubProtocol::new(
+                                s_idx,
                                 params,
                                 pk,
+                                prk,
+                                s.accumulator_value,
                             );
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            derived_accum_pk.get(s_idx).unwrap().clone(),
-                            derived_accum_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                        )?
+                            sp.verify_proof_contribution(
+                                &challenge,
+                                p,
+                                derived_kb_accum_pk.get(s_idx).unwrap().clone(),
+                                derived_kb_accum_param.get(s_idx).unwrap().clone(),
+                                &mut pairing_checker,
+                            )?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::PedersenCommitment(s) => match proof {
+                        StatementProof::PedersenCommitment(ref p) => {
+                            let comm_key = s.get_commitment_key(&proof_spec.setup_params, s_idx)?;
+                            let sp = SchnorrProtocol::new(s_idx, comm_key, s.commitment);
+                            sp.verify_proof_contribution(&challenge, p).map_err(|e| {
+                                ProofSystemError::SchnorrProofContributionFailed(s_idx as u32, e)
+                            })?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::CommittedMessageEquality(s) => match proof {
+                        StatementProof::PedersenCommitment(ref p) => {
+                            let comm_key = s.get_commitment_key(&proof_spec.setup_params, s_idx)?;
+                            let sp = SchnorrProtocol::new(s_idx, comm_key, s.commitment);
+                            sp.verify_proof_contribution(&challenge, p).map_err(|e| {
+                                ProofSystemError::SchnorrProofContributionFailed(s_idx as u32, e)
+                            })?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::SaverVerifier(s) => {
+                        let enc_gens = s.get_encryption_gens(&proof_spec.setup_params, s_idx)?;
+                        let comm_gens =
+                            s.get_chunked_commitment_gens(&proof_spec.setup_params, s_idx)?;
+                        let enc_key = s.get_encryption_key(&proof_spec.setup_params, s_idx)?;
+                        let vk = s.get_snark_verifying_key(&proof_spec.setup_params, s_idx)?;
+                        let sp = SaverProtocol::new_for_verifier(
+                            s_idx,
+                            s.chunk_bit_size,
+                            enc_gens,
+                            comm_gens,
+                            enc_key,
+                            vk,
+                        );
+                        let ek_comm_key = ek_comm.get(s_idx).unwrap();
+                        let cc_keys = chunked_comm.get(s_idx).unwrap();
+
+                        match proof {
+                            StatementProof::Saver(ref saver_proof) => sp
+                                .verify_proof_contribution(
+                                    &challenge,
+                                    saver_proof,
+                                    ek_comm_key,
+                                    &cc_keys.0,
+                                    &cc_keys.1,
+                                    derived_saver_vk.get(s_idx).unwrap(),
+                                    derived_gens.get(s_idx).unwrap().clone(),
+                                    derived_ek.get(s_idx).unwrap().clone(),
+                                    &mut pairing_checker,
+                                )?,
+                            StatementProof::SaverWithAggregation(ref saver_proof) => {
+                                let agg_idx = agg_saver_stmts
+                                    .get(&s_idx)
+                                    .ok_or(ProofSystemError::InvalidStatementProofIndex(s_idx))?;
+                                agg_saver[*agg_idx].push(saver_proof.ciphertext.clone());
+                                sp.verify_ciphertext_and_commitment(
+                                    &challenge,
+                                    &saver_proof.ciphertext,
+                                    saver_proof.comm_combined,
+                                    saver_proof.comm_chunks,
+                                    &saver_proof.sp_ciphertext,
+                                    &saver_proof.sp_chunks,
+                                    &saver_proof.sp_combined,
+                                    ek_comm_key,
+                                    &cc_keys.0,
+                                    &cc_keys.1,
+                                )?
+                            }
+                            _ => {
+                                return Err(ProofSystemError::ProofIncompatibleWithStatement(
+                                    s_idx,
+                                    format!("{:?}", proof),
+                                    format!("{:?}", s),
+                                ))
+                            }
+                        }
                     }
-                    _ => err_incompat_proof!(s_idx, s, proof),
-                },
-                Statement::KBPositiveAccumulatorMembership(s) => match proof {
-                    StatementProof::KBPositiveAccumulatorMembership(ref p) => {
-                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
-                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
-                        let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = KBPositiveAccumulatorMembershipSubProtocol::new(
+                    Statement::BoundCheckLegoGroth16Verifier(s) => {
+                        let verifying_key = s.get_verifying_key(&proof_spec.setup_params, s_idx)?;
+                        let sp = BoundCheckLegoGrothProtocol::new_for_verifier(
                             s_idx,
-                            params,
-                            pk,
-                            prk,
-                            s.accumulator_value,
+                            s.min,
+                            s.max,
+                            verifying_key,
                         );
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            derived_kb_accum_pk.get(s_idx).unwrap().clone(),
-                            derived_kb_accum_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                        )?
+                        let comm_key = bound_check_comm.get(s_idx).unwrap();
+                        match proof {
+                            StatementProof::BoundCheckLegoGroth16(ref bc_proof) => sp
+                                .verify_proof_contribution(
+                                    &challenge,
+                                    bc_proof,
+                                    comm_key,
+                                    derived_lego_vk.get(s_idx).unwrap(),
+                                    &mut pairing_checker,
+                                )?,
+                            StatementProof::BoundCheckLegoGroth16WithAggregation(ref bc_proof) => {
+                                let pub_inp = vec![
+                                    E::ScalarField::from(sp.min),
+                                    E::ScalarField::from(sp.max),
+                                ];
+                                let agg_idx = agg_lego_stmts
+                                    .get(&s_idx)
+                                    .ok_or(ProofSystemError::InvalidStatementProofIndex(s_idx))?;
+                                agg_lego[*agg_idx].0.push(bc_proof.commitment);
+                                agg_lego[*agg_idx].1.push(pub_inp);
+                                sp.verify_proof_contribution_using_prepared_when_aggregating_snark(
+                                    &challenge, bc_proof, comm_key,
+                                )?
+                            }
+                            _ => {
+                                return Err(ProofSystemError::ProofIncompatibleWithStatement(
+                                    s_idx,
+                                    format!("{:?}", proof),
+                                    format!("{:?}", s),
+                                ))
+                            }
+                        }
                     }
-                    _ => err_incompat_proof!(s_idx, s, proof),
-                },
-                Statement::KBPositiveAccumulatorMembershipCDH(s) => match proof {
-                    StatementProof::KBPositiveAccumulatorMembershipCDH(ref p) => {
-                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
-                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
-                        let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = KBPositiveAccumulatorMembershipCDHSubProtocol::new(
+                    Statement::BloomMembershipLegoGroth16Verifier(s) => {
+                        let verifying_key = s.get_verifying_key(&proof_spec.setup_params, s_idx)?;
+                        let sp = BloomMembershipLegoGrothProtocol::new_for_verifier(
                             s_idx,
-                            params,
-                            pk,
-                            prk,
-                            s.accumulator_value,
+                            &s.filter,
+                            verifying_key,
                         );
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            derived_kb_accum_pk.get(s_idx).unwrap().clone(),
-                            derived_kb_accum_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                        )?
+                        let comm_key = bound_check_comm.get(s_idx).unwrap();
+                        match proof {
+                            StatementProof::BloomMembershipLegoGroth16(ref bm_proof) => sp
+                                .verify_proof_contribution(
+                                    &challenge,
+                                    bm_proof,
+                                    comm_key,
+                                    derived_lego_vk.get(s_idx).unwrap(),
+                                    &mut pairing_checker,
+                                )?,
+                            _ => err_incompat_proof!(s_idx, s, proof),
+                        }
                     }
-                    _ => err_incompat_proof!(s_idx, s, proof),
-                },
-                Statement::PedersenCommitment(s) => match proof {
-                    StatementProof::PedersenCommitment(ref p) => {
-                        let comm_key = s.get_commitment_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = SchnorrProtocol::new(s_idx, comm_key, s.commitment);
-                        sp.verify_proof_contribution(&challenge, p).map_err(|e| {
-                            ProofSystemError::SchnorrProofContributionFailed(s_idx as u32, e)
-                        })?
+                    Statement::R1CSCircomVerifier(s) => {
+                        let verifying_key = s.get_verifying_key(&proof_spec.setup_params, s_idx)?;
+                        let sp = R1CSLegogroth16Protocol::new_for_verifier(s_idx, verifying_key);
+                        let pub_inp = s
+                            .get_public_inputs(&proof_spec.setup_params, s_idx)?
+                            .to_vec();
+
+                        match proof {
+                            StatementProof::R1CSLegoGroth16(ref r1cs_proof) => sp
+                                .verify_proof_contribution(
+                                    &challenge,
+                                    &pub_inp,
+                                    r1cs_proof,
+                                    r1cs_comm_keys.get(s_idx).unwrap(),
+                                    derived_lego_vk.get(s_idx).unwrap(),
+                                    &mut pairing_checker,
+                                )?,
+                            StatementProof::R1CSLegoGroth16WithAggregation(ref r1cs_proof) => {
+                                let agg_idx = agg_lego_stmts
+                                    .get(&s_idx)
+                                    .ok_or(ProofSystemError::InvalidStatementProofIndex(s_idx))?;
+                                agg_lego[*agg_idx].0.push(r1cs_proof.commitment);
+                                agg_lego[*agg_idx].1.push(pub_inp);
+
+                                sp.verify_proof_contribution_using_prepared_when_aggregating_snark(
+                                    &challenge,
+                                    r1cs_proof,
+                                    r1cs_comm_keys.get(s_idx).unwrap(),
+                                )?
+                            }
+                            _ => {
+                                return Err(ProofSystemError::ProofIncompatibleWithStatement(
+                                    s_idx,
+                                    format!("{:?}", proof),
+                                    format!("{:?}", s),
+                                ))
+                            }
+                        }
                     }
-                    _ => err_incompat_proof!(s_idx, s, proof),
-                },
-                Statement::SaverVerifier(s) => {
-                    let enc_gens = s.get_encryption_gens(&proof_spec.setup_params, s_idx)?;
-                    let comm_gens =
-                        s.get_chunked_commitment_gens(&proof_spec.setup_params, s_idx)?;
-                    let enc_key = s.get_encryption_key(&proof_spec.setup_params, s_idx)?;
-                    let vk = s.get_snark_verifying_key(&proof_spec.setup_params, s_idx)?;
-                    let sp = SaverProtocol::new_for_verifier(
-                        s_idx,
-                        s.chunk_bit_size,
-                        enc_gens,
-                        comm_gens,
-                        enc_key,
-                        vk,
-                    );
-                    let ek_comm_key = ek_comm.get(s_idx).unwrap();
-                    let cc_keys = chunked_comm.get(s_idx).unwrap();
+                    Statement::PoKPSSignature(s) => match proof {
+                        StatementProof::PoKPSSignature(ref p) => {
+                            let sig_params = s.get_sig_params(&proof_spec.setup_params, s_idx)?;
+                            let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                            let sp =
+                                PSSignaturePoK::new(s_idx, &s.revealed_messages, sig_params, pk);
 
-                    match proof {
-                        StatementProof::Saver(ref saver_proof) => sp.verify_proof_contribution(
-                            &challenge,
-                            saver_proof,
-                            ek_comm_key,
-                            &cc_keys.0,
-                            &cc_keys.1,
-                            derived_saver_vk.get(s_idx).unwrap(),
-                            derived_gens.get(s_idx).unwrap().clone(),
-                            derived_ek.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                        )?,
-                        StatementProof::SaverWithAggregation(ref saver_proof) => {
-                            let agg_idx = agg_saver_stmts.get(&s_idx).ok_or_else(|| {
-                                ProofSystemError::InvalidStatementProofIndex(s_idx)
-                            })?;
-                            agg_saver[*agg_idx].push(saver_proof.ciphertext.clone());
-                            sp.verify_ciphertext_and_commitment(
+                            sp.verify_proof_contribution(
                                 &challenge,
-                                &saver_proof.ciphertext,
-                                saver_proof.comm_combined.clone(),
-                                saver_proof.comm_chunks.clone(),
-                                &saver_proof.sp_ciphertext,
-                                &saver_proof.sp_chunks,
-                                &saver_proof.sp_combined,
-                                ek_comm_key,
-                                &cc_keys.0,
-                                &cc_keys.1,
+                                p,
+                                derived_ps_pk.get(s_idx).unwrap().clone(),
+                                derived_ps_param.get(s_idx).unwrap().clone(),
+                                &mut pairing_checker,
                             )?
                         }
-                        _ => {
-                            return Err(ProofSystemError::ProofIncompatibleWithStatement(
-                                s_idx,
-                                format!("{:?}", proof),
-                                format!("{:?}", s),
-                            ))
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::PoKPSAggregateSignature(s) => match proof {
+                        StatementProof::PoKPSSignature(ref p) => {
+                            let sig_params = s.get_sig_params(&proof_spec.setup_params, s_idx)?;
+                            let pk = aggregated_ps_pks.get(&s_idx).unwrap();
+                            let sp =
+                                PSSignaturePoK::new(s_idx, &s.revealed_messages, sig_params, pk);
+
+                            sp.verify_proof_contribution(
+                                &challenge,
+                                p,
+                                derived_ps_pk.get(s_idx).unwrap().clone(),
+                                derived_ps_param.get(s_idx).unwrap().clone(),
+                                &mut pairing_checker,
+                            )?
                         }
-                    }
-                }
-                Statement::BoundCheckLegoGroth16Verifier(s) => {
-                    let verifying_key = s.get_verifying_key(&proof_spec.setup_params, s_idx)?;
-                    let sp = BoundCheckLegoGrothProtocol::new_for_verifier(
-                        s_idx,
-                        s.min,
-                        s.max,
-                        verifying_key,
-                    );
-                    let comm_key = bound_check_comm.get(s_idx).unwrap();
-                    match proof {
-                        StatementProof::BoundCheckLegoGroth16(ref bc_proof) => sp
-                            .verify_proof_contribution(
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::BoundCheckBpp(s) => match proof {
+                        StatementProof::BoundCheckBpp(ref bc_proof) => {
+                            let setup_params =
+                                s.get_setup_params(&proof_spec.setup_params, s_idx)?;
+                            let sp = BoundCheckBppProtocol::new(s_idx, s.min, s.max, setup_params);
+                            let comm_key = bound_check_bpp_comm.get(s_idx).unwrap();
+                            sp.verify_proof_contribution(
                                 &challenge,
                                 bc_proof,
-                                comm_key,
-                                derived_lego_vk.get(s_idx).unwrap(),
-                                &mut pairing_checker,
-                            )?,
-                        StatementProof::BoundCheckLegoGroth16WithAggregation(ref bc_proof) => {
-                            let pub_inp =
-                                vec![E::ScalarField::from(sp.min), E::ScalarField::from(sp.max)];
-                            let agg_idx = agg_lego_stmts.get(&s_idx).ok_or_else(|| {
-                                ProofSystemError::InvalidStatementProofIndex(s_idx)
-                            })?;
-                            agg_lego[*agg_idx].0.push(bc_proof.commitment);
-                            agg_lego[*agg_idx].1.push(pub_inp);
-                            sp.verify_proof_contribution_using_prepared_when_aggregating_snark(
-                                &challenge, bc_proof, comm_key,
+                                comm_key.as_slice(),
+                                &mut transcript,
                             )?
                         }
-                        _ => {
-                            return Err(ProofSystemError::ProofIncompatibleWithStatement(
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::Monotonic(s) => match proof {
+                        StatementProof::Monotonic(ref m_proof) => {
+                            let setup_params =
+                                s.get_setup_params(&proof_spec.setup_params, s_idx)?;
+                            let sp = MonotonicProtocol::new(
                                 s_idx,
-                                format!("{:?}", proof),
-                                format!("{:?}", s),
-                            ))
+                                s.num_values,
+                                s.strict,
+                                setup_params,
+                            )?;
+                            let comm_key = bound_check_bpp_comm.get(s_idx).unwrap();
+                            sp.verify_proof_contribution(
+                                &challenge,
+                                m_proof,
+                                comm_key.as_slice(),
+                                &mut transcript,
+                            )?
                         }
-                    }
-                }
-                Statement::R1CSCircomVerifier(s) => {
-                    let verifying_key = s.get_verifying_key(&proof_spec.setup_params, s_idx)?;
-                    let sp = R1CSLegogroth16Protocol::new_for_verifier(s_idx, verifying_key);
-                    let pub_inp = s
-                        .get_public_inputs(&proof_spec.setup_params, s_idx)?
-                        .to_vec();
-
-                    match proof {
-                        StatementProof::R1CSLegoGroth16(ref r1cs_proof) => sp
-                            .verify_proof_contribution(
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::AggregatedBoundCheckBpp(s) => match proof {
+                        StatementProof::AggregatedBoundCheckBpp(ref bc_proof) => {
+                            let setup_params =
+                                s.get_setup_params(&proof_spec.setup_params, s_idx)?;
+                            let sp = AggregatedBoundCheckBppProtocol::new(
+                                s_idx,
+                                &s.bounds,
+                                setup_params,
+                            );
+                            let comm_key = bound_check_bpp_comm.get(s_idx).unwrap();
+                            sp.verify_proof_contribution(
                                 &challenge,
-                                &pub_inp,
-                                r1cs_proof,
-                                r1cs_comm_keys.get(s_idx).unwrap(),
-                                derived_lego_vk.get(s_idx).unwrap(),
+                                bc_proof,
+                                comm_key.as_slice(),
+                                &mut transcript,
+                            )?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::BoundCheckSmc(s) => match proof {
+                        StatementProof::BoundCheckSmc(ref bc_proof) => {
+                            let setup_params =
+                                s.get_params_and_comm_key(&proof_spec.setup_params, s_idx)?;
+                            let sp = BoundCheckSmcProtocol::new(s_idx, s.min, s.max, setup_params);
+                            let comm_key_slice = bound_check_smc_comm.get(s_idx).unwrap();
+                            sp.verify_proof_contribution(
+                                &challenge,
+                                bc_proof,
+                                comm_key_slice.as_slice(),
+                                derived_smc_param.get(s_idx).unwrap().clone(),
                                 &mut pairing_checker,
-                            )?,
-                        StatementProof::R1CSLegoGroth16WithAggregation(ref r1cs_proof) => {
-                            let agg_idx = agg_lego_stmts.get(&s_idx).ok_or_else(|| {
-                                ProofSystemError::InvalidStatementProofIndex(s_idx)
-                            })?;
-                            agg_lego[*agg_idx].0.push(r1cs_proof.commitment);
-                            agg_lego[*agg_idx].1.push(pub_inp);
-
-                            sp.verify_proof_contribution_using_prepared_when_aggregating_snark(
+                            )?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::SetMembershipSmc(s) => match proof {
+                        StatementProof::SetMembershipSmc(ref sm_proof) => {
+                            let setup_params =
+                                s.get_params_and_comm_key(&proof_spec.setup_params, s_idx)?;
+                            let sp = SetMembershipSmcProtocol::new(s_idx, setup_params);
+                            let comm_key_slice = bound_check_smc_comm.get(s_idx).unwrap();
+                            sp.verify_proof_contribution(
                                 &challenge,
-                                r1cs_proof,
-                                r1cs_comm_keys.get(s_idx).unwrap(),
+                                sm_proof,
+                                comm_key_slice.as_slice(),
+                                derived_smc_param.get(s_idx).unwrap().clone(),
                             )?
                         }
-                        _ => {
-                            return Err(ProofSystemError::ProofIncompatibleWithStatement(
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::BoundCheckSmcWithKVVerifier(s) => match proof {
+                        StatementProof::BoundCheckSmcWithKV(ref bc_proof) => {
+                            let setup_params =
+                                s.get_params_and_comm_key_and_sk(&proof_spec.setup_params, s_idx)?;
+                            let sp = BoundCheckSmcWithKVProtocol::new_for_verifier(
                                 s_idx,
-                                format!("{:?}", proof),
-                                format!("{:?}", s),
-                            ))
+                                s.min,
+                                s.max,
+                                setup_params,
+                            );
+                            let comm_key_slice = bound_check_smc_comm.get(s_idx).unwrap();
+                            sp.verify_proof_contribution(
+                                &challenge,
+                                bc_proof,
+                                comm_key_slice.as_slice(),
+                            )?
                         }
-                    }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::PublicInequality(s) => match proof {
+                        StatementProof::Inequality(ref iq_proof) => {
+                            let comm_key = s.get_comm_key(&proof_spec.setup_params, s_idx)?;
+                            let sp = InequalityProtocol::new(s_idx, s.inequal_to, comm_key);
+                            let comm_key = ineq_comm.get(s_idx).unwrap();
+                            sp.verify_proof_contribution(&challenge, iq_proof, comm_key.as_slice())?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::PublicSetMembership(s) => match proof {
+                        StatementProof::PublicSetMembership(ref psm_proof) => {
+                            let comm_key = s.get_comm_key(&proof_spec.setup_params, s_idx)?;
+                            let sp = PublicSetMembershipProtocol::new(s_idx, &s.set, comm_key);
+                            let comm_key = ineq_comm.get(s_idx).unwrap();
+                            sp.verify_proof_contribution(
+                                &challenge,
+                                psm_proof,
+                                comm_key.as_slice(),
+                            )?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::CommittedInequality(s) => match proof {
+                        StatementProof::CommittedInequality(ref ci_proof) => {
+                            let comm_key = s.get_comm_key(&proof_spec.setup_params, s_idx)?;
+                            let sp = CommittedInequalityProtocol::new(s_idx, comm_key);
+                            let comm_key = ineq_comm.get(s_idx).unwrap();
+                            sp.verify_proof_contribution(&challenge, ci_proof, comm_key.as_slice())?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::PartialPedersenOpening(s) => match proof {
+                        StatementProof::PedersenCommitment(ref p) => {
+                            let comm_key = s.get_commitment_key(&proof_spec.setup_params, s_idx)?;
+                            let sp = PartialPedersenOpeningProtocol::new(s_idx, s, comm_key)?;
+                            sp.verify_proof_contribution(&challenge, p)?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::SignatureCommitmentConsistency(s) => match proof {
+                        StatementProof::PedersenCommitment(ref p) => {
+                            let comm_key = s.get_commitment_key(&proof_spec.setup_params, s_idx)?;
+                            let sp = SchnorrProtocol::new(s_idx, comm_key, s.commitment);
+                            sp.verify_proof_contribution(&challenge, p).map_err(|e| {
+                                ProofSystemError::SchnorrProofContributionFailed(s_idx as u32, e)
+                            })?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::PoKBDDT16MACFullVerifier(s) => match proof {
+                        StatementProof::PoKOfBDDT16MAC(ref p) => {
+                            let mac_params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                            let sp = PoKOfBDDT16MACSubProtocol::<E>::new(
+                                s_idx,
+                                &s.revealed_messages,
+                                mac_params,
+                            );
+                            sp.verify_proof_contribution(&challenge, p, &s.secret_key)?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::DetachedAccumulatorMembershipVerifier(_s) => (),
+                    Statement::DetachedAccumulatorNonMembershipVerifier(_s) => (),
+                    Statement::DetachedAccumulatorMembershipCDHVerifier(_s) => (),
+                    Statement::ScopedPseudonym(s) => match proof {
+                        StatementProof::PedersenCommitment(ref p) => {
+                            let base = pseudonym_bases.get(&s_idx).unwrap();
+                            let sp =
+                                SchnorrProtocol::new(s_idx, slice::from_ref(base), s.pseudonym);
+                            sp.verify_proof_contribution(&challenge, p).map_err(|e| {
+                                ProofSystemError::SchnorrProofContributionFailed(s_idx as u32, e)
+                            })?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    Statement::DisclosedLinearCombination(s) => match proof {
+                        StatementProof::PedersenCommitment(ref p) => {
+                            let sp = SchnorrProtocol::new(
+                                s_idx,
+                                slice::from_ref(&s.base),
+                                s.commitment(),
+                            );
+                            sp.verify_proof_contribution(&challenge, p).map_err(|e| {
+                                ProofSystemError::SchnorrProofContributionFailed(s_idx as u32, e)
+                            })?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    },
+                    _ => return Err(ProofSystemError::InvalidStatement),
                 }
-                Statement::PoKPSSignature(s) => match proof {
-                    StatementProof::PoKPSSignature(ref p) => {
-                        let sig_params = s.get_sig_params(&proof_spec.setup_params, s_idx)?;
-                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = PSSignaturePoK::new(s_idx, &s.revealed_messages, sig_params, pk);
-
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            derived_ps_pk.get(s_idx).unwrap().clone(),
-                            derived_ps_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                        )?
-                    }
-                    _ => err_incompat_proof!(s_idx, s, proof),
-                },
-                Statement::BoundCheckBpp(s) => match proof {
-                    StatementProof::BoundCheckBpp(ref bc_proof) => {
-                        let setup_params = s.get_setup_params(&proof_spec.setup_params, s_idx)?;
-                        let sp = BoundCheckBppProtocol::new(s_idx, s.min, s.max, setup_params);
-                        let comm_key = bound_check_bpp_comm.get(s_idx).unwrap();
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            bc_proof,
-                            comm_key.as_slice(),
-                            &mut transcript,
-                        )?
-                    }
-                    _ => err_incompat_proof!(s_idx, s, proof),
-                },
-                Statement::BoundCheckSmc(s) => match proof {
-                    StatementProof::BoundCheckSmc(ref bc_proof) => {
-                        let setup_params =
-                            s.get_params_and_comm_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = BoundCheckSmcProtocol::new(s_idx, s.min, s.max, setup_params);
-                        let comm_key_slice = bound_check_smc_comm.get(s_idx).unwrap();
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            bc_proof,
-                            comm_key_slice.as_slice(),
-                            derived_smc_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                        )?
-                    }
-                    _ => err_incompat_proof!(s_idx, s, proof),
-                },
-                Statement::BoundCheckSmcWithKVVerifier(s) => match proof {
-                    StatementProof::BoundCheckSmcWithKV(ref bc_proof) => {
-                        let setup_params =
-                            s.get_params_and_comm_key_and_sk(&proof_spec.setup_params, s_idx)?;
-                        let sp = BoundCheckSmcWithKVProtocol::new_for_verifier(
-                            s_idx,
-                            s.min,
-                            s.max,
-                            setup_params,
-                        );
-                        let comm_key_slice = bound_check_smc_comm.get(s_idx).unwrap();
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            bc_proof,
-                            comm_key_slice.as_slice(),
-                        )?
-                    }
-                    _ => err_incompat_proof!(s_idx, s, proof),
-                },
-                Statement::PublicInequality(s) => match proof {
-                    StatementProof::Inequality(ref iq_proof) => {
-                        let comm_key = s.get_comm_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = InequalityProtocol::new(s_idx, s.inequal_to, comm_key);
-                        let comm_key = ineq_comm.get(s_idx).unwrap();
-                        sp.verify_proof_contribution(&challenge, iq_proof, comm_key.as_slice())?
-                    }
-                    _ => err_incompat_proof!(s_idx, s, proof),
-                },
-                Statement::DetachedAccumulatorMembershipVerifier(_s) => (),
-                Statement::DetachedAccumulatorNonMembershipVerifier(_s) => (),
-                _ => return Err(ProofSystemError::InvalidStatement),
-            }
+                Ok(())
+            };
+            verify_statement_proof().map_err(|e| {
+                ProofSystemError::StatementVerificationFailed {
+                    index: s_idx,
+                    statement_kind: statement.name(),
+                    source: Box::new(e),
+                }
+            })?;
+            timer.stop(s_idx);
         }
 
         if aggregate_snarks {
@@ -1426,6 +2068,138 @@ where
                 return Err(ProofSystemError::RandomizedPairingCheckFailed);
             }
         }
+        Ok(timer)
+    }
+
+    /// Cheap checks on the structure of the proof that don't need any group operations. Checks that there is exactly
+    /// one statement proof for each statement and that each statement proof is of the type expected by its statement.
+    fn check_structure(&self, proof_spec: &ProofSpec<E, G>) -> Result<(), ProofSystemError> {
+        // Number of statement proofs is less than number of statements which means some statements
+        // are not satisfied.
+        if proof_spec.statements.len() > self.statement_proofs.len() {
+            return Err(ProofSystemError::UnsatisfiedStatements(
+                proof_spec.statements.len(),
+                self.statement_proofs.len(),
+            ));
+        }
+        if proof_spec.statements.len() < self.statement_proofs.len() {
+            return Err(ProofSystemError::TooManyStatementProofs(
+                proof_spec.statements.len(),
+                self.statement_proofs.len(),
+            ));
+        }
+        for (s_idx, (s, proof)) in proof_spec
+            .statements
+            .0
+            .iter()
+            .zip(self.statement_proofs.iter())
+            .enumerate()
+        {
+            let compatible = match s {
+                Statement::PoKBBSSignatureG1(_) => {
+                    matches!(proof, StatementProof::PoKBBSSignatureG1(_))
+                }
+                Statement::PoKBBSSignature23G1(_) => {
+                    matches!(proof, StatementProof::PoKBBSSignature23G1(_))
+                }
+                Statement::VBAccumulatorMembership(_) => {
+                    matches!(proof, StatementProof::VBAccumulatorMembership(_))
+                }
+                Statement::VBAccumulatorNonMembership(_) => {
+                    matches!(proof, StatementProof::VBAccumulatorNonMembership(_))
+                }
+                Statement::KBUniversalAccumulatorMembership(_) => {
+                    matches!(proof, StatementProof::KBUniversalAccumulatorMembership(_))
+                }
+                Statement::KBUniversalAccumulatorNonMembership(_) => matches!(
+                    proof,
+                    StatementProof::KBUniversalAccumulatorNonMembership(_)
+                ),
+                Statement::VBAccumulatorMembershipCDHVerifier(_) => {
+                    matches!(proof, StatementProof::VBAccumulatorMembershipCDH(_))
+                }
+                Statement::VBAccumulatorNonMembershipCDHVerifier(_) => {
+                    matches!(proof, StatementProof::VBAccumulatorNonMembershipCDH(_))
+                }
+                Statement::KBUniversalAccumulatorMembershipCDHVerifier(_) => matches!(
+                    proof,
+                    StatementProof::KBUniversalAccumulatorMembershipCDH(_)
+                ),
+                Statement::KBUniversalAccumulatorNonMembershipCDHVerifier(_) => matches!(
+                    proof,
+                    StatementProof::KBUniversalAccumulatorNonMembershipCDH(_)
+                ),
+                Statement::KBPositiveAccumulatorMembership(_) => {
+                    matches!(proof, StatementProof::KBPositiveAccumulatorMembership(_))
+                }
+                Statement::KBPositiveAccumulatorMembershipCDH(_) => {
+                    matches!(proof, StatementProof::KBPositiveAccumulatorMembershipCDH(_))
+                }
+                Statement::PedersenCommitment(_)
+                | Statement::CommittedMessageEquality(_)
+                | Statement::ScopedPseudonym(_)
+                | Statement::DisclosedLinearCombination(_)
+                | Statement::PartialPedersenOpening(_)
+                | Statement::SignatureCommitmentConsistency(_) => {
+                    matches!(proof, StatementProof::PedersenCommitment(_))
+                }
+                Statement::SaverVerifier(_) => matches!(
+                    proof,
+                    StatementProof::Saver(_) | StatementProof::SaverWithAggregation(_)
+                ),
+                Statement::BoundCheckLegoGroth16Verifier(_) => matches!(
+                    proof,
+                    StatementProof::BoundCheckLegoGroth16(_)
+                        | StatementProof::BoundCheckLegoGroth16WithAggregation(_)
+                ),
+                Statement::BloomMembershipLegoGroth16Verifier(_) => {
+                    matches!(proof, StatementProof::BloomMembershipLegoGroth16(_))
+                }
+                Statement::R1CSCircomVerifier(_) => matches!(
+                    proof,
+                    StatementProof::R1CSLegoGroth16(_)
+                        | StatementProof::R1CSLegoGroth16WithAggregation(_)
+                ),
+                Statement::PoKPSSignature(_) | Statement::PoKPSAggregateSignature(_) => {
+                    matches!(proof, StatementProof::PoKPSSignature(_))
+                }
+                Statement::BoundCheckBpp(_) => matches!(proof, StatementProof::BoundCheckBpp(_)),
+                Statement::Monotonic(_) => matches!(proof, StatementProof::Monotonic(_)),
+                Statement::AggregatedBoundCheckBpp(_) => {
+                    matches!(proof, StatementProof::AggregatedBoundCheckBpp(_))
+                }
+                Statement::BoundCheckSmc(_) => matches!(proof, StatementProof::BoundCheckSmc(_)),
+                Statement::SetMembershipSmc(_) => {
+                    matches!(proof, StatementProof::SetMembershipSmc(_))
+                }
+                Statement::BoundCheckSmcWithKVVerifier(_) => {
+                    matches!(proof, StatementProof::BoundCheckSmcWithKV(_))
+                }
+                Statement::PublicInequality(_) => matches!(proof, StatementProof::Inequality(_)),
+                Statement::PublicSetMembership(_) => {
+                    matches!(proof, StatementProof::PublicSetMembership(_))
+                }
+                Statement::CommittedInequality(_) => {
+                    matches!(proof, StatementProof::CommittedInequality(_))
+                }
+                Statement::DetachedAccumulatorMembershipVerifier(_) => {
+                    matches!(proof, StatementProof::DetachedAccumulatorMembership(_))
+                }
+                Statement::DetachedAccumulatorNonMembershipVerifier(_) => {
+                    matches!(proof, StatementProof::DetachedAccumulatorNonMembership(_))
+                }
+                Statement::DetachedAccumulatorMembershipCDHVerifier(_) => {
+                    matches!(proof, StatementProof::DetachedAccumulatorMembershipCDH(_))
+                }
+                Statement::PoKBDDT16MACFullVerifier(_) => {
+                    matches!(proof, StatementProof::PoKOfBDDT16MAC(_))
+                }
+                _ => return Err(ProofSystemError::InvalidStatement),
+            };
+            if !compatible {
+                err_incompat_proof!(s_idx, s, proof)
+            }
+        }
         Ok(())
     }
 
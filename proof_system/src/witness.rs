@@ -1,17 +1,18 @@
 use ark_ec::pairing::Pairing;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{cmp, collections::BTreeMap, fmt::Debug, string::String, vec::Vec};
+use ark_std::{cmp, collections::BTreeMap, fmt::Debug, string::String, vec, vec::Vec};
 use bbs_plus::{
     signature::SignatureG1 as BBSSignatureG1, signature_23::Signature23G1 as BBSSignature23G1,
 };
 use coconut_crypto::Signature;
 use dock_crypto_utils::serde_utils::*;
+use kvac::bddt_2016::mac::MAC;
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, Same};
 use vb_accumulator::witness::{MembershipWitness, NonMembershipWitness};
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
-use crate::error::ProofSystemError;
+use crate::{error::ProofSystemError, sub_protocols::enforce_and_get_u64};
 
 /// Secret data that the prover will prove knowledge of, this data is known only to the prover
 #[serde_as]
@@ -37,6 +38,33 @@ pub enum Witness<E: Pairing> {
     KBUniAccumulatorMembership(KBUniMembership<E>),
     KBUniAccumulatorNonMembership(KBUniNonMembership<E>),
     KBPosAccumulatorMembership(KBPosMembership<E>),
+    /// Secret in the scoped pseudonym
+    ScopedPseudonym(#[serde_as(as = "ArkObjectBytes")] E::ScalarField),
+    /// Message equal to the linear combination of disclosed values
+    DisclosedLinearCombination(#[serde_as(as = "ArkObjectBytes")] E::ScalarField),
+    /// Message whose membership in a Bloom filter is proved
+    BloomMembershipLegoGroth16(#[serde_as(as = "ArkObjectBytes")] E::ScalarField),
+    /// Messages whose order is proved, in that order
+    Monotonic(#[serde_as(as = "Vec<ArkObjectBytes>")] Vec<E::ScalarField>),
+    /// Opening of the commitment whose first value is equal to a signed message
+    CommittedMessageEquality(#[serde_as(as = "Vec<ArkObjectBytes>")] Vec<E::ScalarField>),
+    /// Message which is one of the values of a public set
+    PublicSetMembership(#[serde_as(as = "ArkObjectBytes")] E::ScalarField),
+    /// Messages whose bounds are proved, in the order of the bounds
+    AggregatedBoundCheckBpp(#[serde_as(as = "Vec<ArkObjectBytes>")] Vec<E::ScalarField>),
+    /// Message which is one of the values of the set in the set-membership check params
+    SetMembershipSmc(#[serde_as(as = "ArkObjectBytes")] E::ScalarField),
+    /// Messages proved unequal
+    CommittedInequality(
+        #[serde_as(as = "(ArkObjectBytes, ArkObjectBytes)")] (E::ScalarField, E::ScalarField),
+    ),
+    /// Hidden values of a partially opened Pedersen commitment, keyed by their position in the commitment
+    PartialPedersenOpening(
+        #[serde_as(as = "BTreeMap<Same, ArkObjectBytes>")] BTreeMap<usize, E::ScalarField>,
+    ),
+    /// Opening of the commitment whose values are equal to signed messages
+    SignatureCommitmentConsistency(#[serde_as(as = "Vec<ArkObjectBytes>")] Vec<E::ScalarField>),
+    PoKOfBDDT16MAC(PoKOfBDDT16MAC<E>),
 }
 
 macro_rules! delegate {
@@ -58,7 +86,19 @@ macro_rules! delegate {
                 PublicInequality,
                 KBUniAccumulatorMembership,
                 KBUniAccumulatorNonMembership,
-                KBPosAccumulatorMembership
+                KBPosAccumulatorMembership,
+                ScopedPseudonym,
+                DisclosedLinearCombination,
+                BloomMembershipLegoGroth16,
+                Monotonic,
+                CommittedMessageEquality,
+                PublicSetMembership,
+                AggregatedBoundCheckBpp,
+                SetMembershipSmc,
+                CommittedInequality,
+                PartialPedersenOpening,
+                SignatureCommitmentConsistency,
+                PoKOfBDDT16MAC
             : $($tt)+
         }
     }}
@@ -83,7 +123,19 @@ macro_rules! delegate_reverse {
                 PublicInequality,
                 KBUniAccumulatorMembership,
                 KBUniAccumulatorNonMembership,
-                KBPosAccumulatorMembership
+                KBPosAccumulatorMembership,
+                ScopedPseudonym,
+                DisclosedLinearCombination,
+                BloomMembershipLegoGroth16,
+                Monotonic,
+                CommittedMessageEquality,
+                PublicSetMembership,
+                AggregatedBoundCheckBpp,
+                SetMembershipSmc,
+                CommittedInequality,
+                PartialPedersenOpening,
+                SignatureCommitmentConsistency,
+                PoKOfBDDT16MAC
             : $($tt)+
         }
 
@@ -178,6 +230,47 @@ impl<E: Pairing> Drop for PoKBBSSignature23G1<E> {
     }
 }
 
+/// Secret data when proving knowledge of BDDT16 MAC
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct PoKOfBDDT16MAC<E: Pairing> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub mac: MAC<E::G1Affine>,
+    #[serde_as(as = "BTreeMap<Same, ArkObjectBytes>")]
+    pub unrevealed_messages: BTreeMap<usize, E::ScalarField>,
+}
+
+impl<E: Pairing> PoKOfBDDT16MAC<E> {
+    /// Create a `Witness` variant for proving knowledge of BDDT16 MAC
+    pub fn new_as_witness(
+        mac: MAC<E::G1Affine>,
+        unrevealed_messages: BTreeMap<usize, E::ScalarField>,
+    ) -> Witness<E> {
+        Witness::PoKOfBDDT16MAC(PoKOfBDDT16MAC {
+            mac,
+            unrevealed_messages,
+        })
+    }
+}
+
+impl<E: Pairing> Zeroize for PoKOfBDDT16MAC<E> {
+    fn zeroize(&mut self) {
+        self.mac.zeroize();
+        self.unrevealed_messages
+            .values_mut()
+            .for_each(|v| v.zeroize())
+    }
+}
+
+impl<E: Pairing> Drop for PoKOfBDDT16MAC<E> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
 /// Secret data when proving VB accumulator membership
 #[serde_as]
 #[derive(
@@ -339,6 +432,25 @@ where
     pub fn len(&self) -> usize {
         self.0.len()
     }
+
+    /// Add the witnesses for the statements added by `BoundCheckBpp::add_statements_for_sum`, i.e. for the
+    /// Pedersen commitment and the bound check of the sum `m_1 + m_2`. Both messages must fit in a `u64` and
+    /// so must their sum. Returns the indices of both witnesses.
+    pub fn add_for_bound_check_bpp_of_sum(
+        &mut self,
+        m_1: E::ScalarField,
+        m_2: E::ScalarField,
+    ) -> Result<(usize, usize), ProofSystemError> {
+        let v_1 = enforce_and_get_u64(&m_1)?;
+        let v_2 = enforce_and_get_u64(&m_2)?;
+        if v_1.checked_add(v_2).is_none() {
+            return Err(ProofSystemError::BoundCheckSumOverflow(v_1, v_2));
+        }
+        let sum = m_1 + m_2;
+        let ped_comm_idx = self.add(Witness::PedersenCommitment(vec![m_1, m_2, sum]));
+        let bound_check_idx = self.add(Witness::BoundCheckBpp(sum));
+        Ok((ped_comm_idx, bound_check_idx))
+    }
 }
 
 impl<E: Pairing> PoKBBSSignatureG1<E> {
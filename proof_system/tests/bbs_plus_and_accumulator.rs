@@ -1,6 +1,6 @@
 use ark_bls12_381::{Bls12_381, G1Affine, G1Projective};
 use ark_ec::{pairing::Pairing, CurveGroup, VariableBaseMSM};
-use ark_ff::PrimeField;
+use ark_ff::{PrimeField, Zero};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{
     collections::{BTreeMap, BTreeSet},
@@ -15,11 +15,15 @@ use vb_accumulator::prelude::{Accumulator, MembershipProvingKey, NonMembershipPr
 
 use dock_crypto_utils::commitment::PedersenCommitmentKey;
 use proof_system::{
-    prelude::{EqualWitnesses, MetaStatements, VerifierConfig, Witness, WitnessRef, Witnesses},
+    prelude::{
+        EqualWitnesses, MetaStatements, ProofSystemError, VerifierConfig, Witness, WitnessRef,
+        Witnesses,
+    },
     proof_spec::ProofSpec,
     setup_params::SetupParams,
     statement::{
         accumulator::{
+            DetachedAccumulatorMembershipCDHProver, DetachedAccumulatorMembershipCDHVerifier,
             DetachedAccumulatorMembershipProver, DetachedAccumulatorMembershipVerifier,
             DetachedAccumulatorNonMembershipProver, DetachedAccumulatorNonMembershipVerifier,
             KBPositiveAccumulatorMembership,
@@ -30,13 +34,18 @@ use proof_system::{
         },
         bbs_23::PoKBBSSignature23G1 as PoKSignatureBBS23G1Stmt,
         bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
+        committed_inequality::CommittedInequality as CommittedInequalityStmt,
         inequality::PublicInequality as InequalityStmt,
+        linear_combination::DisclosedLinearCombination as LinearCombinationStmt,
+        partial_ped_opening::PartialPedersenOpening as PartialPedersenOpeningStmt,
         ped_comm::PedersenCommitment as PedersenCommitmentStmt,
-        Statements,
+        scoped_pseudonym::ScopedPseudonym as PseudonymStmt,
+        Statement, Statements,
     },
     statement_proof::StatementProof,
     sub_protocols::accumulator::detached::{
-        DetachedAccumulatorMembershipSubProtocol, DetachedAccumulatorNonMembershipSubProtocol,
+        DetachedAccumulatorMembershipCDHSubProtocol, DetachedAccumulatorMembershipSubProtocol,
+        DetachedAccumulatorNonMembershipSubProtocol,
     },
     witness::{
         KBPosMembership, KBUniMembership as KBMembershipWit,
@@ -2256,170 +2265,101 @@ fn proof_spec_validation() {
 }
 
 #[test]
-fn detached_accumulator() {
-    // Prove knowledge of BBS+ signature and one of the message's membership and non-membership in accumulators
-    let mut rng = StdRng::seed_from_u64(0u64);
+fn link_attribute_across_issuers() {
+    // Prove that an attribute signed by issuer A is the same as an attribute signed by issuer B without
+    // revealing it. The issuers have different params and public keys.
 
-    let msg_count = 6;
-    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+    let mut rng = StdRng::seed_from_u64(0u64);
 
-    let max = 10;
-    let (pos_accum_params, pos_accum_keypair, mut pos_accumulator, mut pos_state) =
-        setup_positive_accum(&mut rng);
-    let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+    let (msgs_a, params_a, keypair_a, sig_a) = bbs_plus_sig_setup(&mut rng, 5);
 
-    // Message with index `accum_member_1_idx` is added in the positive accumulator
-    let accum_member_1_idx = 1;
-    let accum_member_1 = msgs[accum_member_1_idx];
+    // Issuer B signs the same value at index 7 as issuer A at index 2
+    let mut msgs_b = (0..8).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+    msgs_b[7] = msgs_a[2];
+    let (params_b, keypair_b, sig_b) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs_b);
+    assert_ne!(params_a, params_b);
+    assert_ne!(keypair_a.public_key, keypair_b.public_key);
 
-    pos_accumulator = pos_accumulator
-        .add(
-            accum_member_1,
-            &pos_accum_keypair.secret_key,
-            &mut pos_state,
-        )
-        .unwrap();
-    let mem_1_wit = pos_accumulator
-        .get_membership_witness(&accum_member_1, &pos_accum_keypair.secret_key, &pos_state)
-        .unwrap();
-    assert!(pos_accumulator.verify_membership(
-        &accum_member_1,
-        &mem_1_wit,
-        &pos_accum_keypair.public_key,
-        &pos_accum_params
+    let mut statements = Statements::<Bls12_381, <Bls12_381 as Pairing>::G1Affine>::new();
+    let sig_a_idx = statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        params_a.clone(),
+        keypair_a.public_key.clone(),
+        BTreeMap::new(),
     ));
-
-    let mut statements = Statements::new();
-    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
-        sig_params.clone(),
-        sig_keypair.public_key.clone(),
+    let sig_b_idx = statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        params_b.clone(),
+        keypair_b.public_key.clone(),
         BTreeMap::new(),
     ));
-    statements.add(
-        DetachedAccumulatorMembershipProver::new_statement_from_params(
-            pos_accum_params.clone(),
-            pos_accum_keypair.public_key.clone(),
-            mem_prk.clone(),
-            *pos_accumulator.value(),
-        ),
-    );
 
-    // Create meta statement describing that message in the signature at index `accum_member_1_idx` is
-    // same as the accumulator member
     let mut meta_statements = MetaStatements::new();
-    meta_statements.add_witness_equality(EqualWitnesses(
-        vec![
-            (0, accum_member_1_idx),
-            (1, 0), // Since accumulator (non)membership has only one (for applications) which is the (non)member, that witness is at index 0.
-        ]
-        .into_iter()
-        .collect::<BTreeSet<WitnessRef>>(),
-    ));
-
-    test_serialization!(Statements<Bls12_381, G1Affine>, statements);
-    test_serialization!(MetaStatements, meta_statements);
+    meta_statements.link_across_signatures(sig_a_idx, 2, sig_b_idx, 7);
 
-    let context = Some(b"test".to_vec());
-    let proof_spec = ProofSpec::new(
-        statements.clone(),
-        meta_statements.clone(),
-        vec![],
-        context.clone(),
-    );
+    let proof_spec = ProofSpec::new(statements.clone(), meta_statements, vec![], None);
     proof_spec.validate().unwrap();
 
     test_serialization!(ProofSpec<Bls12_381, G1Affine>, proof_spec);
 
     let mut witnesses = Witnesses::new();
     witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
-        sig.clone(),
-        msgs.clone().into_iter().enumerate().collect(),
+        sig_a.clone(),
+        msgs_a.clone().into_iter().enumerate().collect(),
     ));
-    witnesses.add(MembershipWit::new_as_witness(
-        accum_member_1,
-        mem_1_wit.clone(),
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig_b,
+        msgs_b.into_iter().enumerate().collect(),
     ));
-    test_serialization!(Witnesses<Bls12_381>, witnesses);
-
-    let nonce = Some(b"test-nonce".to_vec());
 
+    let nonce = Some(b"test nonce".to_vec());
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
         proof_spec.clone(),
-        witnesses.clone(),
+        witnesses,
         nonce.clone(),
         Default::default(),
     )
     .unwrap()
     .0;
 
-    test_serialization!(ProofG1, proof);
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec, nonce.clone(), Default::default())
+        .unwrap();
 
-    let mut statements = Statements::new();
+    // Issuer B signs a different value at index 7 so the link should not verify
+    let (msgs_c, params_c, keypair_c, sig_c) = bbs_plus_sig_setup(&mut rng, 8);
+    assert_ne!(msgs_c[7], msgs_a[2]);
+
+    let mut statements = Statements::<Bls12_381, <Bls12_381 as Pairing>::G1Affine>::new();
     statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
-        sig_params.clone(),
-        sig_keypair.public_key.clone(),
+        params_a,
+        keypair_a.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        params_c,
+        keypair_c.public_key.clone(),
         BTreeMap::new(),
     ));
-    statements.add(
-        DetachedAccumulatorMembershipVerifier::new_statement_from_params(
-            pos_accum_params.clone(),
-            pos_accum_keypair.public_key.clone(),
-            mem_prk.clone(),
-        ),
-    );
-    let proof_spec = ProofSpec::new(statements.clone(), meta_statements, vec![], context.clone());
 
-    let start = Instant::now();
-    proof
-        .clone()
-        .verify::<StdRng, Blake2b512>(
-            &mut rng,
-            proof_spec.clone(),
-            nonce.clone(),
-            Default::default(),
-        )
-        .unwrap();
-    println!(
-        "Time to verify proof with a BBS+ signature and positive accumulator membership: {:?}",
-        start.elapsed()
-    );
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.link_across_signatures(0, 2, 1, 7);
 
-    match &proof.statement_proofs[1] {
-        StatementProof::DetachedAccumulatorMembership(p) => {
-            let sp = DetachedAccumulatorMembershipSubProtocol::new(
-                1,
-                &pos_accum_params,
-                &pos_accum_keypair.public_key,
-                &mem_prk,
-            );
-            sp.verify_proof_contribution(
-                p,
-                &pos_accum_keypair.secret_key,
-                pos_accum_keypair.public_key.clone(),
-                pos_accum_params.clone(),
-            )
-            .unwrap();
-        }
-        _ => assert!(false, "Needed a detached accumulator proof"),
-    }
+    let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+    proof_spec.validate().unwrap();
 
-    /*// Wrong witness reference fails to verify
-    let mut meta_statements_incorrect = MetaStatements::new();
-    meta_statements_incorrect.add_witness_equality(EqualWitnesses(
-        vec![(0, 0), (1, 0)]
-            .into_iter()
-            .collect::<BTreeSet<WitnessRef>>(),
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig_a,
+        msgs_a.into_iter().enumerate().collect(),
     ));
-    let proof_spec_incorrect = ProofSpec::new(
-        statements.clone(),
-        meta_statements_incorrect,
-        vec![],
-        context.clone(),
-    );
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig_c,
+        msgs_c.into_iter().enumerate().collect(),
+    ));
+
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec_incorrect.clone(),
+        proof_spec.clone(),
         witnesses,
         nonce.clone(),
         Default::default(),
@@ -2428,115 +2368,1202 @@ fn detached_accumulator() {
     .0;
 
     assert!(proof
-        .clone()
-        .verify::<StdRng, Blake2b512>(
-            &mut rng,
-            proof_spec_incorrect.clone(),
-            nonce.clone(),
-            Default::default()
-        )
-        .is_err());
-    assert!(proof
-        .verify::<StdRng, Blake2b512>(
-            &mut rng,
-            proof_spec_incorrect,
-            nonce.clone(),
-            VerifierConfig {
-                use_lazy_randomized_pairing_checks: Some(false),
-            },
-        )
+        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec, nonce, Default::default())
         .is_err());
+}
 
-    // Non-member fails to verify
-    let mut witnesses_incorrect = Witnesses::new();
-    witnesses_incorrect.add(PoKSignatureBBSG1Wit::new_as_witness(
-        sig.clone(),
-        msgs.clone().into_iter().enumerate().collect(),
-    ));
-    witnesses_incorrect.add(Witness::AccumulatorMembership(MembershipWit {
-        element: msgs[2], // 2nd message from BBS+ sig in accumulator
-        witness: mem_1_wit.clone(),
-    }));
-    let mut meta_statements = MetaStatements::new();
-    meta_statements.add_witness_equality(EqualWitnesses(
-        vec![
-            (0, 2), // 2nd message from BBS+ sig in accumulator
-            (1, 0),
-        ]
-        .into_iter()
-        .collect::<BTreeSet<WitnessRef>>(),
-    ));
-    let proof_spec = ProofSpec::new(statements, meta_statements, vec![], context.clone());
-    proof_spec.validate().unwrap();
-    let proof = ProofG1::new::<StdRng, Blake2b512>(
-        &mut rng,
-        proof_spec.clone(),
-        witnesses_incorrect,
-        nonce.clone(),
-        Default::default(),
-    )
-    .unwrap()
-    .0;
-    assert!(proof
-        .clone()
-        .verify::<StdRng, Blake2b512>(
-            &mut rng,
+#[test]
+fn link_attribute_across_bbs_plus_and_bbs() {
+    // Prove that an attribute in a BBS+ signature is the same as an attribute in a BBS signature without
+    // revealing it, like when a holder has credentials of both schemes during a migration
+
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (msgs_a, params_a, keypair_a, sig_a) = bbs_plus_sig_setup(&mut rng, 5);
+
+    let check = |rng: &mut StdRng, msg: Fr| {
+        // The BBS signature has `msg` at index 4
+        let mut msgs_b = (0..6).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        msgs_b[4] = msg;
+        let (params_b, keypair_b, sig_b) = bbs_sig_setup_given_messages(rng, &msgs_b);
+
+        let mut statements = Statements::<Bls12_381, <Bls12_381 as Pairing>::G1Affine>::new();
+        let sig_a_idx = statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            params_a.clone(),
+            keypair_a.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        let sig_b_idx = statements.add(PoKSignatureBBS23G1Stmt::new_statement_from_params(
+            params_b,
+            keypair_b.public_key.clone(),
+            BTreeMap::new(),
+        ));
+
+        let mut meta_statements = MetaStatements::new();
+        meta_statements.link_across_signatures(sig_a_idx, 2, sig_b_idx, 4);
+
+        let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+        proof_spec.validate().unwrap();
+
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig_a.clone(),
+            msgs_a.clone().into_iter().enumerate().collect(),
+        ));
+        witnesses.add(PoKSignatureBBS23G1Wit::new_as_witness(
+            sig_b,
+            msgs_b.into_iter().enumerate().collect(),
+        ));
+
+        let nonce = Some(b"test nonce".to_vec());
+        let proof = ProofG1::new::<StdRng, Blake2b512>(
+            rng,
             proof_spec.clone(),
+            witnesses,
             nonce.clone(),
-            Default::default()
-        )
-        .is_err());
-    assert!(proof
-        .verify::<StdRng, Blake2b512>(
-            &mut rng,
-            proof_spec,
-            nonce.clone(),
-            VerifierConfig {
-                use_lazy_randomized_pairing_checks: Some(false),
-            },
+            Default::default(),
         )
-        .is_err());*/
+        .unwrap()
+        .0;
 
-    // Prove knowledge of signature and membership of message with index `accum_member_2_idx` in universal accumulator
-    let accum_member_2_idx = 2;
-    let accum_member_2 = msgs[accum_member_2_idx];
-    let (uni_accum_params, uni_accum_keypair, mut uni_accumulator, initial_elements, mut uni_state) =
-        setup_universal_accum(&mut rng, max);
-    let non_mem_prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
-    let derived_mem_prk = non_mem_prk.derive_membership_proving_key();
+        test_serialization!(ProofG1, proof);
 
-    uni_accumulator = uni_accumulator
-        .add(
-            accum_member_2,
-            &uni_accum_keypair.secret_key,
-            &initial_elements,
-            &mut uni_state,
-        )
-        .unwrap();
-    let mem_2_wit = uni_accumulator
-        .get_membership_witness(&accum_member_2, &uni_accum_keypair.secret_key, &uni_state)
-        .unwrap();
-    assert!(uni_accumulator.verify_membership(
-        &accum_member_2,
-        &mem_2_wit,
-        &uni_accum_keypair.public_key,
-        &uni_accum_params
-    ));
+        proof.verify::<StdRng, Blake2b512>(rng, proof_spec, nonce, Default::default())
+    };
 
-    let mut statements = Statements::new();
-    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
-        sig_params.clone(),
-        sig_keypair.public_key.clone(),
-        BTreeMap::new(),
-    ));
-    statements.add(
-        DetachedAccumulatorMembershipProver::new_statement_from_params(
-            uni_accum_params.clone(),
-            uni_accum_keypair.public_key.clone(),
-            derived_mem_prk.clone(),
-            *uni_accumulator.value(),
-        ),
-    );
+    check(&mut rng, msgs_a[2]).unwrap();
+    // The BBS signature has a different value so the link should not verify
+    let msg = Fr::rand(&mut rng);
+    assert!(check(&mut rng, msg).is_err());
+}
+
+#[test]
+fn scoped_pseudonym_from_signed_secret() {
+    // Prove knowledge of a BBS+ signature and that the pseudonym for a scope is created from one of the
+    // hidden signed messages. The same scope gives the same pseudonym and different scopes give different ones.
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (msgs, params, keypair, sig) = bbs_plus_sig_setup(&mut rng, 5);
+    let secret_idx = 1;
+    let secret = msgs[secret_idx];
+
+    let scope_1 = b"verifier-1".to_vec();
+    let scope_2 = b"verifier-2".to_vec();
+    let nym_1 = PseudonymStmt::<G1Affine>::pseudonym_for_scope(&scope_1, &secret);
+    let nym_2 = PseudonymStmt::<G1Affine>::pseudonym_for_scope(&scope_2, &secret);
+    assert_ne!(nym_1, nym_2);
+
+    let create_and_verify =
+        |rng: &mut StdRng, scope: &[u8], nym: G1Affine, pseudonym_secret: Fr| {
+            let mut statements = Statements::<Bls12_381, G1Affine>::new();
+            let sig_idx = statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+                params.clone(),
+                keypair.public_key.clone(),
+                BTreeMap::new(),
+            ));
+            let nym_idx = statements.add(PseudonymStmt::new_statement_from_params(
+                scope.to_vec(),
+                nym,
+            ));
+
+            test_serialization!(Statements<Bls12_381, G1Affine>, statements);
+
+            let mut meta_statements = MetaStatements::new();
+            meta_statements.add_witness_equality(EqualWitnesses(
+                vec![(sig_idx, secret_idx), (nym_idx, 0)]
+                    .into_iter()
+                    .collect::<BTreeSet<WitnessRef>>(),
+            ));
+
+            let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+            proof_spec.validate().unwrap();
+
+            let mut witnesses = Witnesses::new();
+            witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+                sig.clone(),
+                msgs.clone().into_iter().enumerate().collect(),
+            ));
+            witnesses.add(Witness::ScopedPseudonym(pseudonym_secret));
+
+            test_serialization!(Witnesses<Bls12_381>, witnesses);
+
+            let nonce = Some(b"test nonce".to_vec());
+            let proof = ProofG1::new::<StdRng, Blake2b512>(
+                rng,
+                proof_spec.clone(),
+                witnesses,
+                nonce.clone(),
+                Default::default(),
+            )
+            .unwrap()
+            .0;
+
+            test_serialization!(ProofG1, proof);
+
+            proof.verify::<StdRng, Blake2b512>(rng, proof_spec, nonce, Default::default())
+        };
+
+    // Presenting twice for the same scope gives the same pseudonym and both presentations verify
+    create_and_verify(&mut rng, &scope_1, nym_1, secret).unwrap();
+    create_and_verify(&mut rng, &scope_1, nym_1, secret).unwrap();
+    create_and_verify(&mut rng, &scope_2, nym_2, secret).unwrap();
+
+    // Pseudonym of one scope can't be used for another scope
+    assert!(create_and_verify(&mut rng, &scope_2, nym_1, secret).is_err());
+
+    // Pseudonym created from a secret that is not the signed message doesn't verify
+    let other_secret = Fr::rand(&mut rng);
+    let other_nym = PseudonymStmt::<G1Affine>::pseudonym_for_scope(&scope_1, &other_secret);
+    assert!(create_and_verify(&mut rng, &scope_1, other_nym, other_secret).is_err());
+}
+
+#[test]
+fn signed_total_equals_linear_combination_of_disclosed() {
+    // Prove that a hidden signed total equals a linear combination of disclosed line items, i.e.
+    // `total = 2 * item_0 + item_1 + 3 * item_2 + 5`
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let coefficients = vec![Fr::from(2u64), Fr::from(1u64), Fr::from(3u64)];
+    let constant = Fr::from(5u64);
+    let base = G1Projective::rand(&mut rng).into_affine();
+    let total_idx = 3;
+
+    let check = |rng: &mut StdRng, tamper: bool| {
+        let mut msgs = (0..5).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        msgs[total_idx] = coefficients
+            .iter()
+            .zip(msgs.iter())
+            .fold(constant, |acc, (a, d)| acc + *a * d);
+        if tamper {
+            msgs[total_idx] += Fr::from(1u64);
+        }
+        let (params, keypair, sig) = bbs_plus_sig_setup_given_messages(rng, &msgs);
+
+        let revealed = (0..3).map(|i| (i, msgs[i])).collect::<BTreeMap<_, _>>();
+        let mut statements = Statements::<Bls12_381, G1Affine>::new();
+        let sig_idx = statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            params,
+            keypair.public_key.clone(),
+            revealed.clone(),
+        ));
+        let lc_idx = statements.add(
+            LinearCombinationStmt::new_statement_from_params(
+                coefficients.clone(),
+                revealed.values().copied().collect(),
+                constant,
+                base,
+            )
+            .unwrap(),
+        );
+
+        test_serialization!(Statements<Bls12_381, G1Affine>, statements);
+
+        let mut meta_statements = MetaStatements::new();
+        meta_statements.add_witness_equality(EqualWitnesses(
+            vec![(sig_idx, total_idx), (lc_idx, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        ));
+
+        let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+        proof_spec.validate().unwrap();
+
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig,
+            msgs.iter()
+                .enumerate()
+                .filter(|(i, _)| !revealed.contains_key(i))
+                .map(|(i, m)| (i, *m))
+                .collect(),
+        ));
+        witnesses.add(Witness::DisclosedLinearCombination(msgs[total_idx]));
+
+        test_serialization!(Witnesses<Bls12_381>, witnesses);
+
+        let nonce = Some(b"test nonce".to_vec());
+        let proof = ProofG1::new::<StdRng, Blake2b512>(
+            rng,
+            proof_spec.clone(),
+            witnesses,
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+
+        test_serialization!(ProofG1, proof);
+
+        proof.verify::<StdRng, Blake2b512>(rng, proof_spec, nonce, Default::default())
+    };
+
+    check(&mut rng, false).unwrap();
+    assert!(check(&mut rng, true).is_err());
+
+    // Number of coefficients and disclosed values must be same
+    assert!(
+        LinearCombinationStmt::new_statement_from_params::<Bls12_381>(
+            coefficients.clone(),
+            vec![Fr::from(1u64)],
+            constant,
+            base
+        )
+        .is_err()
+    );
+
+    // Base can't be the identity as then the proof holds for any message
+    assert!(matches!(
+        LinearCombinationStmt::new_statement_from_params::<Bls12_381>(
+            coefficients.clone(),
+            vec![Fr::from(1u64); 3],
+            constant,
+            G1Affine::identity()
+        ),
+        Err(ProofSystemError::DisclosedLinearCombinationBaseIsIdentity)
+    ));
+
+    // Statements that bypassed the constructor, like deserialized ones, are checked when validating the proof spec
+    let validate = |stmt: LinearCombinationStmt<G1Affine>| {
+        let mut statements = Statements::<Bls12_381, G1Affine>::new();
+        statements.add(Statement::DisclosedLinearCombination(stmt));
+        ProofSpec::new(statements, MetaStatements::new(), vec![], None).validate()
+    };
+    let valid = LinearCombinationStmt {
+        coefficients: coefficients.clone(),
+        disclosed: vec![Fr::from(1u64); 3],
+        constant,
+        base,
+    };
+    validate(valid.clone()).unwrap();
+    let mut fewer_disclosed = valid.clone();
+    fewer_disclosed.disclosed.pop();
+    assert!(matches!(
+        validate(fewer_disclosed),
+        Err(ProofSystemError::UnequalCoefficientAndDisclosedCount(3, 2))
+    ));
+    let mut identity_base = valid;
+    identity_base.base = G1Affine::identity();
+    assert!(matches!(
+        validate(identity_base),
+        Err(ProofSystemError::DisclosedLinearCombinationBaseIsIdentity)
+    ));
+}
+
+#[test]
+fn signed_message_is_nonzero() {
+    // Prove that a hidden signed message is not zero, like a required attribute being filled
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let comm_key = PedersenCommitmentKey::<G1Affine>::new::<Blake2b512>(b"test");
+    let msg_idx = 2;
+
+    let check = |rng: &mut StdRng, msg: Fr| {
+        let mut msgs = (0..5).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        msgs[msg_idx] = msg;
+        let (params, keypair, sig) = bbs_plus_sig_setup_given_messages(rng, &msgs);
+
+        let mut statements = Statements::<Bls12_381, G1Affine>::new();
+        let sig_idx = statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            params,
+            keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        let nonzero_idx = statements.add(InequalityStmt::new_nonzero(comm_key.clone()));
+
+        test_serialization!(Statements<Bls12_381, G1Affine>, statements);
+
+        let mut meta_statements = MetaStatements::new();
+        meta_statements.add_witness_equality(
+            InequalityStmt::<G1Affine>::witness_equality_with_signature(
+                sig_idx,
+                msg_idx,
+                nonzero_idx,
+            ),
+        );
+
+        let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+        proof_spec.validate().unwrap();
+
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig,
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        witnesses.add(Witness::PublicInequality(msgs[msg_idx]));
+
+        let nonce = Some(b"test nonce".to_vec());
+        let proof = ProofG1::new::<StdRng, Blake2b512>(
+            rng,
+            proof_spec.clone(),
+            witnesses,
+            nonce.clone(),
+            Default::default(),
+        )?
+        .0;
+
+        test_serialization!(ProofG1, proof);
+
+        proof.verify::<StdRng, Blake2b512>(rng, proof_spec, nonce, Default::default())
+    };
+
+    check(&mut rng, Fr::from(42u64)).unwrap();
+    // Proof can't be created for a zero message
+    assert!(check(&mut rng, Fr::zero()).is_err());
+}
+
+#[test]
+fn signed_messages_are_unequal() {
+    // Prove that hidden messages from 2 signatures are not equal, like the holder ids in 2 credentials
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let comm_key = PedersenCommitmentKey::<G1Affine>::new::<Blake2b512>(b"test");
+    let msg_idx_1 = 1;
+    let msg_idx_2 = 3;
+
+    // `witness` is the pair of messages given to the inequality statement
+    let check = |rng: &mut StdRng, msg_1: Fr, msg_2: Fr, witness: (Fr, Fr)| {
+        let mut msgs_1 = (0..5).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        msgs_1[msg_idx_1] = msg_1;
+        let (params_1, keypair_1, sig_1) = bbs_plus_sig_setup_given_messages(rng, &msgs_1);
+        let mut msgs_2 = (0..5).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        msgs_2[msg_idx_2] = msg_2;
+        let (params_2, keypair_2, sig_2) = bbs_plus_sig_setup_given_messages(rng, &msgs_2);
+
+        let mut statements = Statements::<Bls12_381, G1Affine>::new();
+        let sig_idx_1 = statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            params_1,
+            keypair_1.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        let sig_idx_2 = statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            params_2,
+            keypair_2.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        let ineq_idx = statements.add(CommittedInequalityStmt::new_statement_from_params(
+            comm_key.clone(),
+        ));
+
+        test_serialization!(Statements<Bls12_381, G1Affine>, statements);
+
+        let mut meta_statements = MetaStatements::new();
+        for eq in CommittedInequalityStmt::<G1Affine>::witness_equalities_with_signatures(
+            (sig_idx_1, msg_idx_1),
+            (sig_idx_2, msg_idx_2),
+            ineq_idx,
+        ) {
+            meta_statements.add_witness_equality(eq);
+        }
+
+        let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+        proof_spec.validate().unwrap();
+
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig_1,
+            msgs_1.clone().into_iter().enumerate().collect(),
+        ));
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig_2,
+            msgs_2.clone().into_iter().enumerate().collect(),
+        ));
+        witnesses.add(Witness::CommittedInequality(witness));
+
+        let nonce = Some(b"test nonce".to_vec());
+        let proof = ProofG1::new::<StdRng, Blake2b512>(
+            rng,
+            proof_spec.clone(),
+            witnesses,
+            nonce.clone(),
+            Default::default(),
+        )?
+        .0;
+
+        test_serialization!(ProofG1, proof);
+
+        proof.verify::<StdRng, Blake2b512>(rng, proof_spec, nonce, Default::default())
+    };
+
+    let msg_1 = Fr::rand(&mut rng);
+    let msg_2 = Fr::rand(&mut rng);
+    check(&mut rng, msg_1, msg_2, (msg_1, msg_2)).unwrap();
+
+    // Proof can't be created when the messages are equal
+    assert!(check(&mut rng, msg_1, msg_1, (msg_1, msg_1)).is_err());
+
+    // Unequal values that aren't the signed messages don't verify as the responses differ from the
+    // signatures'
+    assert!(check(&mut rng, msg_1, msg_1, (msg_1, msg_2)).is_err());
+}
+
+#[test]
+fn proof_bound_to_nonce_and_context() {
+    // A proof only verifies with the nonce and context it was created with, so a captured proof can't
+    // be replayed in another session
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (msgs, params, keypair, sig) = bbs_plus_sig_setup(&mut rng, 5);
+
+    let proof_spec_with_context = |context: Option<Vec<u8>>| {
+        let mut statements = Statements::<Bls12_381, G1Affine>::new();
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            params.clone(),
+            keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], context);
+        proof_spec.validate().unwrap();
+        proof_spec
+    };
+    let create_proof = |rng: &mut StdRng, proof_spec: ProofSpec<Bls12_381, G1Affine>, nonce| {
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig.clone(),
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        ProofG1::new::<StdRng, Blake2b512>(rng, proof_spec, witnesses, nonce, Default::default())
+            .unwrap()
+            .0
+    };
+
+    let nonce = Some(b"session 1".to_vec());
+    let context = Some(b"verifier 1".to_vec());
+    let proof_spec = proof_spec_with_context(context.clone());
+    let proof = create_proof(&mut rng, proof_spec.clone(), nonce.clone());
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec.clone(),
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap();
+
+    // Different or missing nonce
+    for other_nonce in [Some(b"session 2".to_vec()), None] {
+        assert!(proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                proof_spec.clone(),
+                other_nonce,
+                Default::default(),
+            )
+            .is_err());
+    }
+
+    // Different or missing context
+    for other_context in [Some(b"verifier 2".to_vec()), None] {
+        assert!(proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                proof_spec_with_context(other_context),
+                nonce.clone(),
+                Default::default(),
+            )
+            .is_err());
+    }
+
+    // Without nonce and context, the proof verifies without them but not with them
+    let proof_spec = proof_spec_with_context(None);
+    let proof = create_proof(&mut rng, proof_spec.clone(), None);
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec.clone(), None, Default::default())
+        .unwrap();
+    assert!(proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec, nonce, Default::default())
+        .is_err());
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec_with_context(context),
+            None,
+            Default::default()
+        )
+        .is_err());
+}
+
+#[test]
+fn malformed_proof_rejected_by_structural_checks() {
+    // Proofs with wrong number or type of statement proofs are rejected before any cryptographic check
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (msgs, params, keypair, sig) = bbs_plus_sig_setup(&mut rng, 5);
+    let bases = (0..2)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let scalars = vec![msgs[1], Fr::rand(&mut rng)];
+    let commitment = G1Projective::msm_unchecked(&bases, &scalars).into_affine();
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        params,
+        keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        bases, commitment,
+    ));
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 1), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+    proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.into_iter().enumerate().collect(),
+    ));
+    witnesses.add(Witness::PedersenCommitment(scalars));
+
+    let nonce = Some(b"test nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec.clone(),
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec.clone(),
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap();
+
+    // An extra statement proof
+    let mut extra = proof.clone();
+    extra
+        .statement_proofs
+        .push(proof.statement_proofs[1].clone());
+    assert!(matches!(
+        extra.verify::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec.clone(),
+            nonce.clone(),
+            Default::default()
+        ),
+        Err(ProofSystemError::TooManyStatementProofs(2, 3))
+    ));
+
+    // A missing statement proof
+    let mut missing = proof.clone();
+    missing.statement_proofs.pop();
+    assert!(matches!(
+        missing.verify::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec.clone(),
+            nonce.clone(),
+            Default::default()
+        ),
+        Err(ProofSystemError::UnsatisfiedStatements(2, 1))
+    ));
+
+    // Statement proofs in the wrong order
+    let mut swapped = proof;
+    swapped.statement_proofs.swap(0, 1);
+    assert!(matches!(
+        swapped.verify::<StdRng, Blake2b512>(&mut rng, proof_spec, nonce, Default::default()),
+        Err(ProofSystemError::ProofIncompatibleWithStatement(0, _, _))
+    ));
+}
+
+#[test]
+fn failed_statement_verification_names_statement() {
+    // When the proof of a statement is invalid, the error names the index and kind of the statement
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (msgs, params, keypair, sig) = bbs_plus_sig_setup(&mut rng, 5);
+    let bases = (0..2)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let scalars = vec![msgs[1], Fr::rand(&mut rng)];
+    let commitment = G1Projective::msm_unchecked(&bases, &scalars).into_affine();
+    let bases_2 = (0..3)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let scalars_2 = vec![msgs[2], Fr::rand(&mut rng), Fr::rand(&mut rng)];
+    let commitment_2 = G1Projective::msm_unchecked(&bases_2, &scalars_2).into_affine();
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        params,
+        keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        bases, commitment,
+    ));
+    statements.add(PartialPedersenOpeningStmt::new_statement_from_params(
+        bases_2,
+        commitment_2,
+        [(1, scalars_2[1])].into_iter().collect(),
+    ));
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 1), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 2), (2, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+    proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.into_iter().enumerate().collect(),
+    ));
+    witnesses.add(Witness::PedersenCommitment(scalars));
+    witnesses.add(Witness::PartialPedersenOpening(
+        [(0, scalars_2[0]), (2, scalars_2[2])].into_iter().collect(),
+    ));
+
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec.clone(),
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec.clone(), None, Default::default())
+        .unwrap();
+
+    // Corrupt a response of the 3rd statement's proof which isn't used in a witness equality. The challenge
+    // doesn't depend on the responses so only the 3rd statement's proof fails to verify.
+    let mut corrupted = proof;
+    match &mut corrupted.statement_proofs[2] {
+        StatementProof::PedersenCommitment(p) => p.response.0[1] = Fr::rand(&mut rng),
+        _ => panic!("expected a Pedersen commitment proof"),
+    }
+    match corrupted.verify::<StdRng, Blake2b512>(&mut rng, proof_spec, None, Default::default()) {
+        Err(ProofSystemError::StatementVerificationFailed {
+            index,
+            statement_kind,
+            source,
+        }) => {
+            assert_eq!(index, 2);
+            assert_eq!(statement_kind, "PartialPedersenOpening");
+            assert!(matches!(
+                *source,
+                ProofSystemError::SchnorrProofContributionFailed(2, _)
+            ));
+        }
+        r => panic!("unexpected result {:?}", r),
+    }
+}
+
+#[test]
+fn proof_with_creation_time() {
+    // Proof carries its creation time which is bound to the challenge and the verifier rejects old proofs
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (msgs, params, keypair, sig) = bbs_plus_sig_setup(&mut rng, 5);
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        params,
+        keypair.public_key.clone(),
+        BTreeMap::from([(0, msgs[0])]),
+    ));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.into_iter().enumerate().skip(1).collect(),
+    ));
+
+    let nonce = Some(b"test nonce".to_vec());
+    let created_at = 1_700_000_000;
+    let max_age = 60;
+    let proof = ProofG1::new_with_creation_time::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec.clone(),
+        witnesses.clone(),
+        nonce.clone(),
+        Default::default(),
+        created_at,
+    )
+    .unwrap()
+    .0;
+    assert_eq!(proof.created_at, Some(created_at));
+
+    test_serialization!(ProofG1, proof);
+
+    // Fresh proof is accepted
+    proof
+        .clone()
+        .verify_with_max_age::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec.clone(),
+            nonce.clone(),
+            Default::default(),
+            created_at + 30,
+            max_age,
+        )
+        .unwrap();
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec.clone(),
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap();
+
+    // Expired proof is rejected
+    assert!(matches!(
+        proof.clone().verify_with_max_age::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec.clone(),
+            nonce.clone(),
+            Default::default(),
+            created_at + max_age + 1,
+            max_age,
+        ),
+        Err(ProofSystemError::ProofExpired(c, n, m)) if c == created_at && n == created_at + max_age + 1 && m == max_age
+    ));
+
+    // Proof from the future is rejected
+    assert!(matches!(
+        proof.clone().verify_with_max_age::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec.clone(),
+            nonce.clone(),
+            Default::default(),
+            created_at - 1,
+            max_age,
+        ),
+        Err(ProofSystemError::ProofCreatedInFuture(_, _))
+    ));
+
+    // Changing the creation time to make an expired proof look fresh changes the challenge so verification fails
+    let mut tampered = proof;
+    tampered.created_at = Some(created_at + 100);
+    assert!(tampered
+        .clone()
+        .verify_with_max_age::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec.clone(),
+            nonce.clone(),
+            Default::default(),
+            created_at + 120,
+            max_age,
+        )
+        .is_err());
+    tampered.created_at = None;
+    assert!(tampered
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec.clone(),
+            nonce.clone(),
+            Default::default(),
+        )
+        .is_err());
+
+    // Proof without creation time is rejected
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec.clone(),
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    assert!(proof.created_at.is_none());
+    assert!(matches!(
+        proof.verify_with_max_age::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec,
+            nonce,
+            Default::default(),
+            created_at,
+            max_age,
+        ),
+        Err(ProofSystemError::MissingProofCreationTime)
+    ));
+}
+
+#[cfg(feature = "timings")]
+#[test]
+fn proof_with_timings() {
+    // Time taken by each statement is reported when creating and verifying the proof
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (msgs_1, params_1, keypair_1, sig_1) = bbs_plus_sig_setup(&mut rng, 5);
+    let (msgs_2, params_2, keypair_2, sig_2) = bbs_plus_sig_setup(&mut rng, 10);
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        params_1,
+        keypair_1.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        params_2,
+        keypair_2.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig_1,
+        msgs_1.into_iter().enumerate().collect(),
+    ));
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig_2,
+        msgs_2.into_iter().enumerate().collect(),
+    ));
+
+    let (proof, _, prover_timings) = ProofG1::new_with_timings::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec.clone(),
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap();
+    assert_eq!(prover_timings.statements.len(), 2);
+    for t in &prover_timings.statements {
+        assert!(!t.is_zero());
+        assert!(*t <= prover_timings.total);
+    }
+    println!("Prover timings {:?}", prover_timings);
+
+    for config in [
+        Default::default(),
+        VerifierConfig {
+            use_lazy_randomized_pairing_checks: Some(false),
+        },
+    ] {
+        let verifier_timings = proof
+            .clone()
+            .verify_with_timings::<StdRng, Blake2b512>(&mut rng, proof_spec.clone(), None, config)
+            .unwrap();
+        assert_eq!(verifier_timings.statements.len(), 2);
+        for t in &verifier_timings.statements {
+            assert!(!t.is_zero());
+            assert!(*t <= verifier_timings.total);
+        }
+        println!("Verifier timings {:?}", verifier_timings);
+    }
+}
+
+#[test]
+fn detached_accumulator() {
+    // Prove knowledge of BBS+ signature and one of the message's membership and non-membership in accumulators
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 6;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let max = 10;
+    let (pos_accum_params, pos_accum_keypair, mut pos_accumulator, mut pos_state) =
+        setup_positive_accum(&mut rng);
+    let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+    // Message with index `accum_member_1_idx` is added in the positive accumulator
+    let accum_member_1_idx = 1;
+    let accum_member_1 = msgs[accum_member_1_idx];
+
+    pos_accumulator = pos_accumulator
+        .add(
+            accum_member_1,
+            &pos_accum_keypair.secret_key,
+            &mut pos_state,
+        )
+        .unwrap();
+    let mem_1_wit = pos_accumulator
+        .get_membership_witness(&accum_member_1, &pos_accum_keypair.secret_key, &pos_state)
+        .unwrap();
+    assert!(pos_accumulator.verify_membership(
+        &accum_member_1,
+        &mem_1_wit,
+        &pos_accum_keypair.public_key,
+        &pos_accum_params
+    ));
+
+    let mut statements = Statements::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    statements.add(
+        DetachedAccumulatorMembershipProver::new_statement_from_params(
+            pos_accum_params.clone(),
+            pos_accum_keypair.public_key.clone(),
+            mem_prk.clone(),
+            *pos_accumulator.value(),
+        ),
+    );
+
+    // Create meta statement describing that message in the signature at index `accum_member_1_idx` is
+    // same as the accumulator member
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![
+            (0, accum_member_1_idx),
+            (1, 0), // Since accumulator (non)membership has only one (for applications) which is the (non)member, that witness is at index 0.
+        ]
+        .into_iter()
+        .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    test_serialization!(Statements<Bls12_381, G1Affine>, statements);
+    test_serialization!(MetaStatements, meta_statements);
+
+    let context = Some(b"test".to_vec());
+    let proof_spec = ProofSpec::new(
+        statements.clone(),
+        meta_statements.clone(),
+        vec![],
+        context.clone(),
+    );
+    proof_spec.validate().unwrap();
+
+    test_serialization!(ProofSpec<Bls12_381, G1Affine>, proof_spec);
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig.clone(),
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(MembershipWit::new_as_witness(
+        accum_member_1,
+        mem_1_wit.clone(),
+    ));
+    test_serialization!(Witnesses<Bls12_381>, witnesses);
+
+    let nonce = Some(b"test-nonce".to_vec());
+
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec.clone(),
+        witnesses.clone(),
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    test_serialization!(ProofG1, proof);
+
+    let mut statements = Statements::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    statements.add(
+        DetachedAccumulatorMembershipVerifier::new_statement_from_params(
+            pos_accum_params.clone(),
+            pos_accum_keypair.public_key.clone(),
+            mem_prk.clone(),
+        ),
+    );
+    let proof_spec = ProofSpec::new(statements.clone(), meta_statements, vec![], context.clone());
+
+    let start = Instant::now();
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec.clone(),
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap();
+    println!(
+        "Time to verify proof with a BBS+ signature and positive accumulator membership: {:?}",
+        start.elapsed()
+    );
+
+    match &proof.statement_proofs[1] {
+        StatementProof::DetachedAccumulatorMembership(p) => {
+            let sp = DetachedAccumulatorMembershipSubProtocol::new(
+                1,
+                &pos_accum_params,
+                &pos_accum_keypair.public_key,
+                &mem_prk,
+            );
+            sp.verify_proof_contribution(
+                p,
+                &pos_accum_keypair.secret_key,
+                pos_accum_keypair.public_key.clone(),
+                pos_accum_params.clone(),
+            )
+            .unwrap();
+        }
+        _ => assert!(false, "Needed a detached accumulator proof"),
+    }
+
+    /*// Wrong witness reference fails to verify
+    let mut meta_statements_incorrect = MetaStatements::new();
+    meta_statements_incorrect.add_witness_equality(EqualWitnesses(
+        vec![(0, 0), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    let proof_spec_incorrect = ProofSpec::new(
+        statements.clone(),
+        meta_statements_incorrect,
+        vec![],
+        context.clone(),
+    );
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec_incorrect.clone(),
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    assert!(proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec_incorrect.clone(),
+            nonce.clone(),
+            Default::default()
+        )
+        .is_err());
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec_incorrect,
+            nonce.clone(),
+            VerifierConfig {
+                use_lazy_randomized_pairing_checks: Some(false),
+            },
+        )
+        .is_err());
+
+    // Non-member fails to verify
+    let mut witnesses_incorrect = Witnesses::new();
+    witnesses_incorrect.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig.clone(),
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses_incorrect.add(Witness::AccumulatorMembership(MembershipWit {
+        element: msgs[2], // 2nd message from BBS+ sig in accumulator
+        witness: mem_1_wit.clone(),
+    }));
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![
+            (0, 2), // 2nd message from BBS+ sig in accumulator
+            (1, 0),
+        ]
+        .into_iter()
+        .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    let proof_spec = ProofSpec::new(statements, meta_statements, vec![], context.clone());
+    proof_spec.validate().unwrap();
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec.clone(),
+        witnesses_incorrect,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    assert!(proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec.clone(),
+            nonce.clone(),
+            Default::default()
+        )
+        .is_err());
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec,
+            nonce.clone(),
+            VerifierConfig {
+                use_lazy_randomized_pairing_checks: Some(false),
+            },
+        )
+        .is_err());*/
+
+    // Prove knowledge of signature and membership of message with index `accum_member_2_idx` in universal accumulator
+    let accum_member_2_idx = 2;
+    let accum_member_2 = msgs[accum_member_2_idx];
+    let (uni_accum_params, uni_accum_keypair, mut uni_accumulator, initial_elements, mut uni_state) =
+        setup_universal_accum(&mut rng, max);
+    let non_mem_prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
+    let derived_mem_prk = non_mem_prk.derive_membership_proving_key();
+
+    uni_accumulator = uni_accumulator
+        .add(
+            accum_member_2,
+            &uni_accum_keypair.secret_key,
+            &initial_elements,
+            &mut uni_state,
+        )
+        .unwrap();
+    let mem_2_wit = uni_accumulator
+        .get_membership_witness(&accum_member_2, &uni_accum_keypair.secret_key, &uni_state)
+        .unwrap();
+    assert!(uni_accumulator.verify_membership(
+        &accum_member_2,
+        &mem_2_wit,
+        &uni_accum_keypair.public_key,
+        &uni_accum_params
+    ));
+
+    let mut statements = Statements::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    statements.add(
+        DetachedAccumulatorMembershipProver::new_statement_from_params(
+            uni_accum_params.clone(),
+            uni_accum_keypair.public_key.clone(),
+            derived_mem_prk.clone(),
+            *uni_accumulator.value(),
+        ),
+    );
 
     let mut witnesses = Witnesses::new();
     witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
@@ -2957,3 +3984,217 @@ fn detached_accumulator() {
         _ => assert!(false, "Needed a detached accumulator proof"),
     }
 }
+
+#[test]
+fn detached_accumulator_cdh() {
+    // Prove knowledge of BBS+ signature and membership of its messages in positive and universal
+    // accumulators using the CDH approach where the accumulators are only revealed to their managers
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 6;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let max = 10;
+    let (pos_accum_params, pos_accum_keypair, mut pos_accumulator, mut pos_state) =
+        setup_positive_accum(&mut rng);
+    let (uni_accum_params, uni_accum_keypair, mut uni_accumulator, initial_elements, mut uni_state) =
+        setup_universal_accum(&mut rng, max);
+
+    let accum_member_1_idx = 1;
+    let accum_member_1 = msgs[accum_member_1_idx];
+    pos_accumulator = pos_accumulator
+        .add(
+            accum_member_1,
+            &pos_accum_keypair.secret_key,
+            &mut pos_state,
+        )
+        .unwrap();
+    let mem_1_wit = pos_accumulator
+        .get_membership_witness(&accum_member_1, &pos_accum_keypair.secret_key, &pos_state)
+        .unwrap();
+
+    let accum_member_2_idx = 2;
+    let accum_member_2 = msgs[accum_member_2_idx];
+    uni_accumulator = uni_accumulator
+        .add(
+            accum_member_2,
+            &uni_accum_keypair.secret_key,
+            &initial_elements,
+            &mut uni_state,
+        )
+        .unwrap();
+    let mem_2_wit = uni_accumulator
+        .get_membership_witness(&accum_member_2, &uni_accum_keypair.secret_key, &uni_state)
+        .unwrap();
+
+    // Params of the universal accumulator are passed by reference
+    let all_setup_params = vec![
+        SetupParams::VbAccumulatorParams(uni_accum_params.clone()),
+        SetupParams::VbAccumulatorPublicKey(uni_accum_keypair.public_key.clone()),
+    ];
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(
+        DetachedAccumulatorMembershipCDHProver::new_statement_from_params(
+            pos_accum_params.clone(),
+            pos_accum_keypair.public_key.clone(),
+            *pos_accumulator.value(),
+        ),
+    );
+    prover_statements.add(
+        DetachedAccumulatorMembershipCDHProver::new_statement_from_params_ref(
+            0,
+            1,
+            *uni_accumulator.value(),
+        ),
+    );
+    test_serialization!(Statements<Bls12_381, G1Affine>, prover_statements);
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements.add(
+        DetachedAccumulatorMembershipCDHVerifier::new_statement_from_params(
+            pos_accum_params.clone(),
+            pos_accum_keypair.public_key.clone(),
+        ),
+    );
+    verifier_statements
+        .add(DetachedAccumulatorMembershipCDHVerifier::new_statement_from_params_ref(0, 1));
+    test_serialization!(Statements<Bls12_381, G1Affine>, verifier_statements);
+
+    // The new statements are serialized with tags after all existing ones
+    let tags = prover_statements
+        .iter()
+        .chain(verifier_statements.iter())
+        .map(|s| {
+            let mut bytes = vec![];
+            s.serialize_compressed(&mut bytes).unwrap();
+            bytes[0]
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(tags, vec![0, 40, 40, 0, 41, 41]);
+    for (p, v) in prover_statements.iter().zip(verifier_statements.iter()) {
+        assert_eq!(p.to_verifier_statement().unwrap(), *v);
+    }
+
+    let meta_statements = |member_1_idx: usize| {
+        let mut meta_statements = MetaStatements::new();
+        meta_statements.add_witness_equality(EqualWitnesses(
+            vec![(0, member_1_idx), (1, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        ));
+        meta_statements.add_witness_equality(EqualWitnesses(
+            vec![(0, accum_member_2_idx), (2, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        ));
+        meta_statements
+    };
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig.clone(),
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(MembershipWit::new_as_witness(accum_member_1, mem_1_wit));
+    witnesses.add(MembershipWit::new_as_witness(accum_member_2, mem_2_wit));
+
+    let context = Some(b"test".to_vec());
+    let nonce = Some(b"test-nonce".to_vec());
+
+    let proof_spec = ProofSpec::new(
+        prover_statements.clone(),
+        meta_statements(accum_member_1_idx),
+        all_setup_params.clone(),
+        context.clone(),
+    );
+    proof_spec.validate().unwrap();
+    test_serialization!(ProofSpec<Bls12_381, G1Affine>, proof_spec);
+
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec,
+        witnesses.clone(),
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    test_serialization!(ProofG1, proof);
+
+    let verifier_proof_spec = |member_1_idx: usize| {
+        ProofSpec::new(
+            verifier_statements.clone(),
+            meta_statements(member_1_idx),
+            all_setup_params.clone(),
+            context.clone(),
+        )
+    };
+    let start = Instant::now();
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(accum_member_1_idx),
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap();
+    println!(
+        "Time to verify proof with a BBS+ signature and 2 detached CDH accumulator membership checks: {:?}",
+        start.elapsed()
+    );
+
+    // Accumulator managers decrypt the accumulators and verify the membership proofs
+    for (i, (params, keypair)) in [
+        (&pos_accum_params, &pos_accum_keypair),
+        (&uni_accum_params, &uni_accum_keypair),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let statement_proof = &proof.statement_proofs[i + 1];
+        assert!(
+            matches!(
+                statement_proof,
+                StatementProof::DetachedAccumulatorMembershipCDH(_)
+            ),
+            "Needed a detached CDH accumulator proof"
+        );
+        if let StatementProof::DetachedAccumulatorMembershipCDH(p) = statement_proof {
+            let sp = DetachedAccumulatorMembershipCDHSubProtocol::new(
+                i + 1,
+                params,
+                &keypair.public_key,
+            );
+            sp.verify_proof_contribution(
+                p,
+                &keypair.secret_key,
+                keypair.public_key.clone(),
+                params.clone(),
+            )
+            .unwrap();
+        }
+    }
+
+    // Proof doesn't verify when the accumulator member is claimed to be a different message
+    assert!(matches!(
+        proof.verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(accum_member_1_idx + 2),
+            nonce,
+            Default::default(),
+        ),
+        Err(ProofSystemError::WitnessResponseNotEqual(..))
+    ));
+}
@@ -0,0 +1,198 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    rand::{prelude::StdRng, SeedableRng},
+    UniformRand,
+};
+use blake2::Blake2b512;
+use kvac::bddt_2016::{
+    mac::MAC,
+    setup::{MACParams, SecretKey},
+};
+use vb_accumulator::prelude::{Accumulator, MembershipProvingKey};
+
+use proof_system::{
+    prelude::{EqualWitnesses, MetaStatements, ProofSystemError, Witnesses},
+    proof_spec::ProofSpec,
+    statement::{
+        accumulator::VBAccumulatorMembership as AccumulatorMembershipStmt,
+        bddt16_kvac::{PoKBDDT16MAC, PoKBDDT16MACFullVerifier},
+        Statements,
+    },
+    witness::{Membership as MembershipWit, PoKOfBDDT16MAC as PoKOfMACWit},
+};
+use test_utils::{accumulators::*, test_serialization, Fr, ProofG1};
+
+#[test]
+fn pok_of_bddt16_mac_and_accumulator_membership() {
+    // Prove knowledge of a MAC and that one of its messages is a member of an accumulator
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5_usize;
+    let msgs = (0..msg_count)
+        .map(|_| Fr::rand(&mut rng))
+        .collect::<Vec<_>>();
+    let mac_params = MACParams::<G1Affine>::new::<Blake2b512>(b"test", msg_count as u32);
+    let mac_sk = SecretKey::new(&mut rng);
+    let mac = MAC::new(&mut rng, &msgs, &mac_sk, &mac_params).unwrap();
+
+    let (accum_params, accum_keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+    let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+    // Message with index `accum_member_idx` is added in the accumulator
+    let accum_member_idx = 2;
+    let accum_member = msgs[accum_member_idx];
+    accumulator = accumulator
+        .add(accum_member, &accum_keypair.secret_key, &mut state)
+        .unwrap();
+    let mem_wit = accumulator
+        .get_membership_witness(&accum_member, &accum_keypair.secret_key, &state)
+        .unwrap();
+
+    // Message with index 0 is revealed
+    let revealed_msgs = BTreeMap::from([(0, msgs[0])]);
+    let unrevealed_msgs = (1..msg_count)
+        .map(|i| (i, msgs[i]))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(BTreeSet::from([
+        (0, accum_member_idx),
+        (1, 0),
+    ])));
+
+    let accum_statement = AccumulatorMembershipStmt::new_statement_from_params(
+        accum_params.clone(),
+        accum_keypair.public_key.clone(),
+        mem_prk.clone(),
+        *accumulator.value(),
+    );
+
+    let mut prover_statements = Statements::<Bls12_381, G1Affine>::new();
+    prover_statements.add(PoKBDDT16MAC::new_statement_from_params(
+        mac_params.clone(),
+        revealed_msgs.clone(),
+    ));
+    prover_statements.add(accum_statement.clone());
+    test_serialization!(Statements<Bls12_381, G1Affine>, prover_statements);
+
+    let prover_proof_spec = ProofSpec::new(
+        prover_statements.clone(),
+        meta_statements.clone(),
+        vec![],
+        None,
+    );
+    prover_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKOfMACWit::new_as_witness(
+        mac.clone(),
+        unrevealed_msgs.clone(),
+    ));
+    witnesses.add(MembershipWit::new_as_witness(accum_member, mem_wit.clone()));
+    test_serialization!(Witnesses<Bls12_381>, witnesses);
+
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec.clone(),
+        witnesses.clone(),
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    test_serialization!(ProofG1, proof);
+
+    let verifier_proof_spec = |sk: SecretKey<Fr>, revealed_msgs: BTreeMap<usize, Fr>| {
+        let mut verifier_statements = Statements::<Bls12_381, G1Affine>::new();
+        verifier_statements.add(PoKBDDT16MACFullVerifier::new_statement_from_params(
+            mac_params.clone(),
+            sk,
+            revealed_msgs,
+        ));
+        verifier_statements.add(accum_statement.clone());
+        let spec = ProofSpec::new(verifier_statements, meta_statements.clone(), vec![], None);
+        spec.validate().unwrap();
+        spec
+    };
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(mac_sk.clone(), revealed_msgs.clone()),
+            None,
+            Default::default(),
+        )
+        .unwrap();
+
+    // The prover's statement can't be used for verifying as it doesn't have the secret key
+    assert!(matches!(
+        proof.clone().verify::<StdRng, Blake2b512>(
+            &mut rng,
+            prover_proof_spec.clone(),
+            None,
+            Default::default()
+        ),
+        Err(ProofSystemError::InvalidStatement)
+    ));
+
+    // Doesn't verify with a different secret key
+    let wrong_sk = SecretKey::new(&mut rng);
+    assert!(proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(wrong_sk, revealed_msgs.clone()),
+            None,
+            Default::default(),
+        )
+        .is_err());
+
+    // Doesn't verify with a different revealed message
+    let wrong_revealed_msgs = BTreeMap::from([(0, Fr::rand(&mut rng))]);
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(mac_sk.clone(), wrong_revealed_msgs),
+            None,
+            Default::default(),
+        )
+        .is_err());
+
+    // A MAC'd message that isn't in the accumulator can't be proven a member with another member's witness
+    let non_member_idx = 3;
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKOfMACWit::new_as_witness(mac, unrevealed_msgs));
+    witnesses.add(MembershipWit::new_as_witness(msgs[non_member_idx], mem_wit));
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(BTreeSet::from([
+        (0, non_member_idx),
+        (1, 0),
+    ])));
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        ProofSpec::new(prover_statements, meta_statements.clone(), vec![], None),
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    let mut verifier_statements = Statements::<Bls12_381, G1Affine>::new();
+    verifier_statements.add(PoKBDDT16MACFullVerifier::new_statement_from_params(
+        mac_params,
+        mac_sk,
+        revealed_msgs,
+    ));
+    verifier_statements.add(accum_statement);
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            ProofSpec::new(verifier_statements, meta_statements, vec![], None),
+            None,
+            Default::default(),
+        )
+        .is_err());
+}
@@ -0,0 +1,272 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    rand::{prelude::StdRng, SeedableRng},
+    UniformRand,
+};
+use blake2::Blake2b512;
+use std::time::Instant;
+
+use proof_system::{
+    prelude::{
+        EqualWitnesses, MetaStatements, ProofSpec, ProofSystemError, SetupParams, Witness,
+        WitnessRef, Witnesses,
+    },
+    statement::{
+        bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
+        bloom_membership::{
+            BloomMembershipLegoGroth16Prover as BloomMembershipProverStmt,
+            BloomMembershipLegoGroth16Verifier as BloomMembershipVerifierStmt,
+        },
+        Statement, Statements,
+    },
+    sub_protocols::bloom_membership::{generate_snark_srs_bloom_membership, BloomFilter},
+    witness::PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
+};
+
+use test_utils::{bbs::*, test_serialization, Fr, ProofG1};
+
+#[test]
+fn pok_of_bbs_plus_sig_and_bloom_filter_membership() {
+    // Prove knowledge of BBS+ signature and a specific message is present in a public Bloom filter
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let num_bits = 256;
+    let num_hashes = 3;
+
+    // Verifier sets up LegoGroth16 public parameters for Bloom filters with the above parameters. This can be
+    // reused for all filters with the same number of bits and hash functions
+    let start = Instant::now();
+    let snark_pk =
+        generate_snark_srs_bloom_membership::<Bls12_381, _>(&mut rng, num_bits, num_hashes)
+            .unwrap();
+    println!(
+        "Time taken to generate SNARK params for Bloom filter with {} bits and {} hashes {:?}",
+        num_bits,
+        num_hashes,
+        start.elapsed()
+    );
+
+    // Following message's membership will be checked
+    let msg_idx = 1;
+    let msg = msgs[msg_idx];
+
+    // Allowlist containing the message
+    let mut filter = BloomFilter::new(num_bits, num_hashes).unwrap();
+    filter.insert(&msg);
+    for _ in 0..10 {
+        filter.insert(&Fr::rand(&mut rng));
+    }
+    assert!(filter.contains(&msg));
+
+    // Allowlist not containing the message
+    let mut other_filter = BloomFilter::new(num_bits, num_hashes).unwrap();
+    for _ in 0..10 {
+        other_filter.insert(&Fr::rand(&mut rng));
+    }
+    // Since the filter can have false positives, make sure the message isn't one of them
+    assert!(!other_filter.contains(&msg));
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, msg_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(
+        BloomMembershipProverStmt::new_statement_from_params(filter.clone(), snark_pk.clone())
+            .unwrap(),
+    );
+    test_serialization!(Statements<Bls12_381, G1Affine>, prover_statements);
+
+    let proof_spec_prover = ProofSpec::new(
+        prover_statements.clone(),
+        meta_statements.clone(),
+        vec![],
+        None,
+    );
+    proof_spec_prover.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig.clone(),
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(Witness::BloomMembershipLegoGroth16(msg));
+    test_serialization!(Witnesses<Bls12_381>, witnesses);
+
+    let start = Instant::now();
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec_prover.clone(),
+        witnesses.clone(),
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    println!(
+        "Time taken to create proof of Bloom filter membership of 1 message in signature over {} messages {:?}",
+        msg_count,
+        start.elapsed()
+    );
+    test_serialization!(ProofG1, proof);
+
+    let verifier_spec = |f: BloomFilter| {
+        let mut verifier_statements = Statements::new();
+        verifier_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        verifier_statements.add(
+            BloomMembershipVerifierStmt::new_statement_from_params(f, snark_pk.vk.clone()).unwrap(),
+        );
+        ProofSpec::new(verifier_statements, meta_statements.clone(), vec![], None)
+    };
+
+    let verifier_proof_spec = verifier_spec(filter.clone());
+    verifier_proof_spec.validate().unwrap();
+    test_serialization!(ProofSpec<Bls12_381, G1Affine>, verifier_proof_spec);
+
+    let start = Instant::now();
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            None,
+            Default::default(),
+        )
+        .unwrap();
+    println!(
+        "Time taken to verify proof of Bloom filter membership of 1 message in signature over {} messages {:?}",
+        msg_count,
+        start.elapsed()
+    );
+
+    // Verifying with the statement derived from the prover's statement works as well
+    let mut derived_statements = Statements::new();
+    for s in &prover_statements.0 {
+        derived_statements.add(s.to_verifier_statement().unwrap());
+    }
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            ProofSpec::new(derived_statements, meta_statements.clone(), vec![], None),
+            None,
+            Default::default(),
+        )
+        .unwrap();
+
+    // Verification fails against a filter not containing the message
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_spec(other_filter.clone()),
+            None,
+            Default::default(),
+        )
+        .is_err());
+
+    // Prover can't create a proof for a filter not containing the message
+    let mut prover_statements = Statements::<Bls12_381, G1Affine>::new();
+    prover_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(
+        BloomMembershipProverStmt::new_statement_from_params(other_filter, snark_pk.clone())
+            .unwrap(),
+    );
+    assert!(matches!(
+        ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            ProofSpec::new(prover_statements, meta_statements, vec![], None),
+            witnesses,
+            None,
+            Default::default(),
+        ),
+        Err(ProofSystemError::ElementNotInBloomFilter(1))
+    ));
+
+    // SNARK params given in setup params are checked against the filter when they are resolved
+    let small_filter = BloomFilter::new(128, num_hashes).unwrap();
+    let setup_params = vec![
+        SetupParams::<Bls12_381, G1Affine>::LegoSnarkProvingKey(snark_pk.clone()),
+        SetupParams::<Bls12_381, G1Affine>::LegoSnarkVerifyingKey(snark_pk.vk.clone()),
+    ];
+    match BloomMembershipProverStmt::new_statement_from_params_ref::<G1Affine>(
+        small_filter.clone(),
+        0,
+    )
+    .unwrap()
+    {
+        Statement::BloomMembershipLegoGroth16Prover(s) => {
+            assert!(s.get_proving_key(&setup_params, 0).is_err())
+        }
+        _ => panic!("expected a Bloom filter membership prover statement"),
+    }
+    match BloomMembershipVerifierStmt::new_statement_from_params_ref::<G1Affine>(small_filter, 1)
+        .unwrap()
+    {
+        Statement::BloomMembershipLegoGroth16Verifier(s) => {
+            assert!(s.get_verifying_key(&setup_params, 0).is_err())
+        }
+        _ => panic!("expected a Bloom filter membership verifier statement"),
+    }
+    match BloomMembershipVerifierStmt::new_statement_from_params_ref::<G1Affine>(filter.clone(), 1)
+        .unwrap()
+    {
+        Statement::BloomMembershipLegoGroth16Verifier(s) => {
+            assert!(s.get_verifying_key(&setup_params, 0).is_ok())
+        }
+        _ => panic!("expected a Bloom filter membership verifier statement"),
+    }
+
+    // SNARK params must be for a filter of the same size
+    assert!(
+        BloomMembershipVerifierStmt::new_statement_from_params::<G1Affine>(
+            BloomFilter::new(128, num_hashes).unwrap(),
+            snark_pk.vk.clone()
+        )
+        .is_err()
+    );
+
+    // and with the same number of hashes
+    let other_snark_pk =
+        generate_snark_srs_bloom_membership::<Bls12_381, _>(&mut rng, num_bits, num_hashes + 1)
+            .unwrap();
+    assert!(BloomMembershipProverStmt::new_statement_from_params::<G1Affine>(
+        filter.clone(),
+        other_snark_pk.clone()
+    )
+    .is_err());
+    assert!(
+        BloomMembershipVerifierStmt::new_statement_from_params::<G1Affine>(
+            filter.clone(),
+            other_snark_pk.vk.clone()
+        )
+        .is_err()
+    );
+    assert!(
+        BloomMembershipVerifierStmt::new_statement_from_params::<G1Affine>(
+            BloomFilter::new(num_bits, num_hashes + 1).unwrap(),
+            other_snark_pk.vk
+        )
+        .is_ok()
+    );
+}
@@ -3,6 +3,7 @@ use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{
     collections::{BTreeMap, BTreeSet},
     rand::{prelude::StdRng, SeedableRng},
+    UniformRand,
 };
 use bbs_plus::{prelude::KeypairG2, setup::SignatureParamsG1, signature::SignatureG1};
 use blake2::Blake2b512;
@@ -10,11 +11,14 @@ use bulletproofs_plus_plus::prelude::SetupParams;
 use std::time::Instant;
 
 use proof_system::{
+    error::ProofSystemError,
     prelude::{EqualWitnesses, MetaStatements, ProofSpec, Witness, WitnessRef, Witnesses},
     statement::{
+        aggregated_bound_check_bpp::AggregatedBoundCheckBpp as AggregatedBoundCheckStmt,
         bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
         bound_check_bpp::BoundCheckBpp as BoundCheckStmt, Statements,
     },
+    sub_protocols::bound_check_bpp::BppSetupParamsCache,
     witness::PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
 };
 
@@ -177,3 +181,416 @@ fn pok_of_bbs_plus_sig_and_bounded_message_using_bulletproofs_plus_plus() {
         false,
     );
 }
+
+#[test]
+fn pok_of_bbs_plus_sig_and_bounded_sum_of_messages_using_bulletproofs_plus_plus() {
+    // Prove knowledge of BBS+ signature and that the sum of 2 of its messages satisfies some bounds, i.e.
+    // min <= message_1 + message_2 < max, like base salary and bonus
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msgs = vec![
+        Fr::from(1u64),
+        Fr::from(70000u64),
+        Fr::from(2u64),
+        Fr::from(20000u64),
+        Fr::from(3u64),
+    ];
+    let (sig_params, sig_keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+
+    let bpp_setup_params =
+        SetupParams::<G1Affine>::new_for_arbitrary_range_proof::<Blake2b512>(b"test", 2, 64, 1);
+    let g = G1Affine::rand(&mut rng);
+
+    fn check(
+        rng: &mut StdRng,
+        min: u64,
+        max: u64,
+        m_1: Fr,
+        m_2: Fr,
+        msgs: &[Fr],
+        sig_params: &SignatureParamsG1<Bls12_381>,
+        sig_keypair: &KeypairG2<Bls12_381>,
+        sig: &SignatureG1<Bls12_381>,
+        bpp_setup_params: &SetupParams<G1Affine>,
+        g: &G1Affine,
+        valid_proof: bool,
+    ) {
+        let mut statements = Statements::new();
+        let mut meta_statements = MetaStatements::new();
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        let (ped_comm_idx, bound_check_idx) = BoundCheckStmt::add_statements_for_sum(
+            &mut statements,
+            &mut meta_statements,
+            min,
+            max,
+            bpp_setup_params.clone(),
+            *g,
+            (0, 1),
+            (0, 3),
+        )
+        .unwrap();
+        assert_eq!(ped_comm_idx, 1);
+        assert_eq!(bound_check_idx, 2);
+
+        let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+        proof_spec.validate().unwrap();
+        if valid_proof {
+            test_serialization!(ProofSpec<Bls12_381, G1Affine>, proof_spec);
+        }
+
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig.clone(),
+            msgs.iter().cloned().enumerate().collect(),
+        ));
+        assert_eq!(
+            witnesses.add_for_bound_check_bpp_of_sum(m_1, m_2).unwrap(),
+            (1, 2)
+        );
+
+        let proof = match ProofG1::new::<StdRng, Blake2b512>(
+            rng,
+            proof_spec.clone(),
+            witnesses,
+            None,
+            Default::default(),
+        ) {
+            Ok((p, _)) => p,
+            // The prover refuses to create a proof when the sum is out of range
+            Err(ProofSystemError::BulletproofsPlusPlus(_)) => {
+                assert!(!valid_proof);
+                return;
+            }
+            Err(e) => panic!("{:?}", e),
+        };
+        if valid_proof {
+            test_serialization!(ProofG1, proof);
+        }
+
+        let res = proof.verify::<StdRng, Blake2b512>(rng, proof_spec, None, Default::default());
+        assert_eq!(res.is_ok(), valid_proof);
+    }
+
+    // Sum is 90000 which is in range
+    check(
+        &mut rng,
+        50000,
+        100000,
+        msgs[1],
+        msgs[3],
+        &msgs,
+        &sig_params,
+        &sig_keypair,
+        &sig,
+        &bpp_setup_params,
+        &g,
+        true,
+    );
+
+    // Sum is out of range even though each message is within it
+    check(
+        &mut rng,
+        10000,
+        80000,
+        msgs[1],
+        msgs[3],
+        &msgs,
+        &sig_params,
+        &sig_keypair,
+        &sig,
+        &bpp_setup_params,
+        &g,
+        false,
+    );
+
+    // Sum is in range but one of the messages isn't signed
+    check(
+        &mut rng,
+        50000,
+        100000,
+        msgs[1],
+        Fr::from(10000u64),
+        &msgs,
+        &sig_params,
+        &sig_keypair,
+        &sig,
+        &bpp_setup_params,
+        &g,
+        false,
+    );
+
+    // Sum doesn't fit in a u64
+    let mut witnesses = Witnesses::<Bls12_381>::new();
+    assert!(matches!(
+        witnesses.add_for_bound_check_bpp_of_sum(Fr::from(u64::MAX), Fr::from(1u64)),
+        Err(ProofSystemError::BoundCheckSumOverflow(a, 1)) if a == u64::MAX
+    ));
+    assert!(witnesses.is_empty());
+}
+
+#[test]
+fn bulletproofs_plus_plus_setup_params_cache() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let min = 100;
+    let max = 200;
+    let msg_count = 5;
+    let msgs = (0..msg_count)
+        .map(|i| Fr::from(min + 1 + i as u64))
+        .collect::<Vec<_>>();
+    let (sig_params, sig_keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+
+    let mut cache = BppSetupParamsCache::<G1Affine, Blake2b512>::new(b"test", 2);
+    assert!(cache.is_empty());
+
+    let start = Instant::now();
+    let params_1 = cache.get(64).clone();
+    let uncached_time = start.elapsed();
+    assert!(cache.contains(64));
+    assert_eq!(cache.len(), 1);
+
+    let start = Instant::now();
+    let params_2 = cache.get(64).clone();
+    let cached_time = start.elapsed();
+    assert_eq!(cache.len(), 1);
+    assert_eq!(params_1, params_2);
+    assert_eq!(
+        params_1,
+        SetupParams::<G1Affine>::new_for_arbitrary_range_proof::<Blake2b512>(b"test", 2, 64, 1)
+    );
+    println!(
+        "Getting setup params takes {:?} when not cached and {:?} when cached",
+        uncached_time, cached_time
+    );
+
+    // Params for another bit width are created separately
+    assert_ne!(cache.get(32), &params_1);
+    assert_eq!(cache.len(), 2);
+    assert!(cache.contains(32));
+
+    // Proofs using the cached params verify
+    for (msg_idx, params) in [(1, params_1), (3, params_2)] {
+        let mut statements = Statements::new();
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        statements.add(BoundCheckStmt::new_statement_from_params(min, max, params).unwrap());
+
+        let mut meta_statements = MetaStatements::new();
+        meta_statements.add_witness_equality(EqualWitnesses(
+            vec![(0, msg_idx), (1, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        ));
+        let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+        proof_spec.validate().unwrap();
+
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig.clone(),
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        witnesses.add(Witness::BoundCheckBpp(msgs[msg_idx]));
+
+        let proof = ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec.clone(),
+            witnesses,
+            None,
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+        proof
+            .verify::<StdRng, Blake2b512>(&mut rng, proof_spec, None, Default::default())
+            .unwrap();
+    }
+}
+
+#[test]
+fn pok_of_bbs_plus_sig_and_aggregated_bounded_messages_using_bulletproofs_plus_plus() {
+    // Prove knowledge of BBS+ signature and that 4 of its messages satisfy their own bounds using a single
+    // aggregated Bulletproofs++ proof, and compare its size with 4 separate bound check proofs
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msgs = vec![
+        Fr::from(1u64),
+        Fr::from(25u64),
+        Fr::from(170u64),
+        Fr::from(70000u64),
+        Fr::from(1990u64),
+        Fr::from(3u64),
+    ];
+    let msg_indices = [1, 2, 3, 4];
+    let bounds = vec![(18, 65), (150, 200), (50000, 100000), (1900, 2000)];
+    let (sig_params, sig_keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+
+    let aggr_setup_params =
+        SetupParams::<G1Affine>::new_for_arbitrary_range_proof::<Blake2b512>(b"test", 2, 64, 4);
+    let single_setup_params =
+        SetupParams::<G1Affine>::new_for_arbitrary_range_proof::<Blake2b512>(b"test", 2, 64, 1);
+
+    fn prove_and_verify(
+        rng: &mut StdRng,
+        statements: Statements<Bls12_381, G1Affine>,
+        meta_statements: MetaStatements,
+        witnesses: Witnesses<Bls12_381>,
+    ) -> Result<ProofG1, ProofSystemError> {
+        let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+        proof_spec.validate().unwrap();
+        let proof = ProofG1::new::<StdRng, Blake2b512>(
+            rng,
+            proof_spec.clone(),
+            witnesses,
+            None,
+            Default::default(),
+        )?
+        .0;
+        proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(rng, proof_spec, None, Default::default())?;
+        Ok(proof)
+    }
+
+    let sig_stmt = PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    );
+    let sig_wit = PoKSignatureBBSG1Wit::new_as_witness(
+        sig.clone(),
+        msgs.iter().cloned().enumerate().collect(),
+    );
+
+    let aggr_statements = |bounds: Vec<(u64, u64)>| {
+        let mut statements = Statements::new();
+        statements.add(sig_stmt.clone());
+        statements.add(
+            AggregatedBoundCheckStmt::new_statement_from_params(bounds, aggr_setup_params.clone())
+                .unwrap(),
+        );
+        let mut meta_statements = MetaStatements::new();
+        for eq in AggregatedBoundCheckStmt::<G1Affine>::witness_equalities_with_signature(
+            0,
+            &msg_indices,
+            1,
+        ) {
+            meta_statements.add_witness_equality(eq);
+        }
+        (statements, meta_statements)
+    };
+    let aggr_witnesses = |values: Vec<Fr>| {
+        let mut witnesses = Witnesses::new();
+        witnesses.add(sig_wit.clone());
+        witnesses.add(Witness::AggregatedBoundCheckBpp(values));
+        witnesses
+    };
+
+    let (statements, meta_statements) = aggr_statements(bounds.clone());
+    test_serialization!(Statements<Bls12_381, G1Affine>, statements);
+    let aggr_proof = prove_and_verify(
+        &mut rng,
+        statements,
+        meta_statements,
+        aggr_witnesses(msg_indices.iter().map(|i| msgs[*i]).collect()),
+    )
+    .unwrap();
+    test_serialization!(ProofG1, aggr_proof);
+
+    // Same bounds proven with a separate Bulletproofs++ proof for each message
+    let mut statements = Statements::new();
+    let mut meta_statements = MetaStatements::new();
+    let mut witnesses = Witnesses::new();
+    statements.add(sig_stmt.clone());
+    witnesses.add(sig_wit.clone());
+    for (i, (min, max)) in bounds.iter().enumerate() {
+        let s_idx = statements.add(
+            BoundCheckStmt::new_statement_from_params(*min, *max, single_setup_params.clone())
+                .unwrap(),
+        );
+        meta_statements.add_witness_equality(EqualWitnesses(
+            vec![(0, msg_indices[i]), (s_idx, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        ));
+        witnesses.add(Witness::BoundCheckBpp(msgs[msg_indices[i]]));
+    }
+    let separate_proof =
+        prove_and_verify(&mut rng, statements, meta_statements, witnesses).unwrap();
+
+    // Compare only the bound check parts of the proofs as the proof of knowledge of the signature is the same
+    let aggr_size = aggr_proof.statement_proofs[1].compressed_size();
+    let separate_size = separate_proof.statement_proofs[1..]
+        .iter()
+        .map(|p| p.compressed_size())
+        .sum::<usize>();
+    println!(
+        "Size of aggregated bound check proof of 4 messages is {} bytes and of separate bound check proofs is {} bytes",
+        aggr_size, separate_size
+    );
+    assert!(2 * aggr_size < separate_size);
+
+    // A message is out of its bounds so the prover refuses to create the proof
+    let (statements, meta_statements) =
+        aggr_statements(vec![(18, 65), (150, 200), (80000, 100000), (1900, 2000)]);
+    assert!(matches!(
+        prove_and_verify(
+            &mut rng,
+            statements,
+            meta_statements,
+            aggr_witnesses(msg_indices.iter().map(|i| msgs[*i]).collect()),
+        ),
+        Err(ProofSystemError::BulletproofsPlusPlus(_))
+    ));
+
+    // Messages are in their bounds but one of them isn't signed
+    let (statements, meta_statements) = aggr_statements(bounds.clone());
+    assert!(prove_and_verify(
+        &mut rng,
+        statements,
+        meta_statements,
+        aggr_witnesses(vec![msgs[1], msgs[2], Fr::from(60000u64), msgs[4]]),
+    )
+    .is_err());
+
+    // Proof for one set of bounds doesn't verify with tighter bounds
+    let (statements, meta_statements) = aggr_statements(bounds.clone());
+    let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+    let (statements, meta_statements) =
+        aggr_statements(vec![(18, 65), (150, 200), (75000, 100000), (1900, 2000)]);
+    let tighter_proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec,
+        aggr_witnesses(msg_indices.iter().map(|i| msgs[*i]).collect()),
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(&mut rng, tighter_proof_spec, None, Default::default())
+        .is_err());
+
+    // Bounds should be non-empty and each max should be greater than its min
+    assert!(matches!(
+        AggregatedBoundCheckStmt::<G1Affine>::new_statement_from_params::<Bls12_381>(
+            vec![],
+            aggr_setup_params.clone()
+        ),
+        Err(ProofSystemError::NoBoundsForAggregatedBoundCheck)
+    ));
+    assert!(matches!(
+        AggregatedBoundCheckStmt::<G1Affine>::new_statement_from_params_ref::<Bls12_381>(
+            vec![(18, 65), (200, 150)],
+            0
+        ),
+        Err(ProofSystemError::BoundCheckMaxNotGreaterThanMin)
+    ));
+}
@@ -22,6 +22,7 @@ use proof_system::{
             BoundCheckLegoGroth16Prover as BoundCheckProverStmt,
             BoundCheckLegoGroth16Verifier as BoundCheckVerifierStmt,
         },
+        builder::StatementsBuilder,
         Statements,
     },
     sub_protocols::bound_check_legogroth16::generate_snark_srs_bound_check,
@@ -663,3 +664,119 @@ fn pok_of_bbs_plus_sig_and_message_same_as_bound() {
         .verify::<StdRng, Blake2b512>(&mut rng, proof_spec_verifier, None, Default::default())
         .unwrap();
 }
+
+#[test]
+fn bound_check_statements_sharing_snark_keys() {
+    // Prove bounds of 10 messages of a BBS+ signature with 10 statements that all refer to the same LegoGroth16
+    // keys in the setup params, so the keys are loaded once and not cloned into each statement
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let count = 10;
+    let msgs = (0..count)
+        .map(|i| Fr::from(1000u64 + i as u64))
+        .collect::<Vec<_>>();
+    let bounds = (0..count)
+        .map(|i| (900 + i as u64, 1100 + 10 * i as u64))
+        .collect::<Vec<_>>();
+    let (sig_params, sig_keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+    let snark_pk = generate_snark_srs_bound_check::<Bls12_381, _>(&mut rng).unwrap();
+
+    let mut meta_statements = MetaStatements::new();
+    for i in 0..count {
+        meta_statements.add_witness_equality(EqualWitnesses(
+            vec![(0, i), (1 + i, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        ));
+    }
+
+    let mut builder = StatementsBuilder::<Bls12_381, G1Affine>::new();
+    builder.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    let pk_ref = builder.intern(SetupParams::LegoSnarkProvingKey(snark_pk.clone()));
+    for (min, max) in &bounds {
+        builder
+            .add(BoundCheckProverStmt::new_statement_from_params_ref(*min, *max, pk_ref).unwrap());
+    }
+    // A statement with the same key inline refers to the already present key
+    let inline = BoundCheckProverStmt::<Bls12_381>::new_statement_from_params::<G1Affine>(
+        bounds[0].0,
+        bounds[0].1,
+        snark_pk.clone(),
+    )
+    .unwrap();
+    let mut inline_builder = builder.clone();
+    inline_builder.add(inline);
+    assert_eq!(inline_builder.build().1.len(), 3);
+
+    let (prover_statements, prover_setup_params) = builder.build();
+    // Signature params, public key and the proving key
+    assert_eq!(prover_setup_params.len(), 3);
+    assert_eq!(prover_statements.len(), 1 + count);
+    println!(
+        "Size of the shared proving key is {} bytes, {} bound check statements inline would have {} bytes of keys",
+        snark_pk.compressed_size(),
+        count,
+        count * snark_pk.compressed_size()
+    );
+
+    let proof_spec_prover = ProofSpec::new(
+        prover_statements,
+        meta_statements.clone(),
+        prover_setup_params,
+        None,
+    );
+    proof_spec_prover.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    for m in &msgs {
+        witnesses.add(Witness::BoundCheckLegoGroth16(*m));
+    }
+
+    let start = Instant::now();
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec_prover,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    println!(
+        "Time taken to create proof of bound check of {} messages with a shared proving key: {:?}",
+        count,
+        start.elapsed()
+    );
+
+    let mut builder = StatementsBuilder::<Bls12_381, G1Affine>::new();
+    builder.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    let vk_ref = builder.intern(SetupParams::LegoSnarkVerifyingKey(snark_pk.vk.clone()));
+    for (min, max) in &bounds {
+        builder.add(
+            BoundCheckVerifierStmt::new_statement_from_params_ref(*min, *max, vk_ref).unwrap(),
+        );
+    }
+    let (verifier_statements, verifier_setup_params) = builder.build();
+    assert_eq!(verifier_setup_params.len(), 3);
+    let proof_spec_verifier = ProofSpec::new(
+        verifier_statements,
+        meta_statements,
+        verifier_setup_params,
+        None,
+    );
+    proof_spec_verifier.validate().unwrap();
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec_verifier, None, Default::default())
+        .unwrap();
+}
@@ -20,7 +20,7 @@ use proof_system::{
         bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
         bound_check_smc_with_kv::{
             BoundCheckSmcWithKVProver as BoundCheckProverStmt,
-            BoundCheckSmcWithKVVerifier as BoundCheckVerifierStmt,
+            BoundCheckSmcWithKVVerifier as BoundCheckVerifierStmt, VerificationMode,
         },
     },
     sub_protocols::should_use_cls,
@@ -273,3 +273,113 @@ fn pok_of_bbs_plus_sig_and_bounded_message_using_set_membership_check_range_proo
         is_cls,
     );
 }
+
+#[test]
+fn bound_check_smc_statements_from_verification_mode() {
+    // Create the statements and witness for both publicly verifiable and keyed verification proofs using the
+    // same code path
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let msg_count = 5;
+
+    let sig_params = SignatureParamsG1::<Bls12_381>::generate_using_rng(&mut rng, msg_count);
+    let sig_keypair = KeypairG2::<Bls12_381>::generate_using_rng(&mut rng, &sig_params);
+
+    let (smc_setup_params, sk) =
+        SmcParamsAndCommitmentKey::new::<_, Blake2b512>(&mut rng, b"test", 2);
+
+    let min = 100;
+    let max = 200;
+    let msgs = (0..msg_count)
+        .map(|i| Fr::from(min + 1 + i as u64))
+        .collect::<Vec<_>>();
+    let sig = SignatureG1::<Bls12_381>::new(&mut rng, &msgs, &sig_keypair.secret_key, &sig_params)
+        .unwrap();
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 1), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    for mode in [VerificationMode::Public, VerificationMode::Keyed] {
+        let mut prover_statements = Statements::<Bls12_381, G1Affine>::new();
+        prover_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        prover_statements.add(
+            mode.prover_statement(min, max, smc_setup_params.clone())
+                .unwrap(),
+        );
+        let proof_spec_prover = ProofSpec::new(
+            prover_statements.clone(),
+            meta_statements.clone(),
+            vec![],
+            None,
+        );
+        proof_spec_prover.validate().unwrap();
+
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig.clone(),
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        witnesses.add(mode.witness(msgs[1]));
+
+        let proof = ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec_prover,
+            witnesses,
+            None,
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+
+        // Publicly verifiable mode creates a bound check proof and keyed mode one that needs the secret key
+        assert!(
+            matches!(
+                (mode, &proof.statement_proofs[1]),
+                (VerificationMode::Public, StatementProof::BoundCheckSmc(_))
+                    | (
+                        VerificationMode::Keyed,
+                        StatementProof::BoundCheckSmcWithKV(_)
+                    )
+            ),
+            "unexpected statement proof for {:?}",
+            mode
+        );
+
+        let mut verifier_statements = Statements::<Bls12_381, G1Affine>::new();
+        verifier_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        verifier_statements.add(
+            mode.verifier_statement(min, max, smc_setup_params.clone(), Some(sk.clone()))
+                .unwrap(),
+        );
+        let proof_spec_verifier = ProofSpec::new(
+            verifier_statements.clone(),
+            meta_statements.clone(),
+            vec![],
+            None,
+        );
+        proof_spec_verifier.validate().unwrap();
+
+        proof
+            .verify::<StdRng, Blake2b512>(&mut rng, proof_spec_verifier, None, Default::default())
+            .unwrap();
+    }
+
+    // Keyed verification needs the secret key
+    assert!(VerificationMode::Keyed
+        .verifier_statement::<Bls12_381, G1Affine>(min, max, smc_setup_params.clone(), None)
+        .is_err());
+    assert!(VerificationMode::Public
+        .verifier_statement::<Bls12_381, G1Affine>(min, max, smc_setup_params, None)
+        .is_ok());
+}
@@ -0,0 +1,164 @@
+use ark_bls12_381::{Bls12_381, G1Affine, G1Projective};
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    rand::{prelude::StdRng, SeedableRng},
+    UniformRand,
+};
+use blake2::Blake2b512;
+
+use proof_system::{
+    prelude::{
+        EqualWitnesses, MetaStatements, ProofSpec, ProofSystemError, Witness, WitnessRef, Witnesses,
+    },
+    statement::{
+        bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
+        committed_message_equality::CommittedMessageEquality as CommittedMessageEqualityStmt,
+        Statements,
+    },
+    witness::PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
+};
+
+use test_utils::{bbs::*, test_serialization, Fr, ProofG1};
+
+#[test]
+fn pok_of_bbs_plus_sig_and_committed_message_equality() {
+    // Prove knowledge of BBS+ signature and that a message committed in a Pedersen commitment is one of the
+    // signed messages
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, 5);
+
+    // Commit to the 3rd message
+    let msg_idx = 2;
+    let comm_key = (0..2)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let randomness = Fr::rand(&mut rng);
+    let opening = vec![msgs[msg_idx], randomness];
+    let commitment = G1Projective::msm_unchecked(&comm_key, &opening).into_affine();
+
+    let proof_spec = |commitment: G1Affine, revealed: BTreeMap<usize, Fr>| {
+        let mut statements = Statements::<Bls12_381, G1Affine>::new();
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            revealed,
+        ));
+        statements.add(CommittedMessageEqualityStmt::new_statement_from_params(
+            comm_key.clone(),
+            commitment,
+            0,
+            msg_idx,
+        ));
+        // No witness equality is needed as the statement implies it
+        ProofSpec::new(statements, MetaStatements::new(), vec![], None)
+    };
+
+    let witnesses = |opening: Vec<Fr>| {
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig.clone(),
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        witnesses.add(Witness::CommittedMessageEquality(opening));
+        witnesses
+    };
+
+    let spec = proof_spec(commitment, BTreeMap::new());
+    spec.validate().unwrap();
+    test_serialization!(ProofSpec<Bls12_381, G1Affine>, spec);
+    assert_eq!(
+        spec.implied_witness_equalities(),
+        vec![EqualWitnesses(
+            vec![(0, msg_idx), (1, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>()
+        )]
+    );
+    test_serialization!(Witnesses<Bls12_381>, witnesses(opening.clone()));
+
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        spec.clone(),
+        witnesses(opening.clone()),
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    test_serialization!(ProofG1, proof);
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, spec.clone(), None, Default::default())
+        .unwrap();
+
+    // Verification fails if the commitment is tampered with
+    let tampered = (commitment + comm_key[1]).into_affine();
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec(tampered, BTreeMap::new()),
+            None,
+            Default::default()
+        )
+        .is_err());
+
+    // A commitment to a different message doesn't verify even though the prover knows its opening
+    let other_opening = vec![msgs[msg_idx + 1], randomness];
+    let other_commitment = G1Projective::msm_unchecked(&comm_key, &other_opening).into_affine();
+    let other_spec = proof_spec(other_commitment, BTreeMap::new());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        other_spec.clone(),
+        witnesses(other_opening),
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    assert!(matches!(
+        proof.verify::<StdRng, Blake2b512>(&mut rng, other_spec, None, Default::default()),
+        Err(ProofSystemError::WitnessResponseNotEqual(..))
+    ));
+
+    // The committed message can't be revealed
+    let mut revealed = BTreeMap::new();
+    revealed.insert(msg_idx, msgs[msg_idx]);
+    assert!(matches!(
+        proof_spec(commitment, revealed).validate(),
+        Err(ProofSystemError::WitnessAlreadyBeingRevealed(0, 2))
+    ));
+
+    // The referred statement must be a signature statement and the message must be one of the signed messages
+    let spec_with_ref = |signature_statement_index: usize, message_index: usize| {
+        let mut statements = Statements::<Bls12_381, G1Affine>::new();
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        statements.add(CommittedMessageEqualityStmt::new_statement_from_params(
+            comm_key.clone(),
+            commitment,
+            signature_statement_index,
+            message_index,
+        ));
+        ProofSpec::new(statements, MetaStatements::new(), vec![], None)
+    };
+    spec_with_ref(0, 4).validate().unwrap();
+    assert!(matches!(
+        spec_with_ref(1, 0).validate(),
+        Err(ProofSystemError::NotASignatureStatement(1, 1))
+    ));
+    assert!(matches!(
+        spec_with_ref(2, msg_idx).validate(),
+        Err(ProofSystemError::NotASignatureStatement(1, 2))
+    ));
+    assert!(matches!(
+        spec_with_ref(0, 5).validate(),
+        Err(ProofSystemError::SignedMessageIndexOutOfRange(1, 5, 5))
+    ));
+}
@@ -0,0 +1,191 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::BTreeMap,
+    rand::{prelude::StdRng, SeedableRng},
+};
+use blake2::Blake2b512;
+use bulletproofs_plus_plus::prelude::SetupParams;
+use std::time::Instant;
+
+use proof_system::{
+    prelude::{MetaStatement, MetaStatements, ProofSpec, ProofSystemError, Witness, Witnesses},
+    statement::{
+        bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
+        monotonic::Monotonic as MonotonicStmt, Statement, Statements,
+    },
+    statement_proof::StatementProof,
+    witness::PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
+};
+
+use test_utils::{bbs::*, test_serialization, Fr, ProofG1};
+
+#[test]
+fn pok_of_bbs_plus_sig_and_monotonic_messages() {
+    // Prove knowledge of BBS+ signature and that some of the signed messages are in order, like a sequence of readings
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msgs = [1000u64, 7, 12, 12, 40, 3]
+        .into_iter()
+        .map(Fr::from)
+        .collect::<Vec<_>>();
+    let (sig_params, sig_keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+
+    // Enough for proving order of up to 5 values as that needs 4 proofs
+    let bpp_setup_params =
+        SetupParams::<G1Affine>::new_for_perfect_range_proof::<Blake2b512>(b"test", 2, 64, 4);
+
+    let statements = |msg_indices: &[usize], strict: bool| {
+        let mut statements = Statements::<Bls12_381, G1Affine>::new();
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        statements.add(
+            MonotonicStmt::new_statement_from_params(
+                msg_indices.len(),
+                strict,
+                bpp_setup_params.clone(),
+            )
+            .unwrap(),
+        );
+        let mut meta_statements = MetaStatements::new();
+        for eq in MonotonicStmt::<G1Affine>::witness_equalities_with_signature(0, msg_indices, 1) {
+            meta_statements.add(MetaStatement::WitnessEquality(eq));
+        }
+        ProofSpec::new(statements, meta_statements, vec![], None)
+    };
+
+    let witnesses = |msg_indices: &[usize]| {
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig.clone(),
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        witnesses.add(Witness::Monotonic(
+            msg_indices.iter().map(|i| msgs[*i]).collect(),
+        ));
+        witnesses
+    };
+
+    let prove = |rng: &mut StdRng, msg_indices: &[usize], strict: bool| {
+        let proof_spec = statements(msg_indices, strict);
+        proof_spec.validate().unwrap();
+        ProofG1::new::<StdRng, Blake2b512>(
+            rng,
+            proof_spec,
+            witnesses(msg_indices),
+            None,
+            Default::default(),
+        )
+        .map(|p| p.0)
+    };
+
+    // 4 sorted messages with 2 equal adjacent ones
+    let sorted = [1, 2, 3, 4];
+    let proof_spec = statements(&sorted, false);
+    test_serialization!(ProofSpec<Bls12_381, G1Affine>, proof_spec);
+    test_serialization!(Witnesses<Bls12_381>, witnesses(&sorted));
+
+    let start = Instant::now();
+    let proof = prove(&mut rng, &sorted, false).unwrap();
+    println!(
+        "Time taken to create proof of order of {} signed messages {:?}",
+        sorted.len(),
+        start.elapsed()
+    );
+    test_serialization!(ProofG1, proof);
+
+    let start = Instant::now();
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec.clone(), None, Default::default())
+        .unwrap();
+    println!(
+        "Time taken to verify proof of order of {} signed messages {:?}",
+        sorted.len(),
+        start.elapsed()
+    );
+
+    // The same proof doesn't verify when strict order is required
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            statements(&sorted, true),
+            None,
+            Default::default()
+        )
+        .is_err());
+
+    // Messages having equal adjacent values are not in strict order
+    assert!(matches!(
+        prove(&mut rng, &sorted, true),
+        Err(ProofSystemError::MonotonicValuesNotInOrder(1, 1))
+    ));
+
+    // Strictly increasing messages can be proven in strict order
+    let strictly_sorted = [1, 2, 4];
+    let proof = prove(&mut rng, &strictly_sorted, true).unwrap();
+    proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            statements(&strictly_sorted, true),
+            None,
+            Default::default(),
+        )
+        .unwrap();
+
+    // Messages not in order
+    let unsorted = [1, 4, 5];
+    assert!(matches!(
+        prove(&mut rng, &unsorted, false),
+        Err(ProofSystemError::MonotonicValuesNotInOrder(1, 1))
+    ));
+
+    // A proof for sorted messages doesn't verify when linked to messages that aren't
+    let proof = prove(&mut rng, &strictly_sorted, false).unwrap();
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            statements(&[1, 4, 2], false),
+            None,
+            Default::default()
+        )
+        .is_err());
+
+    // A statement for fewer than 2 values, like a deserialized one, is rejected by the verifier rather than
+    // panicking even when the proof is for that number of values
+    let mut tampered_statements = Statements::<Bls12_381, G1Affine>::new();
+    tampered_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    let mut tampered_stmt =
+        MonotonicStmt::new_statement_from_params(2, false, bpp_setup_params.clone()).unwrap();
+    if let Statement::Monotonic(s) = &mut tampered_stmt {
+        s.num_values = 0;
+    }
+    tampered_statements.add(tampered_stmt);
+    let mut tampered_proof = prove(&mut rng, &strictly_sorted, false).unwrap();
+    if let StatementProof::Monotonic(p) = &mut tampered_proof.statement_proofs[1] {
+        p.commitments.clear();
+        p.sp.clear();
+    }
+    assert!(matches!(
+        tampered_proof.verify::<StdRng, Blake2b512>(
+            &mut rng,
+            ProofSpec::new(tampered_statements, MetaStatements::new(), vec![], None),
+            None,
+            Default::default()
+        ),
+        Err(ProofSystemError::InsufficientValuesForMonotonic(0))
+    ));
+
+    // Order needs at least 2 values
+    assert!(matches!(
+        MonotonicStmt::new_statement_from_params::<Bls12_381>(1, false, bpp_setup_params),
+        Err(ProofSystemError::InsufficientValuesForMonotonic(1))
+    ));
+}
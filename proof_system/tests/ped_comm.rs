@@ -1,21 +1,31 @@
-use ark_bls12_381::{Bls12_381, G1Affine, G1Projective};
+use ark_bls12_381::{Bls12_381, G1Affine, G1Projective, G2Affine, G2Projective};
 use ark_ec::{CurveGroup, VariableBaseMSM};
 use ark_ff::PrimeField;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{
-    collections::BTreeSet,
+    collections::{BTreeMap, BTreeSet},
     rand::{prelude::StdRng, SeedableRng},
     UniformRand,
 };
 use blake2::Blake2b512;
 use proof_system::{
+    error::ProofSystemError,
     prelude::{EqualWitnesses, MetaStatement, MetaStatements, Witness, WitnessRef, Witnesses},
     proof_spec::ProofSpec,
     setup_params::SetupParams,
-    statement::{ped_comm::PedersenCommitment as PedersenCommitmentStmt, Statements},
+    statement::{
+        bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
+        partial_ped_opening::PartialPedersenOpening as PartialPedersenOpeningStmt,
+        ped_comm::{
+            PedersenCommitment as PedersenCommitmentStmt, PedersenCommitmentWithHiddenBases,
+        },
+        Statements,
+    },
+    statement_proof::StatementProof,
+    witness::PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
 };
 
-use test_utils::{test_serialization, Fr, ProofG1};
+use test_utils::{bbs::bbs_plus_sig_setup, test_serialization, Fr, ProofG1, ProofG2};
 
 #[test]
 fn pok_of_knowledge_in_pedersen_commitment_and_equality() {
@@ -290,3 +300,456 @@ fn pok_of_knowledge_in_pedersen_commitment_and_equality_with_commitment_key_reus
         .verify::<StdRng, Blake2b512>(&mut rng, proof_spec, nonce, Default::default())
         .unwrap();
 }
+
+#[test]
+fn pok_of_partial_opening_of_pedersen_commitment() {
+    // Prove knowledge of some committed elements of a Pedersen commitment while revealing the others
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let bases = (0..5)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let scalars = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+    let commitment = G1Projective::msm_unchecked(&bases, &scalars).into_affine();
+
+    // Another commitment with one of the hidden elements
+    let bases_2 = (0..2)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let scalars_2 = vec![scalars[4], Fr::rand(&mut rng)];
+    let commitment_2 = G1Projective::msm_unchecked(&bases_2, &scalars_2).into_affine();
+
+    let revealed = [1, 3]
+        .into_iter()
+        .map(|i| (i, scalars[i]))
+        .collect::<BTreeMap<_, _>>();
+    let hidden = [0, 2, 4]
+        .into_iter()
+        .map(|i| (i, scalars[i]))
+        .collect::<BTreeMap<_, _>>();
+
+    let prove_and_verify = |prover_revealed: BTreeMap<usize, Fr>,
+                            verifier_revealed: BTreeMap<usize, Fr>,
+                            hidden: BTreeMap<usize, Fr>,
+                            rng: &mut StdRng| {
+        let create_spec = |revealed| {
+            let mut statements = Statements::new();
+            statements.add(PartialPedersenOpeningStmt::new_statement_from_params(
+                bases.clone(),
+                commitment,
+                revealed,
+            ));
+            statements.add(PedersenCommitmentStmt::new_statement_from_params(
+                bases_2.clone(),
+                commitment_2,
+            ));
+            let mut meta_statements = MetaStatements::new();
+            meta_statements.add_witness_equality(EqualWitnesses(
+                vec![(0, 4), (1, 0)]
+                    .into_iter()
+                    .collect::<BTreeSet<WitnessRef>>(),
+            ));
+            let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+            proof_spec.validate().unwrap();
+            proof_spec
+        };
+
+        let mut witnesses = Witnesses::new();
+        witnesses.add(Witness::PartialPedersenOpening(hidden));
+        witnesses.add(Witness::PedersenCommitment(scalars_2.clone()));
+
+        let proof_spec = create_spec(prover_revealed);
+        test_serialization!(ProofSpec<Bls12_381, G1Affine>, proof_spec);
+        test_serialization!(Witnesses<Bls12_381>, witnesses);
+
+        let proof = ProofG1::new::<StdRng, Blake2b512>(
+            rng,
+            proof_spec,
+            witnesses,
+            None,
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+        test_serialization!(ProofG1, proof);
+
+        proof.verify::<StdRng, Blake2b512>(
+            rng,
+            create_spec(verifier_revealed),
+            None,
+            Default::default(),
+        )
+    };
+
+    prove_and_verify(revealed.clone(), revealed.clone(), hidden.clone(), &mut rng).unwrap();
+
+    // Verifier using a different revealed value
+    let mut tampered = revealed.clone();
+    tampered.insert(1, Fr::rand(&mut rng));
+    assert!(
+        prove_and_verify(revealed.clone(), tampered.clone(), hidden.clone(), &mut rng).is_err()
+    );
+
+    // Verifier revealing an element that the prover hid
+    let mut tampered = revealed.clone();
+    tampered.insert(0, scalars[0]);
+    assert!(prove_and_verify(revealed.clone(), tampered, hidden.clone(), &mut rng).is_err());
+
+    // Prover's witness must contain exactly the hidden elements
+    let mut witnesses = Witnesses::new();
+    witnesses.add(Witness::PartialPedersenOpening(
+        [(0, scalars[0]), (2, scalars[2])].into_iter().collect(),
+    ));
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(PartialPedersenOpeningStmt::new_statement_from_params(
+        bases.clone(),
+        commitment,
+        revealed.clone(),
+    ));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    assert!(matches!(
+        ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec,
+            witnesses,
+            None,
+            Default::default()
+        ),
+        Err(ProofSystemError::IncorrectHiddenPositionsForPartialOpening(
+            0
+        ))
+    ));
+
+    // Revealed position must be in the commitment
+    let mut out_of_range = revealed.clone();
+    out_of_range.insert(5, scalars[0]);
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(PartialPedersenOpeningStmt::new_statement_from_params(
+        bases.clone(),
+        commitment,
+        out_of_range,
+    ));
+    let mut witnesses = Witnesses::new();
+    witnesses.add(Witness::PartialPedersenOpening(hidden.clone()));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    assert!(matches!(
+        ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec,
+            witnesses,
+            None,
+            Default::default()
+        ),
+        Err(ProofSystemError::RevealedPositionOutOfRange(0, 5, 5))
+    ));
+
+    // When all elements are revealed, the verifier only checks that they open the commitment
+    let all_revealed = scalars
+        .iter()
+        .cloned()
+        .enumerate()
+        .collect::<BTreeMap<_, _>>();
+    let create_spec = |revealed| {
+        let mut statements = Statements::<Bls12_381, G1Affine>::new();
+        statements.add(PartialPedersenOpeningStmt::new_statement_from_params(
+            bases.clone(),
+            commitment,
+            revealed,
+        ));
+        ProofSpec::new(statements, MetaStatements::new(), vec![], None)
+    };
+    let mut witnesses = Witnesses::new();
+    witnesses.add(Witness::PartialPedersenOpening(BTreeMap::new()));
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        create_spec(all_revealed.clone()),
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            create_spec(all_revealed.clone()),
+            None,
+            Default::default(),
+        )
+        .unwrap();
+
+    let mut tampered = all_revealed;
+    tampered.insert(2, Fr::rand(&mut rng));
+    assert!(matches!(
+        proof.verify::<StdRng, Blake2b512>(
+            &mut rng,
+            create_spec(tampered),
+            None,
+            Default::default()
+        ),
+        Err(ProofSystemError::StatementVerificationFailed { index: 0, statement_kind: "PartialPedersenOpening", source })
+            if matches!(*source, ProofSystemError::RevealedValuesDontOpenCommitment(0))
+    ));
+}
+
+#[test]
+fn pok_of_knowledge_in_pedersen_commitment_over_g2() {
+    // Prove knowledge of the opening of a Pedersen commitment in G2 and that a committed element is a message
+    // signed with BBS+
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (msgs, sig_params, keypair, sig) = bbs_plus_sig_setup(&mut rng, 5);
+
+    let bases = (0..4)
+        .map(|_| G2Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let mut scalars = (0..4).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+    scalars[2] = msgs[1];
+    let commitment = G2Projective::msm_unchecked(&bases, &scalars).into_affine();
+
+    let create_spec = |commitment: G2Affine| {
+        let mut statements = Statements::<Bls12_381, G2Affine>::new();
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        statements.add(PedersenCommitmentStmt::new_statement_from_params(
+            bases.clone(),
+            commitment,
+        ));
+        let mut meta_statements = MetaStatements::new();
+        meta_statements.add_witness_equality(EqualWitnesses(
+            vec![(0, 1), (1, 2)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        ));
+        ProofSpec::new(statements, meta_statements, vec![], None)
+    };
+
+    let proof_spec = create_spec(commitment);
+    proof_spec.validate().unwrap();
+    test_serialization!(ProofSpec<Bls12_381, G2Affine>, proof_spec);
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.into_iter().enumerate().collect(),
+    ));
+    witnesses.add(Witness::PedersenCommitment(scalars.clone()));
+
+    let proof = ProofG2::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec.clone(),
+        witnesses.clone(),
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    test_serialization!(ProofG2, proof);
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec, None, Default::default())
+        .unwrap();
+
+    // Verifying with a different commitment fails
+    let wrong_commitment = G2Projective::rand(&mut rng).into_affine();
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            create_spec(wrong_commitment),
+            None,
+            Default::default()
+        )
+        .is_err());
+
+    // Proving with a committed element different from the signed message fails
+    let mut witnesses_wrong = Witnesses::new();
+    witnesses_wrong.add(witnesses.0[0].clone());
+    scalars[2] = Fr::rand(&mut rng);
+    witnesses_wrong.add(Witness::PedersenCommitment(scalars.clone()));
+    let proof = ProofG2::new::<StdRng, Blake2b512>(
+        &mut rng,
+        create_spec(G2Projective::msm_unchecked(&bases, &scalars).into_affine()),
+        witnesses_wrong,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            create_spec(G2Projective::msm_unchecked(&bases, &scalars).into_affine()),
+            None,
+            Default::default()
+        )
+        .is_err());
+}
+
+#[test]
+fn pok_of_knowledge_in_pedersen_commitment_with_hidden_bases() {
+    // Prove knowledge of committed elements in a Pedersen commitment whose bases are revealed to the verifier
+    // only after the proof is received
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let count = 5;
+    let bases = (0..count)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let scalars = (0..count).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+    let commitment = G1Projective::msm_bigint(
+        &bases,
+        &scalars.iter().map(|s| s.into_bigint()).collect::<Vec<_>>(),
+    )
+    .into_affine();
+
+    // Prover commits to the bases and gives the verifier only the commitment to them
+    let (hidden, opening) =
+        PedersenCommitmentWithHiddenBases::new::<_, Blake2b512>(&mut rng, &bases, commitment);
+    assert_eq!(hidden.num_bases, count);
+
+    test_serialization!(PedersenCommitmentWithHiddenBases<G1Affine>, hidden);
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        bases.clone(),
+        commitment,
+    ));
+    let prover_proof_spec = ProofSpec::new(prover_statements, MetaStatements::new(), vec![], None);
+    prover_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(Witness::PedersenCommitment(scalars));
+
+    let nonce = Some(b"test nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    // Before the bases are revealed, the verifier can check that the proof is for as many scalars as bases
+    match &proof.statement_proofs[0] {
+        StatementProof::PedersenCommitment(p) => hidden.check_scalar_count(p).unwrap(),
+        _ => panic!("expected a Pedersen commitment proof"),
+    }
+    let hidden_with_wrong_count = PedersenCommitmentWithHiddenBases::<G1Affine> {
+        num_bases: count + 1,
+        ..hidden.clone()
+    };
+    match &proof.statement_proofs[0] {
+        StatementProof::PedersenCommitment(p) => assert!(matches!(
+            hidden_with_wrong_count.check_scalar_count(p),
+            Err(ProofSystemError::UnequalHiddenBasesAndScalarCount(6, 5))
+        )),
+        _ => panic!("expected a Pedersen commitment proof"),
+    }
+
+    // Bases are revealed later and checked against the commitment to them
+    let mut statements = Statements::new();
+    statements.add(
+        hidden
+            .reveal_bases::<Bls12_381, Blake2b512>(bases.clone(), &opening)
+            .unwrap(),
+    );
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    proof_spec.validate().unwrap();
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec, nonce.clone(), Default::default())
+        .unwrap();
+
+    // Revealing a different number of bases fails
+    assert!(matches!(
+        hidden.reveal_bases::<Bls12_381, Blake2b512>(bases[1..].to_vec(), &opening),
+        Err(ProofSystemError::UnequalHiddenBasesAndScalarCount(5, 4))
+    ));
+
+    // Revealing different bases or with a different opening fails
+    let mut wrong_bases = bases.clone();
+    wrong_bases.swap(0, 1);
+    assert!(matches!(
+        hidden.reveal_bases::<Bls12_381, Blake2b512>(wrong_bases, &opening),
+        Err(ProofSystemError::RevealedBasesDontMatchCommitment)
+    ));
+    let mut wrong_opening = opening;
+    wrong_opening[0] ^= 1;
+    assert!(matches!(
+        hidden.reveal_bases::<Bls12_381, Blake2b512>(bases, &wrong_opening),
+        Err(ProofSystemError::RevealedBasesDontMatchCommitment)
+    ));
+}
+
+#[test]
+fn pok_of_knowledge_in_pedersen_commitment_with_bases_from_label() {
+    // Bases derived from the same label are the same for everyone so a commitment created by one party can be
+    // verified by another deriving the bases itself
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let count = 5;
+    let label = b"test-label";
+    let bases = PedersenCommitmentStmt::<G1Affine>::bases_from_label::<Blake2b512>(label, count);
+    assert_eq!(bases.len(), count as usize);
+    assert_eq!(
+        bases,
+        PedersenCommitmentStmt::<G1Affine>::bases_from_label::<Blake2b512>(label, count)
+    );
+    // Fewer bases from the same label are a prefix of more bases
+    assert_eq!(
+        bases[..3],
+        PedersenCommitmentStmt::<G1Affine>::bases_from_label::<Blake2b512>(label, 3)
+    );
+    // A different label gives different bases
+    let other_bases =
+        PedersenCommitmentStmt::<G1Affine>::bases_from_label::<Blake2b512>(b"other-label", count);
+    assert!(bases.iter().zip(other_bases.iter()).all(|(a, b)| a != b));
+
+    let scalars = (0..count).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+    let commitment = G1Projective::msm_bigint(
+        &bases,
+        &scalars.iter().map(|s| s.into_bigint()).collect::<Vec<_>>(),
+    )
+    .into_affine();
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        bases, commitment,
+    ));
+    let prover_proof_spec = ProofSpec::new(prover_statements, MetaStatements::new(), vec![], None);
+    prover_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(Witness::PedersenCommitment(scalars));
+
+    let nonce = Some(b"test nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    // Verifier derives the bases from the label
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        PedersenCommitmentStmt::<G1Affine>::bases_from_label::<Blake2b512>(label, count),
+        commitment,
+    ));
+    let verifier_proof_spec =
+        ProofSpec::new(verifier_statements, MetaStatements::new(), vec![], None);
+    verifier_proof_spec.validate().unwrap();
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec, nonce, Default::default())
+        .unwrap();
+}
@@ -0,0 +1,163 @@
+use ark_bls12_381::{Bls12_381, G1Affine, G1Projective};
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_serialize::CanonicalSerialize;
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    rand::{prelude::StdRng, SeedableRng},
+    UniformRand,
+};
+use blake2::Blake2b512;
+use bulletproofs_plus_plus::prelude::SetupParams as BppSetupParams;
+use dock_crypto_utils::commitment::PedersenCommitmentKey;
+use vb_accumulator::prelude::{Accumulator, MembershipProvingKey};
+
+use proof_system::{
+    prelude::{
+        bound_check_smc::SmcParamsAndCommitmentKey, EqualWitnesses, MetaStatements, Witness,
+        WitnessRef, Witnesses,
+    },
+    proof_spec::ProofSpec,
+    setup_params::SetupParams,
+    statement::{
+        accumulator::VBAccumulatorMembership as AccumulatorMembershipStmt,
+        bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
+        bound_check_bpp::BoundCheckBpp as BoundCheckBppStmt,
+        bound_check_smc::BoundCheckSmc as BoundCheckSmcStmt,
+        inequality::PublicInequality as InequalityStmt,
+        ped_comm::PedersenCommitment as PedersenCommitmentStmt, Statements,
+    },
+    witness::{Membership as MembershipWit, PoKBBSSignatureG1 as PoKSignatureBBSG1Wit},
+};
+use test_utils::{
+    accumulators::setup_positive_accum, bbs::bbs_plus_sig_setup_given_messages, Fr, ProofG1,
+};
+
+#[test]
+fn estimated_proof_size_for_mixed_statements() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let min = 100;
+    let max = 200;
+    let msgs = (0..6)
+        .map(|i| Fr::from(min + 1 + i as u64))
+        .collect::<Vec<_>>();
+    let (sig_params, sig_keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+
+    let (accum_params, accum_keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+    let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+    accumulator = accumulator
+        .add(msgs[1], &accum_keypair.secret_key, &mut state)
+        .unwrap();
+    let mem_wit = accumulator
+        .get_membership_witness(&msgs[1], &accum_keypair.secret_key, &state)
+        .unwrap();
+
+    let bpp_params =
+        BppSetupParams::<G1Affine>::new_for_arbitrary_range_proof::<Blake2b512>(b"test", 2, 64, 1);
+    let (smc_params, _) = SmcParamsAndCommitmentKey::new::<_, Blake2b512>(&mut rng, b"test", 2);
+    let ineq_comm_key = PedersenCommitmentKey::<G1Affine>::new::<Blake2b512>(b"test");
+
+    // Pedersen commitment whose key is given in the setup params
+    let ped_comm_key = (0..3)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let blinding = Fr::rand(&mut rng);
+    let ped_comm_wit = vec![msgs[2], msgs[3], blinding];
+    let ped_comm = G1Projective::msm_unchecked(&ped_comm_key, &ped_comm_wit).into_affine();
+    let setup_params = vec![SetupParams::<Bls12_381, G1Affine>::PedersenCommitmentKey(
+        ped_comm_key,
+    )];
+
+    let mut revealed = BTreeMap::new();
+    revealed.insert(0, msgs[0]);
+    revealed.insert(5, msgs[5]);
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        revealed.clone(),
+    ));
+    statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        accum_params,
+        accum_keypair.public_key.clone(),
+        mem_prk,
+        *accumulator.value(),
+    ));
+    statements.add(BoundCheckBppStmt::new_statement_from_params(min, max, bpp_params).unwrap());
+    statements.add(BoundCheckSmcStmt::new_statement_from_params(min, max, smc_params).unwrap());
+    statements.add(InequalityStmt::new_statement_from_params(
+        Fr::from(5u64),
+        ineq_comm_key,
+    ));
+    statements.add(PedersenCommitmentStmt::new_statement_from_params_refs(
+        0, ped_comm,
+    ));
+
+    let mut meta_statements = MetaStatements::new();
+    for (s_idx, m_idx) in [(1, 1), (2, 2), (3, 3), (4, 4), (5, 2)] {
+        meta_statements.add_witness_equality(EqualWitnesses(
+            vec![(0, m_idx), (s_idx, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        ));
+    }
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 3), (5, 1)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let proof_spec = ProofSpec::new(statements.clone(), meta_statements, setup_params, None);
+    proof_spec.validate().unwrap();
+
+    let mut unrevealed = msgs
+        .clone()
+        .into_iter()
+        .enumerate()
+        .collect::<BTreeMap<_, _>>();
+    unrevealed.remove(&0);
+    unrevealed.remove(&5);
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(sig, unrevealed));
+    witnesses.add(MembershipWit::new_as_witness(msgs[1], mem_wit));
+    witnesses.add(Witness::BoundCheckBpp(msgs[2]));
+    witnesses.add(Witness::BoundCheckSmc(msgs[3]));
+    witnesses.add(Witness::PublicInequality(msgs[4]));
+    witnesses.add(Witness::PedersenCommitment(ped_comm_wit));
+
+    let estimate = proof_spec.estimated_proof_size();
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec.clone(),
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec.clone(), None, Default::default())
+        .unwrap();
+
+    // Each statement's estimate is an upper bound on its proof's size and the total is not much larger
+    // than the actual size
+    for (i, (s, p)) in statements
+        .iter()
+        .zip(proof.statement_proofs.iter())
+        .enumerate()
+    {
+        assert!(s.estimated_proof_size(&proof_spec.setup_params, i) >= p.compressed_size());
+    }
+    let actual = proof.compressed_size();
+    println!(
+        "Estimated proof size {} bytes, actual {} bytes",
+        estimate, actual
+    );
+    assert!(estimate >= actual);
+    assert!(estimate <= actual * 3 / 2);
+
+    // Without the setup params, the Pedersen commitment's responses aren't counted
+    assert!(statements.estimated_proof_size() < estimate);
+}
@@ -14,7 +14,8 @@ use coconut_crypto::setup::*;
 use rayon::prelude::*;
 
 use coconut_crypto::{
-    BlindSignature, CommitmentOrMessage, MessageCommitment, MultiMessageCommitment, Signature,
+    AggregatedSignature, BlindSignature, CommitmentOrMessage, MessageCommitment,
+    MultiMessageCommitment, Signature,
 };
 use dock_crypto_utils::{
     hashing_utils::affine_group_elem_from_try_and_incr, misc::*, owned_pairs::*, pairs::*,
@@ -24,8 +25,8 @@ use vb_accumulator::prelude::{Accumulator, MembershipProvingKey, NonMembershipPr
 
 use proof_system::{
     prelude::{
-        EqualWitnesses, MetaStatement, MetaStatements, VerifierConfig, Witness, WitnessRef,
-        Witnesses,
+        EqualWitnesses, MetaStatement, MetaStatements, ProofSystemError, VerifierConfig, Witness,
+        WitnessRef, Witnesses,
     },
     proof_spec::ProofSpec,
     setup_params::SetupParams,
@@ -35,9 +36,10 @@ use proof_system::{
             VBAccumulatorNonMembership as AccumulatorNonMembershipStmt,
         },
         ped_comm::PedersenCommitment as PedersenCommitmentStmt,
-        ps_signature::PoKPSSignatureStatement,
+        ps_signature::{PoKPSAggregateSignatureStatement, PoKPSSignatureStatement},
         Statements,
     },
+    statement_proof::StatementProof,
     witness::{Membership as MembershipWit, NonMembership as NonMembershipWit, PoKPSSignature},
 };
 use test_utils::{accumulators::*, test_serialization, Fr, ProofG1};
@@ -1681,3 +1683,167 @@ fn proof_spec_validation() {
     let ps_3 = ProofSpec::new(statements_3, meta_statements_3, vec![], None);
     assert!(ps_3.validate().is_err());
 }
+
+#[test]
+fn pok_of_ps_multi_signature() {
+    // Prove knowledge of a PS signature over the same messages by 2 signers, aggregated into 1 signature
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 6;
+    let (secret_key_1, public_key_1, sig_params, msgs) =
+        test_setup::<Bls12_381, Blake2b512, _>(&mut rng, msg_count);
+    let secret_key_2 = SecretKey::rand(&mut rng, msg_count as u32);
+    let public_key_2 = PublicKey::new(&secret_key_2, &sig_params);
+
+    // Both signers sign deterministically so that the signatures can be aggregated
+    let sig_1 =
+        Signature::<Bls12_381>::new_deterministic::<Blake2b512>(&msgs, &secret_key_1).unwrap();
+    let sig_2 =
+        Signature::<Bls12_381>::new_deterministic::<Blake2b512>(&msgs, &secret_key_2).unwrap();
+    let aggregated_sig = AggregatedSignature::new_multi_signature([&sig_1, &sig_2]).unwrap();
+    let public_keys = vec![public_key_1.clone(), public_key_2];
+
+    let mut revealed_msgs = BTreeMap::new();
+    let mut unrevealed_msgs = BTreeMap::new();
+    for (i, m) in msgs.iter().enumerate() {
+        if i == 1 || i == 4 {
+            revealed_msgs.insert(i, *m);
+        } else {
+            unrevealed_msgs.insert(i, *m);
+        }
+    }
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(
+        PoKPSAggregateSignatureStatement::new_statement_from_params(
+            sig_params.clone(),
+            public_keys.clone(),
+            revealed_msgs.clone(),
+        )
+        .unwrap(),
+    );
+
+    test_serialization!(Statements<Bls12_381, G1Affine>, statements);
+
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKPSSignature::new_as_witness(
+        (*aggregated_sig).clone(),
+        unrevealed_msgs,
+    ));
+
+    let nonce = Some(b"test nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec.clone(),
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    test_serialization!(ProofG1, proof);
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec, nonce.clone(), Default::default())
+        .unwrap();
+
+    // Doesn't verify with only one of the signers' public keys
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(
+        PoKPSAggregateSignatureStatement::new_statement_from_params(
+            sig_params.clone(),
+            vec![public_key_1.clone()],
+            revealed_msgs.clone(),
+        )
+        .unwrap(),
+    );
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec, nonce, Default::default())
+        .is_err());
+
+    // Statement without any public keys, like when deserialized, is rejected
+    let statement = PoKPSAggregateSignatureStatement::<Bls12_381> {
+        revealed_messages: revealed_msgs,
+        signature_params: Some(sig_params),
+        signature_params_ref: None,
+        public_keys: vec![],
+    };
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(proof_system::statement::Statement::PoKPSAggregateSignature(
+        statement,
+    ));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    assert!(matches!(
+        proof_spec.validate(),
+        Err(ProofSystemError::CannotAggregatePSPublicKeys)
+    ));
+}
+
+#[test]
+fn ps_sig_proofs_are_unlinkable() {
+    // Proofs of knowledge of the same PS signature, or of its rerandomizations, use fresh randomness so
+    // they can't be linked by the randomized signature in them
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (secret_key, public_key, sig_params, msgs) =
+        test_setup::<Bls12_381, Blake2b512, _>(&mut rng, msg_count);
+    let sig = Signature::<Bls12_381>::new(&mut rng, &msgs, &secret_key, &sig_params).unwrap();
+
+    let sig_1 = sig.rerandomize(&mut rng);
+    let sig_2 = sig.rerandomize(&mut rng);
+    assert_ne!(sig_1, sig_2);
+    sig_1.verify(&msgs, &public_key, &sig_params).unwrap();
+    sig_2.verify(&msgs, &public_key, &sig_params).unwrap();
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(PoKPSSignatureStatement::new_statement_from_params(
+        sig_params.clone(),
+        public_key.clone(),
+        BTreeMap::new(),
+    ));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    proof_spec.validate().unwrap();
+
+    let mut randomized_sigs = vec![];
+    for s in [&sig, &sig, &sig_1, &sig_2] {
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKPSSignature::new_as_witness(
+            s.clone(),
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        let proof = ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec.clone(),
+            witnesses,
+            None,
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+        proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(&mut rng, proof_spec.clone(), None, Default::default())
+            .unwrap();
+        match &proof.statement_proofs[0] {
+            StatementProof::PoKPSSignature(p) => {
+                randomized_sigs.push((**p.randomized_sig()).clone())
+            }
+            _ => panic!("expected a PS signature proof"),
+        }
+    }
+
+    // The randomized signatures differ from each other and from the signatures they were created from
+    for (i, r) in randomized_sigs.iter().enumerate() {
+        assert!(![&sig, &sig_1, &sig_2].contains(&r));
+        for other in &randomized_sigs[i + 1..] {
+            assert_ne!(r, other);
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    rand::{prelude::StdRng, SeedableRng},
+    UniformRand,
+};
+use blake2::Blake2b512;
+use dock_crypto_utils::commitment::PedersenCommitmentKey;
+use std::time::Instant;
+
+use proof_system::{
+    prelude::{
+        EqualWitnesses, MetaStatements, ProofSpec, ProofSystemError, StatementProof, Witness,
+        WitnessRef, Witnesses,
+    },
+    statement::{
+        bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
+        public_set_membership::PublicSetMembership as PublicSetMembershipStmt, Statements,
+    },
+    witness::PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
+};
+
+use test_utils::{bbs::*, test_serialization, Fr, ProofG1};
+
+#[test]
+fn pok_of_bbs_plus_sig_and_public_set_membership() {
+    // Prove knowledge of BBS+ signature and that a specific message is one of the values of a public set
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+    let comm_key = PedersenCommitmentKey::<G1Affine>::new::<Blake2b512>(b"test");
+
+    let msg_idx = 1;
+    let msg = msgs[msg_idx];
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, msg_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let proof_spec = |set: Vec<Fr>| {
+        let mut statements = Statements::<Bls12_381, G1Affine>::new();
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        statements.add(
+            PublicSetMembershipStmt::new_statement_from_params(set, comm_key.clone()).unwrap(),
+        );
+        ProofSpec::new(statements, meta_statements.clone(), vec![], None)
+    };
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig.clone(),
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(Witness::PublicSetMembership(msg));
+    test_serialization!(Witnesses<Bls12_381>, witnesses);
+
+    let mut proof_sizes = vec![];
+    for set_size in [1, 3, 50] {
+        // Set containing the message at a random position
+        let mut set = (0..set_size - 1)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        set.insert(set_size / 2, msg);
+
+        let spec = proof_spec(set.clone());
+        spec.validate().unwrap();
+        test_serialization!(ProofSpec<Bls12_381, G1Affine>, spec);
+
+        let start = Instant::now();
+        let proof = ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            spec.clone(),
+            witnesses.clone(),
+            None,
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+        println!(
+            "Time taken to create proof of membership in a public set of size {} {:?}",
+            set_size,
+            start.elapsed()
+        );
+        test_serialization!(ProofG1, proof);
+
+        let start = Instant::now();
+        proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(&mut rng, spec.clone(), None, Default::default())
+            .unwrap();
+        println!(
+            "Time taken to verify proof of membership in a public set of size {} {:?}",
+            set_size,
+            start.elapsed()
+        );
+
+        match &proof.statement_proofs[1] {
+            StatementProof::PublicSetMembership(p) => {
+                assert_eq!(p.challenges.len(), set_size);
+                assert_eq!(p.responses.len(), set_size);
+                proof_sizes.push(p.compressed_size());
+            }
+            _ => panic!("Expected proof of public set membership"),
+        }
+
+        // Verification fails against a set not containing the message
+        let mut other_set = set.clone();
+        other_set[set_size / 2] = Fr::rand(&mut rng);
+        assert!(proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                proof_spec(other_set.clone()),
+                None,
+                Default::default()
+            )
+            .is_err());
+
+        // Verification fails when the proof is modified to have a different challenge split
+        let mut tampered = proof.clone();
+        if let StatementProof::PublicSetMembership(p) = &mut tampered.statement_proofs[1] {
+            p.challenges[0] += Fr::from(1u64);
+        }
+        assert!(tampered
+            .verify::<StdRng, Blake2b512>(&mut rng, spec, None, Default::default())
+            .is_err());
+
+        // Prover can't create a proof for a set not containing the message
+        assert!(matches!(
+            ProofG1::new::<StdRng, Blake2b512>(
+                &mut rng,
+                proof_spec(other_set),
+                witnesses.clone(),
+                None,
+                Default::default(),
+            ),
+            Err(ProofSystemError::MessageNotInPublicSet(1))
+        ));
+    }
+
+    // Proof size grows linearly with the size of the set, by a challenge and a response per value
+    let per_value = 2 * Fr::from(0u64).compressed_size();
+    assert_eq!(proof_sizes[1] - proof_sizes[0], 2 * per_value);
+    assert_eq!(proof_sizes[2] - proof_sizes[1], 47 * per_value);
+
+    assert!(matches!(
+        PublicSetMembershipStmt::new_statement_from_params::<Bls12_381>(vec![], comm_key),
+        Err(ProofSystemError::EmptyPublicSet)
+    ));
+}
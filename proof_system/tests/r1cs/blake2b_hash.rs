@@ -0,0 +1,188 @@
+use ark_bls12_381::Bls12_381;
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalSerialize;
+use ark_std::{
+    rand::{rngs::StdRng, RngCore, SeedableRng},
+    UniformRand,
+};
+use blake2::{digest::consts::U32, Blake2b, Blake2b512, Digest};
+use legogroth16::{circom::R1CS, ProvingKey};
+use proof_system::{
+    prelude::{
+        EqualWitnesses, MetaStatements, ProofSpec, R1CSCircomWitness, Statements, Witness,
+        WitnessRef, Witnesses,
+    },
+    statement::{
+        bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
+        r1cs_legogroth16::{
+            R1CSCircomProver as R1CSProverStmt, R1CSCircomVerifier as R1CSVerifierStmt,
+        },
+    },
+    witness::PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::r1cs::get_r1cs_and_wasm_bytes;
+use test_utils::{bbs::*, Fr, ProofG1};
+
+/// Load the circuit computing the Blake2b hash with 32 byte digest of a scalar's canonical serialization. Circom code
+/// in tests/r1cs/circom/circuits/blake2b_scalar_hash.circom, compiled with `--prime bls12381`. The only private input
+/// of the circuit is the hashed scalar so the circuit commits to 1 witness.
+pub fn blake2b_scalar_hash_circuit<R: RngCore>(
+    rng: &mut R,
+) -> (ProvingKey<Bls12_381>, R1CS<Bls12_381>, Vec<u8>) {
+    get_r1cs_and_wasm_bytes(
+        "tests/r1cs/circom/bls12-381/blake2b_scalar_hash.r1cs",
+        "tests/r1cs/circom/bls12-381/blake2b_scalar_hash.wasm",
+        1,
+        rng,
+    )
+}
+
+/// The public inputs of the circuit for a 32 byte digest, i.e. the 2 halves of the digest as little-endian integers
+pub fn blake2b_digest_as_public_inputs(digest: &[u8; 32]) -> Vec<Fr> {
+    vec![
+        Fr::from_le_bytes_mod_order(&digest[..16]),
+        Fr::from_le_bytes_mod_order(&digest[16..]),
+    ]
+}
+
+fn blake2b_digest_of_message(msg: &Fr) -> [u8; 32] {
+    let mut bytes = vec![];
+    msg.serialize_compressed(&mut bytes).unwrap();
+    Blake2b::<U32>::digest(&bytes).into()
+}
+
+#[test]
+fn blake2b_digest_public_inputs() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let digest = blake2b_digest_of_message(&Fr::rand(&mut rng));
+    let inputs = blake2b_digest_as_public_inputs(&digest);
+    // Each half of the digest is 128 bits so fits in a field element without reduction
+    let mut bytes = vec![];
+    for i in inputs {
+        let mut b = vec![];
+        i.serialize_compressed(&mut b).unwrap();
+        assert!(b[16..].iter().all(|b| *b == 0));
+        bytes.extend_from_slice(&b[..16]);
+    }
+    assert_eq!(bytes, digest);
+}
+
+#[test]
+#[ignore = "needs tests/r1cs/circom/bls12-381/blake2b_scalar_hash.{r1cs,wasm} compiled with `circom --r1cs --wasm --prime bls12381`"]
+fn pok_of_bbs_plus_sig_and_blake2b_hash_of_message() {
+    // Prove knowledge of a signature and that a specific signed message's Blake2b hash equals a published digest
+    // without revealing the message
+
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let msg_count = 5;
+    let msgs: Vec<Fr> = (0..msg_count).map(|_| Fr::rand(&mut rng)).collect();
+
+    // Message index that will be hashed, like an email
+    let msg_idx_to_hash = 2;
+
+    let (sig_params, sig_keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+
+    let (snark_pk, r1cs, wasm_bytes) = blake2b_scalar_hash_circuit(&mut rng);
+
+    // The digest the verifier knows
+    let digest = blake2b_digest_of_message(&msgs[msg_idx_to_hash]);
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(
+        R1CSProverStmt::new_statement_from_params(r1cs, wasm_bytes, snark_pk.clone()).unwrap(),
+    );
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, msg_idx_to_hash), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let proof_spec_prover = ProofSpec::new(
+        prover_statements.clone(),
+        meta_statements.clone(),
+        vec![],
+        None,
+    );
+    proof_spec_prover.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    let mut r1cs_wit = R1CSCircomWitness::<Bls12_381>::new();
+    r1cs_wit.set_private("in".to_string(), vec![msgs[msg_idx_to_hash]]);
+    witnesses.add(Witness::R1CSLegoGroth16(r1cs_wit));
+
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec_prover,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let verifier_proof_spec = |digest: &[u8; 32]| {
+        let mut verifier_statements = Statements::new();
+        verifier_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        verifier_statements.add(
+            R1CSVerifierStmt::new_statement_from_params(
+                blake2b_digest_as_public_inputs(digest),
+                snark_pk.vk.clone(),
+            )
+            .unwrap(),
+        );
+        let spec = ProofSpec::new(verifier_statements, meta_statements.clone(), vec![], None);
+        spec.validate().unwrap();
+        spec
+    };
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(&digest),
+            None,
+            Default::default(),
+        )
+        .unwrap();
+
+    // Proof doesn't verify with a digest differing in a single bit
+    let mut wrong_digest = digest;
+    wrong_digest[31] ^= 1;
+    assert!(proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(&wrong_digest),
+            None,
+            Default::default()
+        )
+        .is_err());
+
+    // Proof doesn't verify with digest of another message
+    let other_digest = blake2b_digest_of_message(&msgs[msg_idx_to_hash + 1]);
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(&other_digest),
+            None,
+            Default::default()
+        )
+        .is_err());
+}
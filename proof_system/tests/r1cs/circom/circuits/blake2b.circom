@@ -0,0 +1,244 @@
+pragma circom 2.0.0;
+
+include "comparators.circom";
+
+// 64-bit words are represented as arrays of 64 bits with LSB in the beginning and MSB in end.
+
+function blake2b_iv(i) {
+    var iv[8] = [
+        0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+        0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179
+    ];
+    return iv[i];
+}
+
+function blake2b_sigma(r, i) {
+    var sigma[10][16] = [
+        [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+        [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+        [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+        [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+        [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+        [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+        [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+        [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+        [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+        [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0]
+    ];
+    return sigma[r % 10][i];
+}
+
+// `out` is `a ^ b`
+template Xor64() {
+    signal input a[64];
+    signal input b[64];
+    signal output out[64];
+
+    for (var i = 0; i < 64; i++) {
+        out[i] <== a[i] + b[i] - 2*a[i]*b[i];
+    }
+}
+
+// `out` is the sum of the `n` inputs modulo 2^64. `n` is expected to be at most 4.
+template Add64(n) {
+    signal input in[n][64];
+    signal output out[64];
+
+    var sum = 0;
+    for (var j = 0; j < n; j++) {
+        var e2 = 1;
+        for (var i = 0; i < 64; i++) {
+            sum += in[j][i] * e2;
+            e2 = e2 + e2;
+        }
+    }
+
+    component n2b = Num2Bits(66);
+    n2b.in <== sum;
+    for (var i = 0; i < 64; i++) {
+        out[i] <== n2b.out[i];
+    }
+}
+
+// The mixing function G of Blake2b. A rotation right by `r` bits means that bit `i` of the output is bit `(i + r) % 64`
+// of the input so rotations don't need any constraints.
+template Blake2bG() {
+    signal input a[64];
+    signal input b[64];
+    signal input c[64];
+    signal input d[64];
+    signal input x[64];
+    signal input y[64];
+    signal output outA[64];
+    signal output outB[64];
+    signal output outC[64];
+    signal output outD[64];
+
+    component add1 = Add64(3);
+    component xor1 = Xor64();
+    component add2 = Add64(2);
+    component xor2 = Xor64();
+    component add3 = Add64(3);
+    component xor3 = Xor64();
+    component add4 = Add64(2);
+    component xor4 = Xor64();
+
+    // a = a + b + x, d = (d ^ a) >>> 32
+    for (var i = 0; i < 64; i++) {
+        add1.in[0][i] <== a[i];
+        add1.in[1][i] <== b[i];
+        add1.in[2][i] <== x[i];
+        xor1.a[i] <== d[i];
+        xor1.b[i] <== add1.out[i];
+    }
+    // c = c + d, b = (b ^ c) >>> 24
+    for (var i = 0; i < 64; i++) {
+        add2.in[0][i] <== c[i];
+        add2.in[1][i] <== xor1.out[(i + 32) % 64];
+        xor2.a[i] <== b[i];
+        xor2.b[i] <== add2.out[i];
+    }
+    // a = a + b + y, d = (d ^ a) >>> 16
+    for (var i = 0; i < 64; i++) {
+        add3.in[0][i] <== add1.out[i];
+        add3.in[1][i] <== xor2.out[(i + 24) % 64];
+        add3.in[2][i] <== y[i];
+        xor3.a[i] <== xor1.out[(i + 32) % 64];
+        xor3.b[i] <== add3.out[i];
+    }
+    // c = c + d, b = (b ^ c) >>> 63
+    for (var i = 0; i < 64; i++) {
+        add4.in[0][i] <== add2.out[i];
+        add4.in[1][i] <== xor3.out[(i + 16) % 64];
+        xor4.a[i] <== xor2.out[(i + 24) % 64];
+        xor4.b[i] <== add4.out[i];
+    }
+
+    for (var i = 0; i < 64; i++) {
+        outA[i] <== add3.out[i];
+        outB[i] <== xor4.out[(i + 63) % 64];
+        outC[i] <== add4.out[i];
+        outD[i] <== xor3.out[(i + 16) % 64];
+    }
+}
+
+// Blake2b with a 32 byte digest and no key of the 32 byte little-endian representation of a BLS12-381 scalar, i.e.
+// the scalar's canonical serialization. The digest is output as 2 field elements, each being 16 bytes of the digest
+// read as a little-endian integer.
+template Blake2bScalarHash() {
+    signal input in;
+    signal output out[2];
+
+    // Bits of the input which must be its canonical representation, i.e. the integer formed by them must be less than
+    // the field modulus p. Checked by splitting the bits and p into the lower 128 bits and the upper bits.
+    component inBits = Num2Bits(255);
+    inBits.in <== in;
+    var pLo = 0x53bda402fffe5bfeffffffff00000001;
+    var pHi = 0x73eda753299d7d483339d80809a1d805;
+    var lo = 0;
+    var hi = 0;
+    var e2 = 1;
+    for (var i = 0; i < 128; i++) {
+        lo += inBits.out[i] * e2;
+        if (i < 127) {
+            hi += inBits.out[i + 128] * e2;
+        }
+        e2 = e2 + e2;
+    }
+    component ltHi = LessThan(127);
+    ltHi.a <== hi;
+    ltHi.b <== pHi;
+    component eqHi = IsEqual();
+    eqHi.a <== hi;
+    eqHi.b <== pHi;
+    component ltLo = LessThan(128);
+    ltLo.a <== lo;
+    ltLo.b <== pLo;
+    ltHi.out + eqHi.out * ltLo.out === 1;
+
+    // The message block is the 32 bytes of the input followed by zeroes
+    signal m[16][64];
+    for (var w = 0; w < 16; w++) {
+        for (var i = 0; i < 64; i++) {
+            if (64*w + i < 255) {
+                m[w][i] <== inBits.out[64*w + i];
+            } else {
+                m[w][i] <== 0;
+            }
+        }
+    }
+
+    // Initial state. The parameter block sets digest length to 32 and key length to 0, the message is 32 bytes and
+    // it's the final block.
+    var h[8];
+    for (var j = 0; j < 8; j++) {
+        h[j] = blake2b_iv(j);
+    }
+    h[0] = h[0] ^ 0x01010020;
+    var init[16];
+    for (var j = 0; j < 8; j++) {
+        init[j] = h[j];
+        init[j + 8] = blake2b_iv(j);
+    }
+    init[12] = init[12] ^ 32;
+    init[14] = init[14] ^ 0xffffffffffffffff;
+
+    // State after each half of each of the 12 rounds, first half mixes the columns, the second half mixes the diagonals
+    signal v[25][16][64];
+    for (var j = 0; j < 16; j++) {
+        for (var i = 0; i < 64; i++) {
+            v[0][j][i] <== (init[j] >> i) & 1;
+        }
+    }
+
+    component g[12][8];
+    for (var r = 0; r < 12; r++) {
+        for (var half = 0; half < 2; half++) {
+            for (var q = 0; q < 4; q++) {
+                var k = half*4 + q;
+                var ia = q;
+                var ib = 4 + (q + half) % 4;
+                var ic = 8 + (q + 2*half) % 4;
+                var id = 12 + (q + 3*half) % 4;
+                var s = 2*r + half;
+                g[r][k] = Blake2bG();
+                for (var i = 0; i < 64; i++) {
+                    g[r][k].a[i] <== v[s][ia][i];
+                    g[r][k].b[i] <== v[s][ib][i];
+                    g[r][k].c[i] <== v[s][ic][i];
+                    g[r][k].d[i] <== v[s][id][i];
+                    g[r][k].x[i] <== m[blake2b_sigma(r, 2*k)][i];
+                    g[r][k].y[i] <== m[blake2b_sigma(r, 2*k + 1)][i];
+                }
+                for (var i = 0; i < 64; i++) {
+                    v[s + 1][ia][i] <== g[r][k].outA[i];
+                    v[s + 1][ib][i] <== g[r][k].outB[i];
+                    v[s + 1][ic][i] <== g[r][k].outC[i];
+                    v[s + 1][id][i] <== g[r][k].outD[i];
+                }
+            }
+        }
+    }
+
+    // First 4 words of the output are h[j] ^ v[j] ^ v[j + 8]. XOR with the constant h[j] flips the bit where h[j] has a 1.
+    component fin[4];
+    var lc[2] = [0, 0];
+    for (var j = 0; j < 4; j++) {
+        fin[j] = Xor64();
+        for (var i = 0; i < 64; i++) {
+            fin[j].a[i] <== v[24][j][i];
+            fin[j].b[i] <== v[24][j + 8][i];
+        }
+        var e = 1 << (64 * (j % 2));
+        for (var i = 0; i < 64; i++) {
+            if (((h[j] >> i) & 1) == 1) {
+                lc[j \ 2] += (1 - fin[j].out[i]) * e;
+            } else {
+                lc[j \ 2] += fin[j].out[i] * e;
+            }
+            e = e + e;
+        }
+    }
+    out[0] <== lc[0];
+    out[1] <== lc[1];
+}
@@ -0,0 +1,7 @@
+pragma circom 2.0.0;
+
+include "blake2b.circom";
+
+/*This circuit template outputs the Blake2b hash with 32 byte digest of the private input, a BLS12-381 scalar */
+
+component main = Blake2bScalarHash();
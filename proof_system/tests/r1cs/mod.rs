@@ -6,6 +6,7 @@ use legogroth16::{
 };
 use std::path::PathBuf;
 
+pub mod blake2b_hash;
 pub mod bounded_sum;
 pub mod mimc_hash;
 pub mod multiple_circuits_in_single_proof;
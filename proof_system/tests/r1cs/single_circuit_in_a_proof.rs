@@ -13,8 +13,8 @@ use blake2::Blake2b512;
 use legogroth16::{circom::R1CS, ProvingKey};
 use proof_system::{
     prelude::{
-        EqualWitnesses, MetaStatements, ProofSpec, R1CSCircomWitness, SetupParams, Statements,
-        Witness, WitnessRef, Witnesses,
+        EqualWitnesses, MetaStatements, ProofSpec, ProofSystemError, R1CSCircomWitness,
+        SetupParams, Statements, Witness, WitnessRef, Witnesses,
     },
     statement::{
         bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
@@ -969,3 +969,219 @@ fn pok_of_bbs_plus_sig_and_multiplication_check() {
         .verify::<StdRng, Blake2b512>(&mut rng, proof_spec_verifier_2, None, Default::default())
         .is_err());
 }
+
+#[test]
+fn r1cs_statements_from_bytes() {
+    // Create the prover's and verifier's statements from bytes of the circuit and keys like when they can't be read from disk
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let r1cs_bytes = include_bytes!("circom/bls12-381/multiply2.r1cs");
+    let wasm_bytes = include_bytes!("circom/bls12-381/multiply2.wasm");
+
+    let (snark_pk, r1cs, _) = get_r1cs_and_wasm_bytes(
+        "tests/r1cs/circom/bls12-381/multiply2.r1cs",
+        "tests/r1cs/circom/bls12-381/multiply2.wasm",
+        2,
+        &mut rng,
+    );
+    let mut pk_bytes = vec![];
+    snark_pk.serialize_compressed(&mut pk_bytes).unwrap();
+    let mut vk_bytes = vec![];
+    snark_pk.vk.serialize_compressed(&mut vk_bytes).unwrap();
+
+    let prover_stmt =
+        R1CSProverStmt::new_from_bytes::<G1>(r1cs_bytes, wasm_bytes, &pk_bytes).unwrap();
+    assert_eq!(
+        prover_stmt,
+        R1CSProverStmt::new_statement_from_params(r1cs, wasm_bytes.to_vec(), snark_pk.clone())
+            .unwrap()
+    );
+
+    let a = Fr::rand(&mut rng);
+    let b = Fr::rand(&mut rng);
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(prover_stmt);
+    let proof_spec_prover = ProofSpec::new(prover_statements, MetaStatements::new(), vec![], None);
+    proof_spec_prover.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    let mut r1cs_wit = R1CSCircomWitness::<Bls12_381>::new();
+    r1cs_wit.set_private("a".to_string(), vec![a]);
+    r1cs_wit.set_private("b".to_string(), vec![b]);
+    witnesses.add(Witness::R1CSLegoGroth16(r1cs_wit));
+
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec_prover,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let verifier_proof_spec = |product: Fr| {
+        let mut verifier_statements = Statements::new();
+        verifier_statements
+            .add(R1CSVerifierStmt::new_from_bytes::<G1>(vec![product], &vk_bytes).unwrap());
+        ProofSpec::new(verifier_statements, MetaStatements::new(), vec![], None)
+    };
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(a * b),
+            None,
+            Default::default(),
+        )
+        .unwrap();
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(a * b + Fr::one()),
+            None,
+            Default::default()
+        )
+        .is_err());
+
+    // Malformed inputs are rejected
+    assert!(matches!(
+        R1CSProverStmt::<Bls12_381>::new_from_bytes::<G1>(
+            &r1cs_bytes[..r1cs_bytes.len() / 2],
+            wasm_bytes,
+            &pk_bytes
+        ),
+        Err(ProofSystemError::CircomError(_))
+    ));
+    assert!(matches!(
+        R1CSProverStmt::<Bls12_381>::new_from_bytes::<G1>(r1cs_bytes, r1cs_bytes, &pk_bytes),
+        Err(ProofSystemError::CircomError(_))
+    ));
+    assert!(matches!(
+        R1CSProverStmt::<Bls12_381>::new_from_bytes::<G1>(
+            r1cs_bytes,
+            wasm_bytes,
+            &pk_bytes[..pk_bytes.len() - 1]
+        ),
+        Err(ProofSystemError::Serialization(_))
+    ));
+    assert!(matches!(
+        R1CSVerifierStmt::<Bls12_381>::new_from_bytes::<G1>(vec![a * b], &pk_bytes[1..]),
+        Err(ProofSystemError::Serialization(_))
+    ));
+}
+
+#[test]
+fn pok_of_bbs_plus_sig_with_circuit_input_linked_to_message() {
+    // Prove knowledge of a BBS+ signature and that the product of a signed message and another value is a public
+    // value. Only the 1st circuit input is committed and linked to the signed message.
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let msg_count = 5;
+    let msgs: Vec<Fr> = (0..msg_count).map(|_| Fr::rand(&mut rng)).collect();
+    let (sig_params, sig_keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+
+    let msg_idx = 2;
+    let other = Fr::rand(&mut rng);
+    let product = msgs[msg_idx] * other;
+
+    // Circom code for following in tests/r1cs/circom/circuits/multiply2.circom
+    let (snark_pk, r1cs, wasm_bytes) = get_r1cs_and_wasm_bytes(
+        "tests/r1cs/circom/bls12-381/multiply2.r1cs",
+        "tests/r1cs/circom/bls12-381/multiply2.wasm",
+        1,
+        &mut rng,
+    );
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(
+        R1CSProverStmt::new_statement_from_params(r1cs, wasm_bytes, snark_pk.clone()).unwrap(),
+    );
+
+    // Circuit input `a` is linked to the signed message
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.link_r1cs_input(1, 0, (0, msg_idx));
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    let mut r1cs_wit = R1CSCircomWitness::<Bls12_381>::new();
+    r1cs_wit.set_private("a".to_string(), vec![msgs[msg_idx]]);
+    r1cs_wit.set_private("b".to_string(), vec![other]);
+    witnesses.add(Witness::R1CSLegoGroth16(r1cs_wit));
+
+    let proof_spec_prover = ProofSpec::new(
+        prover_statements.clone(),
+        meta_statements.clone(),
+        vec![],
+        None,
+    );
+    proof_spec_prover.validate().unwrap();
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec_prover,
+        witnesses.clone(),
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let verifier_proof_spec = |meta_statements: MetaStatements| {
+        let mut verifier_statements = Statements::new();
+        verifier_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        verifier_statements.add(
+            R1CSVerifierStmt::new_statement_from_params(vec![product], snark_pk.vk.clone())
+                .unwrap(),
+        );
+        ProofSpec::new(verifier_statements, meta_statements, vec![], None)
+    };
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(meta_statements),
+            None,
+            Default::default(),
+        )
+        .unwrap();
+
+    // Circuit input `b` isn't committed so it can't be linked to a signed message
+    let mut meta_statements_uncommitted = MetaStatements::new();
+    meta_statements_uncommitted.link_r1cs_input(1, 1, (0, msg_idx));
+    assert!(matches!(
+        ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            ProofSpec::new(
+                prover_statements,
+                meta_statements_uncommitted.clone(),
+                vec![],
+                None
+            ),
+            witnesses,
+            None,
+            Default::default(),
+        ),
+        Err(ProofSystemError::R1CSInputNotCommitted(1, 1, 1))
+    ));
+    assert!(matches!(
+        proof.verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(meta_statements_uncommitted),
+            None,
+            Default::default()
+        ),
+        Err(ProofSystemError::R1CSInputNotCommitted(1, 1, 1))
+    ));
+}
@@ -7,6 +7,7 @@ use ark_std::{
 };
 use blake2::Blake2b512;
 use proof_system::{
+    error::ProofSystemError,
     prelude::{
         generate_snark_srs_bound_check, EqualWitnesses, MetaStatements, ProofSpec, ProverConfig,
         VerifierConfig, Witness, WitnessRef, Witnesses,
@@ -21,13 +22,15 @@ use proof_system::{
             BoundCheckLegoGroth16Verifier as BoundCheckVerifierStmt,
         },
         saver::{SaverProver as SaverProverStmt, SaverVerifier as SaverVerifierStmt},
-        Statements,
+        Statement, Statements,
     },
+    sub_protocols::saver::SaverProtocol,
     witness::{
         PoKBBSSignature23G1 as PoKSignatureBBS23G1Wit, PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
     },
 };
 use saver::{
+    error::SaverError,
     keygen::{DecryptionKey, EncryptionKey, PreparedDecryptionKey, SecretKey},
     prelude::VerifyingKey,
     saver_groth16::ProvingKey,
@@ -1425,3 +1428,755 @@ fn pok_of_bbs_plus_sig_and_bounded_message_and_verifiable_encryption() {
         )
         .unwrap();
 }
+
+#[test]
+fn verifier_statements_derived_from_prover_statements() {
+    // The holder derives the verifier's statements from its own statements and the verifier's independently
+    // created statements should be the same.
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let min = 100;
+    let max = 200;
+    let msg_count = 5;
+    let msgs = (0..msg_count)
+        .map(|i| Fr::from(min + 1 + i as u64))
+        .collect::<Vec<_>>();
+    let (sig_params, sig_keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+
+    let bound_snark_pk = generate_snark_srs_bound_check::<Bls12_381, _>(&mut rng).unwrap();
+
+    let enc_gens = EncryptionGens::<Bls12_381>::new_using_rng(&mut rng);
+    let chunk_bit_size = 16;
+    let chunked_comm_gens = ChunkedCommitmentGens::<G1Affine>::new_using_rng(&mut rng);
+    let (snark_pk, _, ek, _) = setup_for_groth16(&mut rng, chunk_bit_size, &enc_gens).unwrap();
+
+    let msg_idx = 1;
+    let enc_msg_idx = 3;
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(
+        BoundCheckProverStmt::new_statement_from_params(min, max, bound_snark_pk.clone()).unwrap(),
+    );
+    prover_statements.add(
+        SaverProverStmt::new_statement_from_params(
+            chunk_bit_size,
+            enc_gens.clone(),
+            chunked_comm_gens.clone(),
+            ek.clone(),
+            snark_pk.clone(),
+        )
+        .unwrap(),
+    );
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, msg_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, enc_msg_idx), (2, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let prover_proof_spec = ProofSpec::new(
+        prover_statements.clone(),
+        meta_statements.clone(),
+        vec![],
+        None,
+    );
+    prover_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(Witness::BoundCheckLegoGroth16(msgs[msg_idx]));
+    witnesses.add(Witness::Saver(msgs[enc_msg_idx]));
+
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let mut derived_statements = Statements::new();
+    for s in prover_statements.0.iter() {
+        derived_statements.add(s.to_verifier_statement().unwrap());
+    }
+    test_serialization!(Statements<Bls12_381, G1Affine>, derived_statements);
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements.add(
+        BoundCheckVerifierStmt::new_statement_from_params(min, max, bound_snark_pk.vk.clone())
+            .unwrap(),
+    );
+    verifier_statements.add(
+        SaverVerifierStmt::new_statement_from_params(
+            chunk_bit_size,
+            enc_gens,
+            chunked_comm_gens,
+            ek,
+            snark_pk.pk.vk.clone(),
+        )
+        .unwrap(),
+    );
+    assert_eq!(derived_statements, verifier_statements);
+
+    let derived_proof_spec = ProofSpec::new(derived_statements, meta_statements, vec![], None);
+    derived_proof_spec.validate().unwrap();
+    test_serialization!(ProofSpec<Bls12_381, G1Affine>, derived_proof_spec);
+
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, derived_proof_spec, None, Default::default())
+        .unwrap();
+
+    // Proving key given as a reference to setup params can't be converted
+    let stmt =
+        BoundCheckProverStmt::<Bls12_381>::new_statement_from_params_ref::<G1Affine>(min, max, 0)
+            .unwrap();
+    assert!(stmt.to_verifier_statement().is_err());
+}
+
+#[test]
+fn pok_of_bbs_plus_sig_and_verifiable_encryption_with_different_chunk_sizes() {
+    // Smaller chunks make decryption faster but result in more ciphertexts and hence larger proofs
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let enc_msg_idx = 1;
+    let enc_gens = EncryptionGens::<Bls12_381>::new_using_rng(&mut rng);
+    let chunked_comm_gens = ChunkedCommitmentGens::<G1Affine>::new_using_rng(&mut rng);
+
+    for chunk_bit_size in [4, 8] {
+        let (snark_pk, sk, ek, dk) =
+            setup_for_groth16(&mut rng, chunk_bit_size, &enc_gens).unwrap();
+
+        // Encryption key created for a different chunk size isn't accepted
+        assert!(
+            SaverProverStmt::<Bls12_381>::new_statement_from_params::<G1Affine>(
+                if chunk_bit_size == 4 { 8 } else { 4 },
+                enc_gens.clone(),
+                chunked_comm_gens.clone(),
+                ek.clone(),
+                snark_pk.clone(),
+            )
+            .is_err()
+        );
+
+        let mut prover_statements = Statements::new();
+        prover_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        prover_statements.add(
+            SaverProverStmt::new_statement_from_params(
+                chunk_bit_size,
+                enc_gens.clone(),
+                chunked_comm_gens.clone(),
+                ek.clone(),
+                snark_pk.clone(),
+            )
+            .unwrap(),
+        );
+
+        let mut meta_statements = MetaStatements::new();
+        meta_statements.add_witness_equality(EqualWitnesses(
+            vec![(0, enc_msg_idx), (1, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        ));
+
+        let prover_proof_spec = ProofSpec::new(
+            prover_statements.clone(),
+            meta_statements.clone(),
+            vec![],
+            None,
+        );
+        prover_proof_spec.validate().unwrap();
+
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig.clone(),
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        witnesses.add(Witness::Saver(msgs[enc_msg_idx]));
+
+        let start = Instant::now();
+        let proof = ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            prover_proof_spec,
+            witnesses,
+            None,
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+        let proving_time = start.elapsed();
+
+        let mut verifier_statements = Statements::new();
+        verifier_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        verifier_statements.add(
+            SaverVerifierStmt::new_statement_from_params(
+                chunk_bit_size,
+                enc_gens.clone(),
+                chunked_comm_gens.clone(),
+                ek.clone(),
+                snark_pk.pk.vk.clone(),
+            )
+            .unwrap(),
+        );
+        let verifier_proof_spec =
+            ProofSpec::new(verifier_statements, meta_statements, vec![], None);
+        verifier_proof_spec.validate().unwrap();
+
+        let start = Instant::now();
+        proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec, None, Default::default())
+            .unwrap();
+        let verifying_time = start.elapsed();
+
+        let start = Instant::now();
+        decrypt_and_verify(
+            &proof,
+            1,
+            &snark_pk.pk.vk,
+            msgs[enc_msg_idx],
+            &sk,
+            dk,
+            enc_gens.clone(),
+            chunk_bit_size,
+        );
+        let decryption_time = start.elapsed();
+
+        let mut proof_bytes = vec![];
+        proof.serialize_compressed(&mut proof_bytes).unwrap();
+        println!(
+            "For chunk size {} bits, proof size is {} bytes. Proving takes {:?}, verifying takes {:?} and decryption takes {:?}",
+            chunk_bit_size,
+            proof_bytes.len(),
+            proving_time,
+            verifying_time,
+            decryption_time
+        );
+    }
+}
+
+#[test]
+fn saver_ciphertext_well_formedness() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let enc_msg_idx = 2;
+    let chunk_bit_size = 16;
+    let enc_gens = EncryptionGens::<Bls12_381>::new_using_rng(&mut rng);
+    let chunked_comm_gens = ChunkedCommitmentGens::<G1Affine>::new_using_rng(&mut rng);
+    let (snark_pk, _, ek, _) = setup_for_groth16(&mut rng, chunk_bit_size, &enc_gens).unwrap();
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(
+        SaverProverStmt::new_statement_from_params(
+            chunk_bit_size,
+            enc_gens.clone(),
+            chunked_comm_gens.clone(),
+            ek.clone(),
+            snark_pk.clone(),
+        )
+        .unwrap(),
+    );
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, enc_msg_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let proof_spec = ProofSpec::new(prover_statements, meta_statements, vec![], None);
+    proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(Witness::Saver(msgs[enc_msg_idx]));
+
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    // The decryptor gets the ciphertext without the rest of the proof
+    let ct = proof.get_saver_ciphertext_and_proof(1).unwrap().0.clone();
+    SaverProtocol::verify_ciphertext_commitment(&ct, enc_gens.clone(), ek.clone()).unwrap();
+
+    let mut bad_ct = ct.clone();
+    bad_ct.enc_chunks[1] = G1Affine::rand(&mut rng);
+    assert!(matches!(
+        SaverProtocol::verify_ciphertext_commitment(&bad_ct, enc_gens.clone(), ek.clone()),
+        Err(ProofSystemError::SaverError(SaverError::InvalidCommitment))
+    ));
+
+    let mut bad_ct = ct.clone();
+    bad_ct.X_r = G1Affine::rand(&mut rng);
+    assert!(matches!(
+        SaverProtocol::verify_ciphertext_commitment(&bad_ct, enc_gens.clone(), ek.clone()),
+        Err(ProofSystemError::SaverError(SaverError::InvalidCommitment))
+    ));
+
+    let mut bad_ct = ct.clone();
+    bad_ct.commitment = G1Affine::rand(&mut rng);
+    assert!(matches!(
+        SaverProtocol::verify_ciphertext_commitment(&bad_ct, enc_gens.clone(), ek.clone()),
+        Err(ProofSystemError::SaverError(SaverError::InvalidCommitment))
+    ));
+
+    // Missing a chunk
+    let mut bad_ct = ct;
+    bad_ct.enc_chunks.pop();
+    assert!(matches!(
+        SaverProtocol::verify_ciphertext_commitment(&bad_ct, enc_gens, ek),
+        Err(ProofSystemError::SaverError(
+            SaverError::IncompatibleEncryptionKey(_, _)
+        ))
+    ));
+}
+
+#[test]
+fn saver_chunk_size_mismatch_between_prover_and_verifier() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let prover_chunk_bit_size = 8;
+    let verifier_chunk_bit_size = 4;
+    let enc_gens = EncryptionGens::<Bls12_381>::new_using_rng(&mut rng);
+    let chunked_comm_gens = ChunkedCommitmentGens::<G1Affine>::new_using_rng(&mut rng);
+    let (snark_pk, _, ek, _) =
+        setup_for_groth16(&mut rng, prover_chunk_bit_size, &enc_gens).unwrap();
+
+    let mut prover_statements = Statements::<Bls12_381, G1Affine>::new();
+    prover_statements.add(
+        SaverProverStmt::new_statement_from_params(
+            prover_chunk_bit_size,
+            enc_gens.clone(),
+            chunked_comm_gens.clone(),
+            ek.clone(),
+            snark_pk.clone(),
+        )
+        .unwrap(),
+    );
+    let prover_proof_spec = ProofSpec::new(prover_statements, MetaStatements::new(), vec![], None);
+    prover_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(Witness::Saver(Fr::rand(&mut rng)));
+
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    // Statement referring to the setup params isn't checked against the encryption key so the verifier can
+    // end up with a different chunk bit size than the prover
+    let verifier_setup_params = vec![
+        SetupParams::SaverEncryptionGens(enc_gens),
+        SetupParams::SaverCommitmentGens(chunked_comm_gens),
+        SetupParams::SaverEncryptionKey(ek),
+        SetupParams::SaverVerifyingKey(snark_pk.pk.vk.clone()),
+    ];
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(SaverVerifierStmt::new_statement_from_params_ref(
+        verifier_chunk_bit_size,
+        0,
+        1,
+        2,
+        3,
+    ));
+    let verifier_proof_spec = ProofSpec::new(
+        verifier_statements,
+        MetaStatements::new(),
+        verifier_setup_params,
+        None,
+    );
+    verifier_proof_spec.validate().unwrap();
+
+    let err = proof
+        .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec, None, Default::default())
+        .unwrap_err();
+    match err {
+        ProofSystemError::StatementVerificationFailed { index, source, .. } => {
+            assert_eq!(index, 0);
+            let expected_count = saver::utils::chunks_count::<Fr>(verifier_chunk_bit_size) as usize;
+            let ciphertext_count = saver::utils::chunks_count::<Fr>(prover_chunk_bit_size) as usize;
+            assert!(matches!(
+                *source,
+                ProofSystemError::SaverChunkMismatch(0, c, e, n)
+                    if c == verifier_chunk_bit_size && e == expected_count && n == ciphertext_count
+            ));
+        }
+        e => panic!("unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn decryptor_verifies_decryption_key_against_statement() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let chunk_bit_size = 8;
+    let enc_gens = EncryptionGens::<Bls12_381>::new_using_rng(&mut rng);
+    let chunked_comm_gens = ChunkedCommitmentGens::<G1Affine>::new_using_rng(&mut rng);
+    let (snark_pk, sk, ek, dk) = setup_for_groth16(&mut rng, chunk_bit_size, &enc_gens).unwrap();
+    // Keys of a different decryptor using the same generators
+    let (_, other_sk, _, other_dk) =
+        setup_for_groth16(&mut rng, chunk_bit_size, &enc_gens).unwrap();
+
+    let stmt = SaverVerifierStmt::new_statement_from_params::<G1Affine>(
+        chunk_bit_size,
+        enc_gens.clone(),
+        chunked_comm_gens.clone(),
+        ek.clone(),
+        snark_pk.pk.vk.clone(),
+    )
+    .unwrap();
+    let stmt = match stmt {
+        Statement::SaverVerifier(s) => s,
+        _ => unreachable!(),
+    };
+    let no_setup_params: Vec<SetupParams<Bls12_381, G1Affine>> = vec![];
+    stmt.verify_decryption_key(&sk, &dk, &no_setup_params, 0)
+        .unwrap();
+    assert!(matches!(
+        stmt.verify_decryption_key(&other_sk, &other_dk, &no_setup_params, 0),
+        Err(ProofSystemError::SaverError(
+            SaverError::DecryptionKeyMismatch
+        ))
+    ));
+
+    // Statement referring to setup params
+    let setup_params = vec![
+        SetupParams::<Bls12_381, G1Affine>::SaverEncryptionGens(enc_gens),
+        SetupParams::SaverCommitmentGens(chunked_comm_gens),
+        SetupParams::SaverEncryptionKey(ek),
+        SetupParams::SaverVerifyingKey(snark_pk.pk.vk),
+    ];
+    let stmt = match SaverVerifierStmt::new_statement_from_params_ref::<G1Affine>(
+        chunk_bit_size,
+        0,
+        1,
+        2,
+        3,
+    ) {
+        Statement::SaverVerifier(s) => s,
+        _ => unreachable!(),
+    };
+    stmt.verify_decryption_key(&sk, &dk, &setup_params, 0)
+        .unwrap();
+    assert!(matches!(
+        stmt.verify_decryption_key(&other_sk, &other_dk, &setup_params, 0),
+        Err(ProofSystemError::SaverError(
+            SaverError::DecryptionKeyMismatch
+        ))
+    ));
+}
+
+#[test]
+fn decryptor_proves_correct_decryption_using_statement() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let chunk_bit_size = 8;
+    let enc_gens = EncryptionGens::<Bls12_381>::new_using_rng(&mut rng);
+    let chunked_comm_gens = ChunkedCommitmentGens::<G1Affine>::new_using_rng(&mut rng);
+    let (snark_pk, sk, ek, dk) = setup_for_groth16(&mut rng, chunk_bit_size, &enc_gens).unwrap();
+
+    let mut prover_statements = Statements::<Bls12_381, G1Affine>::new();
+    prover_statements.add(
+        SaverProverStmt::new_statement_from_params(
+            chunk_bit_size,
+            enc_gens.clone(),
+            chunked_comm_gens.clone(),
+            ek.clone(),
+            snark_pk.clone(),
+        )
+        .unwrap(),
+    );
+    let prover_proof_spec = ProofSpec::new(prover_statements, MetaStatements::new(), vec![], None);
+
+    let message = Fr::rand(&mut rng);
+    let mut witnesses = Witnesses::new();
+    witnesses.add(Witness::Saver(message));
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let stmt = match SaverVerifierStmt::new_statement_from_params::<G1Affine>(
+        chunk_bit_size,
+        enc_gens,
+        chunked_comm_gens,
+        ek,
+        snark_pk.pk.vk.clone(),
+    )
+    .unwrap()
+    {
+        Statement::SaverVerifier(s) => s,
+        _ => unreachable!(),
+    };
+    let no_setup_params: Vec<SetupParams<Bls12_381, G1Affine>> = vec![];
+
+    // Decryptor decrypts and proves the decryption
+    let ct = proof.get_saver_ciphertext_and_proof(0).unwrap().0;
+    let (decrypted, nu) = stmt
+        .decrypt_and_prove(ct, &sk, dk.clone(), &no_setup_params, 0)
+        .unwrap();
+    assert_eq!(decrypted, message);
+
+    // Anyone with the decryption key can verify the decryption
+    stmt.verify_decryption(&decrypted, &nu, ct, dk.clone(), &no_setup_params, 0)
+        .unwrap();
+    assert!(matches!(
+        stmt.verify_decryption(
+            &Fr::rand(&mut rng),
+            &nu,
+            ct,
+            dk.clone(),
+            &no_setup_params,
+            0
+        ),
+        Err(ProofSystemError::SaverError(SaverError::InvalidDecryption))
+    ));
+    assert!(matches!(
+        stmt.verify_decryption(
+            &decrypted,
+            &G1Affine::rand(&mut rng),
+            ct,
+            dk,
+            &no_setup_params,
+            0
+        ),
+        Err(ProofSystemError::SaverError(SaverError::InvalidDecryption))
+    ));
+}
+
+#[test]
+fn same_signed_message_encrypted_for_two_decryptors() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count);
+
+    let chunk_bit_size = 8;
+    let enc_gens_1 = EncryptionGens::<Bls12_381>::new_using_rng(&mut rng);
+    let chunked_comm_gens_1 = ChunkedCommitmentGens::<G1Affine>::new_using_rng(&mut rng);
+    let (snark_pk_1, sk_1, ek_1, dk_1) =
+        setup_for_groth16(&mut rng, chunk_bit_size, &enc_gens_1).unwrap();
+    let enc_gens_2 = EncryptionGens::<Bls12_381>::new_using_rng(&mut rng);
+    let chunked_comm_gens_2 = ChunkedCommitmentGens::<G1Affine>::new_using_rng(&mut rng);
+    let (snark_pk_2, sk_2, ek_2, dk_2) =
+        setup_for_groth16(&mut rng, chunk_bit_size, &enc_gens_2).unwrap();
+
+    let enc_msg_idx = 1;
+    let other_msg_idx = 2;
+
+    let mut prover_statements = Statements::<Bls12_381, G1Affine>::new();
+    prover_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(
+        SaverProverStmt::new_statement_from_params(
+            chunk_bit_size,
+            enc_gens_1.clone(),
+            chunked_comm_gens_1.clone(),
+            ek_1.clone(),
+            snark_pk_1.clone(),
+        )
+        .unwrap(),
+    );
+    prover_statements.add(
+        SaverProverStmt::new_statement_from_params(
+            chunk_bit_size,
+            enc_gens_2.clone(),
+            chunked_comm_gens_2.clone(),
+            ek_2.clone(),
+            snark_pk_2.clone(),
+        )
+        .unwrap(),
+    );
+
+    let mut verifier_statements = Statements::<Bls12_381, G1Affine>::new();
+    verifier_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements.add(
+        SaverVerifierStmt::new_statement_from_params(
+            chunk_bit_size,
+            enc_gens_1.clone(),
+            chunked_comm_gens_1,
+            ek_1,
+            snark_pk_1.pk.vk.clone(),
+        )
+        .unwrap(),
+    );
+    verifier_statements.add(
+        SaverVerifierStmt::new_statement_from_params(
+            chunk_bit_size,
+            enc_gens_2.clone(),
+            chunked_comm_gens_2,
+            ek_2,
+            snark_pk_2.pk.vk.clone(),
+        )
+        .unwrap(),
+    );
+
+    // Both ciphertexts encrypt the signed message at `enc_msg_idx`
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, enc_msg_idx), (1, 0), (2, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    let verifier_proof_spec = ProofSpec::new(
+        verifier_statements.clone(),
+        meta_statements.clone(),
+        vec![],
+        None,
+    );
+    verifier_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig.clone(),
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(Witness::Saver(msgs[enc_msg_idx]));
+    witnesses.add(Witness::Saver(msgs[enc_msg_idx]));
+
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        ProofSpec::new(
+            prover_statements.clone(),
+            meta_statements.clone(),
+            vec![],
+            None,
+        ),
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            None,
+            Default::default(),
+        )
+        .unwrap();
+
+    // Each decryptor gets the same message
+    decrypt_and_verify(
+        &proof,
+        1,
+        &snark_pk_1.pk.vk,
+        msgs[enc_msg_idx],
+        &sk_1,
+        dk_1,
+        enc_gens_1,
+        chunk_bit_size,
+    );
+    decrypt_and_verify(
+        &proof,
+        2,
+        &snark_pk_2.pk.vk,
+        msgs[enc_msg_idx],
+        &sk_2,
+        dk_2,
+        enc_gens_2,
+        chunk_bit_size,
+    );
+
+    // The 2nd ciphertext encrypts a different signed message, so the proof is created with a proof spec that
+    // binds it to that message and doesn't verify with the proof spec requiring both to encrypt the same message
+    let mut meta_statements_different = MetaStatements::new();
+    meta_statements_different.add_witness_equality(EqualWitnesses(
+        vec![(0, enc_msg_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    meta_statements_different.add_witness_equality(EqualWitnesses(
+        vec![(0, other_msg_idx), (2, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(Witness::Saver(msgs[enc_msg_idx]));
+    witnesses.add(Witness::Saver(msgs[other_msg_idx]));
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        ProofSpec::new(prover_statements, meta_statements_different, vec![], None),
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    assert!(matches!(
+        proof.verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec, None, Default::default()),
+        Err(ProofSystemError::WitnessResponseNotEqual(_, _))
+    ));
+}
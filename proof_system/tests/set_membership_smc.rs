@@ -0,0 +1,159 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    rand::{prelude::StdRng, SeedableRng},
+};
+use blake2::Blake2b512;
+use std::time::Instant;
+
+use proof_system::{
+    prelude::{
+        bound_check_smc::SmcParamsAndCommitmentKey, EqualWitnesses, MetaStatements, ProofSpec,
+        ProofSystemError, Witness, WitnessRef, Witnesses,
+    },
+    setup_params::SetupParams,
+    statement::{
+        bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
+        set_membership_smc::SetMembershipSmc as SetMembershipSmcStmt, Statements,
+    },
+    witness::PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
+};
+
+use test_utils::{bbs::*, test_serialization, Fr, ProofG1};
+
+#[test]
+fn pok_of_bbs_plus_sig_and_set_membership_using_set_membership_check() {
+    // Prove knowledge of BBS+ signature and that a specific message is one of the values of a public set
+    // using the set-membership check protocol
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msgs = vec![
+        Fr::from(1u64),
+        Fr::from(42u64),
+        Fr::from(43u64),
+        Fr::from(2u64),
+    ];
+    let (sig_params, sig_keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+
+    // Set is sorted and duplicates are removed
+    let (smc_params, _) = SmcParamsAndCommitmentKey::<Bls12_381>::new_for_set::<_, Blake2b512>(
+        &mut rng,
+        b"test",
+        vec![
+            Fr::from(99u64),
+            Fr::from(42u64),
+            Fr::from(10u64),
+            Fr::from(42u64),
+        ],
+    );
+    smc_params.verify().unwrap();
+    assert_eq!(
+        smc_params.params.set,
+        vec![Fr::from(10u64), Fr::from(42u64), Fr::from(99u64)]
+    );
+
+    let setup_params = vec![SetupParams::SmcParamsAndCommKey(smc_params.clone())];
+
+    let proof_spec = |msg_idx: usize, use_ref: bool| {
+        let mut statements = Statements::<Bls12_381, G1Affine>::new();
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        if use_ref {
+            statements.add(SetMembershipSmcStmt::new_statement_from_params_ref(0).unwrap());
+        } else {
+            statements
+                .add(SetMembershipSmcStmt::new_statement_from_params(smc_params.clone()).unwrap());
+        }
+        let mut meta_statements = MetaStatements::new();
+        meta_statements.add_witness_equality(EqualWitnesses(
+            vec![(0, msg_idx), (1, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        ));
+        let setup_params = if use_ref {
+            setup_params.clone()
+        } else {
+            vec![]
+        };
+        ProofSpec::new(statements, meta_statements, setup_params, None)
+    };
+    let witnesses = |msg: Fr| {
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig.clone(),
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        witnesses.add(Witness::SetMembershipSmc(msg));
+        witnesses
+    };
+
+    for use_ref in [false, true] {
+        // 42 is in the set
+        let spec = proof_spec(1, use_ref);
+        spec.validate().unwrap();
+        test_serialization!(ProofSpec<Bls12_381, G1Affine>, spec);
+        test_serialization!(Witnesses<Bls12_381>, witnesses(msgs[1]));
+
+        let start = Instant::now();
+        let proof = ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            spec.clone(),
+            witnesses(msgs[1]),
+            None,
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+        println!(
+            "Time taken to create proof of membership using set-membership check {:?}",
+            start.elapsed()
+        );
+        test_serialization!(ProofG1, proof);
+
+        let start = Instant::now();
+        proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(&mut rng, spec.clone(), None, Default::default())
+            .unwrap();
+        println!(
+            "Time taken to verify proof of membership using set-membership check {:?}",
+            start.elapsed()
+        );
+
+        // Proof doesn't verify when the witness equality refers to another message
+        assert!(proof
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                proof_spec(2, use_ref),
+                None,
+                Default::default()
+            )
+            .is_err());
+
+        // 43 isn't in the set so the prover can't create a proof
+        assert!(matches!(
+            ProofG1::new::<StdRng, Blake2b512>(
+                &mut rng,
+                proof_spec(2, use_ref),
+                witnesses(msgs[2]),
+                None,
+                Default::default(),
+            ),
+            Err(ProofSystemError::MessageNotInPublicSet(1))
+        ));
+    }
+
+    let (empty_params, _) = SmcParamsAndCommitmentKey::<Bls12_381>::new_for_set::<_, Blake2b512>(
+        &mut rng,
+        b"test",
+        vec![],
+    );
+    assert!(matches!(
+        SetMembershipSmcStmt::new_statement_from_params::<G1Affine>(empty_params),
+        Err(ProofSystemError::EmptyPublicSet)
+    ));
+}
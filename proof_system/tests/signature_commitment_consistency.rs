@@ -0,0 +1,206 @@
+use ark_bls12_381::{Bls12_381, G1Affine, G1Projective};
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    rand::{prelude::StdRng, SeedableRng},
+    UniformRand,
+};
+use blake2::Blake2b512;
+
+use proof_system::{
+    prelude::{
+        EqualWitnesses, MetaStatements, ProofSpec, ProofSystemError, Witness, WitnessRef, Witnesses,
+    },
+    statement::{
+        bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
+        signature_commitment_consistency::SignatureCommitmentConsistency as SignatureCommitmentConsistencyStmt,
+        Statements,
+    },
+    witness::PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
+};
+
+use test_utils::{bbs::*, test_serialization, Fr, ProofG1};
+
+#[test]
+fn pok_of_bbs_plus_sig_and_commitment_to_several_messages() {
+    // Prove knowledge of BBS+ signature and that 4 values committed in a Pedersen commitment are signed messages
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, 6);
+
+    // Commit to 4 of the signed messages, not in the order of signing, and the randomness
+    let message_indices = BTreeMap::from([(0, 4), (1, 0), (2, 2), (3, 5)]);
+    let comm_key = (0..5)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let randomness = Fr::rand(&mut rng);
+    let mut opening = message_indices
+        .values()
+        .map(|i| msgs[*i])
+        .collect::<Vec<_>>();
+    opening.push(randomness);
+    let commitment = G1Projective::msm_unchecked(&comm_key, &opening).into_affine();
+
+    let proof_spec =
+        |comm_key: Vec<G1Affine>, commitment: G1Affine, message_indices: BTreeMap<usize, usize>| {
+            let mut statements = Statements::<Bls12_381, G1Affine>::new();
+            statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+                sig_params.clone(),
+                sig_keypair.public_key.clone(),
+                BTreeMap::new(),
+            ));
+            statements.add(
+                SignatureCommitmentConsistencyStmt::new_statement_from_params(
+                    comm_key,
+                    commitment,
+                    0,
+                    message_indices,
+                ),
+            );
+            // No witness equalities are needed as the statement implies them
+            ProofSpec::new(statements, MetaStatements::new(), vec![], None)
+        };
+
+    let witnesses = |opening: Vec<Fr>| {
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig.clone(),
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        witnesses.add(Witness::SignatureCommitmentConsistency(opening));
+        witnesses
+    };
+
+    let spec = proof_spec(comm_key.clone(), commitment, message_indices.clone());
+    spec.validate().unwrap();
+    test_serialization!(ProofSpec<Bls12_381, G1Affine>, spec);
+    assert_eq!(
+        spec.implied_witness_equalities(),
+        message_indices
+            .iter()
+            .map(|(pos, msg_idx)| EqualWitnesses(
+                vec![(0, *msg_idx), (1, *pos)]
+                    .into_iter()
+                    .collect::<BTreeSet<WitnessRef>>()
+            ))
+            .collect::<Vec<_>>()
+    );
+    test_serialization!(Witnesses<Bls12_381>, witnesses(opening.clone()));
+
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        spec.clone(),
+        witnesses(opening.clone()),
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    test_serialization!(ProofG1, proof);
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, spec.clone(), None, Default::default())
+        .unwrap();
+
+    // Verification fails if a base of the commitment key is changed
+    let mut tampered_key = comm_key.clone();
+    tampered_key[1] = G1Projective::rand(&mut rng).into_affine();
+    assert!(matches!(
+        proof.clone().verify::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec(tampered_key, commitment, message_indices.clone()),
+            None,
+            Default::default()
+        ),
+        Err(ProofSystemError::StatementVerificationFailed { .. })
+    ));
+
+    // Verification fails if the verifier expects a committed value to be a different signed message
+    let mut other_indices = message_indices.clone();
+    other_indices.insert(2, 3);
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec(comm_key.clone(), commitment, other_indices),
+            None,
+            Default::default()
+        )
+        .is_err());
+
+    // A commitment where a value isn't the signed message doesn't verify even though the prover knows its opening
+    let mut other_opening = opening.clone();
+    other_opening[2] = msgs[3];
+    let other_commitment = G1Projective::msm_unchecked(&comm_key, &other_opening).into_affine();
+    let other_spec = proof_spec(comm_key.clone(), other_commitment, message_indices.clone());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        other_spec.clone(),
+        witnesses(other_opening),
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    assert!(matches!(
+        proof.verify::<StdRng, Blake2b512>(&mut rng, other_spec, None, Default::default()),
+        Err(ProofSystemError::WitnessResponseNotEqual(..))
+    ));
+
+    // A position outside the commitment is rejected
+    let mut out_of_range = message_indices.clone();
+    out_of_range.insert(5, 1);
+    assert!(matches!(
+        ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec(comm_key.clone(), commitment, out_of_range),
+            witnesses(opening),
+            None,
+            Default::default(),
+        ),
+        Err(ProofSystemError::CommitmentPositionOutOfRange(1, 5, 5))
+    ));
+
+    // Each message must be a hidden message of the referred signature statement
+    let spec_with_refs = |signature_statement_index: usize,
+                          message_indices: BTreeMap<usize, usize>,
+                          revealed: BTreeMap<usize, Fr>| {
+        let mut statements = Statements::<Bls12_381, G1Affine>::new();
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            revealed,
+        ));
+        statements.add(
+            SignatureCommitmentConsistencyStmt::new_statement_from_params(
+                comm_key.clone(),
+                commitment,
+                signature_statement_index,
+                message_indices,
+            ),
+        );
+        ProofSpec::new(statements, MetaStatements::new(), vec![], None)
+    };
+    spec_with_refs(0, message_indices.clone(), BTreeMap::from([(1, msgs[1])]))
+        .validate()
+        .unwrap();
+    assert!(matches!(
+        spec_with_refs(1, message_indices.clone(), BTreeMap::new()).validate(),
+        Err(ProofSystemError::NotASignatureStatement(1, 1))
+    ));
+    assert!(matches!(
+        spec_with_refs(2, message_indices.clone(), BTreeMap::new()).validate(),
+        Err(ProofSystemError::NotASignatureStatement(1, 2))
+    ));
+    let mut out_of_range = message_indices.clone();
+    out_of_range.insert(3, 6);
+    assert!(matches!(
+        spec_with_refs(0, out_of_range, BTreeMap::new()).validate(),
+        Err(ProofSystemError::SignedMessageIndexOutOfRange(1, 6, 6))
+    ));
+    assert!(matches!(
+        spec_with_refs(0, message_indices, BTreeMap::from([(2, msgs[2])])).validate(),
+        Err(ProofSystemError::WitnessAlreadyBeingRevealed(0, 2))
+    ));
+}
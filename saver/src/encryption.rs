@@ -265,7 +265,7 @@ impl<E: Pairing> Encryption<E> {
         chunk_bit_size: u8,
     ) -> crate::Result<(E::ScalarField, E::G1Affine)> {
         let (chunks, nu) = Self::decrypt_to_chunks(c_0, c, sk, dk, g_i, chunk_bit_size)?;
-        Ok((utils::compose(&chunks, chunk_bit_size)?, nu))
+        Ok((Self::compose_decrypted_chunks(&chunks, chunk_bit_size)?, nu))
     }
 
     /// Same as `Self::decrypt` but expects pairing powers (see `PreparedDecryptionKey::pairing_powers`)
@@ -288,7 +288,7 @@ impl<E: Pairing> Encryption<E> {
             chunk_bit_size,
             Some(pairing_powers),
         )?;
-        Ok((utils::compose(&chunks, chunk_bit_size)?, nu))
+        Ok((Self::compose_decrypted_chunks(&chunks, chunk_bit_size)?, nu))
     }
 
     /// Same as `Self::decrypt` but takes Groth16's verification key instead of the generators used for Elgamal encryption
@@ -576,6 +576,20 @@ impl<E: Pairing> Encryption<E> {
         Ok((E::G1::normalize_batch(&ct), r))
     }
 
+    /// Compose the decrypted chunks into the message. Fails if the chunks encode a number not less than the
+    /// field's modulus, as then they weren't created by decomposing a message and composing would reduce them
+    /// to an unrelated message.
+    fn compose_decrypted_chunks(
+        chunks: &[CHUNK_TYPE],
+        chunk_bit_size: u8,
+    ) -> crate::Result<E::ScalarField> {
+        let message = utils::compose(chunks, chunk_bit_size)?;
+        if utils::decompose(&message, chunk_bit_size)? != chunks {
+            return Err(SaverError::DecryptedMessageOutOfRange);
+        }
+        Ok(message)
+    }
+
     /// Does not use precomputation
     fn solve_discrete_log(
         chunk_max_val: CHUNK_TYPE,
@@ -956,6 +970,50 @@ pub(crate) mod tests {
         check(16);
     }
 
+    #[test]
+    fn decrypting_chunks_not_encoding_a_message() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        // Not checking 16-bit chunks as solving discrete log for the maximum chunk value is slow
+        for chunk_bit_size in [4, 8] {
+            let (_, g_i, sk, ek, dk) = enc_setup(chunk_bit_size, &mut rng);
+            let n = chunks_count::<Fr>(chunk_bit_size) as usize;
+            // All chunks at their maximum value encode 2^256 - 1 which is larger than the modulus
+            let chunks = vec![((1u32 << chunk_bit_size) - 1) as CHUNK_TYPE; n];
+            let (ct, _) =
+                Encryption::encrypt_decomposed_message(&mut rng, chunks.clone(), &ek, &g_i)
+                    .unwrap();
+
+            let (decrypted_chunks, _) = Encryption::decrypt_to_chunks(
+                &ct[0],
+                &ct[1..n + 1],
+                &sk,
+                dk.clone(),
+                &g_i,
+                chunk_bit_size,
+            )
+            .unwrap();
+            assert_eq!(decrypted_chunks, chunks);
+
+            assert!(matches!(
+                Encryption::decrypt(&ct[0], &ct[1..n + 1], &sk, dk.clone(), &g_i, chunk_bit_size),
+                Err(SaverError::DecryptedMessageOutOfRange)
+            ));
+            let pairing_powers = dk.pairing_powers(chunk_bit_size, &g_i).unwrap();
+            assert!(matches!(
+                Encryption::decrypt_given_pairing_powers(
+                    &ct[0],
+                    &ct[1..n + 1],
+                    &sk,
+                    dk.clone(),
+                    &g_i,
+                    chunk_bit_size,
+                    &pairing_powers
+                ),
+                Err(SaverError::DecryptedMessageOutOfRange)
+            ));
+        }
+    }
+
     #[test]
     fn encrypt_decrypt_timing() {
         fn check(chunk_bit_size: u8, count: u8) {
@@ -19,6 +19,9 @@ pub enum SaverError {
     CouldNotFindDiscreteLog,
     InvalidPairingPowers,
     PairingCheckFailed,
+    DecryptionKeyMismatch,
+    /// The decrypted chunks encode a number not less than the scalar field's modulus so they aren't a message
+    DecryptedMessageOutOfRange,
 }
 
 impl From<SynthesisError> for SaverError {
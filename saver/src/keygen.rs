@@ -2,7 +2,7 @@ use ark_ec::{
     pairing::{Pairing, PairingOutput},
     AffineRepr, CurveGroup, Group,
 };
-use ark_ff::{One, PrimeField};
+use ark_ff::{One, PrimeField, Zero};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{cfg_iter, rand::RngCore, vec::Vec, UniformRand};
 use serde::{Deserialize, Serialize};
@@ -194,6 +194,35 @@ impl<E: Pairing> PreparedEncryptionKey<E> {
 impl<E: Pairing> DecryptionKey<E> {
     impl_dec_key_funcs!();
 
+    /// Check that this decryption key and the secret key `sk` were created with the encryption key `ek` and
+    /// generators `gens` so that a decryptor can detect a wrong key before attempting decryption. Checks that
+    /// `V_0 = H * rho` and `e(X_i, V_2_i) = e(X_0 * rho, V_1_i)` for each chunk `i`, as `X_0 = G * delta`,
+    /// `X_i = G * delta*s_i`, `V_1_i = H * s_i*v_i` and `V_2_i = H * rho*v_i`.
+    pub fn verify_against_encryption_key(
+        &self,
+        sk: &SecretKey<E::ScalarField>,
+        ek: &EncryptionKey<E>,
+        gens: &EncryptionGens<E>,
+    ) -> crate::Result<()> {
+        let n = ek.supported_chunks_count()? as usize;
+        if self.supported_chunks_count()? as usize != n {
+            return Err(SaverError::IncompatibleDecryptionKey(
+                n,
+                self.supported_chunks_count()? as usize,
+            ));
+        }
+        if gens.H.mul_bigint(sk.0.into_bigint()).into_affine() != self.V_0 {
+            return Err(SaverError::DecryptionKeyMismatch);
+        }
+        let x_0_rho_neg = ek.X_0.mul_bigint((-sk.0).into_bigint()).into_affine();
+        for i in 0..n {
+            if !E::multi_pairing([ek.X[i], x_0_rho_neg], [self.V_2[i], self.V_1[i]]).is_zero() {
+                return Err(SaverError::DecryptionKeyMismatch);
+            }
+        }
+        Ok(())
+    }
+
     /// Calling `pairing_powers` on the prepared decryption key
     pub fn pairing_powers(
         &self,
@@ -378,4 +407,46 @@ pub(crate) mod tests {
         check_keygen(8);
         check_keygen(16);
     }
+
+    #[test]
+    fn decryption_key_verification() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let chunk_bit_size = 8;
+        let chunk_count = chunks_count::<Fr>(chunk_bit_size) as usize;
+        let gens = EncryptionGens::<Bls12_381>::new_using_rng(&mut rng);
+        let g_i = (0..chunk_count)
+            .map(|_| <Bls12_381 as Pairing>::G1Affine::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let g_delta = <Bls12_381 as Pairing>::G1Affine::rand(&mut rng);
+        let g_gamma = <Bls12_381 as Pairing>::G1Affine::rand(&mut rng);
+        let (sk, ek, dk) =
+            keygen(&mut rng, chunk_bit_size, &gens, &g_i, &g_delta, &g_gamma).unwrap();
+        let (sk_1, ek_1, dk_1) =
+            keygen(&mut rng, chunk_bit_size, &gens, &g_i, &g_delta, &g_gamma).unwrap();
+
+        dk.verify_against_encryption_key(&sk, &ek, &gens).unwrap();
+        dk_1.verify_against_encryption_key(&sk_1, &ek_1, &gens)
+            .unwrap();
+
+        // Keys from different runs of keygen don't match even with the same generators
+        assert!(matches!(
+            dk.verify_against_encryption_key(&sk, &ek_1, &gens),
+            Err(SaverError::DecryptionKeyMismatch)
+        ));
+        assert!(matches!(
+            dk_1.verify_against_encryption_key(&sk, &ek, &gens),
+            Err(SaverError::DecryptionKeyMismatch)
+        ));
+        assert!(matches!(
+            dk.verify_against_encryption_key(&sk_1, &ek, &gens),
+            Err(SaverError::DecryptionKeyMismatch)
+        ));
+
+        // Key for a different chunk size
+        let (sk_2, _, dk_2) = keygen(&mut rng, 16, &gens, &g_i, &g_delta, &g_gamma).unwrap();
+        assert!(matches!(
+            dk_2.verify_against_encryption_key(&sk_2, &ek, &gens),
+            Err(SaverError::IncompatibleDecryptionKey(_, _))
+        ));
+    }
 }
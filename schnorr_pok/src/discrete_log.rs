@@ -20,7 +20,7 @@ use ark_ec::{AffineRepr, CurveGroup};
 use ark_ff::PrimeField;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{io::Write, vec::Vec};
-use dock_crypto_utils::serde_utils::ArkObjectBytes;
+use dock_crypto_utils::{msm::WindowTable, serde_utils::ArkObjectBytes};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use zeroize::{Zeroize, ZeroizeOnDrop};
@@ -186,6 +186,27 @@ impl<G: AffineRepr> PokTwoDiscreteLogsProtocol<G> {
         }
     }
 
+    /// Same as `init` but uses a precomputed window table for `base1`, which is faster when `base1` is
+    /// used in several protocols
+    pub fn init_with_base1_table(
+        witness1: G::ScalarField,
+        blinding1: G::ScalarField,
+        base1_table: &WindowTable<G::Group>,
+        witness2: G::ScalarField,
+        blinding2: G::ScalarField,
+        base2: &G,
+    ) -> Self {
+        let t = (base1_table.multiply(&blinding1) + base2.mul_bigint(blinding2.into_bigint()))
+            .into_affine();
+        Self {
+            t,
+            blinding1,
+            witness1,
+            blinding2,
+            witness2,
+        }
+    }
+
     pub fn challenge_contribution<W: Write>(
         &self,
         base1: &G,
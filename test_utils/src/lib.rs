@@ -1,4 +1,4 @@
-use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_bls12_381::{Bls12_381, G1Affine, G2Affine};
 use ark_ec::pairing::Pairing;
 use proof_system::proof::Proof;
 
@@ -6,6 +6,7 @@ pub type Fr = <Bls12_381 as Pairing>::ScalarField;
 pub type G1 = <Bls12_381 as Pairing>::G1Affine;
 pub type G2 = <Bls12_381 as Pairing>::G2Affine;
 pub type ProofG1 = Proof<Bls12_381, G1Affine>;
+pub type ProofG2 = Proof<Bls12_381, G2Affine>;
 
 pub mod accumulators;
 pub mod bbs;
@@ -6,6 +6,7 @@ use ark_std::{
     vec,
     vec::Vec,
 };
+use digest::{Digest, Output};
 pub use merlin::Transcript as Merlin;
 use serde::{Deserialize, Serialize};
 use zeroize::{Zeroize, ZeroizeOnDrop};
@@ -232,6 +233,40 @@ impl Write for MerlinTranscript {
     }
 }
 
+/// Writer that feeds the bytes written to it into a hash function. This allows hashing challenge
+/// contributions, which are written to any `Write`, as they are created rather than first collecting them
+/// in a `Vec<u8>`. The hash is the same as the hash of all the written bytes concatenated.
+#[derive(Clone, Debug)]
+pub struct DigestWriter<D: Digest>(pub D);
+
+impl<D: Digest> DigestWriter<D> {
+    pub fn new() -> Self {
+        Self(D::new())
+    }
+
+    pub fn finalize(self) -> Output<D> {
+        self.0.finalize()
+    }
+}
+
+impl<D: Digest> Default for DigestWriter<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D: Digest> Write for DigestWriter<D> {
+    fn write(&mut self, data: &[u8]) -> ArkResult<usize> {
+        self.0.update(data);
+        Ok(data.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> ArkResult<()> {
+        Ok(())
+    }
+}
+
 // TODO: Impl Write trait for Merlin
 // TODO: Support domain-separator function that adds a label to transcript. One approach is to have MerlinTranscript struct
 // that has a mutable field called write_label set which is used in call to `append_message`
@@ -252,4 +287,26 @@ mod test {
         let f2 = transcript2.challenge_scalar::<Fr>(b"scalar");
         assert_eq!(f1, f2);
     }
+
+    #[test]
+    fn digest_writer() {
+        use ark_std::{rand::prelude::StdRng, rand::SeedableRng, UniformRand};
+        use blake2::Blake2b512;
+
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let points = (0..10)
+            .map(|_| G1Projective::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let scalars = (0..10).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+
+        let mut streamed = DigestWriter::<Blake2b512>::new();
+        let mut buffer = vec![];
+        for (p, s) in points.iter().zip(scalars.iter()) {
+            p.serialize_compressed(&mut streamed).unwrap();
+            s.serialize_compressed(&mut streamed).unwrap();
+            p.serialize_compressed(&mut buffer).unwrap();
+            s.serialize_compressed(&mut buffer).unwrap();
+        }
+        assert_eq!(streamed.finalize(), Blake2b512::digest(&buffer));
+    }
 }
@@ -40,6 +40,18 @@ pub enum VBAccumulatorError {
     InvalidWitness,
     ShortGroupSigError(ShortGroupSigError),
     MismatchBetweenSignatureAndAccumulatorValue,
+    /// Witness cannot be converted as it does not verify against the target accumulator, i.e. the
+    /// target accumulator has a different value or uses a different key or params.
+    WitnessIncompatibleWithAccumulator,
+    /// Number of proofs, accumulators and challenges given for batch verification
+    UnequalNoOfProofsAccumulatorsAndChallenges(usize, usize, usize),
+    /// Number of additions and intermediate accumulators given for updating a witness. Expects an intermediate
+    /// accumulator for each addition except the last
+    UnequalNoOfAdditionsAndIntermediateAccumulators(usize, usize),
+    /// The accumulator values of a state aren't the result of accumulating its members and non-members
+    AccumulatorStateMismatch,
+    /// A proof of membership of several elements needs at least one element
+    NoElementsForMultiMembershipProof,
 }
 
 impl From<SchnorrError> for VBAccumulatorError {
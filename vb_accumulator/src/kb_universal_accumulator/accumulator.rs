@@ -10,14 +10,14 @@ use crate::{
     kb_universal_accumulator::witness::{
         KBUniversalAccumulatorMembershipWitness, KBUniversalAccumulatorNonMembershipWitness,
     },
-    persistence::State,
+    persistence::{State, UniversalAccumulatorState},
     positive::{Accumulator, PositiveAccumulator},
     setup::{PublicKey, SecretKey, SetupParams},
 };
 use ark_ec::pairing::Pairing;
 use ark_ff::{Field, One};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::vec::Vec;
+use ark_std::{collections::BTreeSet, vec::Vec};
 
 #[derive(Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct KBUniversalAccumulator<E: Pairing> {
@@ -27,6 +27,18 @@ pub struct KBUniversalAccumulator<E: Pairing> {
     pub non_mem: PositiveAccumulator<E>,
 }
 
+/// State of the accumulator, i.e. the accumulator and its domain split into the members and non-members, which can be
+/// persisted and later used to restore the accumulator and its databases, like on a restart of the service managing
+/// the accumulator.
+#[derive(Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct KBUniversalAccumulatorState<E: Pairing> {
+    pub accumulator: KBUniversalAccumulator<E>,
+    /// Elements of the domain present in the accumulator
+    pub members: Vec<E::ScalarField>,
+    /// Elements of the domain absent from the accumulator
+    pub non_members: Vec<E::ScalarField>,
+}
+
 impl<E: Pairing> KBUniversalAccumulator<E> {
     /// Initialize a new accumulator. `domain` is the set of all possible accumulator members. Initialization includes adding
     /// the `domain` to the accumulator accumulating all non-members
@@ -42,6 +54,30 @@ impl<E: Pairing> KBUniversalAccumulator<E> {
         Ok(Self { mem, non_mem })
     }
 
+    /// Initialize a new accumulator like `Self::initialize` but with the `domain` given as an iterator. The domain is
+    /// added to the accumulator accumulating all non-members in chunks of `chunk_size` elements so only a chunk needs
+    /// to be in memory at a time. The accumulator is the same as the one created by `Self::initialize` with the same
+    /// elements. A `chunk_size` of 0 is treated as 1.
+    pub fn initialize_from_iter<I: Iterator<Item = E::ScalarField>>(
+        params_gen: impl AsRef<E::G1Affine>,
+        sk: &SecretKey<E::ScalarField>,
+        mut domain: I,
+        chunk_size: usize,
+        non_mem_state: &mut dyn State<E::ScalarField>,
+    ) -> Result<Self, VBAccumulatorError> {
+        let chunk_size = chunk_size.max(1);
+        let mem = PositiveAccumulator::initialize(params_gen);
+        let mut non_mem = mem.clone();
+        loop {
+            let chunk = domain.by_ref().take(chunk_size).collect::<Vec<_>>();
+            if chunk.is_empty() {
+                break;
+            }
+            non_mem = non_mem.add_batch(chunk, sk, non_mem_state)?;
+        }
+        Ok(Self { mem, non_mem })
+    }
+
     pub fn initialize_given_initialized_non_members_accumulator(
         params_gen: impl AsRef<E::G1Affine>,
         non_mem: PositiveAccumulator<E>,
@@ -227,6 +263,18 @@ impl<E: Pairing> KBUniversalAccumulator<E> {
             .map(|ws| ws.into_iter().map(|w| w.into()).collect())
     }
 
+    /// Check if the element is in the accumulator's domain, i.e. it's either a member or a non-member. Only elements of
+    /// the domain can have a membership or non-membership witness. This doesn't do any cryptographic operation as the
+    /// domain is the union of the elements in the states of both the internal accumulators.
+    pub fn is_in_domain(
+        &self,
+        element: &E::ScalarField,
+        mem_state: &dyn State<E::ScalarField>,
+        non_mem_state: &dyn State<E::ScalarField>,
+    ) -> bool {
+        mem_state.has(element) || non_mem_state.has(element)
+    }
+
     pub fn verify_membership(
         &self,
         member: &E::ScalarField,
@@ -269,6 +317,61 @@ impl<E: Pairing> KBUniversalAccumulator<E> {
             non_mem: PositiveAccumulator::from_accumulated(non_mem_accumulated),
         }
     }
+
+    /// Export the accumulator along with its domain taken from the databases of members and non-members.
+    pub fn export_state<'a, 'b>(
+        &self,
+        mem_state: &'a dyn UniversalAccumulatorState<
+            'a,
+            E::ScalarField,
+            ElementIterator = impl Iterator<Item = &'a E::ScalarField>,
+        >,
+        non_mem_state: &'b dyn UniversalAccumulatorState<
+            'b,
+            E::ScalarField,
+            ElementIterator = impl Iterator<Item = &'b E::ScalarField>,
+        >,
+    ) -> KBUniversalAccumulatorState<E> {
+        KBUniversalAccumulatorState {
+            accumulator: self.clone(),
+            members: mem_state.elements().cloned().collect(),
+            non_members: non_mem_state.elements().cloned().collect(),
+        }
+    }
+
+    /// Restore the accumulator from an exported state and fill the databases of members and non-members, which are
+    /// expected to be empty. Checks that the accumulator values are the result of accumulating the members and
+    /// non-members of the state using the given params and secret key. If an element repeats in the state or is
+    /// already in a database, returns `VBAccumulatorError::ElementPresent` without adding anything to the databases.
+    pub fn from_state(
+        state: KBUniversalAccumulatorState<E>,
+        params_gen: impl AsRef<E::G1Affine>,
+        sk: &SecretKey<E::ScalarField>,
+        mem_state: &mut dyn State<E::ScalarField>,
+        non_mem_state: &mut dyn State<E::ScalarField>,
+    ) -> Result<Self, VBAccumulatorError> {
+        let initial = PositiveAccumulator::<E>::initialize(params_gen);
+        if initial.compute_new_post_add_batch(&state.members, sk) != *state.accumulator.mem_value()
+            || initial.compute_new_post_add_batch(&state.non_members, sk)
+                != *state.accumulator.non_mem_value()
+        {
+            return Err(VBAccumulatorError::AccumulatorStateMismatch);
+        }
+        // Check all elements before adding any so that the databases aren't left partially filled on error
+        let mut seen = BTreeSet::new();
+        for e in state.members.iter().chain(state.non_members.iter()) {
+            if !seen.insert(e) || mem_state.has(e) || non_mem_state.has(e) {
+                return Err(VBAccumulatorError::ElementPresent);
+            }
+        }
+        for member in state.members {
+            mem_state.add(member);
+        }
+        for non_member in state.non_members {
+            non_mem_state.add(non_member);
+        }
+        Ok(state.accumulator)
+    }
 }
 
 #[cfg(test)]
@@ -318,6 +421,62 @@ pub mod tests {
         )
     }
 
+    #[test]
+    fn domain_membership() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (_, keypair, accumulator, domain, mut mem_state, mut non_mem_state) =
+            setup_kb_universal_accum(&mut rng, 10);
+
+        assert!(accumulator.is_in_domain(&domain[0], &mem_state, &non_mem_state));
+        assert!(!accumulator.is_in_domain(&Fr::rand(&mut rng), &mem_state, &non_mem_state));
+
+        // Elements stay in the domain when added to the accumulator
+        let accumulator = accumulator
+            .add(
+                domain[0],
+                &keypair.secret_key,
+                &mut mem_state,
+                &mut non_mem_state,
+            )
+            .unwrap();
+        for e in &domain {
+            assert!(accumulator.is_in_domain(e, &mem_state, &non_mem_state));
+        }
+    }
+
+    #[test]
+    fn initialize_from_iter() {
+        // Initializing with the domain as an iterator gives the same accumulator as with the domain as a vector
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (params, keypair, accumulator, domain, _, non_mem_state) =
+            setup_kb_universal_accum(&mut rng, 1000);
+
+        for chunk_size in [0, 1, 64, 1000, 2000] {
+            let mut non_mem_state_from_iter = InMemoryState::new();
+            let accumulator_from_iter = KBUniversalAccumulator::initialize_from_iter(
+                &params,
+                &keypair.secret_key,
+                domain.clone().into_iter(),
+                chunk_size,
+                &mut non_mem_state_from_iter,
+            )
+            .unwrap();
+            assert_eq!(accumulator_from_iter, accumulator);
+            assert_eq!(non_mem_state_from_iter.db, non_mem_state.db);
+        }
+
+        // Duplicate elements across chunks are rejected like with the vector
+        let mut non_mem_state_from_iter = InMemoryState::new();
+        assert!(KBUniversalAccumulator::<Bls12_381>::initialize_from_iter(
+            &params,
+            &keypair.secret_key,
+            domain.iter().cloned().chain([domain[0]]),
+            64,
+            &mut non_mem_state_from_iter,
+        )
+        .is_err());
+    }
+
     #[test]
     fn membership_non_membership() {
         // Test to check membership and non-membership in accumulator
@@ -382,6 +541,116 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn export_and_restore_state() {
+        let max = 50;
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (params, keypair, mut accumulator, domain, mut mem_state, mut non_mem_state) =
+            setup_kb_universal_accum(&mut rng, max);
+
+        for elem in domain.iter().take(10) {
+            accumulator = accumulator
+                .add(
+                    *elem,
+                    &keypair.secret_key,
+                    &mut mem_state,
+                    &mut non_mem_state,
+                )
+                .unwrap();
+        }
+
+        let state = accumulator.export_state(&mem_state, &non_mem_state);
+        assert_eq!(state.members.len(), 10);
+        assert_eq!(state.non_members.len(), max - 10);
+
+        let mut bytes = vec![];
+        state.serialize_compressed(&mut bytes).unwrap();
+        let state_deser =
+            KBUniversalAccumulatorState::<Bls12_381>::deserialize_compressed(&bytes[..]).unwrap();
+        assert_eq!(state, state_deser);
+
+        let mut restored_mem_state = InMemoryState::new();
+        let mut restored_non_mem_state = InMemoryState::new();
+        let mut restored = KBUniversalAccumulator::from_state(
+            state_deser,
+            &params,
+            &keypair.secret_key,
+            &mut restored_mem_state,
+            &mut restored_non_mem_state,
+        )
+        .unwrap();
+        assert_eq!(restored, accumulator);
+        assert_eq!(restored_mem_state.db, mem_state.db);
+        assert_eq!(restored_non_mem_state.db, non_mem_state.db);
+
+        // Continue adding to the restored accumulator
+        for elem in domain.iter().skip(10).take(10) {
+            restored = restored
+                .add(
+                    *elem,
+                    &keypair.secret_key,
+                    &mut restored_mem_state,
+                    &mut restored_non_mem_state,
+                )
+                .unwrap();
+            let wit = restored
+                .get_membership_witness(elem, &keypair.secret_key, &restored_mem_state)
+                .unwrap();
+            assert!(restored.verify_membership(elem, &wit, &keypair.public_key, &params));
+        }
+        let wit = restored
+            .get_non_membership_witness(&domain[20], &keypair.secret_key, &restored_non_mem_state)
+            .unwrap();
+        assert!(restored.verify_non_membership(&domain[20], &wit, &keypair.public_key, &params));
+
+        // A state whose domain doesn't match the accumulator values isn't restored
+        let mut state = accumulator.export_state(&mem_state, &non_mem_state);
+        let moved = state.non_members.pop().unwrap();
+        state.members.push(moved);
+        assert!(matches!(
+            KBUniversalAccumulator::from_state(
+                state,
+                &params,
+                &keypair.secret_key,
+                &mut InMemoryState::new(),
+                &mut InMemoryState::new(),
+            ),
+            Err(VBAccumulatorError::AccumulatorStateMismatch)
+        ));
+
+        // Restoring into a database that already has an element fails without adding any element
+        let state = accumulator.export_state(&mem_state, &non_mem_state);
+        let mut partial_mem_state = InMemoryState::new();
+        let mut partial_non_mem_state = InMemoryState::new();
+        partial_non_mem_state.add(*state.non_members.last().unwrap());
+        assert!(matches!(
+            KBUniversalAccumulator::from_state(
+                state,
+                &params,
+                &keypair.secret_key,
+                &mut partial_mem_state,
+                &mut partial_non_mem_state,
+            ),
+            Err(VBAccumulatorError::ElementPresent)
+        ));
+        assert_eq!(partial_mem_state.db.len(), 0);
+        assert_eq!(partial_non_mem_state.db.len(), 1);
+
+        // Wrong secret key
+        let other_keypair = Keypair::<Bls12_381>::generate_using_rng(&mut rng, &params);
+        assert!(matches!(
+            KBUniversalAccumulator::from_state(
+                accumulator.export_state(&mem_state, &non_mem_state),
+                &params,
+                &other_keypair.secret_key,
+                &mut InMemoryState::new(),
+                &mut InMemoryState::new(),
+            ),
+            Err(VBAccumulatorError::AccumulatorStateMismatch)
+        ));
+    }
+
     #[test]
     fn batch_update_and_membership() {
         // Tests batch updates and batch membership witness generation
@@ -6,4 +6,4 @@ pub mod proofs_cdh;
 pub mod proofs_keyed_verification;
 pub mod witness;
 
-pub use accumulator::KBUniversalAccumulator;
+pub use accumulator::{KBUniversalAccumulator, KBUniversalAccumulatorState};
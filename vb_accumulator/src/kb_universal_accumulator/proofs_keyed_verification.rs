@@ -6,19 +6,29 @@ use crate::{
     kb_universal_accumulator::witness::{
         KBUniversalAccumulatorMembershipWitness, KBUniversalAccumulatorNonMembershipWitness,
     },
-    prelude::SecretKey,
+    prelude::{PreparedPublicKey, PreparedSetupParams, SecretKey},
     proofs_keyed_verification::{
         DelegatedMembershipProof, MembershipProof, MembershipProofProtocol,
     },
 };
-use ark_ec::AffineRepr;
+use ark_ec::{pairing::Pairing, AffineRepr, VariableBaseMSM};
 
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{io::Write, rand::RngCore, vec::Vec};
+use ark_std::{
+    collections::VecDeque, io::Write, iter, ops::Neg, rand::RngCore, vec::Vec, UniformRand, Zero,
+};
+use dock_crypto_utils::msm::WindowTable;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
+/// Domain separation tag written to the transcript by the membership proof before its challenge contribution
+pub const MEMBERSHIP_PROOF_DST: &[u8] = b"KBUniMem";
+/// Domain separation tag written to the transcript by the non-membership proof before its challenge contribution
+pub const NON_MEMBERSHIP_PROOF_DST: &[u8] = b"KBUniNonMem";
+/// Domain separation tag written to the transcript by the multi-membership proof before its challenge contribution
+pub const MULTI_MEMBERSHIP_PROOF_DST: &[u8] = b"KBUniMultiMem";
+
 #[derive(
     Clone, PartialEq, Eq, Debug, Zeroize, ZeroizeOnDrop, CanonicalSerialize, CanonicalDeserialize,
 )]
@@ -61,6 +71,34 @@ pub struct KBUniversalAccumulatorDelegatedNonMembershipProof<G: AffineRepr>(
     pub DelegatedMembershipProof<G>,
 );
 
+/// Membership proof which can be verified by anyone knowing the accumulator manager's pairing-based public key rather
+/// than only by someone knowing the secret key. Created from a keyed-verification proof using `to_public_proof`.
+#[derive(
+    Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct KBUniversalAccumulatorPublicMembershipProof<E: Pairing>(
+    pub MembershipProof<E::G1Affine>,
+);
+
+/// Protocol for proving membership of several elements in the same accumulator with a single proof. This is a batch
+/// of the membership protocol, one for each element, where each witness is randomized with its own `l_i`. Using a
+/// shared randomizer would make the proof smaller but `C_bar_i + C'_i * y_i = V * l` would then hold for each `i`, so
+/// anyone could check a guess for any 2 elements together, without knowing the secret key. The protocols share the
+/// accumulator's window table and the accumulator is written to the transcript only once.
+#[derive(
+    Clone, PartialEq, Eq, Debug, Zeroize, ZeroizeOnDrop, CanonicalSerialize, CanonicalDeserialize,
+)]
+pub struct KBUniversalAccumulatorMultiMembershipProofProtocol<G: AffineRepr>(
+    pub Vec<MembershipProofProtocol<G>>,
+);
+
+#[derive(
+    Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct KBUniversalAccumulatorMultiMembershipProof<G: AffineRepr>(pub Vec<MembershipProof<G>>);
+
 impl<G: AffineRepr> KBUniversalAccumulatorMembershipProofProtocol<G> {
     /// Initialize a membership proof protocol.
     pub fn init<R: RngCore>(
@@ -79,14 +117,43 @@ impl<G: AffineRepr> KBUniversalAccumulatorMembershipProofProtocol<G> {
         ))
     }
 
+    /// Same as `init` but takes a precomputed window table for the accumulator. Useful when creating
+    /// many proofs against the same accumulator value.
+    pub fn init_with_table<R: RngCore>(
+        rng: &mut R,
+        element: G::ScalarField,
+        element_blinding: Option<G::ScalarField>,
+        witness: &KBUniversalAccumulatorMembershipWitness<G>,
+        accumulator_table: &WindowTable<G::Group>,
+    ) -> Self {
+        Self(MembershipProofProtocol::init_with_table(
+            rng,
+            element,
+            element_blinding,
+            &witness.0,
+            accumulator_table,
+        ))
+    }
+
     pub fn challenge_contribution<W: Write>(
         &self,
         accumulator_value: &G,
-        writer: W,
+        mut writer: W,
     ) -> Result<(), VBAccumulatorError> {
+        MEMBERSHIP_PROOF_DST.serialize_compressed(&mut writer)?;
         self.0.challenge_contribution(accumulator_value, writer)
     }
 
+    /// The commitment of the Schnorr protocol for the element. See `MembershipProofProtocol::schnorr_commitment`
+    pub fn schnorr_commitment(&self) -> &G {
+        self.0.schnorr_commitment()
+    }
+
+    /// Abandon the protocol without creating a proof. See `MembershipProofProtocol::abort`
+    pub fn abort(mut self) {
+        self.zeroize();
+    }
+
     pub fn gen_proof(
         self,
         challenge: &G::ScalarField,
@@ -110,8 +177,9 @@ impl<G: AffineRepr> KBUniversalAccumulatorMembershipProof<G> {
     pub fn challenge_contribution<W: Write>(
         &self,
         accumulator_value: &G,
-        writer: W,
+        mut writer: W,
     ) -> Result<(), VBAccumulatorError> {
+        MEMBERSHIP_PROOF_DST.serialize_compressed(&mut writer)?;
         self.0.challenge_contribution(accumulator_value, writer)
     }
 
@@ -127,9 +195,138 @@ impl<G: AffineRepr> KBUniversalAccumulatorMembershipProof<G> {
         KBUniversalAccumulatorDelegatedMembershipProof(self.0.to_delegated_proof())
     }
 
+    /// Convert to a proof that can be verified with the manager's public key using a pairing check rather than the
+    /// secret key. This is possible only when the accumulator is over the first group of a pairing-friendly curve and
+    /// the manager has published a public key created from the same secret key as used for the accumulator, i.e.
+    /// `public_key = P_tilde * secret_key` where `P_tilde` is from the pairing-based `SetupParams`. Publishing such a key
+    /// makes every proof publicly verifiable so the manager shouldn't do it if it wants verification to stay keyed.
+    pub fn to_public_proof<E: Pairing<G1Affine = G>>(
+        &self,
+    ) -> KBUniversalAccumulatorPublicMembershipProof<E> {
+        KBUniversalAccumulatorPublicMembershipProof(self.0.clone())
+    }
+
     pub fn get_schnorr_response_for_element(&self) -> &G::ScalarField {
         self.0.get_schnorr_response_for_element()
     }
+
+    /// Verify several proofs, each with its own accumulator and challenge, using a single multi-scalar
+    /// multiplication. Each proof requires `C_bar - C_prime * alpha = 0` and
+    /// `V * response1 - C_prime * response2 - C_bar * challenge - t = 0` where `V` is the accumulator, so all these
+    /// are combined with random weights and checked together. A failure doesn't tell which proof is invalid, use
+    /// `verify_stream_batched` for that.
+    pub fn verify_batch<R: RngCore>(
+        rng: &mut R,
+        proofs: &[Self],
+        accumulators: &[G],
+        secret_key: &SecretKey<G::ScalarField>,
+        challenges: &[G::ScalarField],
+    ) -> Result<(), VBAccumulatorError> {
+        if proofs.len() != accumulators.len() || proofs.len() != challenges.len() {
+            return Err(
+                VBAccumulatorError::UnequalNoOfProofsAccumulatorsAndChallenges(
+                    proofs.len(),
+                    accumulators.len(),
+                    challenges.len(),
+                ),
+            );
+        }
+        let mut bases = Vec::with_capacity(4 * proofs.len());
+        let mut scalars = Vec::with_capacity(4 * proofs.len());
+        for ((proof, accumulator), challenge) in proofs
+            .iter()
+            .zip(accumulators.iter())
+            .zip(challenges.iter())
+        {
+            // Weights for the 2 equations of this proof
+            let r = G::ScalarField::rand(rng);
+            let s = G::ScalarField::rand(rng);
+            bases.push(proof.0.C_bar);
+            scalars.push(r - s * challenge);
+            bases.push(proof.0.C_prime);
+            scalars.push(-(r * secret_key.0 + s * proof.0.sc.response2));
+            bases.push(*accumulator);
+            scalars.push(s * proof.0.sc.response1);
+            bases.push(proof.0.sc.t);
+            scalars.push(-s);
+        }
+        if G::Group::msm_unchecked(&bases, &scalars).is_zero() {
+            Ok(())
+        } else {
+            Err(VBAccumulatorError::IncorrectRandomizedWitness)
+        }
+    }
+
+    /// Lazily verify a stream of proofs where each item is the proof, the accumulator and the challenge. A proof
+    /// is verified only when the corresponding result is requested from the returned iterator so the results are
+    /// yielded in the same order as the proofs.
+    pub fn verify_stream<'a, I>(
+        proofs: I,
+        secret_key: &'a SecretKey<G::ScalarField>,
+    ) -> impl Iterator<Item = Result<(), VBAccumulatorError>> + 'a
+    where
+        I: Iterator<Item = (Self, G, G::ScalarField)> + 'a,
+    {
+        proofs.map(move |(proof, accumulator, challenge)| {
+            proof.verify(accumulator, secret_key, &challenge)
+        })
+    }
+
+    /// Same as `verify_stream` but takes up to `window` proofs from the stream at a time and does the check
+    /// requiring the secret key for all of them together using a random linear combination. If that check
+    /// fails, each proof in the window is checked individually to find the invalid ones. The results are
+    /// still yielded in the same order as the proofs.
+    pub fn verify_stream_batched<'a, R: RngCore, I>(
+        rng: &'a mut R,
+        mut proofs: I,
+        secret_key: &'a SecretKey<G::ScalarField>,
+        window: usize,
+    ) -> impl Iterator<Item = Result<(), VBAccumulatorError>> + 'a
+    where
+        I: Iterator<Item = (Self, G, G::ScalarField)> + 'a,
+    {
+        let window = window.max(1);
+        let mut results = VecDeque::with_capacity(window);
+        iter::from_fn(move || {
+            if results.is_empty() {
+                let batch = proofs.by_ref().take(window).collect::<Vec<_>>();
+                if batch.is_empty() {
+                    return None;
+                }
+                results.extend(Self::verify_window(rng, batch, secret_key));
+            }
+            results.pop_front()
+        })
+    }
+
+    fn verify_window<R: RngCore>(
+        rng: &mut R,
+        batch: Vec<(Self, G, G::ScalarField)>,
+        secret_key: &SecretKey<G::ScalarField>,
+    ) -> Vec<Result<(), VBAccumulatorError>> {
+        // Check `sum(r_i * C_bar_i) - sum(r_i * C_prime_i) * alpha = 0` for random `r_i` rather than
+        // `C_bar_i = C_prime_i * alpha` for each proof
+        let mut bases = Vec::with_capacity(2 * batch.len());
+        let mut scalars = Vec::with_capacity(2 * batch.len());
+        for (proof, _, _) in &batch {
+            let r = G::ScalarField::rand(rng);
+            bases.push(proof.0.C_bar);
+            scalars.push(r);
+            bases.push(proof.0.C_prime);
+            scalars.push(-(r * secret_key.0));
+        }
+        let key_check_passed = G::Group::msm_unchecked(&bases, &scalars).is_zero();
+        batch
+            .into_iter()
+            .map(|(proof, accumulator, challenge)| {
+                if key_check_passed {
+                    proof.verify_schnorr_proof(accumulator, &challenge)
+                } else {
+                    proof.verify(accumulator, secret_key, &challenge)
+                }
+            })
+            .collect()
+    }
 }
 
 impl<G: AffineRepr> KBUniversalAccumulatorNonMembershipProofProtocol<G> {
@@ -153,11 +350,17 @@ impl<G: AffineRepr> KBUniversalAccumulatorNonMembershipProofProtocol<G> {
     pub fn challenge_contribution<W: Write>(
         &self,
         accumulator_value: &G,
-        writer: W,
+        mut writer: W,
     ) -> Result<(), VBAccumulatorError> {
+        NON_MEMBERSHIP_PROOF_DST.serialize_compressed(&mut writer)?;
         self.0.challenge_contribution(accumulator_value, writer)
     }
 
+    /// Abandon the protocol without creating a proof. See `MembershipProofProtocol::abort`
+    pub fn abort(mut self) {
+        self.zeroize();
+    }
+
     pub fn gen_proof(
         self,
         challenge: &G::ScalarField,
@@ -181,8 +384,9 @@ impl<G: AffineRepr> KBUniversalAccumulatorNonMembershipProof<G> {
     pub fn challenge_contribution<W: Write>(
         &self,
         accumulator_value: &G,
-        writer: W,
+        mut writer: W,
     ) -> Result<(), VBAccumulatorError> {
+        NON_MEMBERSHIP_PROOF_DST.serialize_compressed(&mut writer)?;
         self.0.challenge_contribution(accumulator_value, writer)
     }
 
@@ -203,16 +407,206 @@ impl<G: AffineRepr> KBUniversalAccumulatorNonMembershipProof<G> {
     }
 }
 
+impl<E: Pairing> KBUniversalAccumulatorPublicMembershipProof<E> {
+    /// Verify the proof by checking `e(C_bar, P_tilde) = e(C_prime, public_key)` in place of `C_bar = C_prime * secret_key`
+    /// and then the Schnorr proof. `pk` is the accumulator manager's public key.
+    pub fn verify(
+        &self,
+        accumulator: E::G1Affine,
+        challenge: &E::ScalarField,
+        pk: impl Into<PreparedPublicKey<E>>,
+        params: impl Into<PreparedSetupParams<E>>,
+    ) -> Result<(), VBAccumulatorError> {
+        if !E::multi_pairing(
+            [self.0.C_bar, self.0.C_prime.into_group().neg().into()],
+            [params.into().P_tilde, pk.into().0],
+        )
+        .is_zero()
+        {
+            return Err(VBAccumulatorError::IncorrectRandomizedWitness);
+        }
+        self.0.verify_schnorr_proof(accumulator, challenge)
+    }
+
+    pub fn challenge_contribution<W: Write>(
+        &self,
+        accumulator_value: &E::G1Affine,
+        mut writer: W,
+    ) -> Result<(), VBAccumulatorError> {
+        MEMBERSHIP_PROOF_DST.serialize_compressed(&mut writer)?;
+        self.0.challenge_contribution(accumulator_value, writer)
+    }
+}
+
+impl<G: AffineRepr> KBUniversalAccumulatorMultiMembershipProofProtocol<G> {
+    /// Initialize a protocol for proving membership of `elements` where `witnesses[i]` is the membership witness of
+    /// `elements[i]`. If `element_blindings[i]` is `Some`, it's used as the blinding for `elements[i]`, which is needed
+    /// when proving equality of the element with a witness of another protocol.
+    pub fn init<R: RngCore>(
+        rng: &mut R,
+        elements: Vec<G::ScalarField>,
+        element_blindings: Vec<Option<G::ScalarField>>,
+        witnesses: &[KBUniversalAccumulatorMembershipWitness<G>],
+        accumulator: G,
+    ) -> Result<Self, VBAccumulatorError> {
+        if elements.is_empty() {
+            return Err(VBAccumulatorError::NoElementsForMultiMembershipProof);
+        }
+        if elements.len() != witnesses.len() || elements.len() != element_blindings.len() {
+            return Err(VBAccumulatorError::NeedSameNoOfElementsAndWitnesses);
+        }
+        let accumulator_table = WindowTable::new(2, accumulator.into_group());
+        let protocols = elements
+            .into_iter()
+            .zip(element_blindings)
+            .zip(witnesses.iter())
+            .map(|((element, blinding), witness)| {
+                MembershipProofProtocol::init_with_table(
+                    rng,
+                    element,
+                    blinding,
+                    &witness.0,
+                    &accumulator_table,
+                )
+            })
+            .collect();
+        Ok(Self(protocols))
+    }
+
+    pub fn challenge_contribution<W: Write>(
+        &self,
+        accumulator_value: &G,
+        writer: W,
+    ) -> Result<(), VBAccumulatorError> {
+        KBUniversalAccumulatorMultiMembershipProof::compute_challenge_contribution(
+            accumulator_value,
+            self.0.iter().map(|p| (&p.C_prime, &p.C_bar, &p.sc.t)),
+            writer,
+        )
+    }
+
+    /// Abandon the protocol without creating a proof. See `MembershipProofProtocol::abort`
+    pub fn abort(mut self) {
+        self.zeroize();
+    }
+
+    pub fn gen_proof(
+        mut self,
+        challenge: &G::ScalarField,
+    ) -> Result<KBUniversalAccumulatorMultiMembershipProof<G>, VBAccumulatorError> {
+        let proofs = core::mem::take(&mut self.0)
+            .into_iter()
+            .map(|p| p.gen_proof(challenge))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(KBUniversalAccumulatorMultiMembershipProof(proofs))
+    }
+}
+
+impl<G: AffineRepr> KBUniversalAccumulatorMultiMembershipProof<G> {
+    /// Verify membership of all the elements, i.e. each membership proof in the batch
+    pub fn verify(
+        &self,
+        accumulator: G,
+        secret_key: &SecretKey<G::ScalarField>,
+        challenge: &G::ScalarField,
+    ) -> Result<(), VBAccumulatorError> {
+        if self.0.is_empty() {
+            return Err(VBAccumulatorError::NoElementsForMultiMembershipProof);
+        }
+        for proof in &self.0 {
+            proof.verify(accumulator, secret_key, challenge)?;
+        }
+        Ok(())
+    }
+
+    /// Verify the Schnorr proof of each membership proof in the batch
+    pub fn verify_schnorr_proof(
+        &self,
+        accumulator: G,
+        challenge: &G::ScalarField,
+    ) -> Result<(), VBAccumulatorError> {
+        if self.0.is_empty() {
+            return Err(VBAccumulatorError::NoElementsForMultiMembershipProof);
+        }
+        for proof in &self.0 {
+            proof.verify_schnorr_proof(accumulator, challenge)?;
+        }
+        Ok(())
+    }
+
+    pub fn challenge_contribution<W: Write>(
+        &self,
+        accumulator_value: &G,
+        writer: W,
+    ) -> Result<(), VBAccumulatorError> {
+        Self::compute_challenge_contribution(
+            accumulator_value,
+            self.0.iter().map(|p| (&p.C_prime, &p.C_bar, &p.sc.t)),
+            writer,
+        )
+    }
+
+    /// Response for the element at index `idx`, to check equality with a witness of another protocol
+    pub fn get_schnorr_response_for_element(&self, idx: usize) -> Option<&G::ScalarField> {
+        self.0.get(idx).map(|p| p.get_schnorr_response_for_element())
+    }
+
+    fn compute_challenge_contribution<'a, W: Write>(
+        accumulator_value: &G,
+        randomized_witnesses: impl ExactSizeIterator<Item = (&'a G, &'a G, &'a G)>,
+        mut writer: W,
+    ) -> Result<(), VBAccumulatorError>
+    where
+        G: 'a,
+    {
+        MULTI_MEMBERSHIP_PROOF_DST.serialize_compressed(&mut writer)?;
+        accumulator_value.serialize_compressed(&mut writer)?;
+        randomized_witnesses.len().serialize_compressed(&mut writer)?;
+        for (C_prime, C_bar, t) in randomized_witnesses {
+            C_prime.serialize_compressed(&mut writer)?;
+            C_bar.serialize_compressed(&mut writer)?;
+            t.serialize_compressed(&mut writer)?;
+        }
+        Ok(())
+    }
+}
+
 impl<G: AffineRepr> KBUniversalAccumulatorDelegatedMembershipProof<G> {
     pub fn verify(&self, secret_key: &SecretKey<G::ScalarField>) -> Result<(), VBAccumulatorError> {
         self.0.verify(secret_key)
     }
+
+    /// Verify several delegated membership proofs together such that the multiplication with the secret key is done only once.
+    pub fn verify_batch<R: RngCore>(
+        rng: &mut R,
+        proofs: &[Self],
+        secret_key: &SecretKey<G::ScalarField>,
+    ) -> Result<(), VBAccumulatorError> {
+        DelegatedMembershipProof::verify_batch_from_iter(
+            rng,
+            proofs.iter().map(|p| &p.0),
+            secret_key,
+        )
+    }
 }
 
 impl<G: AffineRepr> KBUniversalAccumulatorDelegatedNonMembershipProof<G> {
     pub fn verify(&self, secret_key: &SecretKey<G::ScalarField>) -> Result<(), VBAccumulatorError> {
         self.0.verify(secret_key)
     }
+
+    /// Verify several delegated non-membership proofs together such that the multiplication with the secret key is done only once.
+    pub fn verify_batch<R: RngCore>(
+        rng: &mut R,
+        proofs: &[Self],
+        secret_key: &SecretKey<G::ScalarField>,
+    ) -> Result<(), VBAccumulatorError> {
+        DelegatedMembershipProof::verify_batch_from_iter(
+            rng,
+            proofs.iter().map(|p| &p.0),
+            secret_key,
+        )
+    }
 }
 
 #[cfg(test)]
@@ -222,20 +616,26 @@ mod tests {
         kb_universal_accumulator::accumulator::KBUniversalAccumulator,
         persistence::test::InMemoryState,
         setup_keyed_verification::{PublicKey, SetupParams},
+        test_serialization,
     };
     use ark_bls12_381::{Bls12_381, Fr, G1Affine};
+    use ark_ec::CurveGroup;
     use ark_std::{
         rand::{rngs::StdRng, SeedableRng},
         UniformRand,
     };
     use blake2::Blake2b512;
-    use schnorr_pok::compute_random_oracle_challenge;
-    use std::time::{Duration, Instant};
+    use schnorr_pok::{
+        compute_random_oracle_challenge,
+        discrete_log::{PokTwoDiscreteLogs, PokTwoDiscreteLogsProtocol},
+    };
+    use std::{
+        cell::Cell,
+        time::{Duration, Instant},
+    };
 
-    pub fn setup_uni_accum(
-        rng: &mut StdRng,
-        max: u64,
-    ) -> (
+    /// Params, keys, accumulator, its domain and the states for members and non-members
+    pub type UniAccumSetup = (
         SetupParams<G1Affine>,
         SecretKey<Fr>,
         PublicKey<G1Affine>,
@@ -243,7 +643,9 @@ mod tests {
         Vec<Fr>,
         InMemoryState<Fr>,
         InMemoryState<Fr>,
-    ) {
+    );
+
+    pub fn setup_uni_accum(rng: &mut StdRng, max: u64) -> UniAccumSetup {
         let params = SetupParams::<G1Affine>::new::<Blake2b512>(b"test");
         let seed = [0, 1, 2, 10, 11];
         let secret_key = SecretKey::generate_using_seed::<Blake2b512>(&seed);
@@ -418,4 +820,904 @@ mod tests {
             count, non_mem_proof_verif_duration
         );
     }
+
+    #[test]
+    fn verify_batch_of_membership_proofs() {
+        let max = 200;
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (_, secret_key, _, mut accumulator, domain, mut mem_state, mut non_mem_state) =
+            setup_uni_accum(&mut rng, max);
+
+        // Proofs are created against 2 different accumulator values
+        let count = 100;
+        let mut proofs = vec![];
+        let mut accums = vec![];
+        let mut challenges = vec![];
+        for members in domain[0..count].chunks(count / 2) {
+            accumulator = accumulator
+                .add_batch(
+                    members.to_vec(),
+                    &secret_key,
+                    &mut mem_state,
+                    &mut non_mem_state,
+                )
+                .unwrap();
+            let witnesses = accumulator
+                .get_membership_witnesses_for_batch(members, &secret_key, &mem_state)
+                .unwrap();
+            for (member, witness) in members.iter().zip(witnesses.iter()) {
+                let protocol = KBUniversalAccumulatorMembershipProofProtocol::init(
+                    &mut rng,
+                    *member,
+                    None,
+                    witness,
+                    *accumulator.mem_value(),
+                );
+                let mut chal_bytes = vec![];
+                protocol
+                    .challenge_contribution(accumulator.mem_value(), &mut chal_bytes)
+                    .unwrap();
+                let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+                proofs.push(protocol.gen_proof(&challenge).unwrap());
+                accums.push(*accumulator.mem_value());
+                challenges.push(challenge);
+            }
+        }
+        assert_ne!(accums[0], accums[count - 1]);
+
+        let start = Instant::now();
+        for i in 0..count {
+            proofs[i]
+                .verify(accums[i], &secret_key, &challenges[i])
+                .unwrap();
+        }
+        println!(
+            "Time to verify {} membership proofs individually: {:?}",
+            count,
+            start.elapsed()
+        );
+
+        let start = Instant::now();
+        KBUniversalAccumulatorMembershipProof::verify_batch(
+            &mut rng,
+            &proofs,
+            &accums,
+            &secret_key,
+            &challenges,
+        )
+        .unwrap();
+        println!(
+            "Time to verify {} membership proofs as a batch: {:?}",
+            count,
+            start.elapsed()
+        );
+
+        // Altering any part of a single proof or its inputs fails the batch
+        for i in [0, 37, count - 1] {
+            let mut altered = proofs.clone();
+            altered[i].0.C_bar = G1Affine::rand(&mut rng);
+            assert!(KBUniversalAccumulatorMembershipProof::verify_batch(
+                &mut rng,
+                &altered,
+                &accums,
+                &secret_key,
+                &challenges
+            )
+            .is_err());
+
+            let mut altered = proofs.clone();
+            altered[i].0.C_prime = G1Affine::rand(&mut rng);
+            assert!(KBUniversalAccumulatorMembershipProof::verify_batch(
+                &mut rng,
+                &altered,
+                &accums,
+                &secret_key,
+                &challenges
+            )
+            .is_err());
+
+            let mut altered = proofs.clone();
+            altered[i].0.sc.response1 += Fr::from(1u64);
+            assert!(KBUniversalAccumulatorMembershipProof::verify_batch(
+                &mut rng,
+                &altered,
+                &accums,
+                &secret_key,
+                &challenges
+            )
+            .is_err());
+
+            let mut altered = challenges.clone();
+            altered[i] = Fr::rand(&mut rng);
+            assert!(KBUniversalAccumulatorMembershipProof::verify_batch(
+                &mut rng,
+                &proofs,
+                &accums,
+                &secret_key,
+                &altered
+            )
+            .is_err());
+        }
+
+        // Proof checked against the other accumulator
+        let mut altered = accums.clone();
+        altered.swap(0, count - 1);
+        assert!(KBUniversalAccumulatorMembershipProof::verify_batch(
+            &mut rng,
+            &proofs,
+            &altered,
+            &secret_key,
+            &challenges
+        )
+        .is_err());
+
+        // Wrong secret key
+        let other_key = SecretKey::generate_using_seed::<Blake2b512>(&[1, 2, 3]);
+        assert!(KBUniversalAccumulatorMembershipProof::verify_batch(
+            &mut rng,
+            &proofs,
+            &accums,
+            &other_key,
+            &challenges
+        )
+        .is_err());
+
+        assert!(matches!(
+            KBUniversalAccumulatorMembershipProof::verify_batch(
+                &mut rng,
+                &proofs,
+                &accums[1..],
+                &secret_key,
+                &challenges
+            ),
+            Err(VBAccumulatorError::UnequalNoOfProofsAccumulatorsAndChallenges(100, 99, 100))
+        ));
+    }
+
+    #[test]
+    fn membership_proofs_with_precomputed_accumulator_table() {
+        let max = 100;
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (_, secret_key, _, mut accumulator, domain, mut mem_state, mut non_mem_state) =
+            setup_uni_accum(&mut rng, max);
+
+        let count = 50;
+        let members = domain[0..count].to_vec();
+        accumulator = accumulator
+            .add_batch(
+                members.clone(),
+                &secret_key,
+                &mut mem_state,
+                &mut non_mem_state,
+            )
+            .unwrap();
+        let witnesses = accumulator
+            .get_membership_witnesses_for_batch(&members, &secret_key, &mem_state)
+            .unwrap();
+        let accum = *accumulator.mem_value();
+
+        type InitProtocol<'a> = dyn Fn(
+                &mut StdRng,
+                Fr,
+                &KBUniversalAccumulatorMembershipWitness<G1Affine>,
+            ) -> KBUniversalAccumulatorMembershipProofProtocol<G1Affine>
+            + 'a;
+
+        let gen_proofs = |rng: &mut StdRng, init: &InitProtocol<'_>| {
+            let mut proofs = vec![];
+            let mut challenges = vec![];
+            for (member, witness) in members.iter().zip(witnesses.iter()) {
+                let protocol = init(rng, *member, witness);
+                let mut chal_bytes = vec![];
+                protocol
+                    .challenge_contribution(&accum, &mut chal_bytes)
+                    .unwrap();
+                let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+                proofs.push(protocol.gen_proof(&challenge).unwrap());
+                challenges.push(challenge);
+            }
+            (proofs, challenges)
+        };
+
+        // Both paths use the same randomness so should create the same proofs
+        let mut rng_1 = StdRng::seed_from_u64(1u64);
+        let start = Instant::now();
+        let (proofs_1, challenges_1) = gen_proofs(&mut rng_1, &|rng, member, witness| {
+            KBUniversalAccumulatorMembershipProofProtocol::init(rng, member, None, witness, accum)
+        });
+        println!(
+            "Time to create {} membership proofs without a precomputed table: {:?}",
+            count,
+            start.elapsed()
+        );
+
+        let mut rng_2 = StdRng::seed_from_u64(1u64);
+        let start = Instant::now();
+        let table = WindowTable::new(count, accum.into_group());
+        let (proofs_2, challenges_2) = gen_proofs(&mut rng_2, &|rng, member, witness| {
+            KBUniversalAccumulatorMembershipProofProtocol::init_with_table(
+                rng, member, None, witness, &table,
+            )
+        });
+        println!(
+            "Time to create {} membership proofs with a precomputed table: {:?}",
+            count,
+            start.elapsed()
+        );
+
+        assert_eq!(proofs_1, proofs_2);
+        assert_eq!(challenges_1, challenges_2);
+        for i in 0..count {
+            proofs_1[i]
+                .verify(accum, &secret_key, &challenges_1[i])
+                .unwrap();
+            proofs_2[i]
+                .verify(accum, &secret_key, &challenges_2[i])
+                .unwrap();
+        }
+        KBUniversalAccumulatorMembershipProof::verify_batch(
+            &mut rng,
+            &proofs_2,
+            &vec![accum; count],
+            &secret_key,
+            &challenges_2,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn verify_batch_of_delegated_proofs() {
+        let max = 100;
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (_, secret_key, _, mut accumulator, domain, mut mem_state, mut non_mem_state) =
+            setup_uni_accum(&mut rng, max);
+
+        let count = 20;
+        let members = domain[0..count].to_vec();
+        let non_members = domain[count..2 * count].to_vec();
+        accumulator = accumulator
+            .add_batch(
+                members.clone(),
+                &secret_key,
+                &mut mem_state,
+                &mut non_mem_state,
+            )
+            .unwrap();
+        let mem_witnesses = accumulator
+            .get_membership_witnesses_for_batch(&members, &secret_key, &mem_state)
+            .unwrap();
+        let non_mem_witnesses = accumulator
+            .get_non_membership_witnesses_for_batch(&non_members, &secret_key, &non_mem_state)
+            .unwrap();
+
+        let mut mem_proofs = vec![];
+        let mut non_mem_proofs = vec![];
+        for i in 0..count {
+            let protocol = KBUniversalAccumulatorMembershipProofProtocol::init(
+                &mut rng,
+                members[i],
+                None,
+                &mem_witnesses[i],
+                *accumulator.mem_value(),
+            );
+            let mut chal_bytes = vec![];
+            protocol
+                .challenge_contribution(accumulator.mem_value(), &mut chal_bytes)
+                .unwrap();
+            let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+            mem_proofs.push(protocol.gen_proof(&challenge).unwrap().to_delegated_proof());
+
+            let protocol = KBUniversalAccumulatorNonMembershipProofProtocol::init(
+                &mut rng,
+                non_members[i],
+                None,
+                &non_mem_witnesses[i],
+                *accumulator.non_mem_value(),
+            );
+            let mut chal_bytes = vec![];
+            protocol
+                .challenge_contribution(accumulator.non_mem_value(), &mut chal_bytes)
+                .unwrap();
+            let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+            non_mem_proofs.push(protocol.gen_proof(&challenge).unwrap().to_delegated_proof());
+        }
+
+        let start = Instant::now();
+        for proof in &mem_proofs {
+            proof.verify(&secret_key).unwrap();
+        }
+        println!(
+            "Time to verify {} delegated membership proofs individually: {:?}",
+            count,
+            start.elapsed()
+        );
+        let start = Instant::now();
+        KBUniversalAccumulatorDelegatedMembershipProof::verify_batch(
+            &mut rng,
+            &mem_proofs,
+            &secret_key,
+        )
+        .unwrap();
+        println!(
+            "Time to verify {} delegated membership proofs as a batch: {:?}",
+            count,
+            start.elapsed()
+        );
+        KBUniversalAccumulatorDelegatedNonMembershipProof::verify_batch(
+            &mut rng,
+            &non_mem_proofs,
+            &secret_key,
+        )
+        .unwrap();
+
+        // Empty batches are valid
+        KBUniversalAccumulatorDelegatedMembershipProof::<G1Affine>::verify_batch(
+            &mut rng,
+            &[],
+            &secret_key,
+        )
+        .unwrap();
+        KBUniversalAccumulatorDelegatedNonMembershipProof::<G1Affine>::verify_batch(
+            &mut rng,
+            &[],
+            &secret_key,
+        )
+        .unwrap();
+
+        // A single corrupted proof fails the batch
+        for i in [0, count / 2, count - 1] {
+            let mut altered = mem_proofs.clone();
+            altered[i].0.C_bar = G1Affine::rand(&mut rng);
+            assert!(
+                KBUniversalAccumulatorDelegatedMembershipProof::verify_batch(
+                    &mut rng,
+                    &altered,
+                    &secret_key
+                )
+                .is_err()
+            );
+
+            let mut altered = non_mem_proofs.clone();
+            altered[i].0.C_prime = G1Affine::rand(&mut rng);
+            assert!(
+                KBUniversalAccumulatorDelegatedNonMembershipProof::verify_batch(
+                    &mut rng,
+                    &altered,
+                    &secret_key
+                )
+                .is_err()
+            );
+        }
+
+        // Wrong secret key
+        let other_key = SecretKey::generate_using_seed::<Blake2b512>(&[1, 2, 3]);
+        assert!(
+            KBUniversalAccumulatorDelegatedMembershipProof::verify_batch(
+                &mut rng,
+                &mem_proofs,
+                &other_key
+            )
+            .is_err()
+        );
+        assert!(
+            KBUniversalAccumulatorDelegatedNonMembershipProof::verify_batch(
+                &mut rng,
+                &non_mem_proofs,
+                &other_key
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn membership_proof_with_equality_to_pedersen_committed_value() {
+        let max = 20;
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (_, secret_key, _, mut accumulator, domain, mut mem_state, mut non_mem_state) =
+            setup_uni_accum(&mut rng, max);
+
+        let member = domain[0];
+        accumulator = accumulator
+            .add(member, &secret_key, &mut mem_state, &mut non_mem_state)
+            .unwrap();
+        let witness = accumulator
+            .get_membership_witness(&member, &secret_key, &mem_state)
+            .unwrap();
+        let accum = *accumulator.mem_value();
+
+        // Pedersen commitment to the member as `g * member + h * r`
+        let g = G1Affine::rand(&mut rng);
+        let h = G1Affine::rand(&mut rng);
+
+        let prove = |rng: &mut StdRng, committed: Fr| {
+            let r = Fr::rand(rng);
+            let comm = (g * committed + h * r).into_affine();
+
+            // Same blinding for the member in both protocols
+            let member_blinding = Fr::rand(rng);
+            let protocol = KBUniversalAccumulatorMembershipProofProtocol::init(
+                rng,
+                member,
+                Some(member_blinding),
+                &witness,
+                accum,
+            );
+            let comm_protocol = PokTwoDiscreteLogsProtocol::init(
+                committed,
+                member_blinding,
+                &g,
+                r,
+                Fr::rand(rng),
+                &h,
+            );
+
+            // Shared challenge over both protocols' commitments
+            let mut chal_bytes = vec![];
+            for p in [
+                accum,
+                protocol.0.C_prime,
+                protocol.0.C_bar,
+                *protocol.schnorr_commitment(),
+                comm,
+                comm_protocol.t,
+            ] {
+                p.serialize_compressed(&mut chal_bytes).unwrap();
+            }
+            let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+            (
+                comm,
+                protocol.gen_proof(&challenge).unwrap(),
+                comm_protocol.gen_proof(&challenge),
+            )
+        };
+
+        let verify = |comm: G1Affine,
+                      proof: &KBUniversalAccumulatorMembershipProof<G1Affine>,
+                      comm_proof: &PokTwoDiscreteLogs<G1Affine>| {
+            let mut chal_bytes = vec![];
+            for p in [
+                accum,
+                proof.0.C_prime,
+                proof.0.C_bar,
+                proof.0.sc.t,
+                comm,
+                comm_proof.t,
+            ] {
+                p.serialize_compressed(&mut chal_bytes).unwrap();
+            }
+            let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+            proof.verify(accum, &secret_key, &challenge).unwrap();
+            assert!(comm_proof.verify(&comm, &g, &h, &challenge));
+            proof.get_schnorr_response_for_element() == &comm_proof.response1
+        };
+
+        let (comm, proof, comm_proof) = prove(&mut rng, member);
+        assert!(verify(comm, &proof, &comm_proof));
+
+        // Committing to a different value gives both valid proofs but with different responses for the member
+        let (comm, proof, comm_proof) = prove(&mut rng, domain[1]);
+        assert!(!verify(comm, &proof, &comm_proof));
+    }
+
+    #[test]
+    fn verify_stream_of_membership_proofs() {
+        let max = 100;
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (_, secret_key, _, mut accumulator, domain, mut mem_state, mut non_mem_state) =
+            setup_uni_accum(&mut rng, max);
+
+        let count = 50;
+        let members = domain[0..count].to_vec();
+        accumulator = accumulator
+            .add_batch(
+                members.clone(),
+                &secret_key,
+                &mut mem_state,
+                &mut non_mem_state,
+            )
+            .unwrap();
+        let witnesses = accumulator
+            .get_membership_witnesses_for_batch(&members, &secret_key, &mem_state)
+            .unwrap();
+
+        let mut items = vec![];
+        let mut expected = vec![];
+        for i in 0..count {
+            let protocol = KBUniversalAccumulatorMembershipProofProtocol::init(
+                &mut rng,
+                members[i],
+                None,
+                &witnesses[i],
+                *accumulator.mem_value(),
+            );
+            let mut chal_bytes = vec![];
+            protocol
+                .challenge_contribution(accumulator.mem_value(), &mut chal_bytes)
+                .unwrap();
+            let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+            let mut proof = protocol.gen_proof(&challenge).unwrap();
+            let mut accum = *accumulator.mem_value();
+            let mut challenge = challenge;
+            // Make some proofs invalid in different ways
+            let valid = match i % 7 {
+                1 => {
+                    challenge = Fr::rand(&mut rng);
+                    false
+                }
+                3 => {
+                    proof.0.C_bar = G1Affine::rand(&mut rng);
+                    false
+                }
+                5 if i > 20 => {
+                    accum = G1Affine::rand(&mut rng);
+                    false
+                }
+                _ => true,
+            };
+            items.push((proof, accum, challenge));
+            expected.push(valid);
+        }
+        assert!(expected.iter().any(|v| !v));
+
+        let results = KBUniversalAccumulatorMembershipProof::verify_stream(
+            items.clone().into_iter(),
+            &secret_key,
+        )
+        .map(|r| r.is_ok())
+        .collect::<Vec<_>>();
+        assert_eq!(results, expected);
+
+        for window in [1, 8, 50, 64] {
+            let results = KBUniversalAccumulatorMembershipProof::verify_stream_batched(
+                &mut rng,
+                items.clone().into_iter(),
+                &secret_key,
+                window,
+            )
+            .map(|r| r.is_ok())
+            .collect::<Vec<_>>();
+            assert_eq!(results, expected);
+        }
+
+        // Proofs are taken from the stream only when results are requested
+        let taken = Cell::new(0);
+        let mut verifier = KBUniversalAccumulatorMembershipProof::verify_stream_batched(
+            &mut rng,
+            items.into_iter().inspect(|_| taken.set(taken.get() + 1)),
+            &secret_key,
+            8,
+        );
+        assert_eq!(taken.get(), 0);
+        for v in expected.iter().take(5) {
+            assert_eq!(verifier.next().unwrap().is_ok(), *v);
+        }
+        assert_eq!(taken.get(), 8);
+    }
+
+    #[test]
+    fn membership_and_non_membership_challenge_contributions_differ() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (_, secret_key, _, accumulator, domain, _, non_mem_state) =
+            setup_uni_accum(&mut rng, 10);
+
+        // Use the same element, witness, accumulator and randomness for both protocols so that only the
+        // domain separation tags can make the transcripts differ
+        let element = domain[0];
+        let witness = accumulator
+            .get_non_membership_witness(&element, &secret_key, &non_mem_state)
+            .unwrap();
+        let accum_value = accumulator.non_mem_value();
+
+        let mem_protocol = KBUniversalAccumulatorMembershipProofProtocol::init(
+            &mut StdRng::seed_from_u64(1u64),
+            element,
+            None,
+            &KBUniversalAccumulatorMembershipWitness(witness.0.clone()),
+            *accum_value,
+        );
+        let non_mem_protocol = KBUniversalAccumulatorNonMembershipProofProtocol::init(
+            &mut StdRng::seed_from_u64(1u64),
+            element,
+            None,
+            &witness,
+            *accum_value,
+        );
+        assert_eq!(mem_protocol.0, non_mem_protocol.0);
+
+        let mut mem_bytes = vec![];
+        mem_protocol
+            .challenge_contribution(accum_value, &mut mem_bytes)
+            .unwrap();
+        let mut non_mem_bytes = vec![];
+        non_mem_protocol
+            .challenge_contribution(accum_value, &mut non_mem_bytes)
+            .unwrap();
+        assert_ne!(mem_bytes, non_mem_bytes);
+
+        let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&mem_bytes);
+        let mem_proof = mem_protocol.gen_proof(&challenge).unwrap();
+        let non_mem_proof = non_mem_protocol.gen_proof(&challenge).unwrap();
+        let mut mem_proof_bytes = vec![];
+        mem_proof
+            .challenge_contribution(accum_value, &mut mem_proof_bytes)
+            .unwrap();
+        let mut non_mem_proof_bytes = vec![];
+        non_mem_proof
+            .challenge_contribution(accum_value, &mut non_mem_proof_bytes)
+            .unwrap();
+        assert_eq!(mem_proof_bytes, mem_bytes);
+        assert_eq!(non_mem_proof_bytes, non_mem_bytes);
+        assert_ne!(mem_proof_bytes, non_mem_proof_bytes);
+    }
+
+    #[test]
+    fn convert_membership_proof_to_public_proof() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (_, secret_key, _, mut accumulator, domain, mut mem_state, mut non_mem_state) =
+            setup_uni_accum(&mut rng, 10);
+        let pairing_params = crate::setup::SetupParams::<Bls12_381>::new::<Blake2b512>(b"test");
+        let pairing_pk = crate::setup::PublicKey::new_from_secret_key(&secret_key, &pairing_params);
+
+        let member = domain[0];
+        accumulator = accumulator
+            .add(member, &secret_key, &mut mem_state, &mut non_mem_state)
+            .unwrap();
+        let witness = accumulator
+            .get_membership_witness(&member, &secret_key, &mem_state)
+            .unwrap();
+
+        let protocol = KBUniversalAccumulatorMembershipProofProtocol::init(
+            &mut rng,
+            member,
+            None,
+            &witness,
+            *accumulator.mem_value(),
+        );
+        let mut chal_bytes = vec![];
+        protocol
+            .challenge_contribution(accumulator.mem_value(), &mut chal_bytes)
+            .unwrap();
+        let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+        let proof = protocol.gen_proof(&challenge).unwrap();
+        proof
+            .verify(*accumulator.mem_value(), &secret_key, &challenge)
+            .unwrap();
+
+        let public_proof = proof.to_public_proof::<Bls12_381>();
+        let mut public_chal_bytes = vec![];
+        public_proof
+            .challenge_contribution(accumulator.mem_value(), &mut public_chal_bytes)
+            .unwrap();
+        assert_eq!(public_chal_bytes, chal_bytes);
+        public_proof
+            .verify(
+                *accumulator.mem_value(),
+                &challenge,
+                pairing_pk.clone(),
+                pairing_params.clone(),
+            )
+            .unwrap();
+        test_serialization!(
+            KBUniversalAccumulatorPublicMembershipProof<Bls12_381>,
+            public_proof
+        );
+
+        // Doesn't verify with a public key from a different secret key
+        let other_pk = crate::setup::PublicKey::new_from_secret_key(
+            &SecretKey::new(&mut rng),
+            &pairing_params,
+        );
+        assert!(public_proof
+            .verify(
+                *accumulator.mem_value(),
+                &challenge,
+                other_pk,
+                pairing_params.clone()
+            )
+            .is_err());
+
+        // Doesn't verify with a different challenge or accumulator
+        assert!(public_proof
+            .verify(
+                *accumulator.mem_value(),
+                &Fr::rand(&mut rng),
+                pairing_pk.clone(),
+                pairing_params.clone()
+            )
+            .is_err());
+        assert!(public_proof
+            .verify(
+                *accumulator.non_mem_value(),
+                &challenge,
+                pairing_pk,
+                pairing_params
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn multi_membership_proof() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (_, secret_key, _, mut accumulator, domain, mut mem_state, mut non_mem_state) =
+            setup_uni_accum(&mut rng, 20);
+
+        let count = 5;
+        let members = domain[0..count].to_vec();
+        accumulator = accumulator
+            .add_batch(
+                members.clone(),
+                &secret_key,
+                &mut mem_state,
+                &mut non_mem_state,
+            )
+            .unwrap();
+        let witnesses = members
+            .iter()
+            .map(|m| {
+                accumulator
+                    .get_membership_witness(m, &secret_key, &mem_state)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let accum_value = *accumulator.mem_value();
+
+        let protocol = KBUniversalAccumulatorMultiMembershipProofProtocol::init(
+            &mut rng,
+            members.clone(),
+            vec![None; count],
+            &witnesses,
+            accum_value,
+        )
+        .unwrap();
+        let mut chal_bytes_prover = vec![];
+        protocol
+            .challenge_contribution(&accum_value, &mut chal_bytes_prover)
+            .unwrap();
+        let challenge_prover =
+            compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes_prover);
+        let proof = protocol.gen_proof(&challenge_prover).unwrap();
+
+        let mut chal_bytes_verifier = vec![];
+        proof
+            .challenge_contribution(&accum_value, &mut chal_bytes_verifier)
+            .unwrap();
+        assert_eq!(chal_bytes_prover, chal_bytes_verifier);
+        let challenge_verifier =
+            compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes_verifier);
+        proof
+            .verify(accum_value, &secret_key, &challenge_verifier)
+            .unwrap();
+        test_serialization!(KBUniversalAccumulatorMultiMembershipProof<G1Affine>, proof);
+
+        // Each witness is randomized independently so a guess for 2 elements can't be checked by comparing
+        // `C_bar_i + C'_i * y_i` across elements
+        assert_eq!(proof.0.len(), count);
+        let V_l = |i: usize| (proof.0[i].C_bar + proof.0[i].C_prime * members[i]).into_affine();
+        for i in 1..count {
+            assert_ne!(V_l(0), V_l(i));
+        }
+        for i in 0..count {
+            assert_eq!(
+                proof.get_schnorr_response_for_element(i),
+                Some(proof.0[i].get_schnorr_response_for_element())
+            );
+        }
+        assert!(proof.get_schnorr_response_for_element(count).is_none());
+
+        // Doesn't verify with a different secret key, challenge or accumulator
+        assert!(proof
+            .verify(accum_value, &SecretKey::new(&mut rng), &challenge_verifier)
+            .is_err());
+        assert!(proof
+            .verify(accum_value, &secret_key, &Fr::rand(&mut rng))
+            .is_err());
+        assert!(proof
+            .verify(
+                *accumulator.non_mem_value(),
+                &secret_key,
+                &challenge_verifier
+            )
+            .is_err());
+
+        // One of the witnesses is wrong
+        let mut wrong_witnesses = witnesses.clone();
+        wrong_witnesses.swap(1, 3);
+        let protocol = KBUniversalAccumulatorMultiMembershipProofProtocol::init(
+            &mut rng,
+            members.clone(),
+            vec![None; count],
+            &wrong_witnesses,
+            accum_value,
+        )
+        .unwrap();
+        let mut chal_bytes = vec![];
+        protocol
+            .challenge_contribution(&accum_value, &mut chal_bytes)
+            .unwrap();
+        let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+        let proof = protocol.gen_proof(&challenge).unwrap();
+        assert!(proof.verify(accum_value, &secret_key, &challenge).is_err());
+
+        assert!(matches!(
+            KBUniversalAccumulatorMultiMembershipProofProtocol::init(
+                &mut rng,
+                members.clone(),
+                vec![None; count],
+                &witnesses[1..],
+                accum_value,
+            ),
+            Err(VBAccumulatorError::NeedSameNoOfElementsAndWitnesses)
+        ));
+        assert!(matches!(
+            KBUniversalAccumulatorMultiMembershipProofProtocol::init(
+                &mut rng,
+                vec![],
+                vec![],
+                &[],
+                accum_value,
+            ),
+            Err(VBAccumulatorError::NoElementsForMultiMembershipProof)
+        ));
+    }
+
+    #[test]
+    fn abort_protocols() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (_, secret_key, _, mut accumulator, domain, mut mem_state, mut non_mem_state) =
+            setup_uni_accum(&mut rng, 10);
+        let member = domain[0];
+        let non_member = domain[1];
+        accumulator = accumulator
+            .add(member, &secret_key, &mut mem_state, &mut non_mem_state)
+            .unwrap();
+        let mem_witness = accumulator
+            .get_membership_witness(&member, &secret_key, &mem_state)
+            .unwrap();
+        let non_mem_witness = accumulator
+            .get_non_membership_witness(&non_member, &secret_key, &non_mem_state)
+            .unwrap();
+
+        // `abort` takes the protocol by value so it can't be used afterwards
+        let _: fn(KBUniversalAccumulatorMembershipProofProtocol<G1Affine>) =
+            KBUniversalAccumulatorMembershipProofProtocol::abort;
+        let _: fn(KBUniversalAccumulatorNonMembershipProofProtocol<G1Affine>) =
+            KBUniversalAccumulatorNonMembershipProofProtocol::abort;
+        let _: fn(KBUniversalAccumulatorMultiMembershipProofProtocol<G1Affine>) =
+            KBUniversalAccumulatorMultiMembershipProofProtocol::abort;
+
+        KBUniversalAccumulatorMembershipProofProtocol::init(
+            &mut rng,
+            member,
+            None,
+            &mem_witness,
+            *accumulator.mem_value(),
+        )
+        .abort();
+        KBUniversalAccumulatorNonMembershipProofProtocol::init(
+            &mut rng,
+            non_member,
+            None,
+            &non_mem_witness,
+            *accumulator.non_mem_value(),
+        )
+        .abort();
+        KBUniversalAccumulatorMultiMembershipProofProtocol::init(
+            &mut rng,
+            vec![member],
+            vec![None],
+            &[mem_witness],
+            *accumulator.mem_value(),
+        )
+        .unwrap()
+        .abort();
+    }
 }
@@ -2,10 +2,10 @@ use crate::{
     error::VBAccumulatorError,
     kb_universal_accumulator::accumulator::KBUniversalAccumulator,
     positive::Accumulator,
-    prelude::SecretKey,
+    prelude::{PublicKey, SecretKey, SetupParams},
     witness::{MembershipWitness, Witness},
 };
-use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
 use ark_ff::{batch_inversion, One, PrimeField, Zero};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{cfg_into_iter, cfg_iter, vec, vec::Vec};
@@ -61,6 +61,40 @@ impl<G: AffineRepr> From<G> for KBUniversalAccumulatorMembershipWitness<G> {
     }
 }
 
+/// A KB universal accumulator's membership witness is a VB positive accumulator's membership witness for
+/// the accumulator `KBUniversalAccumulator::mem`, so this conversion is lossless. The returned witness only
+/// verifies against a VB accumulator whose value is the same as `KBUniversalAccumulator::mem`.
+impl<G: AffineRepr> From<KBUniversalAccumulatorMembershipWitness<G>> for MembershipWitness<G> {
+    fn from(mut w: KBUniversalAccumulatorMembershipWitness<G>) -> Self {
+        // Can't move out of `w` as it's zeroized on drop so take the group element, leaving the identity behind
+        MembershipWitness(core::mem::replace(&mut w.0 .0, G::zero()))
+    }
+}
+
+impl<G: AffineRepr> KBUniversalAccumulatorMembershipWitness<G> {
+    /// Convert to a membership witness of the VB accumulator `target`, like a `PositiveAccumulator` maintained in
+    /// parallel with this KB universal accumulator. A witness is tied to the accumulator value and not just the member,
+    /// so the conversion only succeeds when `target` uses the same secret key and setup params and has the same value
+    /// as `KBUniversalAccumulator::mem`, e.g. a positive accumulator that had the same members added. Else returns
+    /// `VBAccumulatorError::WitnessIncompatibleWithAccumulator` and the accumulator manager must issue a new witness
+    /// for `target`.
+    ///
+    /// There is no such conversion for `KBUniversalAccumulatorNonMembershipWitness` as it is a membership witness in
+    /// the accumulator of non-members and has no relation with the VB universal accumulator's non-membership witness.
+    pub fn try_convert<E: Pairing<G1Affine = G>>(
+        &self,
+        member: &E::ScalarField,
+        target: &impl Accumulator<E>,
+        pk: &PublicKey<E>,
+        params: &SetupParams<E>,
+    ) -> Result<MembershipWitness<G>, VBAccumulatorError> {
+        if !target.verify_membership(member, &self.0, pk, params) {
+            return Err(VBAccumulatorError::WitnessIncompatibleWithAccumulator);
+        }
+        Ok(self.0.clone())
+    }
+}
+
 impl<G: AffineRepr> From<MembershipWitness<G>> for KBUniversalAccumulatorNonMembershipWitness<G> {
     fn from(w: MembershipWitness<G>) -> Self {
         KBUniversalAccumulatorNonMembershipWitness(w)
@@ -187,6 +221,54 @@ impl<E: Pairing> KBUniversalAccumulator<E> {
             .into()
     }
 
+    /// Update the non-membership witness after elements are added one after another. Call this on the accumulator after
+    /// all the additions. `intermediate_accumulators` are the accumulators after each addition except the last, in the
+    /// same order as `additions`, as without the secret key the witness can't be updated using only the accumulators
+    /// before and after the additions. Gives the same result as calling `update_non_mem_wit_on_addition` for each
+    /// addition but needs only a single batch inversion and a multi-scalar multiplication.
+    pub fn update_non_mem_wit_on_batch_additions(
+        &self,
+        wit: &KBUniversalAccumulatorNonMembershipWitness<E::G1Affine>,
+        non_member: &E::ScalarField,
+        additions: &[E::ScalarField],
+        intermediate_accumulators: &[Self],
+    ) -> Result<KBUniversalAccumulatorNonMembershipWitness<E::G1Affine>, VBAccumulatorError> {
+        if additions.is_empty() {
+            return Ok(wit.clone());
+        }
+        if intermediate_accumulators.len() + 1 != additions.len() {
+            return Err(
+                VBAccumulatorError::UnequalNoOfAdditionsAndIntermediateAccumulators(
+                    additions.len(),
+                    intermediate_accumulators.len(),
+                ),
+            );
+        }
+        // Each addition x_i updates the witness as C_i = 1/(x_i - y) * (C_{i-1} - V_i) where V_i is the
+        // accumulator after x_i is added. Expanding this gives
+        // C_n = d_1*d_2*...*d_n * C_0 - \sum_{i=1}^n d_i*d_{i+1}*...*d_n * V_i where d_i = 1/(x_i - y)
+        let mut d = cfg_iter!(additions)
+            .map(|a| *a - *non_member)
+            .collect::<Vec<_>>();
+        if cfg_iter!(d).any(|d_i| d_i.is_zero()) {
+            return Err(VBAccumulatorError::NewElementSameAsCurrent);
+        }
+        batch_inversion(&mut d);
+        let n = additions.len();
+        let mut scalars = vec![E::ScalarField::zero(); n + 1];
+        let mut product = E::ScalarField::one();
+        for i in (0..n).rev() {
+            product *= d[i];
+            scalars[i + 1] = -product;
+        }
+        scalars[0] = product;
+        let mut bases = Vec::with_capacity(n + 1);
+        bases.push(wit.0 .0);
+        bases.extend(intermediate_accumulators.iter().map(|a| *a.non_mem_value()));
+        bases.push(*self.non_mem_value());
+        Ok(MembershipWitness(E::G1::msm_unchecked(&bases, &scalars).into_affine()).into())
+    }
+
     /// Update the non-membership witnesses on addition of a batch of elements. Call this on the accumulator before update
     pub fn update_non_mem_wit_using_secret_key_on_batch_additions(
         &self,
@@ -533,7 +615,10 @@ impl<G: AffineRepr> KBUniversalAccumulatorNonMembershipWitness<G> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::kb_universal_accumulator::accumulator::tests::setup_kb_universal_accum;
+    use crate::{
+        kb_universal_accumulator::accumulator::tests::setup_kb_universal_accum,
+        persistence::test::InMemoryState, positive::PositiveAccumulator,
+    };
     use ark_bls12_381::Fr;
     use ark_std::rand::{prelude::StdRng, SeedableRng};
     use std::time::{Duration, Instant};
@@ -1029,4 +1114,149 @@ mod tests {
             omega_time_1
         );
     }
+
+    #[test]
+    fn non_membership_witness_update_on_batch_additions() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (params, keypair, mut accumulator, domain, mut mem_state, mut non_mem_state) =
+            setup_kb_universal_accum(&mut rng, 100);
+
+        let non_member = domain[0];
+        let wit = accumulator
+            .get_non_membership_witness(&non_member, &keypair.secret_key, &non_mem_state)
+            .unwrap();
+
+        // Add 20 elements while keeping the accumulators after each addition
+        let additions = domain[1..21].to_vec();
+        let mut accumulators = vec![];
+        for elem in &additions {
+            accumulator = accumulator
+                .add(
+                    *elem,
+                    &keypair.secret_key,
+                    &mut mem_state,
+                    &mut non_mem_state,
+                )
+                .unwrap();
+            accumulators.push(accumulator.clone());
+        }
+        assert!(!accumulator.verify_non_membership(
+            &non_member,
+            &wit,
+            &keypair.public_key,
+            &params
+        ));
+
+        let start = Instant::now();
+        let mut expected_wit = wit.clone();
+        for (elem, accum) in additions.iter().zip(accumulators.iter()) {
+            expected_wit = accum
+                .update_non_mem_wit_on_addition(&expected_wit, &non_member, elem)
+                .unwrap();
+        }
+        println!(
+            "Time to update non-membership witness after {} additions one by one {:?}",
+            additions.len(),
+            start.elapsed()
+        );
+
+        let start = Instant::now();
+        let new_wit = accumulator
+            .update_non_mem_wit_on_batch_additions(
+                &wit,
+                &non_member,
+                &additions,
+                &accumulators[..additions.len() - 1],
+            )
+            .unwrap();
+        println!(
+            "Time to update non-membership witness after {} additions in a batch {:?}",
+            additions.len(),
+            start.elapsed()
+        );
+        assert_eq!(new_wit, expected_wit);
+        assert!(accumulator.verify_non_membership(
+            &non_member,
+            &new_wit,
+            &keypair.public_key,
+            &params
+        ));
+
+        assert_eq!(
+            accumulator
+                .update_non_mem_wit_on_batch_additions(&wit, &non_member, &[], &[])
+                .unwrap(),
+            wit
+        );
+        assert!(matches!(
+            accumulator.update_non_mem_wit_on_batch_additions(
+                &wit,
+                &non_member,
+                &additions,
+                &accumulators
+            ),
+            Err(VBAccumulatorError::UnequalNoOfAdditionsAndIntermediateAccumulators(20, 20))
+        ));
+        assert!(matches!(
+            accumulator.update_non_mem_wit_on_batch_additions(
+                &wit,
+                &non_member,
+                &[additions[0], non_member],
+                &accumulators[..1]
+            ),
+            Err(VBAccumulatorError::NewElementSameAsCurrent)
+        ));
+    }
+
+    #[test]
+    fn convert_membership_witness_to_vb_accumulator() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (params, keypair, mut accumulator, domain, mut mem_state, mut non_mem_state) =
+            setup_kb_universal_accum(&mut rng, 20);
+
+        // A VB positive accumulator maintained in parallel with the same key and params
+        let mut parallel = PositiveAccumulator::initialize(&params);
+        let mut parallel_state = InMemoryState::new();
+        for elem in domain.iter().take(5) {
+            accumulator = accumulator
+                .add(
+                    *elem,
+                    &keypair.secret_key,
+                    &mut mem_state,
+                    &mut non_mem_state,
+                )
+                .unwrap();
+            parallel = parallel
+                .add(*elem, &keypair.secret_key, &mut parallel_state)
+                .unwrap();
+        }
+        assert_eq!(accumulator.mem_value(), parallel.value());
+
+        // A VB positive accumulator with different members
+        let different = parallel
+            .add(domain[10], &keypair.secret_key, &mut parallel_state)
+            .unwrap();
+
+        for elem in domain.iter().take(5) {
+            let wit = accumulator
+                .get_membership_witness(elem, &keypair.secret_key, &mem_state)
+                .unwrap();
+            assert!(accumulator.verify_membership(elem, &wit, &keypair.public_key, &params));
+
+            let vb_wit = wit
+                .try_convert(elem, &parallel, &keypair.public_key, &params)
+                .unwrap();
+            assert!(parallel.verify_membership(elem, &vb_wit, &keypair.public_key, &params));
+            assert_eq!(vb_wit, MembershipWitness::from(wit.clone()));
+
+            // Converting back gives the same witness
+            let kb_wit = KBUniversalAccumulatorMembershipWitness::from(vb_wit);
+            assert_eq!(kb_wit, wit);
+
+            assert!(matches!(
+                wit.try_convert(elem, &different, &keypair.public_key, &params),
+                Err(VBAccumulatorError::WitnessIncompatibleWithAccumulator)
+            ));
+        }
+    }
 }
@@ -858,6 +858,47 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn membership_witnesses_for_batch() {
+        // Witnesses for a batch of members are the same as the witnesses computed one at a time, in the order
+        // of the members
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (params, keypair, accumulator, mut state) = setup_positive_accum(&mut rng);
+        let members = (0..40).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let accumulator = accumulator
+            .add_batch(members.clone(), &keypair.secret_key, &mut state)
+            .unwrap();
+
+        let requested = members.iter().step_by(2).rev().cloned().collect::<Vec<_>>();
+        assert_eq!(requested.len(), 20);
+        let witnesses = accumulator
+            .get_membership_witnesses_for_batch(&requested, &keypair.secret_key, &state)
+            .unwrap();
+        assert_eq!(witnesses.len(), requested.len());
+        for (member, witness) in requested.iter().zip(witnesses.iter()) {
+            assert_eq!(
+                *witness,
+                accumulator
+                    .get_membership_witness(member, &keypair.secret_key, &state)
+                    .unwrap()
+            );
+            assert!(accumulator.verify_membership(member, witness, &keypair.public_key, &params));
+        }
+
+        // Fails even if only one of the elements isn't a member
+        let mut with_non_member = requested.clone();
+        with_non_member.insert(10, Fr::rand(&mut rng));
+        assert!(matches!(
+            accumulator.get_membership_witnesses_for_batch(
+                &with_non_member,
+                &keypair.secret_key,
+                &state
+            ),
+            Err(VBAccumulatorError::ElementAbsent)
+        ));
+    }
+
     #[test]
     fn pre_filled_accumulator() {
         // Incase updating an accumulator is expensive like making a blockchain txn, a cheaper strategy
@@ -11,6 +11,10 @@
 //! 4. Prover creates proof for knowledge of `r`, `y`, `d'` in the relations `C_bar = V * r - C' * y - P * d'` and `J = Q * d'`.
 //! 5. Verifier checks proofs from point 4 and that `C'` and `J` are not 0 (ensuring D is not 0).
 //! 6. Verifier checks `e(C_bar, P_tilde) = e(C', pk)`
+//!
+//! The prover doesn't use the public key `pk` in either protocol, it only multiplies the accumulator value and the witness. The
+//! verifier uses `pk` only in the pairing check so when verifying many proofs, a `PreparedPublicKey`, which holds the pairing
+//! precomputation for `pk`, should be created once and passed to each verification.
 
 use crate::{
     error::VBAccumulatorError,
@@ -464,6 +468,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn membership_proof_verification_with_prepared_public_key() {
+        // Verifying with the public key prepared once gives the same result as with the public key
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (params, keypair, accumulator, mut state) = setup_positive_accum(&mut rng);
+        let prepared_pk = PreparedPublicKey::from(keypair.public_key.clone());
+
+        let elem = Fr::rand(&mut rng);
+        let accumulator = accumulator
+            .add(elem, &keypair.secret_key, &mut state)
+            .unwrap();
+        let witness = accumulator
+            .get_membership_witness(&elem, &keypair.secret_key, &state)
+            .unwrap();
+
+        let protocol =
+            MembershipProofProtocol::init(&mut rng, elem, None, *accumulator.value(), &witness);
+        let mut chal_bytes = vec![];
+        protocol
+            .challenge_contribution(*accumulator.value(), &mut chal_bytes)
+            .unwrap();
+        let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+        let proof = protocol.gen_proof(&challenge).unwrap();
+
+        let wrong_challenge = Fr::rand(&mut rng);
+        for c in [challenge, wrong_challenge] {
+            let with_pk = proof
+                .verify(
+                    *accumulator.value(),
+                    &c,
+                    keypair.public_key.clone(),
+                    params.clone(),
+                )
+                .is_ok();
+            let with_prepared_pk = proof
+                .verify(
+                    *accumulator.value(),
+                    &c,
+                    prepared_pk.clone(),
+                    params.clone(),
+                )
+                .is_ok();
+            assert_eq!(with_pk, with_prepared_pk);
+            assert_eq!(with_pk, c == challenge);
+        }
+    }
+
     #[test]
     fn non_membership_proof_universal_accumulator() {
         // Proof of knowledge of non-membership witness
@@ -20,13 +20,13 @@ use crate::{
     setup_keyed_verification::{PublicKey, SetupParams},
     witness::{MembershipWitness, NonMembershipWitness},
 };
-use ark_ec::{AffineRepr, CurveGroup};
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
 use core::mem;
 
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{fmt::Debug, io::Write, ops::Neg, rand::RngCore, vec, vec::Vec, UniformRand};
 use digest::Digest;
-use dock_crypto_utils::serde_utils::ArkObjectBytes;
+use dock_crypto_utils::{msm::WindowTable, serde_utils::ArkObjectBytes};
 use schnorr_pok::{
     compute_random_oracle_challenge,
     discrete_log::{
@@ -279,16 +279,30 @@ impl<G: AffineRepr> MembershipProofProtocol<G> {
         element_blinding: Option<G::ScalarField>,
         witness: &MembershipWitness<G>,
         accumulator: G,
+    ) -> Self {
+        let accumulator_table = WindowTable::new(2, accumulator.into_group());
+        Self::init_with_table(rng, element, element_blinding, witness, &accumulator_table)
+    }
+
+    /// Same as `init` but takes a precomputed window table for the accumulator, created as
+    /// `WindowTable::new(n, accumulator.into_group())`, so that the table can be reused when creating many
+    /// proofs against the same accumulator.
+    pub fn init_with_table<R: RngCore>(
+        rng: &mut R,
+        element: G::ScalarField,
+        element_blinding: Option<G::ScalarField>,
+        witness: &MembershipWitness<G>,
+        accumulator_table: &WindowTable<G::Group>,
     ) -> Self {
         let l = G::ScalarField::rand(rng);
         let C_prime = witness.0 * l;
         let C_prime_neg = C_prime.neg();
-        let C_bar = (accumulator * l + C_prime_neg * element).into_affine();
+        let C_bar = (accumulator_table.multiply(&l) + C_prime_neg * element).into_affine();
         let element_blinding = element_blinding.unwrap_or_else(|| G::ScalarField::rand(rng));
-        let sc = PokTwoDiscreteLogsProtocol::init(
+        let sc = PokTwoDiscreteLogsProtocol::init_with_base1_table(
             l,
             G::ScalarField::rand(rng),
-            &accumulator,
+            accumulator_table,
             element,
             element_blinding,
             &C_prime_neg.into(),
@@ -314,6 +328,20 @@ impl<G: AffineRepr> MembershipProofProtocol<G> {
         )
     }
 
+    /// The commitment `t` of the Schnorr protocol proving knowledge of the randomizer and the element, i.e.
+    /// `accumulator * r1 - C_prime * r2` where `r2` is the element's blinding. Along with a shared blinding for the
+    /// element, this can be included in a challenge shared with other protocols to prove equality of the element
+    /// with their witnesses.
+    pub fn schnorr_commitment(&self) -> &G {
+        &self.sc.t
+    }
+
+    /// Abandon the protocol without creating a proof. Zeroizes the witnesses and blindings immediately rather than
+    /// whenever the object would have been dropped.
+    pub fn abort(mut self) {
+        self.zeroize();
+    }
+
     pub fn gen_proof(
         mut self,
         challenge: &G::ScalarField,
@@ -403,6 +431,37 @@ impl<G: AffineRepr> DelegatedMembershipProof<G> {
         }
         Ok(())
     }
+
+    /// Verify several proofs by checking `\sum_i r_i * C_bar_i = secret_key * \sum_i r_i * C_prime_i` for random `r_i`
+    /// so that the multiplication with the secret key is done only once. A failure doesn't tell which proof is invalid.
+    pub fn verify_batch<R: RngCore>(
+        rng: &mut R,
+        proofs: &[Self],
+        secret_key: &SecretKey<G::ScalarField>,
+    ) -> Result<(), VBAccumulatorError> {
+        Self::verify_batch_from_iter(rng, proofs.iter(), secret_key)
+    }
+
+    pub(crate) fn verify_batch_from_iter<'a, R: RngCore>(
+        rng: &mut R,
+        proofs: impl ExactSizeIterator<Item = &'a Self>,
+        secret_key: &SecretKey<G::ScalarField>,
+    ) -> Result<(), VBAccumulatorError> {
+        let mut C_prime = Vec::with_capacity(proofs.len());
+        let mut C_bar = Vec::with_capacity(proofs.len());
+        let mut r = Vec::with_capacity(proofs.len());
+        for proof in proofs {
+            C_prime.push(proof.C_prime);
+            C_bar.push(proof.C_bar);
+            r.push(G::ScalarField::rand(rng));
+        }
+        if G::Group::msm_unchecked(&C_bar, &r)
+            != G::Group::msm_unchecked(&C_prime, &r) * secret_key.0
+        {
+            return Err(VBAccumulatorError::IncorrectRandomizedWitness);
+        }
+        Ok(())
+    }
 }
 
 impl<G: AffineRepr> NonMembershipProofProtocol<G> {
@@ -464,6 +523,12 @@ impl<G: AffineRepr> NonMembershipProofProtocol<G> {
         )
     }
 
+    /// Abandon the protocol without creating a proof. Zeroizes the witnesses and blindings immediately rather than
+    /// whenever the object would have been dropped.
+    pub fn abort(mut self) {
+        self.zeroize();
+    }
+
     pub fn gen_proof(
         mut self,
         challenge: &G::ScalarField,
@@ -48,8 +48,9 @@ use dock_crypto_utils::hashing_utils::projective_group_elem_from_try_and_incr;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use short_group_sig::common::ProvingKey;
+use subtle::{Choice, ConstantTimeEq};
 
-/// Secret key for accumulator manager
+/// Secret key for accumulator manager. Use `ct_eq` to compare secret keys, the derived `PartialEq` isn't constant time.
 #[serde_as]
 #[derive(
     Clone,
@@ -118,6 +119,15 @@ impl<F: PrimeField> SecretKey<F> {
     }
 }
 
+impl<F: PrimeField> ConstantTimeEq for SecretKey<F> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0
+            .into_bigint()
+            .as_ref()
+            .ct_eq(other.0.into_bigint().as_ref())
+    }
+}
+
 impl<E> SetupParams<E>
 where
     E: Pairing,
@@ -350,6 +360,19 @@ mod tests {
 
     type Fr = <Bls12_381 as Pairing>::ScalarField;
 
+    #[test]
+    fn secret_key_constant_time_equality() {
+        let seed = vec![0, 1, 4, 6, 2, 10];
+        let sk = SecretKey::<Fr>::generate_using_seed::<Blake2b512>(&seed);
+        assert!(bool::from(
+            sk.ct_eq(&SecretKey::generate_using_seed::<Blake2b512>(&seed))
+        ));
+        assert!(!bool::from(sk.ct_eq(&SecretKey::generate_using_seed::<
+            Blake2b512,
+        >(&[1, 2, 3]))));
+        assert!(!bool::from(sk.ct_eq(&SecretKey(sk.0 + Fr::from(1u64)))));
+    }
+
     #[test]
     fn keypair() {
         // Same seed generates same keypair